@@ -4,7 +4,7 @@ import json
 import logging
 
 from pathlib import Path
-from typing import Any, Dict, List, Optional, Union
+from typing import Any, Dict, List, Optional, Tuple, Union
 from nrel.routee.compass.routee_compass_py import (
     CompassAppWrapper,
 )
@@ -196,3 +196,22 @@ class CompassApp:
             List[int]: the edge ids of edges arriving at this vertex
         """
         return self._app.graph_get_in_edge_ids(vertex_id)
+
+    def graph_nearest_vertices(
+        self, x: float, y: float, k: int, distance_unit: Optional[str] = None
+    ) -> List[Tuple[int, float]]:
+        """
+        get the k nearest vertices to a coordinate by haversine distance,
+        without running a search
+
+        Args:
+            x (float): longitude of the coordinate
+            y (float): latitude of the coordinate
+            k (int): number of vertices to return
+            distance_unit (Optional[str]): distance unit, by default meters
+
+        Returns:
+            List[Tuple[int, float]]: vertex ids with their distance to the
+                coordinate, nearest first
+        """
+        return self._app.graph_nearest_vertices(x, y, k, distance_unit)
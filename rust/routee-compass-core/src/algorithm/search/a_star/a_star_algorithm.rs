@@ -2,6 +2,7 @@ use crate::algorithm::search::direction::Direction;
 use crate::algorithm::search::edge_traversal::EdgeTraversal;
 use crate::algorithm::search::search_error::SearchError;
 use crate::algorithm::search::search_instance::SearchInstance;
+use crate::algorithm::search::search_metrics::SearchMetrics;
 use crate::algorithm::search::search_result::SearchResult;
 use crate::algorithm::search::search_tree_branch::SearchTreeBranch;
 use crate::model::road_network::edge_id::EdgeId;
@@ -11,14 +12,24 @@ use crate::model::unit::cost::ReverseCost;
 use crate::model::unit::Cost;
 use crate::util::priority_queue::InternalPriorityQueue;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::time::Instant;
 
 /// run an A* Search over the given directed graph model. traverses links
 /// from the source, via the provided direction, to the target. uses the
 /// provided traversal model for state updates and link costs. estimates
 /// the distance to the destination (the a* heuristic) using the provided
-/// cost estimate function.
+/// cost estimate function. routes are optimal when the estimate never
+/// overestimates the remaining cost, see [`TraversalModel::estimate_traversal`].
+/// a weight factor above one trades optimality for fewer expansions.
+///
+/// [`TraversalModel::estimate_traversal`]: crate::model::traversal::traversal_model::TraversalModel::estimate_traversal
+///
+/// when the cost model has a tie-break feature, paths are compared lexicographically
+/// by cost and then by the tie-break feature, so that among equal-cost paths, the
+/// one with the lower tie-break value is found. when the cost model has a min
+/// improvement, a path replaces the one found so far for a vertex only if its cost
+/// is lower by at least that amount.
 pub fn run_a_star(
     source: VertexId,
     target: Option<VertexId>,
@@ -29,9 +40,41 @@ pub fn run_a_star(
     if target.map_or(false, |t| t == source) {
         return Ok(SearchResult::default());
     }
+    run_search(source, target, direction, weight_factor, si, |_| false)
+}
+
+/// run a search without a destination over the given directed graph model, which
+/// stops once every one of the targets has been settled, that is, once the search
+/// has found its final path to each of them. without a destination there is no
+/// estimate, so this is a Dijkstra search, and the tree holds the lowest-cost path
+/// to each target that can be reached. when some target cannot be reached, the
+/// search continues until the frontier is exhausted or the termination model stops it.
+pub fn run_a_star_to_targets(
+    source: VertexId,
+    targets: &[VertexId],
+    direction: &Direction,
+    si: &SearchInstance,
+) -> Result<SearchResult, SearchError> {
+    let mut unsettled: HashSet<VertexId> = targets.iter().copied().collect();
+    run_search(source, None, direction, None, si, |vertex_id| {
+        unsettled.remove(&vertex_id);
+        unsettled.is_empty()
+    })
+}
 
+/// the search loop of [run_a_star], which additionally stops after settling the
+/// first vertex for which `settled` returns true.
+fn run_search(
+    source: VertexId,
+    target: Option<VertexId>,
+    direction: &Direction,
+    weight_factor: Option<Cost>,
+    si: &SearchInstance,
+    mut settled: impl FnMut(VertexId) -> bool,
+) -> Result<SearchResult, SearchError> {
     // context for the search (graph, search functions, frontier priority queue)
-    let mut costs: InternalPriorityQueue<VertexId, ReverseCost> = InternalPriorityQueue::default();
+    let mut costs: InternalPriorityQueue<VertexId, (ReverseCost, ReverseCost)> =
+        InternalPriorityQueue::default();
     let mut traversal_costs: HashMap<VertexId, Cost> = HashMap::new();
     let mut solution: HashMap<VertexId, SearchTreeBranch> = HashMap::new();
 
@@ -45,19 +88,26 @@ pub fn run_a_star(
             Cost::new(cost_est.as_f64() * weight_factor.unwrap_or(Cost::ONE).as_f64())
         }
     };
-    costs.push(source, origin_cost.into());
+    let origin_tie_break = si.cost_model.tie_break_value(&initial_state)?;
+    costs.push(source, (origin_cost.into(), origin_tie_break.into()));
 
     let start_time = Instant::now();
     let mut iterations = 0;
+    let mut metrics = SearchMetrics::default();
 
     loop {
         si.termination_model
             .test(&start_time, solution.len(), iterations)?;
 
+        metrics.max_frontier_size = metrics.max_frontier_size.max(costs.len());
         let current_vertex_id = match advance_search(&mut costs, source, target)? {
             None => break,
             Some(id) => id,
         };
+        if settled(current_vertex_id) {
+            break;
+        }
+        metrics.nodes_expanded += 1;
 
         let last_edge_id = get_last_traversed_edge_id(&current_vertex_id, &source, &solution)?;
         let last_edge = match last_edge_id {
@@ -98,6 +148,13 @@ pub fn run_a_star(
             }
             let et =
                 direction.perform_edge_traversal(*edge_id, last_edge_id, &current_state, si)?;
+            metrics.edges_relaxed += 1;
+            let valid_result =
+                si.frontier_model
+                    .valid_result_state(e, &et.result_state, &si.state_model)?;
+            if !valid_result {
+                continue;
+            }
             let current_gscore = traversal_costs
                 .get(&terminal_vertex_id)
                 .unwrap_or(&Cost::INFINITY)
@@ -107,7 +164,16 @@ pub fn run_a_star(
                 .get(&key_vertex_id)
                 .unwrap_or(&Cost::INFINITY)
                 .to_owned();
-            if tentative_gscore < existing_gscore {
+            let tentative_tie_break = si.cost_model.tie_break_value(&et.result_state)?;
+            let improves_on_tie = tentative_gscore == existing_gscore
+                && match solution.get(&key_vertex_id) {
+                    Some(existing) => {
+                        let existing_state = &existing.edge_traversal.result_state;
+                        tentative_tie_break < si.cost_model.tie_break_value(existing_state)?
+                    }
+                    None => false,
+                };
+            if si.cost_model.improves_on(tentative_gscore, existing_gscore) || improves_on_tie {
                 traversal_costs.insert(key_vertex_id, tentative_gscore);
 
                 // update solution
@@ -126,7 +192,10 @@ pub fn run_a_star(
                     }
                 };
                 let f_score_value = tentative_gscore + dst_h_cost;
-                costs.push_increase(key_vertex_id, f_score_value.into());
+                costs.push_increase(
+                    key_vertex_id,
+                    (f_score_value.into(), tentative_tie_break.into()),
+                );
             }
         }
         iterations += 1;
@@ -170,7 +239,7 @@ pub fn run_a_star(
         flamegraph_file.write_all(output.as_bytes()).unwrap();
     }
 
-    let result = SearchResult::new(solution, iterations);
+    let result = SearchResult::new(solution, iterations, metrics);
     Ok(result)
 }
 
@@ -206,6 +275,7 @@ pub fn run_a_star_edge_oriented(
             let SearchResult {
                 mut tree,
                 iterations,
+                metrics,
             } = run_a_star(e1_dst, None, direction, weight_factor, si)?;
             if !tree.contains_key(&e1_dst) {
                 tree.extend([(e1_dst, src_branch)]);
@@ -213,6 +283,7 @@ pub fn run_a_star_edge_oriented(
             let updated = SearchResult {
                 tree,
                 iterations: iterations + 1,
+                metrics,
             };
             Ok(updated)
         }
@@ -244,6 +315,7 @@ pub fn run_a_star_edge_oriented(
                 let result = SearchResult {
                     tree,
                     iterations: 1,
+                    metrics: SearchMetrics::default(),
                 };
                 return Ok(result);
             } else {
@@ -251,6 +323,7 @@ pub fn run_a_star_edge_oriented(
                 let SearchResult {
                     mut tree,
                     iterations,
+                    metrics,
                 } = run_a_star(e1_dst, Some(e2_src), direction, weight_factor, si)?;
 
                 if tree.is_empty() {
@@ -285,6 +358,7 @@ pub fn run_a_star_edge_oriented(
                 let result = SearchResult {
                     tree,
                     iterations: iterations + 2,
+                    metrics,
                 };
                 Ok(result)
             }
@@ -308,7 +382,7 @@ pub fn run_a_star_edge_oriented(
 /// destination, or we have reached our destination.
 /// An error if no path exists for a search that includes a destination.
 fn advance_search(
-    cost: &mut InternalPriorityQueue<VertexId, ReverseCost>,
+    cost: &mut InternalPriorityQueue<VertexId, (ReverseCost, ReverseCost)>,
     source: VertexId,
     target: Option<VertexId>,
 ) -> Result<Option<VertexId>, SearchError> {
@@ -363,11 +437,7 @@ mod tests {
     use super::*;
     use crate::algorithm::search::backtrack::vertex_oriented_route;
     use crate::algorithm::search::MinSearchTree;
-    use crate::model::access::default::no_access_model::NoAccessModel;
-    use crate::model::cost::cost_aggregation::CostAggregation;
-    use crate::model::cost::cost_model::CostModel;
-    use crate::model::cost::vehicle::vehicle_cost_rate::VehicleCostRate;
-    use crate::model::frontier::default::no_restriction::NoRestriction;
+    use crate::model::cost::edge_cost_cap::{EdgeCostCap, EdgeCostCapPolicy};
     use crate::model::property::edge::Edge;
     use crate::model::property::vertex::Vertex;
     use crate::model::road_network::edge_id::EdgeId;
@@ -375,9 +445,13 @@ mod tests {
     use crate::model::state::state_feature::StateFeature;
     use crate::model::state::state_model::StateModel;
     use crate::model::termination::termination_model::TerminationModel;
-    use crate::model::traversal::default::distance_traversal_model::DistanceTraversalModel;
-    use crate::model::unit::{Distance, DistanceUnit};
-    use crate::util::compact_ordered_hash_map::CompactOrderedHashMap;
+    use crate::model::traversal::state::state_variable::StateVar;
+    use crate::model::traversal::traversal_model::TraversalModel;
+    use crate::model::traversal::traversal_model_error::TraversalModelError;
+    use crate::model::unit::{Cost, Distance, DistanceUnit};
+    use crate::util::test_util::{
+        distance_search_instance, graph_from_edges, traversal_search_instance,
+    };
     use rayon::prelude::*;
     use std::sync::Arc;
 
@@ -400,20 +474,7 @@ mod tests {
             Edge::new(7, 0, 3, 2.0),
         ];
 
-        let mut adj = vec![CompactOrderedHashMap::empty(); vertices.len()];
-        let mut rev = vec![CompactOrderedHashMap::empty(); vertices.len()];
-
-        for edge in &edges {
-            adj[edge.src_vertex_id.0].insert(edge.edge_id, edge.dst_vertex_id);
-            rev[edge.dst_vertex_id.0].insert(edge.edge_id, edge.src_vertex_id);
-        }
-
-        Graph {
-            adj: adj.into_boxed_slice(),
-            rev: rev.into_boxed_slice(),
-            edges: edges.into_boxed_slice(),
-            vertices: vertices.into_boxed_slice(),
-        }
+        graph_from_edges(vertices, edges)
     }
 
     #[test]
@@ -465,38 +526,7 @@ mod tests {
 
         // setup the graph, traversal model, and a* heuristic to be shared across the queries in parallel
         // these live in the "driver" process and are passed as read-only memory to each executor process
-        let state_model = Arc::new(
-            StateModel::empty()
-                .extend(vec![(
-                    String::from("distance"),
-                    StateFeature::Distance {
-                        distance_unit: DistanceUnit::Kilometers,
-                        initial: Distance::new(0.0),
-                    },
-                )])
-                .unwrap(),
-        );
-        let cost_model = CostModel::new(
-            // vec![(String::from("distance"), 0usize)],
-            Arc::new(HashMap::from([(String::from("distance"), 1.0)])),
-            Arc::new(HashMap::from([(
-                String::from("distance"),
-                VehicleCostRate::Raw,
-            )])),
-            Arc::new(HashMap::new()),
-            CostAggregation::Sum,
-            state_model.clone(),
-        )
-        .unwrap();
-        let si = SearchInstance {
-            directed_graph: Arc::new(build_mock_graph()),
-            state_model: state_model.clone(),
-            traversal_model: Arc::new(DistanceTraversalModel::new(DistanceUnit::Meters)),
-            access_model: Arc::new(NoAccessModel {}),
-            cost_model,
-            frontier_model: Arc::new(NoRestriction {}),
-            termination_model: Arc::new(TerminationModel::IterationsLimit { limit: 20 }),
-        };
+        let si = distance_search_instance(build_mock_graph());
 
         // execute the route search
         let result: Vec<Result<MinSearchTree, SearchError>> = queries
@@ -520,4 +550,424 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_zero_cost_cycle_terminates() {
+        // (0) -[0]-> (1) -[1]-> (2) -[2]-> (0) is a cycle of zero-distance edges,
+        // (2) -[3]-> (3) exits the cycle and (4) is unreachable.
+        // edge costs are floored at Cost::MIN_COST and a vertex is only re-queued
+        // when its cost strictly improves, so the cycle cannot be re-expanded.
+        let vertices = (0..5).map(|i| Vertex::new(i, 0.0, 0.0)).collect::<Vec<_>>();
+        let edges = vec![
+            Edge::new(0, 0, 1, 0.0),
+            Edge::new(1, 1, 2, 0.0),
+            Edge::new(2, 2, 0, 0.0),
+            Edge::new(3, 2, 3, 0.0),
+        ];
+        let graph = graph_from_edges(vertices, edges);
+        let si = SearchInstance {
+            // any re-expansion of the cycle would exhaust this limit
+            termination_model: Arc::new(TerminationModel::IterationsLimit { limit: 10 }),
+            ..distance_search_instance(graph)
+        };
+
+        let result = run_a_star(
+            VertexId(0),
+            Some(VertexId(3)),
+            &Direction::Forward,
+            None,
+            &si,
+        )
+        .unwrap();
+        let route = vertex_oriented_route(VertexId(0), VertexId(3), &result.tree).unwrap();
+        let route_edges: Vec<EdgeId> = route.iter().map(|r| r.edge_id).collect();
+        assert_eq!(route_edges, vec![EdgeId(0), EdgeId(1), EdgeId(3)]);
+
+        let unreachable = run_a_star(
+            VertexId(0),
+            Some(VertexId(4)),
+            &Direction::Forward,
+            None,
+            &si,
+        );
+        assert!(matches!(unreachable, Err(SearchError::NoPathExists(_, _))));
+
+        let all = run_a_star(VertexId(0), None, &Direction::Forward, None, &si).unwrap();
+        assert_eq!(all.iterations, 4);
+        // each of the 4 edges is traversed once, and the cycle never holds more than
+        // one vertex in the frontier
+        let expected_metrics = SearchMetrics {
+            nodes_expanded: 4,
+            edges_relaxed: 4,
+            max_frontier_size: 1,
+        };
+        assert_eq!(all.metrics, expected_metrics);
+    }
+
+    /// builds a search instance minimizing distance in a graph with a route from (0) to (3)
+    /// through (1) over edges 0 and 1, and a route through (2) over edges 2 and 3.
+    fn two_paths_search_instance(
+        distances: [f64; 4],
+        edge_cost_cap: Option<EdgeCostCap>,
+    ) -> SearchInstance {
+        let vertices = (0..4).map(|i| Vertex::new(i, 0.0, 0.0)).collect::<Vec<_>>();
+        let edges = vec![
+            Edge::new(0, 0, 1, distances[0]),
+            Edge::new(1, 1, 3, distances[1]),
+            Edge::new(2, 0, 2, distances[2]),
+            Edge::new(3, 2, 3, distances[3]),
+        ];
+        let si = distance_search_instance(graph_from_edges(vertices, edges));
+        SearchInstance {
+            cost_model: si
+                .cost_model
+                .clone()
+                .with_edge_cost_cap(edge_cost_cap)
+                .unwrap(),
+            ..si
+        }
+    }
+
+    /// finds the route from (0) to (3) of [two_paths_search_instance], returning the edges
+    /// of the route and their traversal costs.
+    fn route_between_two_paths(
+        distances: [f64; 4],
+        edge_cost_cap: Option<EdgeCostCap>,
+    ) -> Vec<(EdgeId, Cost)> {
+        let si = two_paths_search_instance(distances, edge_cost_cap);
+        let (o, d) = (VertexId(0), VertexId(3));
+        let result = run_a_star(o, Some(d), &Direction::Forward, None, &si).unwrap();
+        let route = vertex_oriented_route(o, d, &result.tree).unwrap();
+        route
+            .iter()
+            .map(|et| (et.edge_id, et.traversal_cost))
+            .collect()
+    }
+
+    /// finds the shortest route from (0) to (3) where the short route through (1) has
+    /// an outlier edge with an erroneous 1000km distance, and the route through (2) is 300m
+    fn route_with_edge_cost_cap(edge_cost_cap: Option<EdgeCostCap>) -> Vec<(EdgeId, Cost)> {
+        route_between_two_paths([1.0, 1_000_000.0, 150.0, 150.0], edge_cost_cap)
+    }
+
+    #[test]
+    fn test_edge_cost_cap_policies() {
+        // without a cap, the outlier edge is avoided
+        let route = route_with_edge_cost_cap(None);
+        let edge_ids = route.iter().map(|(e, _)| *e).collect::<Vec<_>>();
+        assert_eq!(edge_ids, vec![EdgeId(2), EdgeId(3)]);
+
+        // capped at 200, the outlier edge costs 200 and the short route is taken
+        let route = route_with_edge_cost_cap(Some(EdgeCostCap {
+            max_cost: 200.0,
+            policy: EdgeCostCapPolicy::Cap,
+        }));
+        assert_eq!(
+            route,
+            vec![(EdgeId(0), Cost::ONE), (EdgeId(1), Cost::new(200.0))]
+        );
+
+        // excluded, the outlier edge is never used even though the cap is below the
+        // cost of the alternative route
+        let route = route_with_edge_cost_cap(Some(EdgeCostCap {
+            max_cost: 200.0,
+            policy: EdgeCostCapPolicy::Exclude,
+        }));
+        let edge_ids = route.iter().map(|(e, _)| *e).collect::<Vec<_>>();
+        assert_eq!(edge_ids, vec![EdgeId(2), EdgeId(3)]);
+    }
+
+    /// traverses edge distances, additionally tracking the distance driven on highway edges
+    struct HighwayDistanceModel {
+        highway_edges: Vec<EdgeId>,
+    }
+
+    impl TraversalModel for HighwayDistanceModel {
+        fn state_features(&self) -> Vec<(String, StateFeature)> {
+            vec![
+                (
+                    String::from("distance"),
+                    StateFeature::Distance {
+                        distance_unit: DistanceUnit::Meters,
+                        initial: Distance::ZERO,
+                        output_alias: None,
+                    },
+                ),
+                (
+                    String::from("highway_distance"),
+                    StateFeature::Distance {
+                        distance_unit: DistanceUnit::Meters,
+                        initial: Distance::ZERO,
+                        output_alias: None,
+                    },
+                ),
+            ]
+        }
+
+        fn traverse_edge(
+            &self,
+            trajectory: (&Vertex, &Edge, &Vertex),
+            state: &mut Vec<StateVar>,
+            state_model: &StateModel,
+        ) -> Result<(), TraversalModelError> {
+            let (_, edge, _) = trajectory;
+            let unit = DistanceUnit::Meters;
+            state_model.add_distance(state, &String::from("distance"), &edge.distance, &unit)?;
+            if self.highway_edges.contains(&edge.edge_id) {
+                let name = String::from("highway_distance");
+                state_model.add_distance(state, &name, &edge.distance, &unit)?;
+            }
+            Ok(())
+        }
+
+        fn estimate_traversal(
+            &self,
+            _od: (&Vertex, &Vertex),
+            _state: &mut Vec<StateVar>,
+            _state_model: &StateModel,
+        ) -> Result<(), TraversalModelError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_tie_break_between_equal_cost_routes() {
+        // two routes of 20 units distance from (0) to (3):
+        // (0) -[0]-> (1) -[1]-> (3) with 10 + 10 units distance
+        // (0) -[2]-> (2) -[3]-> (3) with 5 + 15 units distance
+        let vertices = (0..4).map(|i| Vertex::new(i, 0.0, 0.0)).collect::<Vec<_>>();
+        let edges = vec![
+            Edge::new(0, 0, 1, 10.0),
+            Edge::new(1, 1, 3, 10.0),
+            Edge::new(2, 0, 2, 5.0),
+            Edge::new(3, 2, 3, 15.0),
+        ];
+        let graph = Arc::new(graph_from_edges(vertices, edges));
+
+        // the route found when preferring fewer highway miles, with the given highway edges
+        let route_avoiding_highways = |highway_edges: Vec<EdgeId>| -> Vec<EdgeId> {
+            let traversal_model = Arc::new(HighwayDistanceModel { highway_edges });
+            let si = traversal_search_instance(graph.clone(), traversal_model);
+            let si = SearchInstance {
+                cost_model: si
+                    .cost_model
+                    .clone()
+                    .with_tie_break(Some(&String::from("highway_distance")))
+                    .unwrap(),
+                ..si
+            };
+            let (o, d) = (VertexId(0), VertexId(3));
+            let result = run_a_star(o, Some(d), &Direction::Forward, None, &si).unwrap();
+            let route = vertex_oriented_route(o, d, &result.tree).unwrap();
+            route.iter().map(|r| r.edge_id).collect()
+        };
+
+        assert_eq!(
+            route_avoiding_highways(vec![EdgeId(0)]),
+            vec![EdgeId(2), EdgeId(3)]
+        );
+        assert_eq!(
+            route_avoiding_highways(vec![EdgeId(3)]),
+            vec![EdgeId(0), EdgeId(1)]
+        );
+    }
+
+    #[test]
+    fn test_tie_break_has_no_effect_on_unequal_costs() {
+        // the highway route is shorter, so it is found despite the tie-break preference
+        let graph = build_mock_graph();
+        let traversal_model = Arc::new(HighwayDistanceModel {
+            highway_edges: vec![EdgeId(7), EdgeId(5), EdgeId(3)],
+        });
+        let si = traversal_search_instance(graph, traversal_model);
+        let si = SearchInstance {
+            cost_model: si
+                .cost_model
+                .clone()
+                .with_tie_break(Some(&String::from("highway_distance")))
+                .unwrap(),
+            ..si
+        };
+        let (o, d) = (VertexId(0), VertexId(1));
+        let result = run_a_star(o, Some(d), &Direction::Forward, None, &si).unwrap();
+        let route = vertex_oriented_route(o, d, &result.tree).unwrap();
+        let route_edges: Vec<EdgeId> = route.iter().map(|r| r.edge_id).collect();
+        assert_eq!(route_edges, vec![EdgeId(7), EdgeId(5), EdgeId(3)]);
+    }
+
+    /// traverses edge distances and estimates the remaining distance from each
+    /// vertex using a fixed table, which may be inconsistent
+    struct TableHeuristicModel {
+        heuristic: Vec<f64>,
+    }
+
+    impl TraversalModel for TableHeuristicModel {
+        fn state_features(&self) -> Vec<(String, StateFeature)> {
+            vec![(
+                String::from("distance"),
+                StateFeature::Distance {
+                    distance_unit: DistanceUnit::Meters,
+                    initial: Distance::ZERO,
+                    output_alias: None,
+                },
+            )]
+        }
+
+        fn traverse_edge(
+            &self,
+            trajectory: (&Vertex, &Edge, &Vertex),
+            state: &mut Vec<StateVar>,
+            state_model: &StateModel,
+        ) -> Result<(), TraversalModelError> {
+            let (_, edge, _) = trajectory;
+            let unit = DistanceUnit::Meters;
+            state_model.add_distance(state, &String::from("distance"), &edge.distance, &unit)?;
+            Ok(())
+        }
+
+        fn estimate_traversal(
+            &self,
+            od: (&Vertex, &Vertex),
+            state: &mut Vec<StateVar>,
+            state_model: &StateModel,
+        ) -> Result<(), TraversalModelError> {
+            let (src, _) = od;
+            let estimate = Distance::new(self.heuristic[src.vertex_id.0]);
+            let unit = DistanceUnit::Meters;
+            state_model.add_distance(state, &String::from("distance"), &estimate, &unit)?;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_min_improvement_reduces_relaxations() {
+        // two routes from (0) to (3) that differ by a rounding-sized amount, followed
+        // by a chain of edges to (7):
+        // (0) -[0]-> (1) -[1]-> (3) with 1 + 1 units distance
+        // (0) -[2]-> (2) -[3]-> (3) with 1 + (1 - 1e-9) units distance
+        // (3) -[4]-> (4) -[5]-> (5) -[6]-> (6) -[7]-> (7) with 1 unit distance each
+        // the estimate from (2) delays its expansion until the chain is expanded via
+        // (1), so the slightly cheaper route through (2) re-relaxes the whole chain.
+        let vertices = (0..8).map(|i| Vertex::new(i, 0.0, 0.0)).collect::<Vec<_>>();
+        let edges = vec![
+            Edge::new(0, 0, 1, 1.0),
+            Edge::new(1, 1, 3, 1.0),
+            Edge::new(2, 0, 2, 1.0),
+            Edge::new(3, 2, 3, 1.0 - 1e-9),
+            Edge::new(4, 3, 4, 1.0),
+            Edge::new(5, 4, 5, 1.0),
+            Edge::new(6, 5, 6, 1.0),
+            Edge::new(7, 6, 7, 1.0),
+        ];
+        let graph = Arc::new(graph_from_edges(vertices, edges));
+        let traversal_model = Arc::new(TableHeuristicModel {
+            heuristic: vec![0.0, 0.0, 4.5, 0.0, 0.0, 0.0, 0.0, 0.0],
+        });
+        let si = traversal_search_instance(graph, traversal_model);
+
+        let run_with_min_improvement = |min_improvement: Option<f64>| {
+            let si = SearchInstance {
+                cost_model: si
+                    .cost_model
+                    .clone()
+                    .with_min_improvement(min_improvement)
+                    .unwrap(),
+                ..si.clone()
+            };
+            let (o, d) = (VertexId(0), VertexId(7));
+            let result = run_a_star(o, Some(d), &Direction::Forward, None, &si).unwrap();
+            let route = vertex_oriented_route(o, d, &result.tree).unwrap();
+            let cost = route.iter().map(|r| r.total_cost().as_f64()).sum::<f64>();
+            (result.iterations, cost)
+        };
+
+        let (exact_iterations, exact_cost) = run_with_min_improvement(None);
+        let min_improvement = 1e-6;
+        let (iterations, cost) = run_with_min_improvement(Some(min_improvement));
+        // the chain (3)-(6) is expanded twice without a min improvement
+        assert_eq!(exact_iterations, 11);
+        assert_eq!(iterations, 7);
+        // the route of 6 edges is within the suboptimality bound
+        assert!(cost > exact_cost);
+        assert!(cost - exact_cost < 6.0 * min_improvement);
+
+        let invalid = si.cost_model.clone().with_min_improvement(Some(-1.0));
+        assert!(invalid.is_err());
+    }
+
+    #[test]
+    fn test_haversine_heuristic_is_optimal() {
+        use crate::algorithm::search::search_algorithm::SearchAlgorithm;
+        use crate::util::geo::haversine;
+
+        // a 5x5 grid of vertices 0.01 degrees apart with edges in both directions between
+        // neighbors. each edge is 1% to 41% longer than the straight line between its
+        // vertices, so the haversine distance to the destination never overestimates.
+        let vertices = (0..25)
+            .map(|i| {
+                let (row, col) = (i / 5, i % 5);
+                Vertex::new(i, -105.0 + col as f32 * 0.01, 39.7 + row as f32 * 0.01)
+            })
+            .collect::<Vec<_>>();
+        let mut edges = vec![];
+        for v in 0..25 {
+            let mut neighbors = vec![];
+            if v % 5 < 4 {
+                neighbors.push(v + 1);
+            }
+            if v / 5 < 4 {
+                neighbors.push(v + 5);
+            }
+            for n in neighbors {
+                for (src, dst) in [(v, n), (n, v)] {
+                    let edge_id = edges.len();
+                    let straight = haversine::coord_distance_meters(
+                        &vertices[src].coordinate,
+                        &vertices[dst].coordinate,
+                    )
+                    .unwrap();
+                    let detour = 1.01 + ((edge_id * 7) % 5) as f64 * 0.1;
+                    edges.push(Edge::new(edge_id, src, dst, straight.as_f64() * detour));
+                }
+            }
+        }
+        let si = distance_search_instance(graph_from_edges(vertices, edges));
+
+        let a_star: SearchAlgorithm =
+            serde_json::from_value(serde_json::json!({ "type": "a_star" })).unwrap();
+        let run = |alg: &SearchAlgorithm, o: usize, d: usize| {
+            let result = alg
+                .run_vertex_oriented(VertexId(o), Some(VertexId(d)), &Direction::Forward, &si)
+                .unwrap();
+            let cost = result.routes[0]
+                .iter()
+                .map(|et| et.total_cost().as_f64())
+                .sum::<f64>();
+            (cost, result.iterations)
+        };
+        let (mut a_star_iterations, mut dijkstra_iterations) = (0, 0);
+        for o in 0..25 {
+            for d in 0..25 {
+                let (a_star_cost, a_star_its) = run(&a_star, o, d);
+                let (dijkstra_cost, dijkstra_its) = run(&SearchAlgorithm::Dijkstra, o, d);
+                assert!(
+                    (a_star_cost - dijkstra_cost).abs() < 1e-6,
+                    "route {} to {} costs {} with a* but {} with dijkstra",
+                    o,
+                    d,
+                    a_star_cost,
+                    dijkstra_cost
+                );
+                a_star_iterations += a_star_its;
+                dijkstra_iterations += dijkstra_its;
+            }
+        }
+        // the heuristic guides the search toward the destination
+        assert!(
+            a_star_iterations < dijkstra_iterations,
+            "a* expanded {} vertices but dijkstra expanded {}",
+            a_star_iterations,
+            dijkstra_iterations
+        );
+    }
 }
@@ -0,0 +1,308 @@
+use super::bidirectional_a_star_algorithm::reorient_reverse_route;
+use crate::algorithm::search::{
+    backtrack, direction::Direction, search_algorithm_result::SearchAlgorithmResult,
+    search_error::SearchError, search_instance::SearchInstance, search_metrics::SearchMetrics,
+    search_tree_branch::SearchTreeBranch, MinSearchTree,
+};
+use crate::model::{
+    road_network::vertex_id::VertexId,
+    unit::{cost::ReverseCost, Cost},
+};
+use crate::util::priority_queue::InternalPriorityQueue;
+use std::collections::HashMap;
+use std::time::Instant;
+
+/// runs a bidirectional Dijkstra search between a source and target vertex.
+///
+/// a forward search from the source and a reverse search from the target take turns
+/// settling the vertex with the lowest cost in their frontier. whenever a search reaches
+/// a vertex the other search has reached, the combined cost is a candidate for the best
+/// route. the search ends once the lowest costs in both frontiers add up to at least
+/// the best candidate, which proves that no cheaper route remains.
+///
+/// the route is the forward search route to the meeting vertex followed by the reverse
+/// search route, re-traversed in the forward direction. when edge costs do not depend
+/// on the path taken to reach the edge, the route cost matches a unidirectional search.
+///
+/// # Returns
+///
+/// the route along with the forward and reverse search trees, in that order
+pub fn run_bidirectional_dijkstra(
+    source: VertexId,
+    target: VertexId,
+    si: &SearchInstance,
+) -> Result<SearchAlgorithmResult, SearchError> {
+    if source == target {
+        return Ok(SearchAlgorithmResult {
+            trees: vec![HashMap::new()],
+            routes: vec![vec![]],
+            iterations: 0,
+            metrics: SearchMetrics::default(),
+        });
+    }
+
+    let mut fwd = SearchFrontier::new(Direction::Forward, source);
+    let mut rev = SearchFrontier::new(Direction::Reverse, target);
+    let mut best: Option<(VertexId, Cost)> = None;
+
+    let start_time = Instant::now();
+    let mut iterations = 0;
+    loop {
+        si.termination_model
+            .test(&start_time, fwd.tree.len() + rev.tree.len(), iterations)?;
+
+        // once either frontier is exhausted, every route has been considered
+        let (fwd_min, rev_min) = match (fwd.min_cost(), rev.min_cost()) {
+            (Some(fwd_min), Some(rev_min)) => (fwd_min, rev_min),
+            _ => break,
+        };
+        if let Some((_, best_cost)) = best {
+            if fwd_min + rev_min >= best_cost {
+                break;
+            }
+        }
+
+        let (this, other) = if iterations % 2 == 0 {
+            (&mut fwd, &rev)
+        } else {
+            (&mut rev, &fwd)
+        };
+        for (vertex_id, cost) in this.expand(si)? {
+            if let Some(other_cost) = other.costs.get(&vertex_id) {
+                let total_cost = cost + *other_cost;
+                if best.map(|(_, c)| total_cost < c).unwrap_or(true) {
+                    best = Some((vertex_id, total_cost));
+                }
+            }
+        }
+        iterations += 1;
+    }
+    log::debug!(
+        "bidirectional search iterations: {}, size of forward tree: {}, size of reverse tree: {}",
+        iterations,
+        fwd.tree.len(),
+        rev.tree.len()
+    );
+
+    let (meeting_vertex_id, _) = best.ok_or(SearchError::NoPathExists(source, target))?;
+    let fwd_route = backtrack::vertex_oriented_route(source, meeting_vertex_id, &fwd.tree)?;
+    let rev_route = backtrack::vertex_oriented_route(target, meeting_vertex_id, &rev.tree)?;
+    let rev_route_forward = reorient_reverse_route(&fwd_route, &rev_route, si)?;
+    let route = fwd_route.into_iter().chain(rev_route_forward).collect();
+
+    Ok(SearchAlgorithmResult {
+        trees: vec![fwd.tree, rev.tree],
+        routes: vec![route],
+        iterations,
+        metrics: fwd.metrics.combine(&rev.metrics),
+    })
+}
+
+/// the state of one of the two searches of a bidirectional search
+struct SearchFrontier {
+    direction: Direction,
+    root: VertexId,
+    queue: InternalPriorityQueue<VertexId, ReverseCost>,
+    costs: HashMap<VertexId, Cost>,
+    tree: MinSearchTree,
+    metrics: SearchMetrics,
+}
+
+impl SearchFrontier {
+    fn new(direction: Direction, root: VertexId) -> SearchFrontier {
+        let mut queue = InternalPriorityQueue::default();
+        queue.push(root, Cost::ZERO.into());
+        SearchFrontier {
+            direction,
+            root,
+            queue,
+            costs: HashMap::from([(root, Cost::ZERO)]),
+            tree: HashMap::new(),
+            metrics: SearchMetrics::default(),
+        }
+    }
+
+    /// the lowest cost in the frontier, or None if the frontier is empty
+    fn min_cost(&self) -> Option<Cost> {
+        self.queue.peek().map(|(_, cost)| (**cost).0)
+    }
+
+    /// settles the vertex with the lowest cost in the frontier, traversing its incident
+    /// edges in the direction of this search.
+    ///
+    /// # Returns
+    ///
+    /// each vertex whose cost was improved, along with its new cost
+    fn expand(&mut self, si: &SearchInstance) -> Result<Vec<(VertexId, Cost)>, SearchError> {
+        self.metrics.max_frontier_size = self.metrics.max_frontier_size.max(self.queue.len());
+        let current_vertex_id = match self.queue.pop() {
+            None => return Ok(vec![]),
+            Some((vertex_id, _)) => vertex_id,
+        };
+        self.metrics.nodes_expanded += 1;
+        let current_cost = self
+            .costs
+            .get(&current_vertex_id)
+            .copied()
+            .unwrap_or(Cost::INFINITY);
+        let current_branch = match current_vertex_id == self.root {
+            true => None,
+            false => Some(self.tree.get(&current_vertex_id).ok_or_else(|| {
+                SearchError::InternalSearchError(format!(
+                    "expected vertex id {} missing from solution",
+                    current_vertex_id
+                ))
+            })?),
+        };
+        let last_edge_id = current_branch.map(|b| b.edge_traversal.edge_id);
+        let last_edge = match last_edge_id {
+            Some(id) => Some(si.directed_graph.get_edge(id)?),
+            None => None,
+        };
+        let current_state = match current_branch {
+            Some(branch) => branch.edge_traversal.result_state.clone(),
+            None => si.state_model.initial_state()?,
+        };
+
+        let direction = self.direction;
+        let mut improved = vec![];
+        for edge_id in direction.get_incident_edges(&current_vertex_id, si)? {
+            let e = si.directed_graph.get_edge(*edge_id)?;
+            let key_vertex_id = direction.tree_key_vertex_id(e);
+
+            let valid_frontier =
+                si.frontier_model
+                    .valid_frontier(e, &current_state, last_edge, &si.state_model)?;
+            if !valid_frontier {
+                continue;
+            }
+            let et =
+                direction.perform_edge_traversal(*edge_id, last_edge_id, &current_state, si)?;
+            self.metrics.edges_relaxed += 1;
+            let valid_result =
+                si.frontier_model
+                    .valid_result_state(e, &et.result_state, &si.state_model)?;
+            if !valid_result {
+                continue;
+            }
+
+            let tentative_cost = current_cost + et.total_cost();
+            let existing_cost = self
+                .costs
+                .get(&key_vertex_id)
+                .copied()
+                .unwrap_or(Cost::INFINITY);
+            if si.cost_model.improves_on(tentative_cost, existing_cost) {
+                self.costs.insert(key_vertex_id, tentative_cost);
+                let branch = SearchTreeBranch {
+                    terminal_vertex: direction.terminal_vertex_id(e),
+                    edge_traversal: et,
+                };
+                self.tree.insert(key_vertex_id, branch);
+                self.queue
+                    .push_increase(key_vertex_id, tentative_cost.into());
+                improved.push((key_vertex_id, tentative_cost));
+            }
+        }
+        Ok(improved)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algorithm::search::{
+        edge_traversal::EdgeTraversal, search_algorithm::SearchAlgorithm,
+    };
+    use crate::model::{
+        property::{edge::Edge, vertex::Vertex},
+        road_network::edge_id::EdgeId,
+        unit::as_f64::AsF64,
+    };
+    use crate::util::test_util::{distance_search_instance, graph_from_edges};
+
+    /// a 4x4 grid with edges in both directions between neighboring vertices,
+    /// with distinct distances so that each shortest route is unique, along with
+    /// vertex 16 which has no edges.
+    fn build_search_instance() -> SearchInstance {
+        let vertices = (0..17)
+            .map(|i| Vertex::new(i, 0.0, 0.0))
+            .collect::<Vec<_>>();
+        let mut edges = vec![];
+        for row in 0..4 {
+            for col in 0..4 {
+                let v = row * 4 + col;
+                let mut neighbors = vec![];
+                if col < 3 {
+                    neighbors.push(v + 1);
+                }
+                if row < 3 {
+                    neighbors.push(v + 4);
+                }
+                for n in neighbors {
+                    for (src, dst) in [(v, n), (n, v)] {
+                        let edge_id = edges.len();
+                        let distance = 10.0 + ((edge_id * 7) % 13) as f64 + edge_id as f64 * 0.01;
+                        edges.push(Edge::new(edge_id, src, dst, distance));
+                    }
+                }
+            }
+        }
+        distance_search_instance(graph_from_edges(vertices, edges))
+    }
+
+    fn route_summary(route: &[EdgeTraversal]) -> (Vec<EdgeId>, f64) {
+        let edge_ids = route.iter().map(|et| et.edge_id).collect();
+        let cost = route.iter().map(|et| et.total_cost().as_f64()).sum();
+        (edge_ids, cost)
+    }
+
+    #[test]
+    fn test_matches_unidirectional_search() {
+        let si = build_search_instance();
+        let run = |alg: SearchAlgorithm, source: usize, target: usize| {
+            let result = alg
+                .run_vertex_oriented(
+                    VertexId(source),
+                    Some(VertexId(target)),
+                    &Direction::Forward,
+                    &si,
+                )
+                .unwrap();
+            route_summary(&result.routes[0])
+        };
+        for source in 0..16 {
+            for target in 0..16 {
+                let (expected_edges, expected_cost) =
+                    run(SearchAlgorithm::Dijkstra, source, target);
+                let (edges, cost) = run(SearchAlgorithm::BidirectionalDijkstra, source, target);
+                assert_eq!(edges, expected_edges, "route {} to {}", source, target);
+                assert!(
+                    (cost - expected_cost).abs() < 1e-9,
+                    "route {} to {} costs {} but expected {}",
+                    source,
+                    target,
+                    cost,
+                    expected_cost
+                );
+            }
+        }
+        // the final state of the joined route is that of the forward search
+        let result = run_bidirectional_dijkstra(VertexId(0), VertexId(15), &si).unwrap();
+        let route = &result.routes[0];
+        let distance = route.last().unwrap().result_state[0].0;
+        let (_, cost) = route_summary(route);
+        assert!((distance - cost).abs() < 1e-9);
+        assert_eq!(result.trees.len(), 2);
+    }
+
+    #[test]
+    fn test_no_path_exists() {
+        let si = build_search_instance();
+        let result = run_bidirectional_dijkstra(VertexId(0), VertexId(16), &si);
+        assert!(matches!(
+            result,
+            Err(SearchError::NoPathExists(VertexId(0), VertexId(16)))
+        ));
+    }
+}
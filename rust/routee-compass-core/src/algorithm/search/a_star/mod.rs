@@ -1,2 +1,3 @@
 pub mod a_star_algorithm;
 pub mod bidirectional_a_star_algorithm;
+pub mod bidirectional_dijkstra_algorithm;
@@ -38,6 +38,24 @@ pub fn vertex_oriented_route(
     Ok(reversed)
 }
 
+/// finds the final edge traversal of the route from some source to some target vertex
+/// on a minimum shortest path tree, without reconstructing the route. the result state
+/// of this traversal is the final state of the route. returns None when the source is
+/// the target, in which case the route is empty.
+pub fn vertex_oriented_route_end(
+    source_id: VertexId,
+    target_id: VertexId,
+    solution: &HashMap<VertexId, SearchTreeBranch>,
+) -> Result<Option<&EdgeTraversal>, SearchError> {
+    if source_id == target_id {
+        return Ok(None);
+    }
+    solution
+        .get(&target_id)
+        .map(|branch| Some(&branch.edge_traversal))
+        .ok_or(SearchError::VertexMissingFromSearchTree(target_id))
+}
+
 /// edge-oriented backtrack method
 pub fn edge_oriented_route(
     source_id: EdgeId,
@@ -0,0 +1,134 @@
+use super::search_error::SearchError;
+use super::search_instance::SearchInstance;
+use crate::model::road_network::edge_id::EdgeId;
+use crate::model::unit::Cost;
+
+/// the traversal cost of every edge in the graph, computed once up front.
+/// only valid for static traversal models (see [`crate::model::traversal::traversal_model::TraversalModel::is_static`]),
+/// where the cost of an edge does not depend on the search state when arriving at it.
+/// when attached to a [`SearchInstance`], edge traversals read the cost from this
+/// cache instead of calling the cost model.
+pub struct EdgeCostCache {
+    costs: Vec<Cost>,
+}
+
+impl EdgeCostCache {
+    /// computes the traversal cost of each edge, starting from the initial state.
+    ///
+    /// # Arguments
+    ///
+    /// * `si` - the search assets used to compute each edge cost
+    ///
+    /// # Returns
+    ///
+    /// the cached costs indexed by EdgeId, or an error if the traversal model
+    /// is not static or any edge cost could not be computed.
+    pub fn build(si: &SearchInstance) -> Result<EdgeCostCache, SearchError> {
+        if !si.traversal_model.is_static() {
+            return Err(SearchError::BuildError(String::from(
+                "edge costs can only be cached for static traversal models",
+            )));
+        }
+        let initial_state = si.state_model.initial_state()?;
+        let costs = (0..si.directed_graph.n_edges())
+            .map(|idx| {
+                let trajectory = si.directed_graph.edge_triplet_attrs(EdgeId(idx))?;
+                let mut state = initial_state.clone();
+                si.traversal_model
+                    .traverse_edge(trajectory, &mut state, &si.state_model)?;
+                let (_, edge, _) = trajectory;
+                let cost = si.cost_model.traversal_cost(edge, &initial_state, &state)?;
+                Ok(cost)
+            })
+            .collect::<Result<Vec<_>, SearchError>>()?;
+        Ok(EdgeCostCache { costs })
+    }
+
+    /// the cached traversal cost of an edge, or None if the edge is not in the cache.
+    pub fn get(&self, edge_id: EdgeId) -> Option<Cost> {
+        self.costs.get(edge_id.as_usize()).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.costs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.costs.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algorithm::search::a_star::a_star_algorithm::run_a_star;
+    use crate::algorithm::search::backtrack::vertex_oriented_route;
+    use crate::algorithm::search::direction::Direction;
+    use crate::algorithm::search::edge_traversal::EdgeTraversal;
+    use crate::model::property::{edge::Edge, vertex::Vertex};
+    use crate::model::road_network::{graph::Graph, vertex_id::VertexId};
+    use crate::model::unit::as_f64::AsF64;
+    use crate::util::test_util::{distance_search_instance, graph_from_edges};
+    use std::sync::Arc;
+
+    /// (0) -[0]-> (1) -[1]-> (2), along with a longer shortcut (0) -[2]-> (2)
+    fn build_mock_graph() -> Graph {
+        let vertices = vec![
+            Vertex::new(0, 0.0, 0.0),
+            Vertex::new(1, 0.0, 0.0),
+            Vertex::new(2, 0.0, 0.0),
+        ];
+        let edges = vec![
+            Edge::new(0, 0, 1, 10.0),
+            Edge::new(1, 1, 2, 15.0),
+            Edge::new(2, 0, 2, 40.0),
+        ];
+        graph_from_edges(vertices, edges)
+    }
+
+    fn build_search_instance() -> SearchInstance {
+        distance_search_instance(build_mock_graph())
+    }
+
+    /// per-call traversal costs subtract the access cost, which is floored at
+    /// Cost::MIN_COST, so they may differ from the cached cost by that amount.
+    fn assert_cost_eq(a: Cost, b: Cost) {
+        assert!((a.as_f64() - b.as_f64()).abs() < 1e-6, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn test_cached_costs_match_per_call_costs() {
+        let mut si = build_search_instance();
+        let cache = EdgeCostCache::build(&si).unwrap();
+        assert_eq!(cache.len(), 3);
+
+        // costs computed per call, mid-route, match the costs cached from the initial state
+        let mut state = si.state_model.initial_state().unwrap();
+        let mut prev_edge_id = None;
+        for edge_id in [EdgeId(0), EdgeId(1)] {
+            let et = EdgeTraversal::forward_traversal(edge_id, prev_edge_id, &state, &si).unwrap();
+            assert_cost_eq(et.traversal_cost, cache.get(edge_id).unwrap());
+            state = et.result_state;
+            prev_edge_id = Some(edge_id);
+        }
+        let et = EdgeTraversal::forward_traversal(EdgeId(2), None, &state, &si).unwrap();
+        assert_cost_eq(et.traversal_cost, cache.get(EdgeId(2)).unwrap());
+
+        // searching with the cache attached finds the same route with the same costs
+        let (o, d) = (VertexId(0), VertexId(2));
+        let uncached_result = run_a_star(o, Some(d), &Direction::Forward, None, &si).unwrap();
+        let uncached_route = vertex_oriented_route(o, d, &uncached_result.tree).unwrap();
+        si.edge_cost_cache = Some(Arc::new(cache));
+        let cached_result = run_a_star(o, Some(d), &Direction::Forward, None, &si).unwrap();
+        let cached_route = vertex_oriented_route(o, d, &cached_result.tree).unwrap();
+        assert_eq!(uncached_route.len(), cached_route.len());
+        for (uncached, cached) in uncached_route.iter().zip(cached_route.iter()) {
+            assert_eq!(uncached.edge_id, cached.edge_id);
+            assert_cost_eq(uncached.total_cost(), cached.total_cost());
+        }
+        assert_eq!(
+            cached_route.iter().map(|et| et.edge_id).collect::<Vec<_>>(),
+            vec![EdgeId(0), EdgeId(1)]
+        );
+    }
+}
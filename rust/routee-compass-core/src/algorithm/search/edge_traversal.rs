@@ -20,6 +20,50 @@ impl EdgeTraversal {
     pub fn total_cost(&self) -> Cost {
         self.access_cost + self.traversal_cost
     }
+
+    /// walks a route and describes each of its edges, such as to debug the models
+    /// behind a route cost. each record has the edge id, the edge distance in meters,
+    /// the access, traversal and total cost of the edge, the state after traversing
+    /// it, and the change in state along it. states are serialized as in
+    /// [StateModel::serialize_state], in the unit of each feature.
+    ///
+    /// [StateModel::serialize_state]: crate::model::state::state_model::StateModel::serialize_state
+    ///
+    /// # Arguments
+    ///
+    /// * `route` - the edge traversals of a route, in order from the origin
+    /// * `si`    - the search assets used to find the route
+    ///
+    /// # Returns
+    ///
+    /// a JSON record for each edge of the route, or an error
+    pub fn route_breakdown(
+        route: &[EdgeTraversal],
+        si: &SearchInstance,
+    ) -> Result<Vec<serde_json::Value>, SearchError> {
+        let mut prev_state = si.state_model.initial_state()?;
+        let mut records = vec![];
+        for et in route.iter() {
+            let edge = si.directed_graph.get_edge(et.edge_id)?;
+            let delta = si
+                .state_model
+                .delta_vec(&prev_state, &et.result_state)?
+                .into_iter()
+                .map(|(_, delta)| delta)
+                .collect::<Vec<_>>();
+            records.push(serde_json::json!({
+                "edge_id": et.edge_id,
+                "edge_distance": edge.distance,
+                "access_cost": et.access_cost,
+                "traversal_cost": et.traversal_cost,
+                "cost": et.total_cost(),
+                "state": si.state_model.serialize_state(&et.result_state),
+                "state_delta": si.state_model.serialize_state(&delta),
+            }));
+            prev_state.clone_from(&et.result_state);
+        }
+        Ok(records)
+    }
 }
 
 impl Display for EdgeTraversal {
@@ -92,12 +136,27 @@ impl EdgeTraversal {
             .traverse_edge(traversal_trajectory, &mut result_state, &si.state_model)
             .map_err(SearchError::TraversalModelFailure)?;
 
-        let (_, edge, _) = traversal_trajectory;
-        let total_cost = si
+        let traversal_cost = match si
+            .edge_cost_cache
+            .as_ref()
+            .and_then(|c| c.get(next_edge_id))
+        {
+            Some(cached_cost) => cached_cost,
+            None => {
+                let (_, edge, _) = traversal_trajectory;
+                let total_cost = si
+                    .cost_model
+                    .traversal_cost(edge, prev_state, &result_state)
+                    .map_err(SearchError::CostError)?;
+                total_cost - access_cost
+            }
+        };
+        let traversal_cost = si
+            .cost_model
+            .apply_edge_cost_cap(next_edge_id, traversal_cost);
+        let traversal_cost = si
             .cost_model
-            .traversal_cost(edge, prev_state, &result_state)
-            .map_err(SearchError::CostError)?;
-        let traversal_cost = total_cost - access_cost;
+            .apply_edge_penalty(next_edge_id, traversal_cost);
 
         let result = EdgeTraversal {
             edge_id: next_edge_id,
@@ -116,6 +175,11 @@ impl EdgeTraversal {
     /// edges is the same as the forward traversal: (v1)-[prev]->(v2)-[next]->(v3)
     /// but the "next" edge is now the Optional edge.
     ///
+    /// a reverse search finds routes that arrive at its root, and those routes still
+    /// travel along each edge from its source to its destination vertex. so the
+    /// traversal model receives the edge in its own orientation, the same as in a
+    /// forward search, and direction-sensitive features such as grade keep their sign.
+    ///
     /// # Arguments
     ///
     /// * `prev_edge_id`     - the edge to traverse
@@ -171,12 +235,27 @@ impl EdgeTraversal {
             .traverse_edge(traversal_trajectory, &mut result_state, &si.state_model)
             .map_err(SearchError::TraversalModelFailure)?;
 
-        let (_, edge, _) = traversal_trajectory;
-        let total_cost = si
+        let traversal_cost = match si
+            .edge_cost_cache
+            .as_ref()
+            .and_then(|c| c.get(prev_edge_id))
+        {
+            Some(cached_cost) => cached_cost,
+            None => {
+                let (_, edge, _) = traversal_trajectory;
+                let total_cost = si
+                    .cost_model
+                    .traversal_cost(edge, prev_state, &result_state)
+                    .map_err(SearchError::CostError)?;
+                total_cost - access_cost
+            }
+        };
+        let traversal_cost = si
             .cost_model
-            .traversal_cost(edge, prev_state, &result_state)
-            .map_err(SearchError::CostError)?;
-        let traversal_cost = total_cost - access_cost;
+            .apply_edge_cost_cap(prev_edge_id, traversal_cost);
+        let traversal_cost = si
+            .cost_model
+            .apply_edge_penalty(prev_edge_id, traversal_cost);
 
         let result = EdgeTraversal {
             edge_id: prev_edge_id,
@@ -188,3 +267,93 @@ impl EdgeTraversal {
         Ok(result)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::{
+        cost::{
+            cost_aggregation::CostAggregation, cost_model::CostModel,
+            vehicle::vehicle_cost_rate::VehicleCostRate,
+        },
+        property::{edge::Edge, vertex::Vertex},
+        state::state_model::StateModel,
+        termination::termination_model::TerminationModel,
+        traversal::{
+            default::grade_distance_traversal_model::GradeDistanceTraversalModel,
+            traversal_model::TraversalModel,
+        },
+        unit::{as_f64::AsF64, DistanceUnit, Grade},
+    };
+    use crate::util::test_util::{distance_search_instance, graph_from_edges};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    /// a single 100 meter edge (0)->(1) with a 5% uphill grade, with a cost of the
+    /// grade distance, which adds a 50% penalty
+    fn build_search_instance() -> SearchInstance {
+        let vertices = vec![Vertex::new(0, 0.0, 0.0), Vertex::new(1, 0.0, 0.0)];
+        let edges = vec![Edge::new(0, 0, 1, 100.0)];
+        let graph = graph_from_edges(vertices, edges);
+        let traversal_model = Arc::new(
+            GradeDistanceTraversalModel::new(
+                vec![Grade::new(0.05)].into_boxed_slice(),
+                10.0,
+                0.0,
+                DistanceUnit::Meters,
+            )
+            .unwrap(),
+        );
+        let state_model = Arc::new(
+            StateModel::empty()
+                .extend(traversal_model.state_features())
+                .unwrap(),
+        );
+        let grade_distance = String::from(GradeDistanceTraversalModel::GRADE_DISTANCE);
+        let cost_model = CostModel::new(
+            Arc::new(HashMap::from([(grade_distance.clone(), 1.0)])),
+            Arc::new(HashMap::from([(grade_distance, VehicleCostRate::Raw)])),
+            Arc::new(HashMap::new()),
+            CostAggregation::Sum,
+            Arc::new(vec![]),
+            state_model.clone(),
+        )
+        .unwrap();
+        SearchInstance {
+            state_model,
+            traversal_model,
+            cost_model,
+            termination_model: Arc::new(TerminationModel::IterationsLimit { limit: 10 }),
+            ..distance_search_instance(graph)
+        }
+    }
+
+    #[test]
+    fn test_reverse_traversal_keeps_edge_orientation() {
+        let si = build_search_instance();
+        let initial = si.state_model.initial_state().unwrap();
+        let forward = EdgeTraversal::forward_traversal(EdgeId(0), None, &initial, &si).unwrap();
+        let reverse = EdgeTraversal::reverse_traversal(EdgeId(0), None, &initial, &si).unwrap();
+        // the uphill penalty applies in both searches, since the edge is driven uphill
+        assert!((forward.total_cost().as_f64() - 150.0).abs() < 1e-9);
+        assert_eq!(forward.total_cost(), reverse.total_cost());
+    }
+
+    #[test]
+    fn test_route_breakdown() {
+        let si = build_search_instance();
+        let initial = si.state_model.initial_state().unwrap();
+        let first = EdgeTraversal::forward_traversal(EdgeId(0), None, &initial, &si).unwrap();
+        let second =
+            EdgeTraversal::forward_traversal(EdgeId(0), None, &first.result_state, &si).unwrap();
+        let records = EdgeTraversal::route_breakdown(&[first, second], &si).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1]["edge_id"], 0);
+        assert_eq!(records[1]["edge_distance"], 100.0);
+        assert_eq!(records[1]["cost"], 150.0);
+        // the state accumulates along the route, while the delta is of one edge
+        assert_eq!(records[1]["state"]["grade_distance"], 300.0);
+        assert_eq!(records[1]["state_delta"]["grade_distance"], 150.0);
+        assert_eq!(records[1]["state_delta"]["distance"], 100.0);
+    }
+}
@@ -0,0 +1,333 @@
+use super::a_star::a_star_algorithm::run_a_star;
+use super::direction::Direction;
+use super::reachable_edges::{reachable_edges, EdgeReachability, ReachableEdge};
+use super::search_error::SearchError;
+use super::search_instance::SearchInstance;
+use super::MinSearchTree;
+use crate::model::frontier::{
+    frontier_model::FrontierModel, frontier_model_error::FrontierModelError,
+};
+use crate::model::property::edge::Edge;
+use crate::model::road_network::{graph::Graph, vertex_id::VertexId};
+use crate::model::state::state_model::StateModel;
+use crate::model::traversal::state::state_variable::StateVar;
+use geo::{ConcaveHull, ConvexHull, Coord, MultiPoint, Point, Polygon};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// the edges reachable from an origin within one threshold of a state feature
+#[derive(Serialize, Clone, Debug)]
+pub struct Isochrone {
+    pub threshold: f64,
+    pub edges: Vec<ReachableEdge>,
+}
+
+/// the shape of the polygon drawn around the reachable edges of an isochrone.
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum IsochroneHull {
+    /// the smallest convex polygon containing the reachable points
+    Convex,
+    /// a polygon that follows the reachable points more closely, where a lower
+    /// concavity gives a tighter fit. see [geo::ConcaveHull].
+    Concave { concavity: f32 },
+}
+
+/// finds the edges reachable from an origin within each of several thresholds on a
+/// state feature, such as time, distance or energy, with a single search. the search
+/// stops expanding any branch once the feature exceeds the largest threshold.
+///
+/// # Arguments
+///
+/// * `origin`          - the vertex to search from
+/// * `feature`         - name of the state feature the thresholds apply to
+/// * `thresholds`      - limits on the feature value, in the unit of the feature
+/// * `include_partial` - whether to include boundary edges
+/// * `si`              - the search assets
+///
+/// # Returns
+///
+/// an isochrone for each threshold, in the order of the thresholds, or an error
+pub fn run_isochrones(
+    origin: VertexId,
+    feature: &String,
+    thresholds: &[f64],
+    include_partial: bool,
+    si: &SearchInstance,
+) -> Result<Vec<Isochrone>, SearchError> {
+    let max_threshold = max_threshold(thresholds)?;
+    if !si.state_model.contains_key(feature) {
+        return Err(SearchError::BuildError(format!(
+            "isochrone feature '{}' is not in the state model, found [{}]",
+            feature,
+            si.state_model.get_names()
+        )));
+    }
+    let bounded_si = bounded_search_instance(si, feature, max_threshold);
+    let result = run_a_star(origin, None, &Direction::Forward, None, &bounded_si)?;
+    isochrones_from_tree(
+        origin,
+        &result.tree,
+        feature,
+        thresholds,
+        include_partial,
+        si,
+    )
+}
+
+/// finds the edges reachable within each of several thresholds from a search tree
+/// rooted at the origin, such as one built by a search without a destination. the
+/// reachable edges are found once for the largest threshold and then split by the
+/// state feature value at each end of the edge, so the edges of a threshold are
+/// always a subset of the edges of any larger threshold.
+///
+/// # Arguments
+///
+/// * `origin`          - the root of the search tree
+/// * `tree`            - the search tree
+/// * `feature`         - name of the state feature the thresholds apply to
+/// * `thresholds`      - limits on the feature value, in the unit of the feature
+/// * `include_partial` - whether to include boundary edges
+/// * `si`              - the search assets used to build the tree
+///
+/// # Returns
+///
+/// an isochrone for each threshold, in the order of the thresholds, or an error
+pub fn isochrones_from_tree(
+    origin: VertexId,
+    tree: &MinSearchTree,
+    feature: &String,
+    thresholds: &[f64],
+    include_partial: bool,
+    si: &SearchInstance,
+) -> Result<Vec<Isochrone>, SearchError> {
+    let max_threshold = max_threshold(thresholds)?;
+    let candidates = reachable_edges(origin, tree, feature, max_threshold, true, si)?;
+    let isochrones = thresholds
+        .iter()
+        .map(|threshold| {
+            let edges = candidates
+                .iter()
+                .filter_map(|edge| within_threshold(edge, *threshold, include_partial))
+                .collect();
+            Isochrone {
+                threshold: *threshold,
+                edges,
+            }
+        })
+        .collect();
+    Ok(isochrones)
+}
+
+/// builds a polygon around the reachable edges of an isochrone, from the coordinates
+/// of the origin and of the endpoints of each edge. boundary edges contribute the
+/// point along the straight line between their vertices at the reachable fraction.
+///
+/// # Arguments
+///
+/// * `edges`  - the reachable edges
+/// * `origin` - the vertex the edges were reached from
+/// * `hull`   - the shape of the polygon
+/// * `graph`  - the graph of the edges
+///
+/// # Returns
+///
+/// the polygon, which is empty if there are fewer than three distinct points, or an error
+pub fn isochrone_hull(
+    edges: &[ReachableEdge],
+    origin: VertexId,
+    hull: &IsochroneHull,
+    graph: &Graph,
+) -> Result<Polygon<f32>, SearchError> {
+    let mut points: Vec<Point<f32>> = vec![graph.get_vertex(origin)?.coordinate.0.into()];
+    for reachable in edges.iter() {
+        let edge = graph.get_edge(reachable.edge_id)?;
+        let src: Coord<f32> = graph.get_vertex(edge.src_vertex_id)?.coordinate.0;
+        let dst: Coord<f32> = graph.get_vertex(edge.dst_vertex_id)?.coordinate.0;
+        let end = match reachable.reachability {
+            EdgeReachability::Full => dst,
+            EdgeReachability::Partial { fraction } => src + (dst - src) * fraction as f32,
+        };
+        points.push(src.into());
+        points.push(end.into());
+    }
+    let points = MultiPoint::new(points);
+    let polygon = match hull {
+        IsochroneHull::Convex => points.convex_hull(),
+        IsochroneHull::Concave { concavity } => points.concave_hull(*concavity),
+    };
+    Ok(polygon)
+}
+
+/// the largest threshold, confirming that there is at least one and that all are
+/// non-negative numbers
+fn max_threshold(thresholds: &[f64]) -> Result<f64, SearchError> {
+    if let Some(t) = thresholds.iter().find(|t| !t.is_finite() || **t < 0.0) {
+        return Err(SearchError::BuildError(format!(
+            "isochrone thresholds must be non-negative numbers, found {}",
+            t
+        )));
+    }
+    thresholds
+        .iter()
+        .copied()
+        .reduce(f64::max)
+        .ok_or_else(|| SearchError::BuildError(String::from("no isochrone thresholds provided")))
+}
+
+/// the part of an edge reachable within a threshold, if any
+fn within_threshold(
+    edge: &ReachableEdge,
+    threshold: f64,
+    include_partial: bool,
+) -> Option<ReachableEdge> {
+    let reachability = if edge.dst_value <= threshold {
+        EdgeReachability::Full
+    } else if include_partial && edge.src_value <= threshold {
+        let fraction = (threshold - edge.src_value) / (edge.dst_value - edge.src_value);
+        EdgeReachability::Partial { fraction }
+    } else {
+        return None;
+    };
+    Some(ReachableEdge {
+        reachability,
+        ..edge.clone()
+    })
+}
+
+/// a copy of the search instance that stops expanding any branch whose feature
+/// value exceeds the limit. the tree of a search without a destination is then
+/// only as large as needed to find the edges reachable within the limit.
+pub fn bounded_search_instance(si: &SearchInstance, feature: &str, limit: f64) -> SearchInstance {
+    let frontier_model = FeatureLimitFrontierModel {
+        underlying: si.frontier_model.clone(),
+        feature: String::from(feature),
+        limit,
+    };
+    SearchInstance {
+        frontier_model: Arc::new(frontier_model),
+        ..si.clone()
+    }
+}
+
+/// prunes search branches beyond the largest isochrone threshold, in addition to any
+/// restrictions of the underlying frontier model
+struct FeatureLimitFrontierModel {
+    underlying: Arc<dyn FrontierModel>,
+    feature: String,
+    limit: f64,
+}
+
+impl FrontierModel for FeatureLimitFrontierModel {
+    fn valid_frontier(
+        &self,
+        edge: &Edge,
+        state: &[StateVar],
+        previous_edge: Option<&Edge>,
+        state_model: &StateModel,
+    ) -> Result<bool, FrontierModelError> {
+        self.underlying
+            .valid_frontier(edge, state, previous_edge, state_model)
+    }
+
+    fn valid_result_state(
+        &self,
+        edge: &Edge,
+        result_state: &[StateVar],
+        state_model: &StateModel,
+    ) -> Result<bool, FrontierModelError> {
+        let value = state_model
+            .get_state_variable(result_state, &self.feature)
+            .map_err(|e| FrontierModelError::StateError(e.to_string()))?;
+        if value.0 > self.limit {
+            return Ok(false);
+        }
+        self.underlying
+            .valid_result_state(edge, result_state, state_model)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::property::vertex::Vertex;
+    use crate::model::road_network::edge_id::EdgeId;
+    use crate::util::test_util::{distance_search_instance, graph_from_edges};
+    use geo::{Area, Contains};
+    use std::collections::HashSet;
+
+    /// (4) <-[3]- (0) -[0]-> (1) -[1]-> (2) -[2]-> (3), with edges of 30, 10, 10
+    /// and 20 meters, drawn around a square of side 0.001
+    fn build_search_instance() -> SearchInstance {
+        let vertices = vec![
+            Vertex::new(0, 0.0, 0.0),
+            Vertex::new(1, 0.001, 0.0),
+            Vertex::new(2, 0.001, 0.001),
+            Vertex::new(3, 0.0, 0.001),
+            Vertex::new(4, -0.001, 0.0),
+        ];
+        let edges = vec![
+            Edge::new(0, 0, 1, 10.0),
+            Edge::new(1, 1, 2, 10.0),
+            Edge::new(2, 2, 3, 20.0),
+            Edge::new(3, 0, 4, 30.0),
+        ];
+        distance_search_instance(graph_from_edges(vertices, edges))
+    }
+
+    #[test]
+    fn test_nested_isochrones() {
+        let si = build_search_instance();
+        let origin = VertexId(0);
+        let feature = String::from("distance");
+        let thresholds = [15.0, 25.0, 40.0];
+        let isochrones = run_isochrones(origin, &feature, &thresholds, true, &si).unwrap();
+        assert_eq!(isochrones.len(), 3);
+
+        // 15 meters covers edge 0 and half of edges 1 and 3
+        let reachability = isochrones[0]
+            .edges
+            .iter()
+            .map(|e| (e.edge_id, e.reachability))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            reachability,
+            vec![
+                (EdgeId(0), EdgeReachability::Full),
+                (EdgeId(3), EdgeReachability::Partial { fraction: 0.5 }),
+                (EdgeId(1), EdgeReachability::Partial { fraction: 0.5 }),
+            ]
+        );
+
+        // each tighter threshold reaches a subset of the edges of a looser one
+        let edge_sets = isochrones
+            .iter()
+            .map(|i| i.edges.iter().map(|e| e.edge_id).collect::<HashSet<_>>())
+            .collect::<Vec<_>>();
+        for pair in edge_sets.windows(2) {
+            assert!(pair[0].is_subset(&pair[1]), "{:?}", pair);
+        }
+        assert_eq!(edge_sets[2].len(), 4);
+
+        // and its hull is no larger
+        let hulls = isochrones
+            .iter()
+            .map(|i| isochrone_hull(&i.edges, origin, &IsochroneHull::Convex, &si.directed_graph))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        for pair in hulls.windows(2) {
+            assert!(pair[0].unsigned_area() <= pair[1].unsigned_area());
+        }
+        assert!(hulls[0].unsigned_area() > 0.0);
+        assert!(hulls[2].contains(&Point::new(0.0005, 0.0005)));
+
+        // a search bounded by 25 meters does not reach vertices 3 or 4
+        let bounded_si = bounded_search_instance(&si, &feature, 25.0);
+        let result = run_a_star(origin, None, &Direction::Forward, None, &bounded_si).unwrap();
+        assert!(!result.tree.contains_key(&VertexId(3)));
+        assert!(!result.tree.contains_key(&VertexId(4)));
+
+        assert!(run_isochrones(origin, &feature, &[], true, &si).is_err());
+        assert!(run_isochrones(origin, &feature, &[-1.0], true, &si).is_err());
+    }
+}
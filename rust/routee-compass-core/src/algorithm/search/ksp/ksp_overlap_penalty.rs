@@ -0,0 +1,241 @@
+use crate::{
+    algorithm::search::{
+        direction::Direction, edge_traversal::EdgeTraversal, search_algorithm::SearchAlgorithm,
+        search_algorithm_result::SearchAlgorithmResult, search_error::SearchError,
+        search_instance::SearchInstance, search_metrics::SearchMetrics,
+    },
+    model::{
+        road_network::{edge_id::EdgeId, vertex_id::VertexId},
+        unit::as_f64::AsF64,
+    },
+};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+/// number of searches allowed for each requested route before giving up on
+/// finding more alternatives
+const MAX_SEARCHES_PER_ROUTE: usize = 5;
+
+/// generates up to k alternative routes by iteratively penalizing the edges of
+/// previously found routes. after each search, the traversal cost of each edge
+/// of the route found is multiplied by the overlap penalty for the following
+/// searches, compounding for edges found by several searches, so that each
+/// successive route diverges from the previous ones.
+///
+/// overlap is the fraction of the distance of a route that it shares with another
+/// route. a route that repeats a previous route, or that overlaps any previous
+/// route by more than the max overlap when provided, is discarded. the routes
+/// returned have the costs of the unpenalized search instance.
+pub fn run(
+    source: VertexId,
+    target: VertexId,
+    k: usize,
+    overlap_penalty: f64,
+    max_overlap: Option<f64>,
+    si: &SearchInstance,
+    underlying: &SearchAlgorithm,
+) -> Result<SearchAlgorithmResult, SearchError> {
+    let mut penalties: HashMap<EdgeId, f64> = HashMap::new();
+    let mut trees = vec![];
+    let mut solution: Vec<Vec<EdgeTraversal>> = vec![];
+    let mut iterations = 0;
+    let mut metrics = SearchMetrics::default();
+    for ksp_it in 0..(k * MAX_SEARCHES_PER_ROUTE) {
+        if solution.len() == k {
+            log::debug!("ksp:{} solution contains {} entries, quitting", ksp_it, k);
+            break;
+        }
+        let penalized_si = penalized_search_instance(si, &penalties)?;
+        let SearchAlgorithmResult {
+            trees: search_trees,
+            routes,
+            iterations: search_iterations,
+            metrics: search_metrics,
+        } = underlying.run_vertex_oriented(
+            source,
+            Some(target),
+            &Direction::Forward,
+            &penalized_si,
+        )?;
+        iterations += search_iterations;
+        metrics = metrics.combine(&search_metrics);
+        let penalized_route = routes.into_iter().next().ok_or_else(|| {
+            SearchError::InternalSearchError(String::from(
+                "ksp underlying search returned no route",
+            ))
+        })?;
+        let this_route = evaluate_route(&penalized_route, si)?;
+
+        for et in this_route.iter() {
+            let penalty = penalties.entry(et.edge_id).or_insert(1.0);
+            *penalty *= overlap_penalty;
+        }
+
+        let mut accept_route = true;
+        for solution_route in solution.iter() {
+            let overlap = route_overlap(&this_route, solution_route, si)?;
+            if overlap >= 1.0 || max_overlap.map(|max| overlap > max).unwrap_or_default() {
+                log::debug!("ksp:{} overlaps a previous route by {}", ksp_it, overlap);
+                accept_route = false;
+                break;
+            }
+        }
+        if accept_route {
+            log::debug!("ksp:{} alternative accepted", ksp_it);
+            solution.push(this_route);
+            trees.extend(search_trees);
+        }
+    }
+
+    Ok(SearchAlgorithmResult {
+        trees,
+        routes: solution,
+        iterations,
+        metrics,
+    })
+}
+
+/// the fraction of the distance of route `a` on edges that are also in route `b`.
+/// a route with no distance has no overlap.
+pub fn route_overlap(
+    a: &[EdgeTraversal],
+    b: &[EdgeTraversal],
+    si: &SearchInstance,
+) -> Result<f64, SearchError> {
+    let b_edges = b.iter().map(|et| et.edge_id).collect::<HashSet<_>>();
+    let mut total = 0.0;
+    let mut shared = 0.0;
+    for et in a.iter() {
+        let distance = si.directed_graph.get_edge(et.edge_id)?.distance.as_f64();
+        total += distance;
+        if b_edges.contains(&et.edge_id) {
+            shared += distance;
+        }
+    }
+    if total == 0.0 {
+        Ok(0.0)
+    } else {
+        Ok(shared / total)
+    }
+}
+
+/// copies a search instance, replacing the cost model with one that applies the
+/// edge penalties.
+fn penalized_search_instance(
+    si: &SearchInstance,
+    penalties: &HashMap<EdgeId, f64>,
+) -> Result<SearchInstance, SearchError> {
+    let cost_model = si
+        .cost_model
+        .clone()
+        .with_edge_penalties(Some(Arc::new(penalties.clone())))?;
+    Ok(SearchInstance {
+        cost_model,
+        ..si.clone()
+    })
+}
+
+/// re-traverses the edges of a route found with penalized costs, so that its
+/// costs and states are those of the unpenalized search instance.
+fn evaluate_route(
+    route: &[EdgeTraversal],
+    si: &SearchInstance,
+) -> Result<Vec<EdgeTraversal>, SearchError> {
+    let mut result: Vec<EdgeTraversal> = Vec::with_capacity(route.len());
+    let mut state = si.state_model.initial_state()?;
+    for et in route.iter() {
+        let prev_edge_id = result.last().map(|prev| prev.edge_id);
+        let traversal = EdgeTraversal::forward_traversal(et.edge_id, prev_edge_id, &state, si)?;
+        state.clone_from(&traversal.result_state);
+        result.push(traversal);
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::property::{edge::Edge, vertex::Vertex};
+    use crate::util::test_util::{distance_search_instance, graph_from_edges};
+
+    /// a graph from (0) to (3) with three routes of increasing distance:
+    /// (0)-(1)-(2)-(3) of 30, (0)-(1)-(5)-(3) of 31, which shares (0)-(1)
+    /// with the first route, and (0)-(4)-(3) of 32.
+    fn build_search_instance() -> SearchInstance {
+        let vertices = (0..6).map(|i| Vertex::new(i, 0.0, 0.0)).collect::<Vec<_>>();
+        let edges = vec![
+            Edge::new(0, 0, 1, 10.0),
+            Edge::new(1, 1, 2, 10.0),
+            Edge::new(2, 2, 3, 10.0),
+            Edge::new(3, 1, 5, 10.0),
+            Edge::new(4, 5, 3, 11.0),
+            Edge::new(5, 0, 4, 16.0),
+            Edge::new(6, 4, 3, 16.0),
+        ];
+        distance_search_instance(graph_from_edges(vertices, edges))
+    }
+
+    fn route_edges(route: &[EdgeTraversal]) -> Vec<EdgeId> {
+        route.iter().map(|et| et.edge_id).collect()
+    }
+
+    #[test]
+    fn test_alternatives_limit_overlap() {
+        let si = build_search_instance();
+        let run_with_max_overlap = |max_overlap: f64| {
+            run(
+                VertexId(0),
+                VertexId(3),
+                3,
+                2.0,
+                Some(max_overlap),
+                &si,
+                &SearchAlgorithm::Dijkstra,
+            )
+            .unwrap()
+        };
+
+        // the (0)-(1)-(5)-(3) route shares 10 of its 31 distance with the optimal route,
+        // and is found last, once the (0)-(4)-(3) route is penalized as well
+        let result = run_with_max_overlap(0.5);
+        let routes = result
+            .routes
+            .iter()
+            .map(|r| route_edges(r))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            routes,
+            vec![
+                vec![EdgeId(0), EdgeId(1), EdgeId(2)],
+                vec![EdgeId(5), EdgeId(6)],
+                vec![EdgeId(0), EdgeId(3), EdgeId(4)],
+            ]
+        );
+        for (i, a) in result.routes.iter().enumerate() {
+            for b in result.routes.iter().skip(i + 1) {
+                assert!(route_overlap(b, a, &si).unwrap() <= 0.5);
+            }
+        }
+        // returned routes carry their unpenalized costs
+        let optimal_cost = result.routes[0]
+            .iter()
+            .map(|et| et.traversal_cost.as_f64())
+            .sum::<f64>();
+        assert!((optimal_cost - 30.0).abs() < 1e-9, "{}", optimal_cost);
+
+        // with a lower max overlap, the near-duplicate route is discarded
+        let result = run_with_max_overlap(0.3);
+        let routes = result
+            .routes
+            .iter()
+            .map(|r| route_edges(r))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            routes,
+            vec![
+                vec![EdgeId(0), EdgeId(1), EdgeId(2)],
+                vec![EdgeId(5), EdgeId(6)]
+            ]
+        );
+    }
+}
@@ -6,17 +6,23 @@ use crate::{
         search_algorithm_result::SearchAlgorithmResult, search_error::SearchError,
         search_instance::SearchInstance,
     },
-    model::{road_network::vertex_id::VertexId, unit::cost::ReverseCost},
+    model::{
+        road_network::vertex_id::VertexId,
+        unit::{as_f64::AsF64, cost::ReverseCost, Cost},
+    },
     util::priority_queue::InternalPriorityQueue,
 };
 use std::collections::HashMap;
 
 /// generates a set of k-shortest paths using the single-via path algorithm.
+/// if a max detour factor is provided, alternatives that cost more than that
+/// factor times the cost of the optimal route are discarded.
 pub fn run(
     source: VertexId,
     target: VertexId,
     k: usize,
     similarity: &RouteSimilarityFunction,
+    max_detour_factor: Option<f64>,
     si: &SearchInstance,
     underlying: &SearchAlgorithm,
 ) -> Result<SearchAlgorithmResult, SearchError> {
@@ -25,11 +31,13 @@ pub fn run(
         trees: fwd_trees,
         routes: _,
         iterations: fwd_iterations,
+        metrics: fwd_metrics,
     } = underlying.run_vertex_oriented(source, Some(target), &Direction::Forward, si)?;
     let SearchAlgorithmResult {
         trees: rev_trees,
         routes: _,
         iterations: rev_iterations,
+        metrics: rev_metrics,
     } = underlying.run_vertex_oriented(target, Some(source), &Direction::Reverse, si)?;
     if fwd_trees.len() != 1 {
         Err(SearchError::InternalSearchError(format!(
@@ -71,6 +79,7 @@ pub fn run(
     log::debug!("ksp intersection has {} vertices", intersection_queue.len());
 
     let tsp = backtrack::vertex_oriented_route(source, target, fwd_tree)?;
+    let tsp_cost = route_cost(&tsp);
     let mut solution: Vec<Vec<EdgeTraversal>> = vec![tsp];
     let mut ksp_it: u64 = 0;
     loop {
@@ -103,6 +112,12 @@ pub fn run(
                     accept_route = false;
                 }
 
+                // test detour
+                if !within_detour_factor(&this_route, tsp_cost, max_detour_factor) {
+                    log::debug!("ksp:{} exceeds max detour factor", ksp_it);
+                    accept_route = false;
+                }
+
                 // test similarity
                 for solution_route in solution.iter() {
                     let similarity_value =
@@ -129,6 +144,58 @@ pub fn run(
         trees: vec![fwd_tree.clone(), rev_tree.clone()], // todo: figure out how to avoid this clone
         routes: solution,
         iterations: fwd_iterations + rev_iterations + ksp_it, // todo: figure out how to report individually
+        metrics: fwd_metrics.combine(&rev_metrics),
     };
     Ok(result)
 }
+
+/// the total cost of a route
+fn route_cost(route: &[EdgeTraversal]) -> Cost {
+    route
+        .iter()
+        .fold(Cost::ZERO, |acc, et| acc + et.total_cost())
+}
+
+/// tests that a route does not cost more than the max detour factor times the optimal cost.
+/// if no factor is provided, all routes are accepted.
+fn within_detour_factor(
+    route: &[EdgeTraversal],
+    optimal_cost: Cost,
+    max_detour_factor: Option<f64>,
+) -> bool {
+    match max_detour_factor {
+        None => true,
+        Some(factor) => route_cost(route).as_f64() <= optimal_cost.as_f64() * factor,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::road_network::edge_id::EdgeId;
+
+    fn mock_route(costs: &[f64]) -> Vec<EdgeTraversal> {
+        costs
+            .iter()
+            .enumerate()
+            .map(|(idx, cost)| EdgeTraversal {
+                edge_id: EdgeId(idx),
+                access_cost: Cost::ZERO,
+                traversal_cost: Cost::new(*cost),
+                result_state: vec![],
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_detour_factor() {
+        let optimal = mock_route(&[5.0, 5.0]);
+        let alternative = mock_route(&[10.0, 8.0]);
+        let optimal_cost = route_cost(&optimal);
+        assert!(within_detour_factor(&optimal, optimal_cost, Some(1.0)));
+        // alternative is 1.8x the optimal cost
+        assert!(!within_detour_factor(&alternative, optimal_cost, Some(1.5)));
+        assert!(within_detour_factor(&alternative, optimal_cost, Some(2.0)));
+        assert!(within_detour_factor(&alternative, optimal_cost, None));
+    }
+}
@@ -0,0 +1,323 @@
+use crate::{
+    algorithm::search::{
+        direction::Direction, edge_traversal::EdgeTraversal, search_algorithm::SearchAlgorithm,
+        search_algorithm_result::SearchAlgorithmResult, search_error::SearchError,
+        search_instance::SearchInstance,
+    },
+    model::{
+        frontier::{frontier_model::FrontierModel, frontier_model_error::FrontierModelError},
+        property::edge::Edge,
+        road_network::{edge_id::EdgeId, vertex_id::VertexId},
+        state::state_model::StateModel,
+        traversal::state::state_variable::StateVar,
+        unit::Cost,
+    },
+};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// generates up to k loopless routes in order of increasing cost using Yen's algorithm.
+///
+/// after the optimal route is found, each following route is found by deviating from
+/// the previous route. for each vertex of the previous route, a spur search runs from
+/// that vertex to the target, with the edges that leave the shared root path of any
+/// accepted route removed along with the vertices of the root path. the root path joined
+/// with the spur route becomes a candidate, and the lowest-cost candidate is accepted.
+///
+/// candidates are costed by re-traversing the complete route, so the costs returned are
+/// those of the search instance. routes of equal cost, including the optimal route, are
+/// returned in the order of their edge ids, so that results are reproducible. when fewer
+/// than k routes exist, all of them are returned.
+///
+/// spur searches begin from the initial search state, so with state-dependent costs a
+/// candidate may not be the cheapest deviation at its spur vertex.
+pub fn run(
+    source: VertexId,
+    target: VertexId,
+    k: usize,
+    si: &SearchInstance,
+    underlying: &SearchAlgorithm,
+) -> Result<SearchAlgorithmResult, SearchError> {
+    let SearchAlgorithmResult {
+        trees,
+        routes,
+        mut iterations,
+        mut metrics,
+    } = underlying.run_vertex_oriented(source, Some(target), &Direction::Forward, si)?;
+    let optimal_route = routes.into_iter().next().ok_or_else(|| {
+        SearchError::InternalSearchError(String::from("ksp underlying search returned no route"))
+    })?;
+
+    let mut solution: Vec<Vec<EdgeTraversal>> = vec![optimal_route];
+    let mut candidates: Vec<(Cost, Vec<EdgeId>, Vec<EdgeTraversal>)> = vec![];
+    let mut found: HashSet<Vec<EdgeId>> = HashSet::from([route_edges(&solution[0])]);
+    while solution.len() < k {
+        let previous_route = solution.last().ok_or_else(|| {
+            SearchError::InternalSearchError(String::from("ksp solution is empty"))
+        })?;
+        let previous_vertices = route_vertices(source, previous_route, si)?;
+        let previous_edges = route_edges(previous_route);
+
+        for spur_index in 0..previous_route.len() {
+            let spur_vertex = previous_vertices[spur_index];
+            let root_edges = &previous_edges[0..spur_index];
+
+            // remove the next edge of each accepted route sharing this root path, and
+            // the root path itself, so the spur route deviates here and has no loop
+            let blocked_edges = solution
+                .iter()
+                .filter(|route| {
+                    route.len() > spur_index
+                        && route[0..spur_index]
+                            .iter()
+                            .map(|et| et.edge_id)
+                            .eq(root_edges.iter().copied())
+                })
+                .map(|route| route[spur_index].edge_id)
+                .collect::<HashSet<_>>();
+            let blocked_vertices = previous_vertices[0..spur_index]
+                .iter()
+                .copied()
+                .collect::<HashSet<_>>();
+            let spur_si = blocked_search_instance(si, blocked_edges, blocked_vertices);
+
+            let spur_result = match underlying.run_vertex_oriented(
+                spur_vertex,
+                Some(target),
+                &Direction::Forward,
+                &spur_si,
+            ) {
+                Ok(result) => result,
+                Err(SearchError::NoPathExists(_, _)) => continue,
+                Err(e) => return Err(e),
+            };
+            iterations += spur_result.iterations;
+            metrics = metrics.combine(&spur_result.metrics);
+            let spur_route = spur_result.routes.into_iter().next().ok_or_else(|| {
+                SearchError::InternalSearchError(String::from("ksp spur search returned no route"))
+            })?;
+
+            let candidate_edges = root_edges
+                .iter()
+                .copied()
+                .chain(spur_route.iter().map(|et| et.edge_id))
+                .collect::<Vec<_>>();
+            if found.insert(candidate_edges.clone()) {
+                let candidate_route = evaluate_edges(&candidate_edges, si)?;
+                let cost = route_cost(&candidate_route);
+                candidates.push((cost, candidate_edges, candidate_route));
+            }
+        }
+
+        // accept the lowest-cost candidate, breaking ties by edge ids
+        let next = candidates
+            .iter()
+            .enumerate()
+            .min_by(|(_, (a_cost, a_edges, _)), (_, (b_cost, b_edges, _))| {
+                a_cost.cmp(b_cost).then_with(|| a_edges.cmp(b_edges))
+            })
+            .map(|(index, _)| index);
+        match next {
+            None => {
+                log::debug!("ksp found {} routes, no candidates remain", solution.len());
+                break;
+            }
+            Some(index) => {
+                let (_, _, route) = candidates.swap_remove(index);
+                solution.push(route);
+            }
+        }
+    }
+
+    // the optimal route is not a candidate, so equal-cost routes are re-ordered by edge ids
+    let mut ordered = solution
+        .into_iter()
+        .map(|route| (route_cost(&route), route_edges(&route), route))
+        .collect::<Vec<_>>();
+    ordered.sort_by(|(a_cost, a_edges, _), (b_cost, b_edges, _)| {
+        a_cost.cmp(b_cost).then_with(|| a_edges.cmp(b_edges))
+    });
+    let routes = ordered.into_iter().map(|(_, _, route)| route).collect();
+
+    Ok(SearchAlgorithmResult {
+        trees,
+        routes,
+        iterations,
+        metrics,
+    })
+}
+
+/// the edge ids of a route, in order
+fn route_edges(route: &[EdgeTraversal]) -> Vec<EdgeId> {
+    route.iter().map(|et| et.edge_id).collect()
+}
+
+/// the total cost of a route
+fn route_cost(route: &[EdgeTraversal]) -> Cost {
+    route
+        .iter()
+        .fold(Cost::ZERO, |acc, et| acc + et.total_cost())
+}
+
+/// the vertices visited by a route in order, beginning with the source
+fn route_vertices(
+    source: VertexId,
+    route: &[EdgeTraversal],
+    si: &SearchInstance,
+) -> Result<Vec<VertexId>, SearchError> {
+    let mut vertices = Vec::with_capacity(route.len() + 1);
+    vertices.push(source);
+    for et in route.iter() {
+        vertices.push(si.directed_graph.get_edge(et.edge_id)?.dst_vertex_id);
+    }
+    Ok(vertices)
+}
+
+/// traverses a sequence of edges from the initial search state
+fn evaluate_edges(
+    edges: &[EdgeId],
+    si: &SearchInstance,
+) -> Result<Vec<EdgeTraversal>, SearchError> {
+    let mut result: Vec<EdgeTraversal> = Vec::with_capacity(edges.len());
+    let mut state = si.state_model.initial_state()?;
+    for edge_id in edges.iter() {
+        let prev_edge_id = result.last().map(|prev| prev.edge_id);
+        let traversal = EdgeTraversal::forward_traversal(*edge_id, prev_edge_id, &state, si)?;
+        state.clone_from(&traversal.result_state);
+        result.push(traversal);
+    }
+    Ok(result)
+}
+
+/// copies a search instance, replacing the frontier model with one that also removes
+/// the blocked edges and any edge entering a blocked vertex.
+fn blocked_search_instance(
+    si: &SearchInstance,
+    edges: HashSet<EdgeId>,
+    vertices: HashSet<VertexId>,
+) -> SearchInstance {
+    let frontier_model = BlockedFrontierModel {
+        underlying: si.frontier_model.clone(),
+        edges,
+        vertices,
+    };
+    SearchInstance {
+        frontier_model: Arc::new(frontier_model),
+        ..si.clone()
+    }
+}
+
+/// removes edges and vertices from the graph for a spur search, in addition to any
+/// restrictions of the underlying frontier model
+struct BlockedFrontierModel {
+    underlying: Arc<dyn FrontierModel>,
+    edges: HashSet<EdgeId>,
+    vertices: HashSet<VertexId>,
+}
+
+impl FrontierModel for BlockedFrontierModel {
+    fn valid_frontier(
+        &self,
+        edge: &Edge,
+        state: &[StateVar],
+        previous_edge: Option<&Edge>,
+        state_model: &StateModel,
+    ) -> Result<bool, FrontierModelError> {
+        if self.edges.contains(&edge.edge_id) || self.vertices.contains(&edge.dst_vertex_id) {
+            return Ok(false);
+        }
+        self.underlying
+            .valid_frontier(edge, state, previous_edge, state_model)
+    }
+
+    fn valid_result_state(
+        &self,
+        edge: &Edge,
+        result_state: &[StateVar],
+        state_model: &StateModel,
+    ) -> Result<bool, FrontierModelError> {
+        self.underlying
+            .valid_result_state(edge, result_state, state_model)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::property::vertex::Vertex;
+    use crate::model::unit::as_f64::AsF64;
+    use crate::util::test_util::{distance_search_instance, graph_from_edges};
+
+    /// a graph from (0) to (3) with four loopless routes: (0)-(1)-(3) and (0)-(2)-(3)
+    /// of 20, (0)-(1)-(2)-(3) of 21 and (0)-(3) of 25. the edge (3)-(0) allows loops.
+    fn build_search_instance() -> SearchInstance {
+        let vertices = (0..4).map(|i| Vertex::new(i, 0.0, 0.0)).collect::<Vec<_>>();
+        let edges = vec![
+            Edge::new(0, 0, 1, 10.0),
+            Edge::new(1, 1, 3, 10.0),
+            Edge::new(2, 0, 2, 10.0),
+            Edge::new(3, 2, 3, 10.0),
+            Edge::new(4, 0, 3, 25.0),
+            Edge::new(5, 1, 2, 1.0),
+            Edge::new(6, 3, 0, 1.0),
+        ];
+        distance_search_instance(graph_from_edges(vertices, edges))
+    }
+
+    fn route_summary(route: &[EdgeTraversal]) -> (Vec<EdgeId>, f64) {
+        let cost = route.iter().map(|et| et.total_cost().as_f64()).sum::<f64>();
+        (route_edges(route), cost)
+    }
+
+    #[test]
+    fn test_routes_in_cost_order() {
+        let si = build_search_instance();
+        let result = run(VertexId(0), VertexId(3), 3, &si, &SearchAlgorithm::Dijkstra).unwrap();
+        let routes = result
+            .routes
+            .iter()
+            .map(|r| route_summary(r))
+            .collect::<Vec<_>>();
+        // the two routes of equal cost come first in edge id order, followed by the 21 route
+        assert_eq!(
+            routes,
+            vec![
+                (vec![EdgeId(0), EdgeId(1)], 20.0),
+                (vec![EdgeId(2), EdgeId(3)], 20.0),
+                (vec![EdgeId(0), EdgeId(5), EdgeId(3)], 21.0),
+            ]
+        );
+
+        // repeated runs return the same routes
+        let repeated = run(VertexId(0), VertexId(3), 3, &si, &SearchAlgorithm::Dijkstra).unwrap();
+        let repeated_routes = repeated
+            .routes
+            .iter()
+            .map(|r| route_summary(r))
+            .collect::<Vec<_>>();
+        assert_eq!(repeated_routes, routes);
+    }
+
+    #[test]
+    fn test_fewer_than_k_routes() {
+        let si = build_search_instance();
+        let result = run(
+            VertexId(0),
+            VertexId(3),
+            10,
+            &si,
+            &SearchAlgorithm::Dijkstra,
+        )
+        .unwrap();
+        let routes = result
+            .routes
+            .iter()
+            .map(|r| route_summary(r))
+            .collect::<Vec<_>>();
+        // the four loopless routes, none of which use the (3)-(0) edge
+        assert_eq!(routes.len(), 4);
+        assert_eq!(routes[3], (vec![EdgeId(4)], 25.0));
+        let costs = routes.iter().map(|(_, c)| *c).collect::<Vec<_>>();
+        assert_eq!(costs, vec![20.0, 20.0, 21.0, 25.0]);
+    }
+}
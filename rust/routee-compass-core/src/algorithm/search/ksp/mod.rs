@@ -1,2 +1,4 @@
+pub mod ksp_overlap_penalty;
 pub mod ksp_single_via_paths;
+pub mod ksp_yens;
 pub mod route_similarity_function;
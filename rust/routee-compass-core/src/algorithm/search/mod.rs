@@ -5,12 +5,17 @@ use std::collections::HashMap;
 pub mod a_star;
 pub mod backtrack;
 pub mod direction;
+pub mod edge_cost_cache;
 pub mod edge_traversal;
+pub mod isochrone;
 pub mod ksp;
+pub mod one_to_many;
+pub mod reachable_edges;
 pub mod search_algorithm;
 pub mod search_algorithm_result;
 pub mod search_error;
 pub mod search_instance;
+pub mod search_metrics;
 pub mod search_result;
 pub mod search_tree_branch;
 
@@ -0,0 +1,127 @@
+use super::a_star::a_star_algorithm::run_a_star_to_targets;
+use super::backtrack::vertex_oriented_route;
+use super::direction::Direction;
+use super::search_error::SearchError;
+use super::search_instance::SearchInstance;
+use crate::model::road_network::vertex_id::VertexId;
+use crate::model::unit::Cost;
+
+/// finds the cost from an origin to each of many destinations with a single search,
+/// such as to fill one row of an origin-destination cost matrix. the search stops
+/// once all destinations are settled, and the cost of each destination is the
+/// total cost of the edges along its path in the search tree, which matches the
+/// cost of a search from the origin to that destination alone.
+///
+/// # Arguments
+///
+/// * `origin`       - the vertex to search from
+/// * `destinations` - the vertices to find costs to
+/// * `si`           - the search assets
+///
+/// # Returns
+///
+/// the cost to each destination, in the order of the destinations, which is None
+/// for destinations that cannot be reached, or an error
+pub fn search_one_to_many(
+    origin: VertexId,
+    destinations: &[VertexId],
+    si: &SearchInstance,
+) -> Result<Vec<Option<Cost>>, SearchError> {
+    let result = run_a_star_to_targets(origin, destinations, &Direction::Forward, si)?;
+    destinations
+        .iter()
+        .map(|destination| {
+            if *destination == origin {
+                Ok(Some(Cost::ZERO))
+            } else if !result.tree.contains_key(destination) {
+                Ok(None)
+            } else {
+                let route = vertex_oriented_route(origin, *destination, &result.tree)?;
+                let cost = route
+                    .iter()
+                    .fold(Cost::ZERO, |acc, et| acc + et.total_cost());
+                Ok(Some(cost))
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algorithm::search::a_star::a_star_algorithm::run_a_star;
+    use crate::model::property::{edge::Edge, vertex::Vertex};
+    use crate::util::test_util::{distance_search_instance, graph_from_edges};
+
+    /// (0) -[0]-> (1) -[1]-> (2) -[3]-> (3) -[4]-> (4) with a shortcut (0) -[2]-> (2)
+    /// of 25 meters, other edges of 10, 10, 5 and 100 meters, and the unreachable (5)
+    fn build_search_instance() -> SearchInstance {
+        let vertices = (0..6).map(|i| Vertex::new(i, 0.0, 0.0)).collect::<Vec<_>>();
+        let edges = vec![
+            Edge::new(0, 0, 1, 10.0),
+            Edge::new(1, 1, 2, 10.0),
+            Edge::new(2, 0, 2, 25.0),
+            Edge::new(3, 2, 3, 5.0),
+            Edge::new(4, 3, 4, 100.0),
+        ];
+        distance_search_instance(graph_from_edges(vertices, edges))
+    }
+
+    #[test]
+    fn test_matches_point_to_point_costs() {
+        let si = build_search_instance();
+        let origin = VertexId(0);
+        let destinations = [
+            VertexId(2),
+            VertexId(0),
+            VertexId(5),
+            VertexId(4),
+            VertexId(3),
+        ];
+        let costs = search_one_to_many(origin, &destinations, &si).unwrap();
+        assert_eq!(
+            costs,
+            vec![
+                Some(Cost::new(20.0)),
+                Some(Cost::ZERO),
+                None,
+                Some(Cost::new(125.0)),
+                Some(Cost::new(25.0)),
+            ]
+        );
+
+        // each entry matches a search to that destination alone
+        for (destination, cost) in destinations.iter().zip(costs.iter()) {
+            if *destination == origin {
+                continue;
+            }
+            let point_to_point =
+                match run_a_star(origin, Some(*destination), &Direction::Forward, None, &si) {
+                    Ok(result) => {
+                        let route =
+                            vertex_oriented_route(origin, *destination, &result.tree).unwrap();
+                        Some(
+                            route
+                                .iter()
+                                .fold(Cost::ZERO, |acc, et| acc + et.total_cost()),
+                        )
+                    }
+                    Err(SearchError::NoPathExists(_, _)) => None,
+                    Err(e) => panic!("{}", e),
+                };
+            assert_eq!(*cost, point_to_point, "destination {}", destination);
+        }
+    }
+
+    #[test]
+    fn test_stops_once_destinations_are_settled() {
+        let si = build_search_instance();
+        let targets = [VertexId(1), VertexId(2)];
+        let result =
+            run_a_star_to_targets(VertexId(0), &targets, &Direction::Forward, &si).unwrap();
+        // vertex 2 is settled before it is expanded, so vertex 3 is never reached
+        assert!(result.tree.contains_key(&VertexId(2)));
+        assert!(!result.tree.contains_key(&VertexId(3)));
+        assert_eq!(result.metrics.nodes_expanded, 2);
+    }
+}
@@ -0,0 +1,153 @@
+use super::edge_traversal::EdgeTraversal;
+use super::search_error::SearchError;
+use super::search_instance::SearchInstance;
+use super::MinSearchTree;
+use crate::model::road_network::{edge_id::EdgeId, vertex_id::VertexId};
+use serde::Serialize;
+
+/// how much of an edge can be traversed within a budget.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum EdgeReachability {
+    /// the entire edge can be traversed within the budget
+    Full,
+    /// the edge begins within the budget but ends beyond it. the fraction is the
+    /// portion of the edge, from its source vertex, that is reachable, assuming the
+    /// budgeted feature accumulates uniformly along the edge.
+    Partial { fraction: f64 },
+}
+
+/// an edge that can be reached, fully or partially, within a budget, along with
+/// the value of the budgeted state feature on arrival at both of its endpoints.
+#[derive(Serialize, Clone, Debug)]
+pub struct ReachableEdge {
+    pub edge_id: EdgeId,
+    pub reachability: EdgeReachability,
+    pub src_value: f64,
+    pub dst_value: f64,
+}
+
+/// finds the edges reachable within a budget on some state feature, such as time
+/// or distance, from a search tree rooted at the origin. the tree should be built
+/// by a search without a destination, with termination limits large enough to
+/// reach beyond the budget.
+///
+/// each edge leaving a vertex of the tree that was reached within the budget is
+/// traversed from the arrival state at that vertex. edges that end within the
+/// budget are fully reachable. otherwise, they are boundary edges, which are
+/// included with the reachable fraction when `include_partial` is true.
+///
+/// # Arguments
+///
+/// * `origin`          - the root of the search tree
+/// * `tree`            - the search tree
+/// * `feature`         - name of the state feature the budget applies to
+/// * `budget`          - limit on the feature value, in the unit of the feature
+/// * `include_partial` - whether to include boundary edges
+/// * `si`              - the search assets used to build the tree
+///
+/// # Returns
+///
+/// the reachable edges, ordered by source vertex, or an error
+pub fn reachable_edges(
+    origin: VertexId,
+    tree: &MinSearchTree,
+    feature: &String,
+    budget: f64,
+    include_partial: bool,
+    si: &SearchInstance,
+) -> Result<Vec<ReachableEdge>, SearchError> {
+    let initial_state = si.state_model.initial_state()?;
+    let mut arrivals = vec![(origin, None, initial_state)];
+    for (vertex_id, branch) in tree.iter() {
+        if *vertex_id != origin {
+            let et = &branch.edge_traversal;
+            arrivals.push((*vertex_id, Some(et.edge_id), et.result_state.clone()));
+        }
+    }
+    arrivals.sort_by_key(|(vertex_id, _, _)| *vertex_id);
+
+    let mut result = vec![];
+    for (vertex_id, prev_edge_id, state) in arrivals.iter() {
+        let src_value = si.state_model.get_state_variable(state, feature)?.0;
+        if src_value > budget {
+            continue;
+        }
+        for edge_id in si.directed_graph.out_edges(*vertex_id)? {
+            let et = EdgeTraversal::forward_traversal(edge_id, *prev_edge_id, state, si)?;
+            let dst_value = si
+                .state_model
+                .get_state_variable(&et.result_state, feature)?
+                .0;
+            let reachability = if dst_value <= budget {
+                EdgeReachability::Full
+            } else if include_partial {
+                let fraction = (budget - src_value) / (dst_value - src_value);
+                EdgeReachability::Partial { fraction }
+            } else {
+                continue;
+            };
+            result.push(ReachableEdge {
+                edge_id,
+                reachability,
+                src_value,
+                dst_value,
+            });
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algorithm::search::a_star::a_star_algorithm::run_a_star;
+    use crate::algorithm::search::direction::Direction;
+    use crate::model::property::{edge::Edge, vertex::Vertex};
+    use crate::util::test_util::{distance_search_instance, graph_from_edges};
+
+    /// (0) -[0]-> (1) -[1]-> (2) -[2]-> (3), with edges of 10, 10 and 20 meters
+    fn build_search_instance() -> SearchInstance {
+        let vertices = vec![
+            Vertex::new(0, 0.0, 0.0),
+            Vertex::new(1, 0.0, 0.0),
+            Vertex::new(2, 0.0, 0.0),
+            Vertex::new(3, 0.0, 0.0),
+        ];
+        let edges = vec![
+            Edge::new(0, 0, 1, 10.0),
+            Edge::new(1, 1, 2, 10.0),
+            Edge::new(2, 2, 3, 20.0),
+        ];
+        distance_search_instance(graph_from_edges(vertices, edges))
+    }
+
+    #[test]
+    fn test_full_and_boundary_edges() {
+        let si = build_search_instance();
+        let origin = VertexId(0);
+        let result = run_a_star(origin, None, &Direction::Forward, None, &si).unwrap();
+        let feature = String::from("distance");
+
+        // a 25 meter budget covers edges 0 and 1 and the first quarter of edge 2
+        let edges = reachable_edges(origin, &result.tree, &feature, 25.0, true, &si).unwrap();
+        let reachability = edges
+            .iter()
+            .map(|e| (e.edge_id, e.reachability))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            reachability,
+            vec![
+                (EdgeId(0), EdgeReachability::Full),
+                (EdgeId(1), EdgeReachability::Full),
+                (EdgeId(2), EdgeReachability::Partial { fraction: 0.25 }),
+            ]
+        );
+        assert_eq!((edges[2].src_value, edges[2].dst_value), (20.0, 40.0));
+
+        // boundary edges are excluded unless requested
+        let full_only = reachable_edges(origin, &result.tree, &feature, 25.0, false, &si).unwrap();
+        let full_ids = full_only.iter().map(|e| e.edge_id).collect::<Vec<_>>();
+        assert_eq!(full_ids, vec![EdgeId(0), EdgeId(1)]);
+    }
+}
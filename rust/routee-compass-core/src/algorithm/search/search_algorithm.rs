@@ -1,12 +1,16 @@
 use super::backtrack;
 use super::edge_traversal::EdgeTraversal;
-use super::ksp::ksp_single_via_paths;
 use super::ksp::route_similarity_function::RouteSimilarityFunction;
+use super::ksp::{ksp_overlap_penalty, ksp_single_via_paths, ksp_yens};
 use super::search_algorithm_result::SearchAlgorithmResult;
 use super::search_error::SearchError;
 use super::search_instance::SearchInstance;
+use super::search_metrics::SearchMetrics;
 use super::search_tree_branch::SearchTreeBranch;
-use super::{a_star::a_star_algorithm, direction::Direction};
+use super::{
+    a_star::{a_star_algorithm, bidirectional_dijkstra_algorithm},
+    direction::Direction,
+};
 use crate::model::road_network::{edge_id::EdgeId, vertex_id::VertexId};
 
 use crate::model::unit::Cost;
@@ -17,7 +21,12 @@ use std::collections::HashMap;
 #[serde(rename_all = "snake_case", tag = "type")]
 pub enum SearchAlgorithm {
     Dijkstra,
-    #[serde(rename = "a*")]
+    /// dijkstra searches from both the source and the destination which meet in the middle.
+    /// searches without a destination, or in the reverse direction, run dijkstra instead
+    BidirectionalDijkstra,
+    /// a* search guided by the traversal model cost estimate, which must never overestimate.
+    /// configured as "a*" or "a_star"
+    #[serde(rename = "a*", alias = "a_star")]
     AStarAlgorithm {
         weight_factor: Option<Cost>,
     },
@@ -25,10 +34,89 @@ pub enum SearchAlgorithm {
         k: usize,
         underlying: Box<SearchAlgorithm>,
         similarity: RouteSimilarityFunction,
+        /// alternatives costing more than this factor times the optimal route cost are discarded
+        #[serde(default)]
+        max_detour_factor: Option<f64>,
+    },
+    /// alternatives found by repeated searches which penalize the edges of previous routes
+    KspOverlapPenalty {
+        k: usize,
+        underlying: Box<SearchAlgorithm>,
+        /// multiplier on the traversal cost of each edge per previous search that used it
+        overlap_penalty: f64,
+        /// alternatives sharing more than this fraction of their distance with a previous route are discarded
+        #[serde(default)]
+        max_overlap: Option<f64>,
+    },
+    /// the k loopless routes of lowest cost in order, found with Yen's algorithm.
+    /// fewer than k routes are returned when no more exist
+    KspYens {
+        k: usize,
+        underlying: Box<SearchAlgorithm>,
     },
 }
 
 impl SearchAlgorithm {
+    /// confirms that the algorithm parameters are valid.
+    pub fn validate(&self) -> Result<(), SearchError> {
+        match self {
+            SearchAlgorithm::Dijkstra => Ok(()),
+            SearchAlgorithm::BidirectionalDijkstra => Ok(()),
+            SearchAlgorithm::AStarAlgorithm { weight_factor: _ } => Ok(()),
+            SearchAlgorithm::KspSingleVia {
+                k,
+                underlying,
+                similarity: _,
+                max_detour_factor,
+            } => match (max_detour_factor, underlying.as_ref()) {
+                _ if *k == 0 => Err(SearchError::BuildError(String::from(
+                    "ksp k must be at least 1",
+                ))),
+                (Some(factor), _) if factor.is_nan() || *factor < 1.0 => {
+                    Err(SearchError::BuildError(format!(
+                        "ksp max_detour_factor must be >= 1.0, found {}",
+                        factor
+                    )))
+                }
+                // single-via paths are found from complete forward and reverse search trees
+                (_, SearchAlgorithm::BidirectionalDijkstra) => Err(SearchError::BuildError(
+                    String::from("ksp single via cannot use an underlying bidirectional search"),
+                )),
+                _ => underlying.validate(),
+            },
+            SearchAlgorithm::KspOverlapPenalty {
+                k,
+                underlying,
+                overlap_penalty,
+                max_overlap,
+            } => {
+                if *k == 0 {
+                    return Err(SearchError::BuildError(String::from(
+                        "ksp k must be at least 1",
+                    )));
+                }
+                if !overlap_penalty.is_finite() || *overlap_penalty < 1.0 {
+                    return Err(SearchError::BuildError(format!(
+                        "ksp overlap_penalty must be >= 1.0, found {}",
+                        overlap_penalty
+                    )));
+                }
+                match max_overlap {
+                    Some(max) if !(0.0..=1.0).contains(max) => Err(SearchError::BuildError(
+                        format!("ksp max_overlap must be within [0, 1], found {}", max),
+                    )),
+                    _ => underlying.validate(),
+                }
+            }
+            SearchAlgorithm::KspYens { k, underlying } => match k {
+                0 => Err(SearchError::BuildError(String::from(
+                    "ksp k must be at least 1",
+                ))),
+                _ => underlying.validate(),
+            },
+        }
+    }
+
     pub fn run_vertex_oriented(
         &self,
         src_id: VertexId,
@@ -41,6 +129,14 @@ impl SearchAlgorithm {
                 weight_factor: Some(Cost::ZERO),
             }
             .run_vertex_oriented(src_id, dst_id_opt, direction, si),
+            SearchAlgorithm::BidirectionalDijkstra => match (dst_id_opt, direction) {
+                (Some(dst_id), Direction::Forward) => {
+                    bidirectional_dijkstra_algorithm::run_bidirectional_dijkstra(src_id, dst_id, si)
+                }
+                _ => {
+                    SearchAlgorithm::Dijkstra.run_vertex_oriented(src_id, dst_id_opt, direction, si)
+                }
+            },
             SearchAlgorithm::AStarAlgorithm { weight_factor } => {
                 let search_result = a_star_algorithm::run_a_star(
                     src_id,
@@ -52,6 +148,7 @@ impl SearchAlgorithm {
                 let routes = match dst_id_opt {
                     None => vec![],
                     Some(dst_id) => {
+                        let _span = tracing::info_span!("route_reconstruction").entered();
                         let route =
                             backtrack::vertex_oriented_route(src_id, dst_id, &search_result.tree)?;
                         vec![route]
@@ -61,16 +158,49 @@ impl SearchAlgorithm {
                     trees: vec![search_result.tree],
                     routes,
                     iterations: search_result.iterations,
+                    metrics: search_result.metrics,
                 })
             }
             SearchAlgorithm::KspSingleVia {
                 k,
                 underlying,
                 similarity,
+                max_detour_factor,
             } => match dst_id_opt {
-                Some(dst_id) => {
-                    ksp_single_via_paths::run(src_id, dst_id, *k, similarity, si, underlying)
-                }
+                Some(dst_id) => ksp_single_via_paths::run(
+                    src_id,
+                    dst_id,
+                    *k,
+                    similarity,
+                    *max_detour_factor,
+                    si,
+                    underlying,
+                ),
+                None => Err(SearchError::BuildError(String::from(
+                    "request has source but no destination which is invalid for k-shortest paths",
+                ))),
+            },
+            SearchAlgorithm::KspOverlapPenalty {
+                k,
+                underlying,
+                overlap_penalty,
+                max_overlap,
+            } => match dst_id_opt {
+                Some(dst_id) => ksp_overlap_penalty::run(
+                    src_id,
+                    dst_id,
+                    *k,
+                    *overlap_penalty,
+                    *max_overlap,
+                    si,
+                    underlying,
+                ),
+                None => Err(SearchError::BuildError(String::from(
+                    "request has source but no destination which is invalid for k-shortest paths",
+                ))),
+            },
+            SearchAlgorithm::KspYens { k, underlying } => match dst_id_opt {
+                Some(dst_id) => ksp_yens::run(src_id, dst_id, *k, si, underlying),
                 None => Err(SearchError::BuildError(String::from(
                     "request has source but no destination which is invalid for k-shortest paths",
                 ))),
@@ -89,6 +219,9 @@ impl SearchAlgorithm {
                 weight_factor: Some(Cost::ZERO),
             }
             .run_edge_oriented(src_id, dst_id_opt, direction, search_instance),
+            SearchAlgorithm::BidirectionalDijkstra => {
+                run_edge_oriented(src_id, dst_id_opt, direction, self, search_instance)
+            }
             SearchAlgorithm::AStarAlgorithm { weight_factor } => {
                 let search_result = a_star_algorithm::run_a_star_edge_oriented(
                     src_id,
@@ -100,6 +233,7 @@ impl SearchAlgorithm {
                 let routes = match dst_id_opt {
                     None => vec![],
                     Some(dst_id) => {
+                        let _span = tracing::info_span!("route_reconstruction").entered();
                         let route = backtrack::edge_oriented_route(
                             src_id,
                             dst_id,
@@ -113,15 +247,93 @@ impl SearchAlgorithm {
                     trees: vec![search_result.tree],
                     routes,
                     iterations: search_result.iterations,
+                    metrics: search_result.metrics,
                 })
             }
             SearchAlgorithm::KspSingleVia {
                 k: _,
                 underlying: _,
                 similarity: _,
+                max_detour_factor: _,
+            } => run_edge_oriented(src_id, dst_id_opt, direction, self, search_instance),
+            SearchAlgorithm::KspOverlapPenalty {
+                k: _,
+                underlying: _,
+                overlap_penalty: _,
+                max_overlap: _,
+            } => run_edge_oriented(src_id, dst_id_opt, direction, self, search_instance),
+            SearchAlgorithm::KspYens {
+                k: _,
+                underlying: _,
             } => run_edge_oriented(src_id, dst_id_opt, direction, self, search_instance),
         }
     }
+
+    /// runs a search which only builds the search tree, leaving route reconstruction
+    /// to the caller, see [`backtrack`]. only algorithms which find their route on a
+    /// single search tree (dijkstra and a*) support this, others return None.
+    pub fn run_vertex_oriented_tree(
+        &self,
+        src_id: VertexId,
+        dst_id_opt: Option<VertexId>,
+        direction: &Direction,
+        si: &SearchInstance,
+    ) -> Result<Option<SearchAlgorithmResult>, SearchError> {
+        match self {
+            SearchAlgorithm::Dijkstra => SearchAlgorithm::AStarAlgorithm {
+                weight_factor: Some(Cost::ZERO),
+            }
+            .run_vertex_oriented_tree(src_id, dst_id_opt, direction, si),
+            SearchAlgorithm::AStarAlgorithm { weight_factor } => {
+                let search_result = a_star_algorithm::run_a_star(
+                    src_id,
+                    dst_id_opt,
+                    direction,
+                    *weight_factor,
+                    si,
+                )?;
+                Ok(Some(SearchAlgorithmResult {
+                    trees: vec![search_result.tree],
+                    routes: vec![],
+                    iterations: search_result.iterations,
+                    metrics: search_result.metrics,
+                }))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// edge-oriented variant of [`SearchAlgorithm::run_vertex_oriented_tree`].
+    pub fn run_edge_oriented_tree(
+        &self,
+        src_id: EdgeId,
+        dst_id_opt: Option<EdgeId>,
+        direction: &Direction,
+        si: &SearchInstance,
+    ) -> Result<Option<SearchAlgorithmResult>, SearchError> {
+        match self {
+            SearchAlgorithm::Dijkstra => SearchAlgorithm::AStarAlgorithm {
+                weight_factor: Some(Cost::ZERO),
+            }
+            .run_edge_oriented_tree(src_id, dst_id_opt, direction, si),
+            SearchAlgorithm::AStarAlgorithm { weight_factor } => {
+                let search_result = a_star_algorithm::run_a_star_edge_oriented(
+                    src_id,
+                    dst_id_opt,
+                    direction,
+                    *weight_factor,
+                    si,
+                )?;
+                Ok(Some(SearchAlgorithmResult {
+                    trees: vec![search_result.tree],
+                    routes: vec![],
+                    iterations: search_result.iterations,
+                    metrics: search_result.metrics,
+                }))
+            }
+            _ => Ok(None),
+        }
+    }
 }
 
 // convenience method when origin and destination are specified using
@@ -157,6 +369,7 @@ pub fn run_edge_oriented(
                 mut trees,
                 mut routes,
                 iterations,
+                metrics,
             } = alg.run_vertex_oriented(e1_dst, None, direction, si)?;
             for tree in trees.iter_mut() {
                 if !tree.contains_key(&e1_dst) {
@@ -170,6 +383,7 @@ pub fn run_edge_oriented(
                 trees,
                 routes,
                 iterations: iterations + 1,
+                metrics,
             };
             Ok(updated)
         }
@@ -203,6 +417,7 @@ pub fn run_edge_oriented(
                     trees: vec![tree],
                     routes: vec![route],
                     iterations: 1,
+                    metrics: SearchMetrics::default(),
                 };
                 return Ok(result);
             } else {
@@ -211,6 +426,7 @@ pub fn run_edge_oriented(
                     trees,
                     mut routes,
                     iterations,
+                    metrics,
                 } = alg.run_vertex_oriented(e1_dst, Some(e2_src), direction, si)?;
 
                 if trees.is_empty() {
@@ -238,9 +454,64 @@ pub fn run_edge_oriented(
                     trees,
                     routes,
                     iterations: iterations + 2,
+                    metrics,
                 };
                 Ok(result)
             }
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn ksp_with_detour_factor(factor: f64) -> SearchAlgorithm {
+        ksp_single_via(3, factor)
+    }
+
+    fn ksp_single_via(k: usize, factor: f64) -> SearchAlgorithm {
+        serde_json::from_value(serde_json::json!({
+            "type": "ksp_single_via",
+            "k": k,
+            "underlying": { "type": "dijkstra" },
+            "similarity": { "type": "edge_id_cosine_similarity", "threshold": 0.5 },
+            "max_detour_factor": factor
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_validate_max_detour_factor() {
+        assert!(ksp_with_detour_factor(1.5).validate().is_ok());
+        assert!(ksp_with_detour_factor(1.0).validate().is_ok());
+        assert!(matches!(
+            ksp_with_detour_factor(0.9).validate(),
+            Err(SearchError::BuildError(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate_single_via_k() {
+        assert!(ksp_single_via(1, 1.5).validate().is_ok());
+        assert!(matches!(
+            ksp_single_via(0, 1.5).validate(),
+            Err(SearchError::BuildError(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate_overlap_penalty_k() {
+        let ksp = |k: usize| -> SearchAlgorithm {
+            serde_json::from_value(serde_json::json!({
+                "type": "ksp_overlap_penalty",
+                "k": k,
+                "underlying": { "type": "dijkstra" },
+                "overlap_penalty": 1.5
+            }))
+            .unwrap()
+        };
+        assert!(ksp(1).validate().is_ok());
+        assert!(matches!(ksp(0).validate(), Err(SearchError::BuildError(_))));
+    }
+}
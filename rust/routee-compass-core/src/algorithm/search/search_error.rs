@@ -32,6 +32,10 @@ pub enum SearchError {
     QueryTerminated(String),
     #[error("no path exists between vertices {0} and {1}")]
     NoPathExists(VertexId, VertexId),
+    #[error("path is not connected, edge {0} does not end where edge {1} begins")]
+    DisconnectedPath(EdgeId, EdgeId),
+    #[error("path does not begin at origin vertex {0}, first edge {1} begins elsewhere")]
+    PathDoesNotStartAtOrigin(VertexId, EdgeId),
     #[error("search tree is missing linked vertex {0}")]
     VertexMissingFromSearchTree(VertexId),
     #[error("error accessing shared read-only dataset: {0}")]
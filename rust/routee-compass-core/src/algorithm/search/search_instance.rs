@@ -1,3 +1,4 @@
+use super::edge_cost_cache::EdgeCostCache;
 use super::search_error::SearchError;
 use crate::model::{
     access::access_model::AccessModel,
@@ -13,6 +14,7 @@ use std::sync::Arc;
 
 /// instances of read-only objects used for a search that have
 /// been prepared for a specific query.
+#[derive(Clone)]
 pub struct SearchInstance {
     pub directed_graph: Arc<Graph>,
     pub state_model: Arc<StateModel>,
@@ -21,6 +23,7 @@ pub struct SearchInstance {
     pub cost_model: CostModel,
     pub frontier_model: Arc<dyn FrontierModel>,
     pub termination_model: Arc<TerminationModel>,
+    pub edge_cost_cache: Option<Arc<EdgeCostCache>>,
 }
 
 impl SearchInstance {
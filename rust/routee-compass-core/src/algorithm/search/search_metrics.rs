@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+/// counters of the work done by a search, which describe how algorithmically
+/// expensive a query was independently of how long it took to run.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchMetrics {
+    /// number of vertices removed from the frontier and expanded
+    pub nodes_expanded: usize,
+    /// number of edges traversed from expanded vertices
+    pub edges_relaxed: usize,
+    /// largest number of vertices in the frontier at once
+    pub max_frontier_size: usize,
+}
+
+impl SearchMetrics {
+    /// combines the metrics of two searches, such as the forward and reverse
+    /// searches of a bidirectional search. work is summed, and the frontier size
+    /// is the larger of the two.
+    pub fn combine(&self, other: &SearchMetrics) -> SearchMetrics {
+        SearchMetrics {
+            nodes_expanded: self.nodes_expanded + other.nodes_expanded,
+            edges_relaxed: self.edges_relaxed + other.edges_relaxed,
+            max_frontier_size: self.max_frontier_size.max(other.max_frontier_size),
+        }
+    }
+}
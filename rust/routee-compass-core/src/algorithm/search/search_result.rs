@@ -1,4 +1,4 @@
-use super::search_tree_branch::SearchTreeBranch;
+use super::{search_metrics::SearchMetrics, search_tree_branch::SearchTreeBranch};
 use crate::model::road_network::vertex_id::VertexId;
 use std::collections::HashMap;
 
@@ -6,10 +6,19 @@ use std::collections::HashMap;
 pub struct SearchResult {
     pub tree: HashMap<VertexId, SearchTreeBranch>,
     pub iterations: u64,
+    pub metrics: SearchMetrics,
 }
 
 impl SearchResult {
-    pub fn new(tree: HashMap<VertexId, SearchTreeBranch>, iterations: u64) -> SearchResult {
-        SearchResult { tree, iterations }
+    pub fn new(
+        tree: HashMap<VertexId, SearchTreeBranch>,
+        iterations: u64,
+        metrics: SearchMetrics,
+    ) -> SearchResult {
+        SearchResult {
+            tree,
+            iterations,
+            metrics,
+        }
     }
 }
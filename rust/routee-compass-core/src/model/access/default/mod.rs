@@ -1,3 +1,4 @@
 pub mod combined_model;
 pub mod no_access_model;
+pub mod road_reentry;
 pub mod turn_delays;
@@ -0,0 +1,280 @@
+use crate::model::{
+    access::{
+        access_model::AccessModel, access_model_error::AccessModelError,
+        access_model_service::AccessModelService,
+    },
+    property::{edge::Edge, vertex::Vertex},
+    state::{
+        custom_feature_format::CustomFeatureFormat, state_feature::StateFeature,
+        state_model::StateModel,
+    },
+    traversal::state::state_variable::StateVar,
+};
+use ordered_float::OrderedFloat;
+use std::{collections::HashMap, sync::Arc};
+
+/// road id stored in the state for edges without a road name
+const NO_ROAD: i64 = -1;
+
+/// penalizes routes that leave a named road and return to it within a few edges.
+///
+/// road identity is determined by edge name: edges whose names match, ignoring
+/// case and surrounding whitespace, are on the same road. edges with an empty
+/// name are not on any road, so returning to them is never penalized.
+///
+/// the roads of the most recently traversed edges are kept in the search state.
+/// when accessing an edge on a different road than the previous edge, if its road
+/// was traversed within the `lookback` edges before the previous edge, the
+/// `penalty` is added to the "road_reentry_penalty" state feature, which can be
+/// weighted in the cost model. the penalty is applied on the edge that returns
+/// to the road, so it affects which route is found when the detour and the
+/// direct route are compared at the end of that edge.
+#[derive(Clone)]
+pub struct RoadReentryAccessModel {
+    pub edge_road_ids: Arc<Box<[i64]>>,
+    pub lookback: usize,
+    pub penalty: f64,
+}
+
+impl RoadReentryAccessModel {
+    pub const PENALTY_FEATURE: &'static str = "road_reentry_penalty";
+    const WINDOW_FEATURE_PREFIX: &'static str = "road_reentry_window";
+
+    /// creates a road re-entry access model from the road name of each edge,
+    /// indexed by edge id.
+    ///
+    /// # Arguments
+    ///
+    /// * `edge_road_names` - the road name of each edge, empty for unnamed edges
+    /// * `lookback`        - number of edges away from a road after which returning is not penalized
+    /// * `penalty`         - value added to the penalty feature on returning to a road
+    ///
+    /// # Returns
+    ///
+    /// the model, or an error if the lookback is zero or the penalty is negative
+    pub fn new(
+        edge_road_names: &[String],
+        lookback: usize,
+        penalty: f64,
+    ) -> Result<RoadReentryAccessModel, AccessModelError> {
+        if lookback == 0 {
+            return Err(AccessModelError::BuildError(String::from(
+                "road re-entry lookback must be at least 1 edge",
+            )));
+        }
+        if penalty.is_nan() || penalty < 0.0 {
+            return Err(AccessModelError::BuildError(format!(
+                "road re-entry penalty must be non-negative, found {}",
+                penalty
+            )));
+        }
+        let mut road_ids: HashMap<String, i64> = HashMap::new();
+        let edge_road_ids = edge_road_names
+            .iter()
+            .map(|name| {
+                let key = name.trim().to_lowercase();
+                if key.is_empty() {
+                    NO_ROAD
+                } else {
+                    let next_id = road_ids.len() as i64;
+                    *road_ids.entry(key).or_insert(next_id)
+                }
+            })
+            .collect::<Box<[i64]>>();
+        Ok(RoadReentryAccessModel {
+            edge_road_ids: Arc::new(edge_road_ids),
+            lookback,
+            penalty,
+        })
+    }
+
+    fn window_feature(index: usize) -> String {
+        format!("{}_{}", Self::WINDOW_FEATURE_PREFIX, index)
+    }
+
+    fn road_id(&self, edge: &Edge) -> Result<i64, AccessModelError> {
+        self.edge_road_ids
+            .get(edge.edge_id.as_usize())
+            .copied()
+            .ok_or_else(|| AccessModelError::RuntimeError {
+                name: String::from("road re-entry"),
+                error: format!("no road name found for edge {}", edge.edge_id),
+            })
+    }
+}
+
+impl AccessModel for RoadReentryAccessModel {
+    /// the accumulated penalty along with the roads of the `lookback` most recent
+    /// edges before the previous edge, most recent first
+    fn state_features(&self) -> Vec<(String, StateFeature)> {
+        let penalty = (
+            String::from(Self::PENALTY_FEATURE),
+            StateFeature::Custom {
+                r#type: String::from(Self::PENALTY_FEATURE),
+                unit: String::from("penalty"),
+                format: CustomFeatureFormat::FloatingPoint {
+                    initial: OrderedFloat(0.0),
+                },
+                output_alias: None,
+            },
+        );
+        let window = (0..self.lookback).map(|index| {
+            (
+                Self::window_feature(index),
+                StateFeature::Custom {
+                    r#type: String::from(Self::WINDOW_FEATURE_PREFIX),
+                    unit: String::from("road_id"),
+                    format: CustomFeatureFormat::SignedInteger { initial: NO_ROAD },
+                    output_alias: None,
+                },
+            )
+        });
+        std::iter::once(penalty).chain(window).collect()
+    }
+
+    fn access_edge(
+        &self,
+        traversal: (&Vertex, &Edge, &Vertex, &Edge, &Vertex),
+        state: &mut Vec<StateVar>,
+        state_model: &StateModel,
+    ) -> Result<(), AccessModelError> {
+        let (_, prev_edge, _, next_edge, _) = traversal;
+        let prev_road = self.road_id(prev_edge)?;
+        let next_road = self.road_id(next_edge)?;
+        let window = (0..self.lookback)
+            .map(|index| state_model.get_custom_i64(state, &Self::window_feature(index)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if next_road != NO_ROAD && next_road != prev_road && window.contains(&next_road) {
+            let penalty_feature = String::from(Self::PENALTY_FEATURE);
+            let prev_penalty = state_model.get_custom_f64(state, &penalty_feature)?;
+            let next_penalty = prev_penalty + self.penalty;
+            state_model.set_custom_f64(state, &penalty_feature, &next_penalty)?;
+        }
+
+        // the previous edge becomes the most recent edge of the window
+        let shifted = std::iter::once(prev_road).chain(window).take(self.lookback);
+        for (index, road_id) in shifted.enumerate() {
+            state_model.set_custom_i64(state, &Self::window_feature(index), &road_id)?;
+        }
+        Ok(())
+    }
+}
+
+impl AccessModelService for RoadReentryAccessModel {
+    fn build(&self, _query: &serde_json::Value) -> Result<Arc<dyn AccessModel>, AccessModelError> {
+        Ok(Arc::new(self.clone()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algorithm::search::a_star::a_star_algorithm::run_a_star;
+    use crate::algorithm::search::backtrack::vertex_oriented_route;
+    use crate::algorithm::search::direction::Direction;
+    use crate::algorithm::search::search_instance::SearchInstance;
+    use crate::model::cost::cost_aggregation::CostAggregation;
+    use crate::model::cost::cost_model::CostModel;
+    use crate::model::cost::vehicle::vehicle_cost_rate::VehicleCostRate;
+    use crate::model::road_network::{edge_id::EdgeId, graph::Graph, vertex_id::VertexId};
+    use crate::model::traversal::default::distance_traversal_model::DistanceTraversalModel;
+    use crate::model::traversal::traversal_model::TraversalModel;
+    use crate::model::unit::DistanceUnit;
+    use crate::util::test_util::{distance_search_instance, graph_from_edges};
+
+    /// main street runs (0) -[0]-> (1) -[1]-> (2) -[2]-> (3), 300 meters in total.
+    /// a side street leaves it at (1) and returns at (3), which is 80 meters shorter:
+    /// (1) -[3]-> (4) -[4]-> (5) -[5]-> (3), where edge 5 is back on main street.
+    fn build_graph() -> (Graph, Vec<String>) {
+        let vertices = (0..6).map(|i| Vertex::new(i, 0.0, 0.0)).collect::<Vec<_>>();
+        let edges = vec![
+            Edge::new(0, 0, 1, 100.0),
+            Edge::new(1, 1, 2, 100.0),
+            Edge::new(2, 2, 3, 100.0),
+            Edge::new(3, 1, 4, 40.0),
+            Edge::new(4, 4, 5, 40.0),
+            Edge::new(5, 5, 3, 40.0),
+        ];
+        let names = [
+            "Main St", "Main St", "main st ", "Side St", "Side St", "Main St",
+        ]
+        .iter()
+        .map(|n| n.to_string())
+        .collect();
+        let graph = graph_from_edges(vertices, edges);
+        (graph, names)
+    }
+
+    /// finds the shortest route from (0) to (3), counting each unit of penalty as a meter
+    fn route_with_lookback(lookback: usize) -> Vec<EdgeId> {
+        let (graph, names) = build_graph();
+        let access_model = Arc::new(RoadReentryAccessModel::new(&names, lookback, 100.0).unwrap());
+        let traversal_model = Arc::new(DistanceTraversalModel::new(DistanceUnit::Meters));
+        let state_model = Arc::new(
+            StateModel::empty()
+                .extend(traversal_model.state_features())
+                .unwrap()
+                .extend(access_model.state_features())
+                .unwrap(),
+        );
+        let (distance, penalty) = (
+            String::from("distance"),
+            String::from(RoadReentryAccessModel::PENALTY_FEATURE),
+        );
+        let cost_model = CostModel::new(
+            Arc::new(HashMap::from([
+                (distance.clone(), 1.0),
+                (penalty.clone(), 1.0),
+            ])),
+            Arc::new(HashMap::from([
+                (distance, VehicleCostRate::Raw),
+                (penalty, VehicleCostRate::Raw),
+            ])),
+            Arc::new(HashMap::new()),
+            CostAggregation::Sum,
+            Arc::new(vec![]),
+            state_model.clone(),
+        )
+        .unwrap();
+        let si = SearchInstance {
+            state_model,
+            traversal_model,
+            access_model,
+            cost_model,
+            ..distance_search_instance(graph)
+        };
+        let (o, d) = (VertexId(0), VertexId(3));
+        let result = run_a_star(o, Some(d), &Direction::Forward, None, &si).unwrap();
+        let route = vertex_oriented_route(o, d, &result.tree).unwrap();
+        route.iter().map(|et| et.edge_id).collect()
+    }
+
+    #[test]
+    fn test_penalty_suppresses_short_detour() {
+        // the detour leaves main street for 2 edges. with a lookback of 2 edges, returning
+        // to main street costs a penalty of 100, so the 80 meter saving is not worth it
+        assert_eq!(
+            route_with_lookback(2),
+            vec![EdgeId(0), EdgeId(1), EdgeId(2)]
+        );
+        // with a lookback of 1 edge, main street is forgotten by the time the route
+        // returns to it, so the detour is taken
+        assert_eq!(
+            route_with_lookback(1),
+            vec![EdgeId(0), EdgeId(3), EdgeId(4), EdgeId(5)]
+        );
+    }
+
+    #[test]
+    fn test_road_identity_from_names() {
+        let names = ["Main St", " main st", "", "Side St"]
+            .iter()
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>();
+        let model = RoadReentryAccessModel::new(&names, 3, 1.0).unwrap();
+        assert_eq!(model.edge_road_ids.as_ref().as_ref(), &[0, 0, NO_ROAD, 1]);
+        assert!(RoadReentryAccessModel::new(&names, 0, 1.0).is_err());
+        assert!(RoadReentryAccessModel::new(&names, 3, -1.0).is_err());
+    }
+}
@@ -7,6 +7,14 @@ use crate::model::{
 };
 use std::sync::Arc;
 
+/// adds a delay for the turn between two consecutive edges of a route. the angle of the
+/// turn is found from the end heading of the previous edge and the start heading of the
+/// next edge, which is bucketed into a [`super::turn::Turn`] with a delay in the turn
+/// delay model.
+///
+/// the delay is added to the time state feature as the search moves onto the next edge,
+/// so it accumulates into the route time and any time cost alongside the traversal time
+/// of each edge. the first edge of a route has no previous edge and no delay.
 pub struct TurnDelayAccessModel {
     pub engine: Arc<TurnDelayAccessModelEngine>,
 }
@@ -27,3 +35,78 @@ impl AccessModel for TurnDelayAccessModel {
         vec![]
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::{
+        access::default::turn_delays::{
+            edge_heading::EdgeHeading, turn::Turn, turn_delay_model::TurnDelayModel,
+        },
+        unit::{as_f64::AsF64, Time, TimeUnit},
+    };
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_left_turn_delay() {
+        // edge 0 heads east, edge 1 heads north and edge 2 heads south
+        let edge_headings = vec![
+            EdgeHeading::new(90, 90),
+            EdgeHeading::new(0, 0),
+            EdgeHeading::new(180, 180),
+        ];
+        let table = HashMap::from([
+            (Turn::NoTurn, Time::ZERO),
+            (Turn::Left, Time::new(2.5)),
+            (Turn::Right, Time::new(1.0)),
+        ]);
+        let engine = TurnDelayAccessModelEngine {
+            edge_headings: edge_headings.into_boxed_slice(),
+            turn_delay_model: TurnDelayModel::TabularDiscrete {
+                table,
+                time_unit: TimeUnit::Seconds,
+            },
+            time_feature_name: String::from("time"),
+        };
+        let model = TurnDelayAccessModel {
+            engine: Arc::new(engine),
+        };
+        let time = String::from("time");
+        let state_model = StateModel::new(vec![(
+            time.clone(),
+            StateFeature::Time {
+                time_unit: TimeUnit::Minutes,
+                initial: Time::ZERO,
+                output_alias: None,
+            },
+        )]);
+        let vertices = (0..4).map(|i| Vertex::new(i, 0.0, 0.0)).collect::<Vec<_>>();
+        let edges = [
+            Edge::new(0, 0, 1, 10.0),
+            Edge::new(1, 1, 2, 10.0),
+            Edge::new(2, 1, 3, 10.0),
+        ];
+        let access_delay = |next: usize| {
+            let mut state = state_model.initial_state().unwrap();
+            let traversal = (
+                &vertices[0],
+                &edges[0],
+                &vertices[1],
+                &edges[next],
+                &vertices[edges[next].dst_vertex_id.0],
+            );
+            model
+                .access_edge(traversal, &mut state, &state_model)
+                .unwrap();
+            state_model
+                .get_time(&state, &time, &TimeUnit::Seconds)
+                .unwrap()
+        };
+
+        // turning from east to north is a 90 degree left turn
+        let angle = EdgeHeading::new(90, 90).bearing_to_destination(&EdgeHeading::new(0, 0));
+        assert_eq!(angle, -90);
+        assert!((access_delay(1).as_f64() - 2.5).abs() < 1e-9);
+        assert!((access_delay(2).as_f64() - 1.0).abs() < 1e-9);
+    }
+}
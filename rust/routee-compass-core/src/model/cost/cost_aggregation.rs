@@ -1,17 +1,44 @@
 use crate::model::unit::{as_f64::AsF64, Cost};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 use super::cost_error::CostError;
 
-#[derive(Deserialize, Serialize, Clone, Copy, Debug, Default)]
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum CostAggregation {
     #[default]
     Sum,
     Mul,
+    /// the least of the feature costs. with no feature costs, the cost is zero.
+    Min,
+    /// the greatest of the feature costs, such as for costs that represent a
+    /// worst-case bound. with no feature costs, the cost is zero.
+    Max,
+    /// sums feature costs after multiplying each by the weight for its feature
+    /// name. features without a weight have a weight of 1.0.
+    WeightedSum {
+        weights: HashMap<String, f64>,
+    },
 }
 
 impl CostAggregation {
+    /// confirms that the parameters of this aggregation are valid, which for a
+    /// weighted sum requires each weight to be a non-negative number.
+    pub fn validate(&self) -> Result<(), CostError> {
+        if let CostAggregation::WeightedSum { weights } = self {
+            for (name, weight) in weights.iter() {
+                if !(weight.is_finite() && *weight >= 0.0) {
+                    return Err(CostError::InvalidConfiguration(format!(
+                        "weighted sum weight for '{}' must be a non-negative number, found {}",
+                        name, weight
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub fn agg(&self, costs: &[(&String, Cost)]) -> Cost {
         match self {
             CostAggregation::Sum => costs.iter().fold(Cost::ZERO, |acc, (_, c)| acc + *c),
@@ -24,6 +51,13 @@ impl CostAggregation {
                     })
                 }
             }
+            CostAggregation::WeightedSum { weights } => {
+                costs.iter().fold(Cost::ZERO, |acc, (name, c)| {
+                    acc + Self::weighted(weights, name, *c)
+                })
+            }
+            CostAggregation::Min => costs.iter().map(|(_, c)| *c).min().unwrap_or(Cost::ZERO),
+            CostAggregation::Max => costs.iter().map(|(_, c)| *c).max().unwrap_or(Cost::ZERO),
         }
     }
 
@@ -53,6 +87,108 @@ impl CostAggregation {
                 }
                 Ok(product)
             }
+            CostAggregation::WeightedSum { weights } => {
+                let mut sum = Cost::ZERO;
+                for cost in costs {
+                    let (name, cost) = cost?;
+                    sum = sum + Self::weighted(weights, name, cost);
+                }
+                Ok(sum)
+            }
+            CostAggregation::Min => {
+                let mut min: Option<Cost> = None;
+                for cost in costs {
+                    let (_, cost) = cost?;
+                    min = Some(min.map_or(cost, |m| m.min(cost)));
+                }
+                Ok(min.unwrap_or(Cost::ZERO))
+            }
+            CostAggregation::Max => {
+                let mut max: Option<Cost> = None;
+                for cost in costs {
+                    let (_, cost) = cost?;
+                    max = Some(max.map_or(cost, |m| m.max(cost)));
+                }
+                Ok(max.unwrap_or(Cost::ZERO))
+            }
+        }
+    }
+
+    /// the cost of a feature scaled by its weight in a weighted sum.
+    fn weighted(weights: &HashMap<String, f64>, name: &String, cost: Cost) -> Cost {
+        let weight = weights.get(name).cloned().unwrap_or(1.0);
+        Cost::new(cost.as_f64() * weight)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_min_max_aggregation() {
+        let (a, b, c) = (String::from("a"), String::from("b"), String::from("c"));
+        let costs = vec![
+            (&a, Cost::new(2.5)),
+            (&b, Cost::new(0.5)),
+            (&c, Cost::new(7.0)),
+        ];
+        let agg_iter = |aggregation: CostAggregation, costs: &[(&String, Cost)]| {
+            aggregation
+                .agg_iter(costs.iter().map(|(n, c)| Ok((*n, *c))))
+                .unwrap()
+        };
+        assert_eq!(CostAggregation::Min.agg(&costs), Cost::new(0.5));
+        assert_eq!(CostAggregation::Max.agg(&costs), Cost::new(7.0));
+        assert_eq!(agg_iter(CostAggregation::Min, &costs), Cost::new(0.5));
+        assert_eq!(agg_iter(CostAggregation::Max, &costs), Cost::new(7.0));
+
+        // with no feature costs, the cost is zero
+        for aggregation in [CostAggregation::Min, CostAggregation::Max] {
+            assert_eq!(aggregation.agg(&[]), Cost::ZERO);
+            assert_eq!(agg_iter(aggregation, &[]), Cost::ZERO);
         }
+
+        let min: CostAggregation = serde_json::from_value(serde_json::json!("min")).unwrap();
+        assert!(matches!(min, CostAggregation::Min));
+        let max: CostAggregation = serde_json::from_value(serde_json::json!("max")).unwrap();
+        assert!(matches!(max, CostAggregation::Max));
+    }
+
+    #[test]
+    fn test_weighted_sum_aggregation() {
+        let (distance, time, energy) = (
+            String::from("distance"),
+            String::from("time"),
+            String::from("energy"),
+        );
+        let costs = vec![
+            (&distance, Cost::new(2.0)),
+            (&time, Cost::new(3.0)),
+            (&energy, Cost::new(5.0)),
+        ];
+        let weighted_sum = |weights: serde_json::Value| -> CostAggregation {
+            serde_json::from_value(serde_json::json!({ "weighted_sum": { "weights": weights } }))
+                .unwrap()
+        };
+
+        // energy has no weight, which counts it once
+        let aggregation = weighted_sum(serde_json::json!({ "distance": 0.5, "time": 2.0 }));
+        aggregation.validate().unwrap();
+        assert_eq!(aggregation.agg(&costs), Cost::new(12.0));
+
+        // a zero weight removes the feature from the cost
+        let aggregation = weighted_sum(serde_json::json!({ "energy": 0.0 }));
+        assert_eq!(aggregation.agg(&costs), Cost::new(5.0));
+        let iter_cost = aggregation
+            .agg_iter(costs.iter().map(|(n, c)| Ok((*n, *c))))
+            .unwrap();
+        assert_eq!(iter_cost, Cost::new(5.0));
+
+        let negative = weighted_sum(serde_json::json!({ "time": -1.0 }));
+        assert!(matches!(
+            negative.validate(),
+            Err(CostError::InvalidConfiguration(_))
+        ));
     }
 }
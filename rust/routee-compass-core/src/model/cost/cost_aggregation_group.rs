@@ -0,0 +1,141 @@
+use super::{cost_aggregation::CostAggregation, cost_error::CostError};
+use crate::model::unit::Cost;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// name used for the cost term produced by a nested group when it is
+/// aggregated within its parent.
+const GROUP_TERM: &str = "group";
+
+/// a group of feature costs that are aggregated together with their own
+/// aggregation operator before being combined with the remaining costs.
+/// groups may be nested, and are evaluated bottom-up, so that the result of
+/// a child group is treated as a single cost term within its parent group.
+///
+/// # Example
+///
+/// ### Deserialization
+///
+/// combine two penalty features multiplicatively and sum the result with the
+/// remaining (ungrouped) features, which aggregate via the root `cost_aggregation`:
+///
+/// ```toml
+/// [cost]
+/// cost_aggregation = "sum"
+/// cost_aggregation_groups = [
+///   { aggregation = "mul", features = ["turn_penalty", "class_penalty"] }
+/// ]
+/// ```
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct CostAggregationGroup {
+    pub aggregation: CostAggregation,
+    #[serde(default)]
+    pub features: Vec<String>,
+    #[serde(default)]
+    pub groups: Vec<CostAggregationGroup>,
+}
+
+impl CostAggregationGroup {
+    /// aggregates the feature costs of this group, recursing into any child groups.
+    ///
+    /// # Arguments
+    ///
+    /// * `costs` - lookup from feature name to the cost computed for that feature
+    ///
+    /// # Returns
+    ///
+    /// the aggregated cost of this group. features that are missing from the
+    /// lookup do not participate in the aggregation.
+    pub fn agg(&self, costs: &HashMap<&String, Cost>) -> Cost {
+        let group_term = String::from(GROUP_TERM);
+        let terms = self
+            .features
+            .iter()
+            .filter_map(|f| costs.get(f).map(|c| (f, *c)))
+            .chain(self.groups.iter().map(|g| (&group_term, g.agg(costs))))
+            .collect::<Vec<_>>();
+        self.aggregation.agg(&terms)
+    }
+
+    /// iterates over all feature names referenced by this group and its descendants.
+    pub fn features(&self) -> Box<dyn Iterator<Item = &String> + '_> {
+        Box::new(
+            self.features
+                .iter()
+                .chain(self.groups.iter().flat_map(|g| g.features())),
+        )
+    }
+
+    /// confirms that the aggregation of this group and each of its descendants is valid.
+    fn validate_aggregations(&self) -> Result<(), CostError> {
+        self.aggregation.validate()?;
+        self.groups
+            .iter()
+            .try_for_each(|g| g.validate_aggregations())
+    }
+
+    /// confirms that no feature is referenced more than once across a set of groups
+    /// and that each referenced feature is known.
+    ///
+    /// # Arguments
+    ///
+    /// * `groups` - the groups to validate
+    /// * `known`  - the feature names available for aggregation
+    pub fn validate(
+        groups: &[CostAggregationGroup],
+        known: &HashSet<&String>,
+    ) -> Result<(), CostError> {
+        for group in groups.iter() {
+            group.validate_aggregations()?;
+        }
+        let mut seen = HashSet::new();
+        for feature in groups.iter().flat_map(|g| g.features()) {
+            if !known.contains(feature) {
+                return Err(CostError::InvalidConfiguration(format!(
+                    "cost aggregation group references unknown feature '{}'",
+                    feature
+                )));
+            }
+            if !seen.insert(feature) {
+                return Err(CostError::InvalidConfiguration(format!(
+                    "feature '{}' appears in more than one cost aggregation group",
+                    feature
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// aggregates a set of feature costs as a tree. each group is evaluated bottom-up
+/// and contributes a single term to the root aggregation, along with every
+/// feature cost that does not belong to any group.
+///
+/// # Arguments
+///
+/// * `aggregation` - the root aggregation operator
+/// * `groups`      - nested groups of features with their own aggregation operators
+/// * `costs`       - the cost of each feature
+///
+/// # Returns
+///
+/// the aggregated cost, or an error if computing any feature cost failed
+pub fn agg_grouped<'a>(
+    aggregation: &CostAggregation,
+    groups: &[CostAggregationGroup],
+    costs: impl Iterator<Item = Result<(&'a String, Cost), CostError>>,
+) -> Result<Cost, CostError> {
+    let costs = costs.collect::<Result<Vec<_>, _>>()?;
+    let lookup = costs.iter().cloned().collect::<HashMap<_, _>>();
+    let grouped = groups
+        .iter()
+        .flat_map(|g| g.features())
+        .collect::<HashSet<_>>();
+    let group_term = String::from(GROUP_TERM);
+    let terms = costs
+        .into_iter()
+        .filter(|(n, _)| !grouped.contains(n))
+        .chain(groups.iter().map(|g| (&group_term, g.agg(&lookup))))
+        .collect::<Vec<_>>();
+    Ok(aggregation.agg(&terms))
+}
@@ -1,25 +1,39 @@
 use super::cost_aggregation::CostAggregation;
+use super::cost_aggregation_group::CostAggregationGroup;
 use super::cost_ops;
+use super::edge_cost_cap::{EdgeCostCap, EdgeCostCapPolicy};
 use super::network::network_cost_rate::NetworkCostRate;
 use super::vehicle::vehicle_cost_rate::VehicleCostRate;
 use crate::model::cost::cost_error::CostError;
 use crate::model::property::edge::Edge;
+use crate::model::road_network::edge_id::EdgeId;
 use crate::model::state::state_model::StateModel;
 use crate::model::traversal::state::state_variable::StateVar;
-use crate::model::unit::Cost;
+use crate::model::unit::{as_f64::AsF64, Cost};
 use serde_json::json;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 /// implementation of a model for calculating Cost from a state transition.
 /// vectorized, where each index in these vectors matches the corresponding index
 /// in the state model.
+#[derive(Clone)]
 pub struct CostModel {
     feature_indices: Vec<(String, usize)>,
     weights: Vec<f64>,
     vehicle_rates: Vec<VehicleCostRate>,
     network_rates: Vec<NetworkCostRate>,
     cost_aggregation: CostAggregation,
+    cost_aggregation_groups: Arc<Vec<CostAggregationGroup>>,
+    tie_break: Option<(String, usize)>,
+    edge_cost_cap: Option<EdgeCostCap>,
+    /// set once a capped traversal cost has been logged as a warning
+    cap_warned: Arc<AtomicBool>,
+    /// multipliers on the traversal cost of specific edges, such as to steer a search away from them
+    edge_penalties: Option<Arc<HashMap<EdgeId, f64>>>,
+    /// the least a path cost must improve on the cost found so far to replace it in a search
+    min_improvement: Cost,
 }
 
 impl CostModel {
@@ -32,6 +46,7 @@ impl CostModel {
     const FEATURE: &'static str = "feature";
     const WEIGHT: &'static str = "weight";
     const COST_AGGREGATION: &'static str = "cost_aggregation";
+    const COST_AGGREGATION_GROUPS: &'static str = "cost_aggregation_groups";
 
     /// builds a cost model for a specific query.
     ///
@@ -45,12 +60,14 @@ impl CostModel {
     /// * `vehicle_rate_mapping` - for each feature name, a vehicle cost rate for that feature
     /// * `network_rate_mapping` - for each feature name, a network cost rate for that feature
     /// * `cost_aggregation`     - function for aggregating each feature cost (for example, Sum)
+    /// * `cost_aggregation_groups` - groups of features aggregated separately before the root aggregation
     /// * `state_model`          - state model instance for this search
     pub fn new(
         weights_mapping: Arc<HashMap<String, f64>>,
         vehicle_rate_mapping: Arc<HashMap<String, VehicleCostRate>>,
         network_rate_mapping: Arc<HashMap<String, NetworkCostRate>>,
         cost_aggregation: CostAggregation,
+        cost_aggregation_groups: Arc<Vec<CostAggregationGroup>>,
         state_model: Arc<StateModel>,
     ) -> Result<CostModel, CostError> {
         let mut indices = vec![];
@@ -58,7 +75,7 @@ impl CostModel {
         let mut vehicle_rates = vec![];
         let mut network_rates = vec![];
 
-        for (index, (name, _)) in state_model.indexed_iter() {
+        for (index, (name, feature)) in state_model.indexed_iter() {
             // always instantiate a value for each vector, diverting to default (zero-valued) if not provided
             // which has the following effect:
             // - weight: deactivates costs for this feature (product)
@@ -67,6 +84,7 @@ impl CostModel {
             let weight = weights_mapping.get(name).cloned().unwrap_or_default();
             let v_rate = vehicle_rate_mapping.get(name).cloned().unwrap_or_default();
             let n_rate = network_rate_mapping.get(name).cloned().unwrap_or_default();
+            v_rate.validate(name, feature)?;
 
             indices.push((name.clone(), index));
             weights.push(weight);
@@ -77,15 +95,201 @@ impl CostModel {
         if weights.iter().sum::<f64>() == 0.0 {
             return Err(CostError::InvalidCostVariables);
         }
+        let known_features = indices.iter().map(|(n, _)| n).collect::<HashSet<_>>();
+        CostAggregationGroup::validate(&cost_aggregation_groups, &known_features)?;
+        cost_aggregation.validate()?;
+
         Ok(CostModel {
             feature_indices: indices,
             weights,
             vehicle_rates,
             network_rates,
             cost_aggregation,
+            cost_aggregation_groups,
+            tie_break: None,
+            edge_cost_cap: None,
+            cap_warned: Arc::new(AtomicBool::new(false)),
+            edge_penalties: None,
+            min_improvement: Cost::ZERO,
         })
     }
 
+    /// sets a secondary objective used only to break ties between search paths
+    /// with equal cost. the path with the lower accumulated value of the tie-break
+    /// feature is preferred. when costs differ, the tie-break feature has no effect.
+    ///
+    /// # Arguments
+    /// * `feature` - name of the state feature to minimize among equal-cost paths, or None to disable
+    pub fn with_tie_break(mut self, feature: Option<&String>) -> Result<CostModel, CostError> {
+        self.tie_break = match feature {
+            None => None,
+            Some(name) => {
+                let (_, index) = self
+                    .feature_indices
+                    .iter()
+                    .find(|(n, _)| n == name)
+                    .ok_or_else(|| {
+                        let names = self.feature_indices.iter().map(|(n, _)| n.clone());
+                        CostError::StateVariableNotFound(
+                            name.clone(),
+                            String::from("tie break"),
+                            names.collect::<Vec<_>>().join(","),
+                        )
+                    })?;
+                Some((name.clone(), *index))
+            }
+        };
+        Ok(self)
+    }
+
+    /// sets the least a path cost must improve on the cost found so far for a vertex
+    /// to replace it during a search. on graphs with many near-equal-cost paths, this
+    /// avoids re-relaxing vertices over tiny floating-point improvements, at the cost
+    /// of a bounded suboptimality: the cost found for a vertex n edges from the origin
+    /// exceeds its optimal cost by less than n times the min improvement. a min
+    /// improvement of zero keeps exact search behavior.
+    ///
+    /// # Arguments
+    /// * `min_improvement` - the non-negative minimum improvement, or None for zero
+    pub fn with_min_improvement(
+        mut self,
+        min_improvement: Option<f64>,
+    ) -> Result<CostModel, CostError> {
+        let min_improvement = min_improvement.unwrap_or_default();
+        if !min_improvement.is_finite() || min_improvement < 0.0 {
+            return Err(CostError::InvalidConfiguration(format!(
+                "min improvement must be a non-negative number, found {}",
+                min_improvement
+            )));
+        }
+        self.min_improvement = Cost::new(min_improvement);
+        Ok(self)
+    }
+
+    /// sets a maximum traversal cost for any single edge. edges whose traversal cost
+    /// exceeds the cap are capped or excluded according to the cap policy.
+    ///
+    /// # Arguments
+    /// * `edge_cost_cap` - the max traversal cost and policy, or None to disable
+    pub fn with_edge_cost_cap(
+        mut self,
+        edge_cost_cap: Option<EdgeCostCap>,
+    ) -> Result<CostModel, CostError> {
+        if let Some(cap) = &edge_cost_cap {
+            if cap.max_cost.is_nan() || cap.max_cost <= 0.0 {
+                return Err(CostError::InvalidConfiguration(format!(
+                    "edge cost cap must be positive, found {}",
+                    cap.max_cost
+                )));
+            }
+        }
+        self.edge_cost_cap = edge_cost_cap;
+        Ok(self)
+    }
+
+    /// sets multipliers on the traversal cost of specific edges, which are applied
+    /// after the edge cost cap. a multiplier greater than one discourages a search
+    /// from using an edge.
+    ///
+    /// # Arguments
+    /// * `edge_penalties` - multiplier for each penalized edge, or None to disable
+    pub fn with_edge_penalties(
+        mut self,
+        edge_penalties: Option<Arc<HashMap<EdgeId, f64>>>,
+    ) -> Result<CostModel, CostError> {
+        if let Some(penalties) = &edge_penalties {
+            if let Some((edge_id, penalty)) =
+                penalties.iter().find(|(_, p)| p.is_nan() || **p < 1.0)
+            {
+                return Err(CostError::InvalidConfiguration(format!(
+                    "edge penalty must be at least 1.0, found {} for edge {}",
+                    penalty, edge_id
+                )));
+            }
+        }
+        self.edge_penalties = edge_penalties;
+        Ok(self)
+    }
+
+    /// applies the edge cost cap, if any, to the traversal cost of an edge.
+    /// an excluded edge receives an infinite cost so that no route uses it. the
+    /// first capped cost is logged as a warning and any further ones at debug level.
+    ///
+    /// # Arguments
+    /// * `edge_id` - edge traversed
+    /// * `cost`    - traversal cost of the edge
+    ///
+    /// # Returns
+    ///
+    /// the traversal cost after applying the cap
+    pub fn apply_edge_cost_cap(&self, edge_id: EdgeId, cost: Cost) -> Cost {
+        match &self.edge_cost_cap {
+            None => cost,
+            Some(cap) => {
+                let result = cap.apply(cost);
+                if result != cost && cap.policy == EdgeCostCapPolicy::Cap {
+                    if !self.cap_warned.swap(true, Ordering::Relaxed) {
+                        log::warn!(
+                            "traversal cost {} of edge {} exceeds the edge cost cap, capped at {}. further occurrences are logged at debug level.",
+                            cost,
+                            edge_id,
+                            result
+                        );
+                    } else {
+                        log::debug!(
+                            "traversal cost {} of edge {} exceeds the edge cost cap, capped at {}",
+                            cost,
+                            edge_id,
+                            result
+                        );
+                    }
+                }
+                result
+            }
+        }
+    }
+
+    /// applies the edge penalty, if any, to the traversal cost of an edge.
+    ///
+    /// # Arguments
+    /// * `edge_id` - edge traversed
+    /// * `cost`    - traversal cost of the edge
+    ///
+    /// # Returns
+    ///
+    /// the traversal cost after applying the penalty
+    pub fn apply_edge_penalty(&self, edge_id: EdgeId, cost: Cost) -> Cost {
+        match self
+            .edge_penalties
+            .as_ref()
+            .and_then(|penalties| penalties.get(&edge_id))
+        {
+            None => cost,
+            Some(penalty) => Cost::new(cost.as_f64() * penalty),
+        }
+    }
+
+    /// tests if a candidate path cost improves on the cost found so far by at least
+    /// the min improvement. with a min improvement of zero, any lower cost improves.
+    pub fn improves_on(&self, candidate: Cost, existing: Cost) -> bool {
+        candidate < existing && existing - candidate >= self.min_improvement
+    }
+
+    /// the value of the tie-break feature in some state, which is compared between
+    /// paths with equal cost. returns zero when no tie-break feature is set, so that
+    /// all equal-cost paths remain tied.
+    pub fn tie_break_value(&self, state: &[StateVar]) -> Result<Cost, CostError> {
+        match &self.tie_break {
+            None => Ok(Cost::ZERO),
+            Some((name, index)) => {
+                let value = state
+                    .get(*index)
+                    .ok_or_else(|| CostError::StateIndexOutOfBounds(*index, name.clone()))?;
+                Ok(Cost::new(value.0))
+            }
+        }
+    }
+
     /// Calculates the cost of traversing an edge due to some state transition.
     ///
     /// # Arguments
@@ -109,6 +313,7 @@ impl CostModel {
             &self.weights,
             &self.vehicle_rates,
             &self.cost_aggregation,
+            &self.cost_aggregation_groups,
         )?;
         let network_cost = cost_ops::calculate_network_traversal_costs(
             (prev_state, next_state),
@@ -117,6 +322,7 @@ impl CostModel {
             &self.weights,
             &self.network_rates,
             &self.cost_aggregation,
+            &self.cost_aggregation_groups,
         )?;
         let total_cost = vehicle_cost + network_cost;
         let pos_cost = Cost::enforce_strictly_positive(total_cost);
@@ -153,6 +359,7 @@ impl CostModel {
             &self.weights,
             &self.vehicle_rates,
             &self.cost_aggregation,
+            &self.cost_aggregation_groups,
         )?;
         let network_cost = cost_ops::calculate_network_access_costs(
             (prev_state, next_state),
@@ -161,6 +368,7 @@ impl CostModel {
             &self.weights,
             &self.network_rates,
             &self.cost_aggregation,
+            &self.cost_aggregation_groups,
         )?;
         let total_cost = vehicle_cost + network_cost;
         let pos_cost = Cost::enforce_strictly_positive(total_cost);
@@ -191,6 +399,7 @@ impl CostModel {
             &self.weights,
             &self.vehicle_rates,
             &self.cost_aggregation,
+            &self.cost_aggregation_groups,
         )?;
         let pos_cost = Cost::enforce_non_negative(vehicle_cost);
         Ok(pos_cost)
@@ -294,7 +503,128 @@ impl CostModel {
             Self::COST_AGGREGATION.to_string(),
             json![self.cost_aggregation],
         );
+        result.insert(
+            Self::COST_AGGREGATION_GROUPS.to_string(),
+            json![self.cost_aggregation_groups.as_ref()],
+        );
 
         Ok(json![result])
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::state::state_feature::StateFeature;
+    use crate::model::unit::{Distance, DistanceUnit, Energy, EnergyUnit};
+
+    fn mock_model(
+        cost_aggregation_groups: Vec<CostAggregationGroup>,
+    ) -> Result<CostModel, CostError> {
+        let features = ["distance", "turn_penalty", "class_penalty"];
+        let state_model = StateModel::new(
+            features
+                .iter()
+                .map(|n| {
+                    let feature = StateFeature::Distance {
+                        distance_unit: DistanceUnit::Kilometers,
+                        initial: Distance::ZERO,
+                        output_alias: None,
+                    };
+                    (String::from(*n), feature)
+                })
+                .collect(),
+        );
+        let weights = features.iter().map(|n| (String::from(*n), 1.0)).collect();
+        let rates = features
+            .iter()
+            .map(|n| (String::from(*n), VehicleCostRate::Raw))
+            .collect();
+        CostModel::new(
+            Arc::new(weights),
+            Arc::new(rates),
+            Arc::new(HashMap::new()),
+            CostAggregation::Sum,
+            Arc::new(cost_aggregation_groups),
+            Arc::new(state_model),
+        )
+    }
+
+    #[test]
+    fn test_nested_aggregation_differs_from_flat_sum() {
+        let prev = vec![StateVar(0.0), StateVar(0.0), StateVar(0.0)];
+        let next = vec![StateVar(1.0), StateVar(2.0), StateVar(3.0)];
+        let edge = Edge::default();
+
+        let flat = mock_model(vec![]).unwrap();
+        let flat_cost = flat.traversal_cost(&edge, &prev, &next).unwrap();
+        assert_eq!(flat_cost, Cost::new(6.0));
+
+        let nested = mock_model(vec![CostAggregationGroup {
+            aggregation: CostAggregation::Mul,
+            features: vec![String::from("turn_penalty"), String::from("class_penalty")],
+            groups: vec![],
+        }])
+        .unwrap();
+        let nested_cost = nested.traversal_cost(&edge, &prev, &next).unwrap();
+        assert_eq!(nested_cost, Cost::new(7.0));
+    }
+
+    #[test]
+    fn test_nested_aggregation_rejects_duplicate_feature() {
+        let group = CostAggregationGroup {
+            aggregation: CostAggregation::Mul,
+            features: vec![String::from("turn_penalty")],
+            groups: vec![],
+        };
+        let result = mock_model(vec![group.clone(), group]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_energy_price_unit_must_match_feature() {
+        let state_model = Arc::new(StateModel::new(vec![
+            (
+                String::from("distance"),
+                StateFeature::Distance {
+                    distance_unit: DistanceUnit::Kilometers,
+                    initial: Distance::ZERO,
+                    output_alias: None,
+                },
+            ),
+            (
+                String::from("energy"),
+                StateFeature::Energy {
+                    energy_unit: EnergyUnit::KilowattHours,
+                    initial: Energy::ZERO,
+                    output_alias: None,
+                },
+            ),
+        ]));
+        let build = |feature: &str, energy_unit: EnergyUnit| {
+            CostModel::new(
+                Arc::new(HashMap::from([(String::from(feature), 1.0)])),
+                Arc::new(HashMap::from([(
+                    String::from(feature),
+                    VehicleCostRate::EnergyPrice {
+                        price: 0.15,
+                        energy_unit,
+                    },
+                )])),
+                Arc::new(HashMap::new()),
+                CostAggregation::Sum,
+                Arc::new(vec![]),
+                state_model.clone(),
+            )
+        };
+        assert!(build("energy", EnergyUnit::KilowattHours).is_ok());
+        assert!(matches!(
+            build("energy", EnergyUnit::GallonsGasoline),
+            Err(CostError::InvalidConfiguration(_))
+        ));
+        assert!(matches!(
+            build("distance", EnergyUnit::KilowattHours),
+            Err(CostError::InvalidConfiguration(_))
+        ));
+    }
+}
@@ -1,6 +1,9 @@
 use super::{
-    cost_aggregation::CostAggregation, cost_error::CostError,
-    network::network_cost_rate::NetworkCostRate, vehicle::vehicle_cost_rate::VehicleCostRate,
+    cost_aggregation::CostAggregation,
+    cost_aggregation_group::{self, CostAggregationGroup},
+    cost_error::CostError,
+    network::network_cost_rate::NetworkCostRate,
+    vehicle::vehicle_cost_rate::VehicleCostRate,
 };
 use crate::model::{property::edge::Edge, traversal::state::state_variable::StateVar, unit::Cost};
 
@@ -11,12 +14,15 @@ use crate::model::{property::edge::Edge, traversal::state::state_variable::State
 /// * `prev_state` - the state before beginning the traversal
 /// * `next_state` - the state after traversal
 /// * `indices`    - feature names and corresponding state indices
+/// * `cost_aggregation`        - operation used to aggregate ungrouped feature costs
+/// * `cost_aggregation_groups` - nested groups of features with their own aggregation
 pub fn calculate_vehicle_costs(
     state_sequence: (&[StateVar], &[StateVar]),
     indices: &[(String, usize)],
     weights: &[f64],
     rates: &[VehicleCostRate],
     cost_aggregation: &CostAggregation,
+    cost_aggregation_groups: &[CostAggregationGroup],
 ) -> Result<Cost, CostError> {
     let (prev_state, next_state) = state_sequence;
     let costs = indices.iter().map(|(name, state_idx)| {
@@ -43,7 +49,7 @@ pub fn calculate_vehicle_costs(
         Ok((name, cost))
     });
 
-    cost_aggregation.agg_iter(costs)
+    aggregate(cost_aggregation, cost_aggregation_groups, costs)
 }
 
 pub fn calculate_network_traversal_costs(
@@ -53,6 +59,7 @@ pub fn calculate_network_traversal_costs(
     weights: &[f64],
     rates: &[NetworkCostRate],
     cost_aggregation: &CostAggregation,
+    cost_aggregation_groups: &[CostAggregationGroup],
 ) -> Result<Cost, CostError> {
     let (prev_state, next_state) = state_sequence;
     let costs = indices.iter().map(|(name, state_idx)| {
@@ -75,7 +82,7 @@ pub fn calculate_network_traversal_costs(
         Ok((name, cost))
     });
 
-    cost_aggregation.agg_iter(costs)
+    aggregate(cost_aggregation, cost_aggregation_groups, costs)
 }
 
 pub fn calculate_network_access_costs(
@@ -85,6 +92,7 @@ pub fn calculate_network_access_costs(
     weights: &[f64],
     rates: &[NetworkCostRate],
     cost_aggregation: &CostAggregation,
+    cost_aggregation_groups: &[CostAggregationGroup],
 ) -> Result<Cost, CostError> {
     let (prev_state, next_state) = state_sequence;
     let (prev_edge, next_edge) = edge_sequence;
@@ -105,5 +113,19 @@ pub fn calculate_network_access_costs(
         }
     });
 
-    cost_aggregation.agg_iter(costs)
+    aggregate(cost_aggregation, cost_aggregation_groups, costs)
+}
+
+/// aggregates feature costs, using the flat aggregation when no groups are configured
+/// and the tree aggregation otherwise.
+fn aggregate<'a>(
+    cost_aggregation: &CostAggregation,
+    cost_aggregation_groups: &[CostAggregationGroup],
+    costs: impl Iterator<Item = Result<(&'a String, Cost), CostError>>,
+) -> Result<Cost, CostError> {
+    if cost_aggregation_groups.is_empty() {
+        cost_aggregation.agg_iter(costs)
+    } else {
+        cost_aggregation_group::agg_grouped(cost_aggregation, cost_aggregation_groups, costs)
+    }
 }
@@ -0,0 +1,37 @@
+use crate::model::unit::Cost;
+use serde::{Deserialize, Serialize};
+
+/// a maximum traversal cost for any single edge, which guards against edges with
+/// erroneous data, such as an absurd distance or energy, dominating a search.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy)]
+pub struct EdgeCostCap {
+    /// the maximum traversal cost of an edge, in the unit of the cost model
+    pub max_cost: f64,
+    /// how to treat edges whose traversal cost exceeds the maximum
+    #[serde(default)]
+    pub policy: EdgeCostCapPolicy,
+}
+
+/// declares how an edge whose traversal cost exceeds the cap is treated.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EdgeCostCapPolicy {
+    /// reduce the traversal cost to the cap and log a warning
+    #[default]
+    Cap,
+    /// give the edge an infinite traversal cost so that no route uses it
+    Exclude,
+}
+
+impl EdgeCostCap {
+    /// applies the cap to the traversal cost of an edge.
+    pub fn apply(&self, cost: Cost) -> Cost {
+        if cost <= Cost::new(self.max_cost) {
+            return cost;
+        }
+        match self.policy {
+            EdgeCostCapPolicy::Cap => Cost::new(self.max_cost),
+            EdgeCostCapPolicy::Exclude => Cost::INFINITY,
+        }
+    }
+}
@@ -1,6 +1,8 @@
 pub mod cost_aggregation;
+pub mod cost_aggregation_group;
 pub mod cost_error;
 pub mod cost_model;
 pub mod cost_ops;
+pub mod edge_cost_cap;
 pub mod network;
 pub mod vehicle;
@@ -1,3 +1,6 @@
+use crate::model::cost::cost_error::CostError;
+use crate::model::state::state_feature::StateFeature;
+use crate::model::unit::EnergyUnit;
 use crate::model::{traversal::state::state_variable::StateVar, unit::as_f64::AsF64, unit::Cost};
 use serde::{Deserialize, Serialize};
 /// a mapping for how to transform vehicle state values into a Cost.
@@ -22,6 +25,13 @@ pub enum VehicleCostRate {
         offset: f64,
     },
     Combined(Vec<VehicleCostRate>),
+    /// convert an energy value into money by multiplying it by a price per unit
+    /// of energy. the energy unit must match the unit of the energy feature, so
+    /// that, for example, a price per gallon is not applied to kilowatt-hours.
+    EnergyPrice {
+        price: f64,
+        energy_unit: EnergyUnit,
+    },
     // leaving room for extension if we need to do any fancier math, maybe not needed
     // Poly2 { x0: f64, x1: f64 },
     // Exp { base: f64, exp_coefficient: f64 },
@@ -44,6 +54,7 @@ impl VehicleCostRate {
             VehicleCostRate::Raw => Cost::new(state.0),
             VehicleCostRate::Factor { factor } => Cost::new(state.0 * factor),
             VehicleCostRate::Offset { offset } => Cost::new(state.0 + offset),
+            VehicleCostRate::EnergyPrice { price, .. } => Cost::new(state.0 * price),
             VehicleCostRate::Combined(mappings) => {
                 mappings.iter().fold(Cost::new(state.0), |acc, f| {
                     f.map_value(StateVar(acc.as_f64()))
@@ -51,4 +62,42 @@ impl VehicleCostRate {
             }
         }
     }
+
+    /// confirms that this rate can be applied to a state feature. energy prices
+    /// may only be applied to energy features measured in the unit of the price.
+    ///
+    /// # Arguments
+    ///
+    /// * `name`    - name of the state feature
+    /// * `feature` - the state feature this rate applies to
+    ///
+    /// # Returns
+    ///
+    /// nothing, or an error if the rate is incompatible with the feature
+    pub fn validate(&self, name: &String, feature: &StateFeature) -> Result<(), CostError> {
+        match self {
+            VehicleCostRate::EnergyPrice { energy_unit, .. } => match feature {
+                StateFeature::Energy {
+                    energy_unit: feature_unit,
+                    ..
+                } if feature_unit == energy_unit => Ok(()),
+                StateFeature::Energy {
+                    energy_unit: feature_unit,
+                    ..
+                } => Err(CostError::InvalidConfiguration(format!(
+                    "energy price for feature '{}' is per {} but the feature is measured in {}",
+                    name, energy_unit, feature_unit
+                ))),
+                _ => Err(CostError::InvalidConfiguration(format!(
+                    "energy price can only be applied to energy features, but feature '{}' has type '{}'",
+                    name,
+                    feature.get_feature_type()
+                ))),
+            },
+            VehicleCostRate::Combined(rates) => {
+                rates.iter().try_for_each(|r| r.validate(name, feature))
+            }
+            _ => Ok(()),
+        }
+    }
 }
@@ -0,0 +1,287 @@
+use crate::model::{
+    frontier::{
+        frontier_model::FrontierModel, frontier_model_error::FrontierModelError,
+        frontier_model_service::FrontierModelService,
+    },
+    property::edge::Edge,
+    state::state_model::StateModel,
+    traversal::state::state_variable::StateVar,
+};
+use std::sync::Arc;
+
+/// builds frontier models that limit the energy accumulated along a route, such as
+/// the remaining charge of an electric vehicle. the limit is read from the `max_energy`
+/// key of the query, in the unit of the configured energy feature. when a query has
+/// no limit, the search is unrestricted.
+#[derive(Clone)]
+pub struct EnergyBudgetService {
+    pub energy_feature: String,
+}
+
+/// prunes any search branch whose accumulated energy exceeds the budget. when the
+/// destination cannot be reached within the budget, the search finds no route.
+///
+/// the search keeps only the lowest-cost branch to each vertex, so a branch that
+/// exceeds the budget is not replaced by a costlier one that stays within it. the
+/// constraint is exact when the cost model minimizes the budgeted energy.
+pub struct EnergyBudgetFrontierModel {
+    pub energy_feature: String,
+    pub max_energy: Option<f64>,
+}
+
+impl EnergyBudgetService {
+    const MAX_ENERGY: &'static str = "max_energy";
+}
+
+impl FrontierModelService for EnergyBudgetService {
+    fn build(
+        &self,
+        query: &serde_json::Value,
+        state_model: Arc<StateModel>,
+    ) -> Result<Arc<dyn FrontierModel>, FrontierModelError> {
+        let max_energy = match query.get(Self::MAX_ENERGY) {
+            None => None,
+            Some(v) => {
+                let max_energy = v.as_f64().ok_or_else(|| {
+                    FrontierModelError::BuildError(format!(
+                        "query field {} must be a number, found {}",
+                        Self::MAX_ENERGY,
+                        v
+                    ))
+                })?;
+                if max_energy.is_nan() || max_energy < 0.0 {
+                    return Err(FrontierModelError::BuildError(format!(
+                        "query field {} must be non-negative, found {}",
+                        Self::MAX_ENERGY,
+                        max_energy
+                    )));
+                }
+                Some(max_energy)
+            }
+        };
+        if max_energy.is_some() && !state_model.contains_key(&self.energy_feature) {
+            return Err(FrontierModelError::BuildError(format!(
+                "energy budget feature '{}' is not in the state model, found [{}]",
+                self.energy_feature,
+                state_model.get_names()
+            )));
+        }
+        let model = EnergyBudgetFrontierModel {
+            energy_feature: self.energy_feature.clone(),
+            max_energy,
+        };
+        Ok(Arc::new(model))
+    }
+}
+
+impl FrontierModel for EnergyBudgetFrontierModel {
+    fn valid_result_state(
+        &self,
+        _edge: &Edge,
+        result_state: &[StateVar],
+        state_model: &StateModel,
+    ) -> Result<bool, FrontierModelError> {
+        match self.max_energy {
+            None => Ok(true),
+            Some(max_energy) => {
+                let energy = state_model
+                    .get_state_variable(result_state, &self.energy_feature)
+                    .map_err(|e| FrontierModelError::StateError(e.to_string()))?;
+                Ok(energy.0 <= max_energy)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algorithm::search::{
+        a_star::a_star_algorithm::run_a_star, backtrack::vertex_oriented_route,
+        direction::Direction, search_error::SearchError, search_instance::SearchInstance,
+    };
+    use crate::model::cost::{
+        cost_aggregation::CostAggregation, cost_model::CostModel,
+        network::network_cost_rate::NetworkCostRate, vehicle::vehicle_cost_rate::VehicleCostRate,
+    };
+    use crate::model::property::vertex::Vertex;
+    use crate::model::road_network::{edge_id::EdgeId, vertex_id::VertexId};
+    use crate::model::state::state_feature::StateFeature;
+    use crate::model::traversal::{
+        traversal_model::TraversalModel, traversal_model_error::TraversalModelError,
+    };
+    use crate::model::unit::{as_f64::AsF64, Cost, Distance, DistanceUnit, Energy, EnergyUnit};
+    use crate::util::test_util::{graph_from_edges, traversal_search_instance};
+    use std::collections::HashMap;
+
+    /// accumulates distance along with fuel, consumed at a fixed rate per unit distance on each edge
+    struct FuelModel {
+        fuel_rates: Vec<f64>,
+    }
+
+    impl TraversalModel for FuelModel {
+        fn state_features(&self) -> Vec<(String, StateFeature)> {
+            vec![
+                (
+                    String::from("distance"),
+                    StateFeature::Distance {
+                        distance_unit: DistanceUnit::Meters,
+                        initial: Distance::ZERO,
+                        output_alias: None,
+                    },
+                ),
+                (
+                    String::from("fuel"),
+                    StateFeature::Energy {
+                        energy_unit: EnergyUnit::GallonsGasoline,
+                        initial: Energy::ZERO,
+                        output_alias: None,
+                    },
+                ),
+            ]
+        }
+
+        fn traverse_edge(
+            &self,
+            trajectory: (&Vertex, &Edge, &Vertex),
+            state: &mut Vec<StateVar>,
+            state_model: &StateModel,
+        ) -> Result<(), TraversalModelError> {
+            let (_, edge, _) = trajectory;
+            let unit = DistanceUnit::Meters;
+            state_model.add_distance(state, &String::from("distance"), &edge.distance, &unit)?;
+            let fuel = Energy::new(edge.distance.as_f64() * self.fuel_rates[edge.edge_id.0]);
+            state_model.add_energy(
+                state,
+                &String::from("fuel"),
+                &fuel,
+                &EnergyUnit::GallonsGasoline,
+            )?;
+            Ok(())
+        }
+
+        fn estimate_traversal(
+            &self,
+            _od: (&Vertex, &Vertex),
+            _state: &mut Vec<StateVar>,
+            _state_model: &StateModel,
+        ) -> Result<(), TraversalModelError> {
+            Ok(())
+        }
+    }
+
+    /// a search instance minimizing distance over two routes from (0) to (3), with
+    /// the FuelModel burning:
+    /// (0) -[0]-> (1) -[1]-> (3), 20 units distance burning 4 gallons
+    /// (0) -[2]-> (2) -[3]-> (3), 30 units distance burning 1 gallon
+    fn build_two_route_fuel_search_instance() -> SearchInstance {
+        let vertices = (0..4).map(|i| Vertex::new(i, 0.0, 0.0)).collect::<Vec<_>>();
+        let edges = vec![
+            Edge::new(0, 0, 1, 10.0),
+            Edge::new(1, 1, 3, 10.0),
+            Edge::new(2, 0, 2, 15.0),
+            Edge::new(3, 2, 3, 15.0),
+        ];
+        let graph = graph_from_edges(vertices, edges);
+        let traversal_model = Arc::new(FuelModel {
+            fuel_rates: vec![0.2, 0.2, 1.0 / 30.0, 1.0 / 30.0],
+        });
+        traversal_search_instance(graph, traversal_model)
+    }
+
+    /// the route from (0) to (3), or None if there is no route
+    fn route(si: &SearchInstance) -> Option<Vec<EdgeId>> {
+        let (o, d) = (VertexId(0), VertexId(3));
+        match run_a_star(o, Some(d), &Direction::Forward, None, si) {
+            Err(SearchError::NoPathExists(_, _)) => None,
+            Err(e) => panic!("{}", e),
+            Ok(result) => {
+                let route = vertex_oriented_route(o, d, &result.tree).unwrap();
+                Some(route.iter().map(|r| r.edge_id).collect())
+            }
+        }
+    }
+
+    #[test]
+    fn test_fuel_price_shifts_route_toward_efficient_path() {
+        // the short route has no toll, and the efficient route has a toll of 1 on edge 2
+        let si = build_two_route_fuel_search_instance();
+
+        // the energy price and the toll are both monetary costs of the fuel feature
+        let route_with_fuel_price = |price: f64| -> Option<Vec<EdgeId>> {
+            let (distance, fuel) = (String::from("distance"), String::from("fuel"));
+            let cost_model = CostModel::new(
+                Arc::new(HashMap::from([
+                    (distance.clone(), 1.0),
+                    (fuel.clone(), 1.0),
+                ])),
+                Arc::new(HashMap::from([
+                    (distance, VehicleCostRate::Raw),
+                    (
+                        fuel.clone(),
+                        VehicleCostRate::EnergyPrice {
+                            price,
+                            energy_unit: EnergyUnit::GallonsGasoline,
+                        },
+                    ),
+                ])),
+                Arc::new(HashMap::from([(
+                    fuel,
+                    NetworkCostRate::EdgeLookup {
+                        lookup: HashMap::from([(EdgeId(2), Cost::new(1.0))]),
+                    },
+                )])),
+                CostAggregation::Sum,
+                Arc::new(vec![]),
+                si.state_model.clone(),
+            )
+            .unwrap();
+            route(&SearchInstance {
+                cost_model,
+                ..si.clone()
+            })
+        };
+
+        // at $2/gallon, the short route costs 20 + 8 = 28 and the efficient route 30 + 2 + 1 = 33
+        assert_eq!(route_with_fuel_price(2.0), Some(vec![EdgeId(0), EdgeId(1)]));
+        // at $5/gallon, the short route costs 20 + 20 = 40 and the efficient route 30 + 5 + 1 = 36
+        assert_eq!(route_with_fuel_price(5.0), Some(vec![EdgeId(2), EdgeId(3)]));
+    }
+
+    #[test]
+    fn test_energy_budget_prunes_infeasible_routes() {
+        let si = build_two_route_fuel_search_instance();
+        let budget_service = EnergyBudgetService {
+            energy_feature: String::from("fuel"),
+        };
+
+        // finds the shortest route by distance that burns at most max_energy gallons
+        let route_with_budget = |query: serde_json::Value| -> Option<Vec<EdgeId>> {
+            let frontier_model = budget_service
+                .build(&query, si.state_model.clone())
+                .unwrap();
+            route(&SearchInstance {
+                frontier_model,
+                ..si.clone()
+            })
+        };
+
+        // without a budget, or with a loose one, the short route burning 4 gallons is found
+        let short_route = Some(vec![EdgeId(0), EdgeId(1)]);
+        assert_eq!(route_with_budget(serde_json::json!({})), short_route);
+        assert_eq!(
+            route_with_budget(serde_json::json!({"max_energy": 5.0})),
+            short_route
+        );
+        // a tight budget makes the short route infeasible, forcing the efficient detour
+        assert_eq!(
+            route_with_budget(serde_json::json!({"max_energy": 2.0})),
+            Some(vec![EdgeId(2), EdgeId(3)])
+        );
+        // when neither route fits the budget, there is no route
+        assert_eq!(
+            route_with_budget(serde_json::json!({"max_energy": 0.5})),
+            None
+        );
+    }
+}
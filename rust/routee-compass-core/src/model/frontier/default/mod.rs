@@ -1 +1,2 @@
+pub mod energy_budget;
 pub mod no_restriction;
@@ -31,4 +31,25 @@ pub trait FrontierModel: Send + Sync {
     ) -> Result<bool, FrontierModelError> {
         Ok(true)
     }
+
+    /// Validates the state of the traversal at the end of an edge, before the edge
+    /// is added to the search tree. This allows pruning search branches based on
+    /// state accumulated along the edge, such as a limit on energy use.
+    ///
+    /// # Arguments
+    ///
+    /// * `edge` - the edge that was traversed
+    /// * `result_state` - the state of the traversal at the end of this edge
+    ///
+    /// # Returns
+    ///
+    /// True if the state is valid, false otherwise; Or, an error from processing
+    fn valid_result_state(
+        &self,
+        _edge: &Edge,
+        _result_state: &[StateVar],
+        _state_model: &StateModel,
+    ) -> Result<bool, FrontierModelError> {
+        Ok(true)
+    }
 }
@@ -16,7 +16,7 @@ pub struct EdgeLoader {
 }
 
 pub struct EdgeLoaderConfig {
-    pub edge_list_csv: PathBuf,
+    pub edge_list_csvs: Vec<PathBuf>,
     pub n_edges: usize,
     pub n_vertices: usize,
 }
@@ -59,7 +59,12 @@ impl TryFrom<EdgeLoaderConfig> for EdgeLoader {
             let _ = pb.update(1);
         });
 
-        let edges = read_utils::from_csv(&c.edge_list_csv, true, Some(cb))?;
+        // rows are decoded in parallel, while the adjacency lists are built in edge order
+        let edges = if c.edge_list_csvs.len() == 1 {
+            read_utils::from_csv_par(&c.edge_list_csvs[0], true, c.n_edges, Some(cb))?
+        } else {
+            read_shards(&c.edge_list_csvs, c.n_edges, cb)?
+        };
 
         println!();
         let result = EdgeLoader {
@@ -71,3 +76,30 @@ impl TryFrom<EdgeLoaderConfig> for EdgeLoader {
         Ok(result)
     }
 }
+
+/// reads edges from each shard in order, confirming that each edge id matches
+/// its position in the combined edge list.
+fn read_shards(
+    edge_list_csvs: &[PathBuf],
+    n_edges: usize,
+    mut row_callback: impl FnMut(&Edge),
+) -> Result<Box<[Edge]>, GraphError> {
+    let mut edges: Vec<Edge> = Vec::with_capacity(n_edges);
+    for edge_list_csv in edge_list_csvs.iter() {
+        let shard: Box<[Edge]> = read_utils::from_csv_par(edge_list_csv, true, 0, None)?;
+        for edge in shard.iter() {
+            let expected = edges.len();
+            if edge.edge_id.0 != expected {
+                return Err(GraphError::ShardIdMismatch {
+                    kind: String::from("edge"),
+                    filename: edge_list_csv.clone(),
+                    expected,
+                    found: edge.edge_id.0,
+                });
+            }
+            row_callback(edge);
+            edges.push(*edge);
+        }
+    }
+    Ok(edges.into_boxed_slice())
+}
@@ -3,10 +3,15 @@ use crate::model::property::edge::Edge;
 use crate::model::property::vertex::Vertex;
 use crate::model::road_network::graph_error::GraphError;
 use crate::model::road_network::{edge_id::EdgeId, vertex_id::VertexId};
+use crate::model::unit::as_f64::AsF64;
 use crate::util::compact_ordered_hash_map::CompactOrderedHashMap;
+use crate::util::geo::coord;
+use geo::Coord;
 use std::path::Path;
 
-use super::graph_loader::graph_from_files;
+use super::graph_contraction::ContractedGraph;
+use super::graph_loader::{graph_from_files, graph_from_sharded_files};
+use super::graph_validation::GraphValidationReport;
 
 use allocative::Allocative;
 
@@ -64,6 +69,35 @@ impl Graph {
     ) -> Result<Graph, GraphError> {
         graph_from_files(edge_list_csv, vertex_list_csv, n_edges, n_vertices, verbose)
     }
+    /// build a `Graph` from edge and vertex lists split across multiple files.
+    /// see [`graph_from_sharded_files`] for the id requirements across shards.
+    pub fn from_sharded_files<P: AsRef<Path>>(
+        edge_list_csvs: &[P],
+        vertex_list_csvs: &[P],
+        n_edges: Option<usize>,
+        n_vertices: Option<usize>,
+        verbose: Option<bool>,
+    ) -> Result<Graph, GraphError> {
+        graph_from_sharded_files(
+            edge_list_csvs,
+            vertex_list_csvs,
+            n_edges,
+            n_vertices,
+            verbose,
+        )
+    }
+    /// contracts chains of vertices with exactly one in edge and one out edge into
+    /// single edges, retaining a mapping back to this graph. see [`ContractedGraph`].
+    pub fn contract_degree_two_vertices(&self) -> Result<ContractedGraph, GraphError> {
+        ContractedGraph::new(self)
+    }
+
+    /// reports vertices without out edges or in edges and, optionally, the sizes of
+    /// the weakly-connected components of the graph. see [`GraphValidationReport`].
+    pub fn validate(&self, components: bool) -> Result<GraphValidationReport, GraphError> {
+        GraphValidationReport::new(self, components)
+    }
+
     /// number of edges in the Graph
     pub fn n_edges(&self) -> usize {
         self.edges.len()
@@ -74,6 +108,15 @@ impl Graph {
         self.vertices.len()
     }
 
+    /// the bounding box of the graph, computed from the vertex coordinates.
+    ///
+    /// # Returns
+    ///
+    /// the (min, max) corners of the bounding box, or None if the graph has no vertices
+    pub fn bounds(&self) -> Option<(Coord<f32>, Coord<f32>)> {
+        coord::bounding_box(self.vertices.iter().map(|v| v.coordinate.0))
+    }
+
     /// helper function for creating a range of all edge ids in the graph.
     /// uses the knowledge that all ids are unique and consecutive integers
     /// beginning at zero.
@@ -338,4 +381,86 @@ impl Graph {
             })
             .collect()
     }
+
+    /// renders the graph as a GraphViz DOT digraph, with vertices labeled by id
+    /// and edges labeled by distance. intended for visualizing small graphs
+    /// while debugging, so graphs above `max_vertices` are refused.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_vertices` - the largest number of vertices to render
+    ///
+    /// # Returns
+    ///
+    /// the DOT document, or an error if the graph has too many vertices
+    pub fn to_dot(&self, max_vertices: usize) -> Result<String, GraphError> {
+        if self.n_vertices() > max_vertices {
+            return Err(GraphError::TooLargeForDot {
+                n_vertices: self.n_vertices(),
+                max_vertices,
+            });
+        }
+        let mut dot = String::from("digraph {\n");
+        for vertex in self.vertices.iter() {
+            dot.push_str(&format!(
+                "  {} [label=\"{}\"];\n",
+                vertex.vertex_id, vertex.vertex_id
+            ));
+        }
+        for edge in self.edges.iter() {
+            dot.push_str(&format!(
+                "  {} -> {} [label=\"{}\"];\n",
+                edge.src_vertex_id,
+                edge.dst_vertex_id,
+                edge.distance.as_f64()
+            ));
+        }
+        dot.push('}');
+        dot.push('\n');
+        Ok(dot)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util::test_util::graph_from_edges;
+
+    /// (0) -[0]-> (1) -[1]-> (2), along with (2) -[2]-> (0)
+    fn build_mock_graph() -> Graph {
+        let vertices = vec![
+            Vertex::new(0, 0.0, 0.0),
+            Vertex::new(1, 0.0, 0.0),
+            Vertex::new(2, 0.0, 0.0),
+        ];
+        let edges = vec![
+            Edge::new(0, 0, 1, 10.0),
+            Edge::new(1, 1, 2, 15.5),
+            Edge::new(2, 2, 0, 20.0),
+        ];
+        graph_from_edges(vertices, edges)
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let graph = build_mock_graph();
+        let expected = "\
+digraph {
+  0 [label=\"0\"];
+  1 [label=\"1\"];
+  2 [label=\"2\"];
+  0 -> 1 [label=\"10\"];
+  1 -> 2 [label=\"15.5\"];
+  2 -> 0 [label=\"20\"];
+}
+";
+        assert_eq!(graph.to_dot(3).unwrap(), expected);
+        assert!(matches!(
+            graph.to_dot(2),
+            Err(GraphError::TooLargeForDot {
+                n_vertices: 3,
+                max_vertices: 2
+            })
+        ));
+    }
 }
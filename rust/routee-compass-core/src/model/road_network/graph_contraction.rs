@@ -0,0 +1,316 @@
+use super::{edge_id::EdgeId, graph::Graph, graph_error::GraphError, vertex_id::VertexId};
+use crate::model::property::{edge::Edge, vertex::Vertex};
+use crate::model::unit::{as_f64::AsF64, Distance};
+use crate::util::compact_ordered_hash_map::CompactOrderedHashMap;
+use std::sync::Arc;
+
+/// a graph where chains of degree-2 vertices, which have exactly one in edge and
+/// one out edge, are contracted into single edges. the contracted graph has
+/// fewer vertices and edges to search, and retains a mapping back to the
+/// original graph for route reconstruction and output.
+///
+/// vertex and edge ids of the contracted graph are new consecutive ids beginning
+/// at zero. queries must be mapped to contracted vertex ids with
+/// [`ContractedGraph::contracted_vertex_id`], and routes mapped back to original
+/// edges with [`ContractedGraph::expand_route`], whose geometries are then used
+/// for output.
+pub struct ContractedGraph {
+    /// the contracted search graph
+    pub graph: Arc<Graph>,
+    /// for each contracted edge id, the original edge ids it replaces, in traversal order
+    pub edge_mapping: Box<[Vec<EdgeId>]>,
+    /// for each contracted vertex id, the original vertex id
+    pub vertex_mapping: Box<[VertexId]>,
+    /// for each original vertex id, the contracted vertex id, or None if contracted away
+    original_vertex_mapping: Box<[Option<VertexId>]>,
+}
+
+impl ContractedGraph {
+    /// contracts chains of degree-2 vertices in a graph. each contracted edge spans
+    /// a chain of original edges between two retained vertices and its distance is
+    /// the sum of their distances. a cycle made only of degree-2 vertices retains
+    /// one of its vertices.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - the original graph
+    ///
+    /// # Returns
+    ///
+    /// the contracted graph, or an error if the graph adjacencies are inconsistent
+    pub fn new(graph: &Graph) -> Result<ContractedGraph, GraphError> {
+        // a vertex is contracted when it has one in edge and one out edge that is not a self-loop
+        let contractible = graph
+            .vertex_ids()
+            .map(|vertex_id| {
+                let out_edges = graph.out_edges(vertex_id)?;
+                let in_edges = graph.in_edges(vertex_id)?;
+                let degree_two = out_edges.len() == 1 && in_edges.len() == 1;
+                let self_loop = degree_two && graph.dst_vertex_id(out_edges[0])? == vertex_id;
+                Ok(degree_two && !self_loop)
+            })
+            .collect::<Result<Vec<_>, GraphError>>()?;
+
+        // walk each chain from a retained vertex. any contractible vertex not reached
+        // afterward lies on a cycle of contractible vertices, so one vertex is retained
+        let mut retained = contractible.iter().map(|c| !c).collect::<Vec<_>>();
+        let mut visited = vec![false; graph.n_vertices()];
+        let mut chains: Vec<(VertexId, VertexId, Vec<EdgeId>)> = vec![];
+        let mut next_unvisited = 0;
+        let mut sources = graph
+            .vertex_ids()
+            .filter(|v| retained[v.0])
+            .collect::<Vec<_>>();
+        loop {
+            for src in sources.drain(..) {
+                visited[src.0] = true;
+                for edge_id in graph.out_edges(src)? {
+                    let mut path = vec![edge_id];
+                    let mut dst = graph.dst_vertex_id(edge_id)?;
+                    while !retained[dst.0] {
+                        visited[dst.0] = true;
+                        let next_edge_id = graph.out_edges(dst)?[0];
+                        path.push(next_edge_id);
+                        dst = graph.dst_vertex_id(next_edge_id)?;
+                    }
+                    chains.push((src, dst, path));
+                }
+            }
+            while next_unvisited < visited.len() && visited[next_unvisited] {
+                next_unvisited += 1;
+            }
+            if next_unvisited == visited.len() {
+                break;
+            }
+            retained[next_unvisited] = true;
+            sources.push(VertexId(next_unvisited));
+        }
+
+        // assign new ids to the retained vertices and build the contracted graph
+        let vertex_mapping = graph
+            .vertex_ids()
+            .filter(|v| retained[v.0])
+            .collect::<Vec<_>>();
+        let mut original_vertex_mapping = vec![None; graph.n_vertices()];
+        let mut vertices = Vec::with_capacity(vertex_mapping.len());
+        for (idx, original_id) in vertex_mapping.iter().enumerate() {
+            original_vertex_mapping[original_id.0] = Some(VertexId(idx));
+            let original = graph.get_vertex(*original_id)?;
+            vertices.push(Vertex {
+                vertex_id: VertexId(idx),
+                coordinate: original.coordinate,
+            });
+        }
+        let mut adj = vec![CompactOrderedHashMap::empty(); vertices.len()];
+        let mut rev = vec![CompactOrderedHashMap::empty(); vertices.len()];
+        let mut edges = Vec::with_capacity(chains.len());
+        let mut edge_mapping = Vec::with_capacity(chains.len());
+        for (idx, (src, dst, path)) in chains.into_iter().enumerate() {
+            let src_vertex_id = original_vertex_mapping[src.0]
+                .ok_or(GraphError::VertexIdNotFound { vertex_id: src })?;
+            let dst_vertex_id = original_vertex_mapping[dst.0]
+                .ok_or(GraphError::VertexIdNotFound { vertex_id: dst })?;
+            let distance = path
+                .iter()
+                .map(|e| graph.get_edge(*e).map(|edge| edge.distance))
+                .sum::<Result<Distance, GraphError>>()?;
+            let edge_id = EdgeId(idx);
+            adj[src_vertex_id.0].insert(edge_id, dst_vertex_id);
+            rev[dst_vertex_id.0].insert(edge_id, src_vertex_id);
+            edges.push(Edge {
+                edge_id,
+                src_vertex_id,
+                dst_vertex_id,
+                distance,
+            });
+            edge_mapping.push(path);
+        }
+
+        let contracted = Graph {
+            adj: adj.into_boxed_slice(),
+            rev: rev.into_boxed_slice(),
+            edges: edges.into_boxed_slice(),
+            vertices: vertices.into_boxed_slice(),
+        };
+        log::info!(
+            "contracted graph from {} to {} vertices and {} to {} edges",
+            graph.n_vertices(),
+            contracted.n_vertices(),
+            graph.n_edges(),
+            contracted.n_edges()
+        );
+        Ok(ContractedGraph {
+            graph: Arc::new(contracted),
+            edge_mapping: edge_mapping.into_boxed_slice(),
+            vertex_mapping: vertex_mapping.into_boxed_slice(),
+            original_vertex_mapping: original_vertex_mapping.into_boxed_slice(),
+        })
+    }
+
+    /// the contracted vertex id of an original vertex, or None if the vertex
+    /// was contracted away.
+    pub fn contracted_vertex_id(&self, original: VertexId) -> Option<VertexId> {
+        self.original_vertex_mapping
+            .get(original.0)
+            .copied()
+            .flatten()
+    }
+
+    /// the original edge ids replaced by a contracted edge, in traversal order.
+    pub fn original_edge_ids(&self, edge_id: EdgeId) -> Result<&[EdgeId], GraphError> {
+        self.edge_mapping
+            .get(edge_id.0)
+            .map(|path| path.as_slice())
+            .ok_or(GraphError::EdgeIdNotFound { edge_id })
+    }
+
+    /// maps a route of contracted edge ids back to the original edge ids.
+    ///
+    /// # Arguments
+    ///
+    /// * `route` - edge ids of a route in the contracted graph, in traversal order
+    ///
+    /// # Returns
+    ///
+    /// the edge ids of the same route in the original graph, or an error if an
+    /// edge id is not in the contracted graph
+    pub fn expand_route(&self, route: &[EdgeId]) -> Result<Vec<EdgeId>, GraphError> {
+        let mut result = vec![];
+        for edge_id in route.iter() {
+            result.extend_from_slice(self.original_edge_ids(*edge_id)?);
+        }
+        Ok(result)
+    }
+
+    /// combines a per-edge value of the original graph, such as grade, into a value
+    /// for each contracted edge, as the mean of its original edges weighted by distance.
+    /// for grade, this is the total rise over the total run of the chain.
+    ///
+    /// # Arguments
+    ///
+    /// * `original` - the original graph
+    /// * `values`   - a value for each original edge, indexed by edge id
+    ///
+    /// # Returns
+    ///
+    /// a value for each contracted edge, indexed by contracted edge id
+    pub fn distance_weighted_edge_values(
+        &self,
+        original: &Graph,
+        values: &[f64],
+    ) -> Result<Vec<f64>, GraphError> {
+        self.edge_mapping
+            .iter()
+            .map(|path| {
+                let mut total_distance = 0.0;
+                let mut weighted_total = 0.0;
+                let mut total = 0.0;
+                for edge_id in path.iter() {
+                    let distance = original.get_edge(*edge_id)?.distance.as_f64();
+                    let value = values
+                        .get(edge_id.0)
+                        .ok_or(GraphError::EdgeAttributeNotFound { edge_id: *edge_id })?;
+                    total_distance += distance;
+                    weighted_total += value * distance;
+                    total += value;
+                }
+                if total_distance > 0.0 {
+                    Ok(weighted_total / total_distance)
+                } else {
+                    // a chain of zero-length edges has no distance to weight by
+                    Ok(total / path.len() as f64)
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algorithm::search::a_star::a_star_algorithm::run_a_star;
+    use crate::algorithm::search::backtrack::vertex_oriented_route;
+    use crate::algorithm::search::direction::Direction;
+    use crate::util::test_util::{distance_search_instance, graph_from_edges};
+
+    fn build_graph(vertices: usize, edges: Vec<Edge>) -> Graph {
+        let vertices = (0..vertices)
+            .map(|i| Vertex::new(i, 0.0, 0.0))
+            .collect::<Vec<_>>();
+        graph_from_edges(vertices, edges)
+    }
+
+    /// runs a distance-minimizing search, returning the edge ids of the route
+    fn shortest_route(graph: Arc<Graph>, o: VertexId, d: VertexId) -> Vec<EdgeId> {
+        let si = distance_search_instance(graph);
+        let result = run_a_star(o, Some(d), &Direction::Forward, None, &si).unwrap();
+        vertex_oriented_route(o, d, &result.tree)
+            .unwrap()
+            .iter()
+            .map(|et| et.edge_id)
+            .collect()
+    }
+
+    #[test]
+    fn test_contracted_route_maps_to_original_edges() {
+        // (0) -[0]-> (1) -[1]-> (2) -[2]-> (3) with distance 6
+        // (0) -[3]-> (4) -[4]-> (3) with distance 20
+        // (3) -[5]-> (0)
+        // vertices 1, 2 and 4 have one in edge and one out edge
+        let edges = vec![
+            Edge::new(0, 0, 1, 1.0),
+            Edge::new(1, 1, 2, 2.0),
+            Edge::new(2, 2, 3, 3.0),
+            Edge::new(3, 0, 4, 10.0),
+            Edge::new(4, 4, 3, 10.0),
+            Edge::new(5, 3, 0, 5.0),
+        ];
+        let graph = build_graph(5, edges);
+        let contracted = graph.contract_degree_two_vertices().unwrap();
+        assert_eq!(contracted.graph.n_vertices(), 2);
+        assert_eq!(contracted.graph.n_edges(), 3);
+        assert_eq!(
+            contracted.vertex_mapping.to_vec(),
+            vec![VertexId(0), VertexId(3)]
+        );
+        assert_eq!(contracted.contracted_vertex_id(VertexId(2)), None);
+        let long_edge = contracted
+            .graph
+            .edges
+            .iter()
+            .find(|e| contracted.edge_mapping[e.edge_id.0] == vec![EdgeId(3), EdgeId(4)])
+            .unwrap();
+        assert_eq!(long_edge.distance, Distance::new(20.0));
+
+        let o = contracted.contracted_vertex_id(VertexId(0)).unwrap();
+        let d = contracted.contracted_vertex_id(VertexId(3)).unwrap();
+        let route = shortest_route(contracted.graph.clone(), o, d);
+        assert_eq!(route.len(), 1);
+        let original_route = contracted.expand_route(&route).unwrap();
+        let expected = shortest_route(Arc::new(graph), VertexId(0), VertexId(3));
+        assert_eq!(original_route, expected);
+        assert_eq!(original_route, vec![EdgeId(0), EdgeId(1), EdgeId(2)]);
+    }
+
+    #[test]
+    fn test_contract_cycle_and_weight_grades() {
+        // (0) -[0]-> (1) -[1]-> (2) -[2]-> (0), where every vertex has degree 2
+        let edges = vec![
+            Edge::new(0, 0, 1, 1.0),
+            Edge::new(1, 1, 2, 3.0),
+            Edge::new(2, 2, 0, 0.0),
+        ];
+        let graph = build_graph(3, edges);
+        let contracted = graph.contract_degree_two_vertices().unwrap();
+        assert_eq!(contracted.graph.n_vertices(), 1);
+        assert_eq!(
+            contracted.expand_route(&[EdgeId(0)]).unwrap(),
+            vec![EdgeId(0), EdgeId(1), EdgeId(2)]
+        );
+        // grade is the total rise over the total run of the chain
+        let grades = contracted
+            .distance_weighted_edge_values(&graph, &[0.04, 0.0, 0.5])
+            .unwrap();
+        assert_eq!(grades, vec![0.01]);
+    }
+}
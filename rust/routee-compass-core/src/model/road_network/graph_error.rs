@@ -22,6 +22,13 @@ pub enum GraphError {
     AttributeError(String, String),
     #[error("{filename} file source was empty")]
     EmptyFileSource { filename: PathBuf },
+    #[error("{kind} list file {filename:?} has id {found} where id {expected} was expected, ids across all files must be contiguous and start from zero")]
+    ShardIdMismatch {
+        kind: String,
+        filename: PathBuf,
+        expected: usize,
+        found: usize,
+    },
     #[error("failure reading TomTom graph: {source}")]
     IOError {
         #[from]
@@ -34,6 +41,13 @@ pub enum GraphError {
     },
     #[error("internal error: adjacency list missing vertex {0}")]
     AdjacencyVertexMissing(VertexId),
+    #[error(
+        "graph has {n_vertices} vertices, exceeding the limit of {max_vertices} for DOT export"
+    )]
+    TooLargeForDot {
+        n_vertices: usize,
+        max_vertices: usize,
+    },
     #[error("error creating progress bar for {0}: {1}")]
     ProgressBarBuildError(String, String),
 }
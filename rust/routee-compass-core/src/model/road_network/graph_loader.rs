@@ -1,4 +1,4 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use log::warn;
 
@@ -17,6 +17,38 @@ pub fn graph_from_files<P: AsRef<Path>>(
     n_edges: Option<usize>,
     n_vertices: Option<usize>,
     verbose: Option<bool>,
+) -> Result<Graph, GraphError> {
+    graph_from_sharded_files(
+        &[edge_list_csv],
+        &[vertex_list_csv],
+        n_edges,
+        n_vertices,
+        verbose,
+    )
+}
+
+/// builds a graph from edge and vertex lists that may each be split across
+/// multiple CSV files (shards). shards are read in the order provided and
+/// must together cover a contiguous range of ids starting from zero, so that
+/// ids remain valid indices into the combined edge and vertex arrays.
+///
+/// # Arguments
+///
+/// * `edge_list_csvs`   - edge list shards, in id order
+/// * `vertex_list_csvs` - vertex list shards, in id order
+/// * `n_edges`          - total number of edges across all shards, if known
+/// * `n_vertices`       - total number of vertices across all shards, if known
+/// * `verbose`          - log when input sizes must be scanned
+///
+/// # Returns
+///
+/// the graph, or an error if reading failed or shard ids have a gap or overlap
+pub fn graph_from_sharded_files<P: AsRef<Path>>(
+    edge_list_csvs: &[P],
+    vertex_list_csvs: &[P],
+    n_edges: Option<usize>,
+    n_vertices: Option<usize>,
+    verbose: Option<bool>,
 ) -> Result<Graph, GraphError> {
     let verbose = verbose.unwrap_or(false);
     let n_edges = match n_edges {
@@ -25,7 +57,10 @@ pub fn graph_from_files<P: AsRef<Path>>(
             if verbose {
                 warn!("edge list size not provided, scanning input to determine size");
             }
-            get_n_edges(&edge_list_csv)?
+            edge_list_csvs
+                .iter()
+                .map(get_n_edges)
+                .sum::<Result<usize, GraphError>>()?
         }
     };
 
@@ -35,23 +70,29 @@ pub fn graph_from_files<P: AsRef<Path>>(
             if verbose {
                 warn!("vertex list size not provided, scanning input to determine size");
             }
-            get_n_vertices(&vertex_list_csv)?
+            vertex_list_csvs
+                .iter()
+                .map(get_n_vertices)
+                .sum::<Result<usize, GraphError>>()?
         }
     };
     let e_conf = EdgeLoaderConfig {
-        edge_list_csv: edge_list_csv.as_ref().to_path_buf(),
+        edge_list_csvs: to_path_bufs(edge_list_csvs),
         n_edges,
         n_vertices,
     };
-
-    let e_result = EdgeLoader::try_from(e_conf)?;
-
     let v_conf = VertexLoaderConfig {
-        vertex_list_csv: vertex_list_csv.as_ref().to_path_buf(),
+        vertex_list_csvs: to_path_bufs(vertex_list_csvs),
         n_vertices,
     };
 
-    let vertices: Box<[Vertex]> = v_conf.try_into()?;
+    // the edge and vertex lists are independent, so they are loaded concurrently
+    let (e_result, v_result) = rayon::join(
+        || EdgeLoader::try_from(e_conf),
+        || Box::<[Vertex]>::try_from(v_conf),
+    );
+    let e_result = e_result?;
+    let vertices = v_result?;
 
     let graph = Graph {
         adj: e_result.adj,
@@ -63,6 +104,10 @@ pub fn graph_from_files<P: AsRef<Path>>(
     Ok(graph)
 }
 
+fn to_path_bufs<P: AsRef<Path>>(paths: &[P]) -> Vec<PathBuf> {
+    paths.iter().map(|p| p.as_ref().to_path_buf()).collect()
+}
+
 fn get_n_edges<P: AsRef<Path>>(edge_list_csv: &P) -> Result<usize, GraphError> {
     // check if the extension is .gz
     let is_gzip = edge_list_csv
@@ -95,3 +140,96 @@ fn get_n_vertices<P: AsRef<Path>>(vertex_list_csv: &P) -> Result<usize, GraphErr
     }
     Ok(n - 1) // drop count of header line
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_file(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join("model")
+            .join("road_network")
+            .join("test")
+            .join(name)
+    }
+
+    #[test]
+    fn test_sharded_graph_matches_single_file() {
+        let single = graph_from_files(
+            &test_file("edges.csv"),
+            &test_file("vertices.csv"),
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let sharded = graph_from_sharded_files(
+            &[test_file("edges_00.csv"), test_file("edges_01.csv")],
+            &[test_file("vertices_00.csv"), test_file("vertices_01.csv")],
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(single.n_edges(), sharded.n_edges());
+        assert_eq!(single.n_vertices(), sharded.n_vertices());
+        for (a, b) in single.edges.iter().zip(sharded.edges.iter()) {
+            assert_eq!(a.edge_id, b.edge_id);
+            assert_eq!(a.src_vertex_id, b.src_vertex_id);
+            assert_eq!(a.dst_vertex_id, b.dst_vertex_id);
+            assert_eq!(a.distance, b.distance);
+        }
+        for (a, b) in single.vertices.iter().zip(sharded.vertices.iter()) {
+            assert_eq!(a.vertex_id, b.vertex_id);
+            assert_eq!(a.coordinate, b.coordinate);
+        }
+        for (a, b) in single.adj.iter().zip(sharded.adj.iter()) {
+            assert_eq!(a.iter().collect::<Vec<_>>(), b.iter().collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn test_bad_row_reports_line() {
+        let result = graph_from_files(
+            &test_file("edges_bad_row.csv"),
+            &test_file("vertices.csv"),
+            None,
+            None,
+            None,
+        );
+        match result {
+            Err(GraphError::CsvError { source }) => {
+                let msg = source.to_string();
+                assert!(msg.contains("line: 3"), "{}", msg);
+            }
+            Err(e) => panic!("expected csv error, found {}", e),
+            Ok(_) => panic!("expected the bad row to fail"),
+        }
+    }
+
+    #[test]
+    fn test_sharded_graph_id_gap_fails() {
+        let result = graph_from_sharded_files(
+            &[test_file("edges_00.csv"), test_file("edges_gap.csv")],
+            &[test_file("vertices_00.csv"), test_file("vertices_01.csv")],
+            None,
+            None,
+            None,
+        );
+        assert!(matches!(result, Err(GraphError::ShardIdMismatch { .. })));
+    }
+
+    #[test]
+    fn test_sharded_graph_id_overlap_fails() {
+        let result = graph_from_sharded_files(
+            &[test_file("edges_00.csv"), test_file("edges_01.csv")],
+            &[test_file("vertices_00.csv"), test_file("vertices_00.csv")],
+            None,
+            None,
+            None,
+        );
+        assert!(matches!(result, Err(GraphError::ShardIdMismatch { .. })));
+    }
+}
@@ -0,0 +1,216 @@
+use super::{graph::Graph, graph_error::GraphError, vertex_id::VertexId};
+use serde::Serialize;
+
+/// the number of vertex ids or component sizes listed in a validation report
+pub const VALIDATION_SAMPLE_SIZE: usize = 10;
+
+/// a summary of the structural problems of a graph that make some queries fail,
+/// such as dead ends and regions disconnected from the rest of the network.
+/// each problem is reported as a count along with a sample, so that reports of
+/// very large graphs stay small.
+#[derive(Debug, Clone, Serialize)]
+pub struct GraphValidationReport {
+    pub n_vertices: usize,
+    pub n_edges: usize,
+    /// vertices that no route can leave
+    pub vertices_without_out_edges: VertexSample,
+    /// vertices that no route can reach
+    pub vertices_without_in_edges: VertexSample,
+    /// the weakly-connected components of the graph, if computed
+    pub components: Option<ComponentSummary>,
+}
+
+/// a count of vertices along with the lowest of their ids
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct VertexSample {
+    pub count: usize,
+    pub sample: Vec<VertexId>,
+}
+
+/// the number of weakly-connected components and the sizes of the largest ones
+#[derive(Debug, Clone, Serialize)]
+pub struct ComponentSummary {
+    pub count: usize,
+    /// component sizes in vertices, largest first
+    pub largest_sizes: Vec<usize>,
+}
+
+impl VertexSample {
+    fn add(&mut self, vertex_id: VertexId) {
+        self.count += 1;
+        if self.sample.len() < VALIDATION_SAMPLE_SIZE {
+            self.sample.push(vertex_id);
+        }
+    }
+}
+
+impl GraphValidationReport {
+    /// builds the report of a graph.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - the graph to validate
+    /// * `components` - whether to find the weakly-connected components, which
+    ///   takes a pass over all edges
+    ///
+    /// # Returns
+    ///
+    /// the report, or an error if an edge refers to a vertex that is not in the graph
+    pub fn new(graph: &Graph, components: bool) -> Result<GraphValidationReport, GraphError> {
+        let mut vertices_without_out_edges = VertexSample::default();
+        let mut vertices_without_in_edges = VertexSample::default();
+        for vertex_id in graph.vertex_ids() {
+            let out_edges = graph
+                .adj
+                .get(vertex_id.0)
+                .ok_or(GraphError::AdjacencyVertexMissing(vertex_id))?;
+            let in_edges = graph
+                .rev
+                .get(vertex_id.0)
+                .ok_or(GraphError::AdjacencyVertexMissing(vertex_id))?;
+            if out_edges.is_empty() {
+                vertices_without_out_edges.add(vertex_id);
+            }
+            if in_edges.is_empty() {
+                vertices_without_in_edges.add(vertex_id);
+            }
+        }
+        let components = match components {
+            true => Some(weakly_connected_components(graph)?),
+            false => None,
+        };
+        Ok(GraphValidationReport {
+            n_vertices: graph.n_vertices(),
+            n_edges: graph.n_edges(),
+            vertices_without_out_edges,
+            vertices_without_in_edges,
+            components,
+        })
+    }
+
+    /// whether the graph has no dead ends and, if components were found, is connected
+    pub fn is_valid(&self) -> bool {
+        self.vertices_without_out_edges.count == 0
+            && self.vertices_without_in_edges.count == 0
+            && self
+                .components
+                .as_ref()
+                .map(|c| c.count <= 1)
+                .unwrap_or(true)
+    }
+
+    /// logs a warning for each problem found in the graph
+    pub fn log_warnings(&self) {
+        let samples = [
+            ("out", &self.vertices_without_out_edges),
+            ("in", &self.vertices_without_in_edges),
+        ];
+        for (direction, sample) in samples {
+            if sample.count > 0 {
+                log::warn!(
+                    "graph has {} vertices without {} edges, such as {:?}",
+                    sample.count,
+                    direction,
+                    sample.sample.iter().map(|v| v.0).collect::<Vec<_>>()
+                );
+            }
+        }
+        if let Some(components) = &self.components {
+            if components.count > 1 {
+                log::warn!(
+                    "graph has {} weakly-connected components, the largest with {:?} vertices",
+                    components.count,
+                    components.largest_sizes
+                );
+            }
+        }
+    }
+}
+
+/// finds the weakly-connected components of a graph, where edges connect vertices
+/// regardless of their direction, with a union-find over the edge list.
+fn weakly_connected_components(graph: &Graph) -> Result<ComponentSummary, GraphError> {
+    let n = graph.n_vertices();
+    let mut parent: Vec<usize> = (0..n).collect();
+    let mut size: Vec<usize> = vec![1; n];
+    for edge in graph.edges.iter() {
+        for vertex_id in [edge.src_vertex_id, edge.dst_vertex_id] {
+            if vertex_id.0 >= n {
+                return Err(GraphError::VertexIdNotFound { vertex_id });
+            }
+        }
+        let a = find_root(&mut parent, edge.src_vertex_id.0);
+        let b = find_root(&mut parent, edge.dst_vertex_id.0);
+        if a != b {
+            let (large, small) = if size[a] >= size[b] { (a, b) } else { (b, a) };
+            parent[small] = large;
+            size[large] += size[small];
+        }
+    }
+    let mut sizes = (0..n)
+        .filter(|v| parent[*v] == *v)
+        .map(|root| size[root])
+        .collect::<Vec<_>>();
+    sizes.sort_unstable_by(|a, b| b.cmp(a));
+    let count = sizes.len();
+    sizes.truncate(VALIDATION_SAMPLE_SIZE);
+    Ok(ComponentSummary {
+        count,
+        largest_sizes: sizes,
+    })
+}
+
+/// the root of the set of a vertex, halving the path to the root along the way
+fn find_root(parent: &mut [usize], mut v: usize) -> usize {
+    while parent[v] != v {
+        parent[v] = parent[parent[v]];
+        v = parent[v];
+    }
+    v
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::property::{edge::Edge, vertex::Vertex};
+    use crate::util::test_util::graph_from_edges;
+
+    /// a cycle (0)-(1)-(2), a one-way pair (3)->(4), and the isolated vertex (5)
+    fn build_graph() -> Graph {
+        let vertices = (0..6).map(|i| Vertex::new(i, 0.0, 0.0)).collect::<Vec<_>>();
+        let edges = vec![
+            Edge::new(0, 0, 1, 1.0),
+            Edge::new(1, 1, 2, 1.0),
+            Edge::new(2, 2, 0, 1.0),
+            Edge::new(3, 3, 4, 1.0),
+        ];
+        graph_from_edges(vertices, edges)
+    }
+
+    #[test]
+    fn test_validation_report() {
+        let graph = build_graph();
+        let report = graph.validate(true).unwrap();
+        assert!(!report.is_valid());
+        assert_eq!(report.vertices_without_out_edges.count, 2);
+        assert_eq!(
+            report.vertices_without_out_edges.sample,
+            vec![VertexId(4), VertexId(5)]
+        );
+        assert_eq!(
+            report.vertices_without_in_edges.sample,
+            vec![VertexId(3), VertexId(5)]
+        );
+        let components = report.components.as_ref().unwrap();
+        assert_eq!(components.count, 3);
+        assert_eq!(components.largest_sizes, vec![3, 2, 1]);
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["vertices_without_in_edges"]["count"], 2);
+        assert_eq!(json["components"]["count"], 3);
+
+        // components are optional
+        let report = graph.validate(false).unwrap();
+        assert!(report.components.is_none());
+    }
+}
@@ -1,7 +1,9 @@
 pub mod edge_id;
 pub mod edge_loader;
 pub mod graph;
+pub mod graph_contraction;
 pub mod graph_error;
 pub mod graph_loader;
+pub mod graph_validation;
 pub mod vertex_id;
 pub mod vertex_loader;
@@ -6,7 +6,7 @@ use crate::util::fs::read_utils;
 use kdam::{Bar, BarExt};
 
 pub struct VertexLoaderConfig {
-    pub vertex_list_csv: PathBuf,
+    pub vertex_list_csvs: Vec<PathBuf>,
     pub n_vertices: usize,
 }
 
@@ -19,14 +19,45 @@ impl TryFrom<VertexLoaderConfig> for Box<[Vertex]> {
             .total(conf.n_vertices)
             .animation("fillup")
             .desc("vertex list")
+            // the vertex list loads alongside the edge list, so its bar is drawn below
+            .position(1)
             .build()
             .map_err(|e| GraphError::ProgressBarBuildError(String::from("vertex list"), e))?;
 
-        let cb = Box::new(|_v: &Vertex| {
+        let mut cb = |_v: &Vertex| {
             let _ = pb.update(1);
             processed += 1;
-        });
-        let result: Box<[Vertex]> = read_utils::from_csv(&conf.vertex_list_csv, true, Some(cb))?;
+        };
+        // rows are decoded in parallel, in the order of each file
+        let result: Box<[Vertex]> = if conf.vertex_list_csvs.len() == 1 {
+            read_utils::from_csv_par(
+                &conf.vertex_list_csvs[0],
+                true,
+                conf.n_vertices,
+                Some(Box::new(cb)),
+            )?
+        } else {
+            // shards must continue the id sequence of the previous shard
+            let mut vertices: Vec<Vertex> = Vec::with_capacity(conf.n_vertices);
+            for vertex_list_csv in conf.vertex_list_csvs.iter() {
+                let shard: Box<[Vertex]> =
+                    read_utils::from_csv_par(vertex_list_csv, true, 0, None)?;
+                for vertex in shard.iter() {
+                    let expected = vertices.len();
+                    if vertex.vertex_id.0 != expected {
+                        return Err(GraphError::ShardIdMismatch {
+                            kind: String::from("vertex"),
+                            filename: vertex_list_csv.clone(),
+                            expected,
+                            found: vertex.vertex_id.0,
+                        });
+                    }
+                    cb(vertex);
+                    vertices.push(*vertex);
+                }
+            }
+            vertices.into_boxed_slice()
+        };
 
         println!();
         Ok(result)
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+/// describes one feature of a state model, as listed by [`super::state_model::StateModel::schema`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FeatureSchema {
+    /// name of the feature in the state model
+    pub name: String,
+    /// position of the feature in the state vector
+    pub index: usize,
+    /// the kind of quantity tracked by the feature
+    pub kind: FeatureKind,
+    /// the feature type. this is the kind for distance, time, energy and speed features, and
+    /// the configured type name for custom features, such as "soc"
+    pub feature_type: String,
+    /// name of the unit of the feature
+    pub unit: String,
+    /// the encoding of a custom feature, such as "floating_point", or None for
+    /// distance, time, energy and speed features
+    pub format: Option<String>,
+    /// the initial value of the feature, encoded as a state variable
+    pub initial: f64,
+    /// the key used for the feature in serialized states, if it differs from the name
+    pub output_alias: Option<String>,
+}
+
+/// the kind of quantity tracked by a state feature.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FeatureKind {
+    Distance,
+    Time,
+    Energy,
+    Speed,
+    Custom,
+}
@@ -1,4 +1,5 @@
 pub mod custom_feature_format;
+pub mod feature_schema;
 pub mod indexed_state_feature;
 pub mod state_error;
 pub mod state_feature;
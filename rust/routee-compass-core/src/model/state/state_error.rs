@@ -12,10 +12,16 @@ pub enum StateError {
     UnknownStateVariableName(String, String),
     #[error("invalid state variable index {0}, should be in range [0, {1})")]
     InvalidStateVariableIndex(usize, usize),
+    #[error("state vector has {found} entries but the state model has {expected} features")]
+    InvalidStateLength { expected: usize, found: usize },
     #[error("expected feature to have type '{0}' but found '{1}'")]
     UnexpectedFeatureType(String, String),
     #[error("expected feature unit to be {0} but found {1}")]
     UnexpectedFeatureUnit(String, String),
+    #[error("state feature '{0}' declared more than once with conflicting definitions")]
+    DuplicateFeature(String),
+    #[error("state models are not compatible: {0}")]
+    IncompatibleStateModels(String),
     #[error("{0}")]
     BuildError(String),
     #[error("{0}")]
@@ -6,12 +6,16 @@ use serde::{Deserialize, Serialize};
 
 /// a state variable unit tracks the domain of a StateVar in a
 /// state vector. if the value represents quantity in distance,
-/// time, or energy, then we have a system of internal unit
+/// time, energy, or speed, then we have a system of internal unit
 /// objects which provide conversion arithmetic. if the user
 /// specifies a StateVar has a custom state variable unit, then
 /// they provide a mapping codec and name for the variable, and
 /// it does not interact with our native unit system.
 ///
+/// any feature may set an optional `output_alias`, which replaces the
+/// feature name as its key when a state is serialized, so that a feature
+/// can be renamed internally without changing the output.
+///
 /// # Example
 ///
 /// ### Deserialization
@@ -21,7 +25,8 @@ use serde::{Deserialize, Serialize};
 /// ```toml
 /// state = [
 ///   { distance_unit = "kilometers", initial = 0.0 },
-///   { time_unit = "minutes", initial = 0.0 },
+///   { time_unit = "minutes", initial = 0.0, output_alias = "travel_time" },
+///   { speed_unit = "kilometers_per_hour", initial = 0.0 },
 ///   { name = "soc", unit = "percent", format = { type = "floating_point", initial = 0.0 } }
 /// ]
 ///
@@ -35,26 +40,40 @@ pub enum StateFeature {
     Distance {
         distance_unit: unit::DistanceUnit,
         initial: unit::Distance,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        output_alias: Option<String>,
     },
     Time {
         time_unit: unit::TimeUnit,
         initial: unit::Time,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        output_alias: Option<String>,
     },
     Energy {
         energy_unit: unit::EnergyUnit,
         initial: unit::Energy,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        output_alias: Option<String>,
+    },
+    Speed {
+        speed_unit: unit::SpeedUnit,
+        initial: unit::Speed,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        output_alias: Option<String>,
     },
     Custom {
         r#type: String,
         unit: String,
         format: CustomFeatureFormat,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        output_alias: Option<String>,
     },
 }
 
 impl PartialEq for StateFeature {
     /// tests equality based on the feature type.
     ///
-    /// for distance|time|energy, it's fine to modify either the unit
+    /// for distance|time|energy|speed, it's fine to modify either the unit
     /// or the initial value as this should not interfere with properly-
     /// implemented TraversalModel, AccessModel, and FrontierModel instances.
     ///
@@ -67,42 +86,51 @@ impl PartialEq for StateFeature {
                 StateFeature::Distance {
                     distance_unit: _,
                     initial: _,
+                    output_alias: _,
                 },
                 StateFeature::Distance {
                     distance_unit: _,
                     initial: _,
+                    output_alias: _,
                 },
             ) => true,
             (
                 StateFeature::Time {
                     time_unit: _,
                     initial: _,
+                    output_alias: _,
                 },
                 StateFeature::Time {
                     time_unit: _,
                     initial: _,
+                    output_alias: _,
                 },
             ) => true,
             (
                 StateFeature::Energy {
                     energy_unit: _,
                     initial: _,
+                    output_alias: _,
                 },
                 StateFeature::Energy {
                     energy_unit: _,
                     initial: _,
+                    output_alias: _,
                 },
             ) => true,
+            (StateFeature::Speed { .. }, StateFeature::Speed { .. }) => true,
             (
                 StateFeature::Custom {
                     r#type: a_name,
                     unit: a_unit,
                     format: _,
+                    output_alias: _,
                 },
                 StateFeature::Custom {
                     r#type: b_name,
                     unit: b_unit,
                     format: _,
+                    output_alias: _,
                 },
             ) => a_name == b_name && a_unit == b_unit,
             _ => false,
@@ -116,18 +144,30 @@ impl Display for StateFeature {
             StateFeature::Distance {
                 distance_unit,
                 initial,
+                output_alias: _,
             } => write!(f, "unit: {}, initial: {}", distance_unit, initial),
-            StateFeature::Time { time_unit, initial } => {
+            StateFeature::Time {
+                time_unit,
+                initial,
+                output_alias: _,
+            } => {
                 write!(f, "unit: {}, initial: {}", time_unit, initial)
             }
             StateFeature::Energy {
                 energy_unit,
                 initial,
+                output_alias: _,
             } => write!(f, "unit: {}, initial: {}", energy_unit, initial),
+            StateFeature::Speed {
+                speed_unit,
+                initial,
+                output_alias: _,
+            } => write!(f, "unit: {}, initial: {}", speed_unit, initial),
             StateFeature::Custom {
                 r#type: name,
                 unit,
                 format,
+                output_alias: _,
             } => {
                 write!(f, "name: {} unit: {}, repr: {}", name, unit, format)
             }
@@ -141,19 +181,28 @@ impl StateFeature {
             StateFeature::Distance {
                 distance_unit: _,
                 initial: _,
+                output_alias: _,
             } => String::from("distance"),
             StateFeature::Time {
                 time_unit: _,
                 initial: _,
+                output_alias: _,
             } => String::from("time"),
             StateFeature::Energy {
                 energy_unit: _,
                 initial: _,
+                output_alias: _,
             } => String::from("energy"),
+            StateFeature::Speed {
+                speed_unit: _,
+                initial: _,
+                output_alias: _,
+            } => String::from("speed"),
             StateFeature::Custom {
                 r#type,
                 unit: _,
                 format: _,
+                output_alias: _,
             } => r#type.clone(),
         }
     }
@@ -163,23 +212,54 @@ impl StateFeature {
             StateFeature::Distance {
                 distance_unit,
                 initial: _,
+                output_alias: _,
             } => distance_unit.to_string(),
             StateFeature::Time {
                 time_unit,
                 initial: _,
+                output_alias: _,
             } => time_unit.to_string(),
             StateFeature::Energy {
                 energy_unit,
                 initial: _,
+                output_alias: _,
             } => energy_unit.to_string(),
+            StateFeature::Speed {
+                speed_unit,
+                initial: _,
+                output_alias: _,
+            } => speed_unit.to_string(),
             StateFeature::Custom {
                 r#type: _,
                 unit,
                 format: _,
+                output_alias: _,
             } => unit.clone(),
         }
     }
 
+    /// the name used in place of the feature name when serializing a state, if any.
+    pub fn get_output_alias(&self) -> Option<&String> {
+        match self {
+            StateFeature::Distance { output_alias, .. } => output_alias.as_ref(),
+            StateFeature::Time { output_alias, .. } => output_alias.as_ref(),
+            StateFeature::Energy { output_alias, .. } => output_alias.as_ref(),
+            StateFeature::Speed { output_alias, .. } => output_alias.as_ref(),
+            StateFeature::Custom { output_alias, .. } => output_alias.as_ref(),
+        }
+    }
+
+    /// sets the output alias of this feature, replacing any existing alias.
+    pub fn set_output_alias(&mut self, alias: Option<String>) {
+        match self {
+            StateFeature::Distance { output_alias, .. } => *output_alias = alias,
+            StateFeature::Time { output_alias, .. } => *output_alias = alias,
+            StateFeature::Energy { output_alias, .. } => *output_alias = alias,
+            StateFeature::Speed { output_alias, .. } => *output_alias = alias,
+            StateFeature::Custom { output_alias, .. } => *output_alias = alias,
+        }
+    }
+
     /// custom state variable units may have a custom codec
     /// for domains outside of the real number plane.
     /// this is a helper function to support generic use of the codec,
@@ -190,6 +270,7 @@ impl StateFeature {
                 r#type: _,
                 unit: _,
                 format,
+                output_alias: _,
             } => *format,
             _ => CustomFeatureFormat::default(),
         }
@@ -200,19 +281,28 @@ impl StateFeature {
             StateFeature::Distance {
                 distance_unit: _,
                 initial,
+                output_alias: _,
             } => Ok((*initial).into()),
             StateFeature::Time {
                 time_unit: _,
                 initial,
+                output_alias: _,
             } => Ok((*initial).into()),
             StateFeature::Energy {
                 energy_unit: _,
                 initial,
+                output_alias: _,
+            } => Ok((*initial).into()),
+            StateFeature::Speed {
+                speed_unit: _,
+                initial,
+                output_alias: _,
             } => Ok((*initial).into()),
             StateFeature::Custom {
                 r#type: _,
                 unit: _,
                 format,
+                output_alias: _,
             } => format.initial(),
         }
     }
@@ -222,6 +312,7 @@ impl StateFeature {
             StateFeature::Distance {
                 distance_unit: unit,
                 initial: _,
+                output_alias: _,
             } => Ok(*unit),
             _ => Err(StateError::UnexpectedFeatureUnit(
                 String::from("distance"),
@@ -235,6 +326,7 @@ impl StateFeature {
             StateFeature::Time {
                 time_unit: unit,
                 initial: _,
+                output_alias: _,
             } => Ok(*unit),
             _ => Err(StateError::UnexpectedFeatureUnit(
                 String::from("time"),
@@ -248,6 +340,7 @@ impl StateFeature {
             StateFeature::Energy {
                 energy_unit,
                 initial: _,
+                output_alias: _,
             } => Ok(*energy_unit),
             _ => Err(StateError::UnexpectedFeatureUnit(
                 String::from("energy"),
@@ -256,12 +349,27 @@ impl StateFeature {
         }
     }
 
+    pub fn get_speed_unit(&self) -> Result<unit::SpeedUnit, StateError> {
+        match self {
+            StateFeature::Speed {
+                speed_unit,
+                initial: _,
+                output_alias: _,
+            } => Ok(*speed_unit),
+            _ => Err(StateError::UnexpectedFeatureUnit(
+                String::from("speed"),
+                self.get_feature_type(),
+            )),
+        }
+    }
+
     pub fn get_custom_feature_format(&self) -> Result<&CustomFeatureFormat, StateError> {
         match self {
             StateFeature::Custom {
                 r#type: _,
                 unit: _,
                 format,
+                output_alias: _,
             } => Ok(format),
             _ => Err(StateError::UnexpectedFeatureUnit(
                 self.get_feature_unit_name(),
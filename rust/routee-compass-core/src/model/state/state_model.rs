@@ -1,12 +1,15 @@
 use super::{
-    custom_feature_format::CustomFeatureFormat, state_error::StateError,
-    state_feature::StateFeature, update_operation::UpdateOperation,
+    custom_feature_format::CustomFeatureFormat,
+    feature_schema::{FeatureKind, FeatureSchema},
+    state_error::StateError,
+    state_feature::StateFeature,
+    update_operation::UpdateOperation,
 };
 use crate::util::compact_ordered_hash_map::CompactOrderedHashMap;
 use crate::{
     model::{
         traversal::state::state_variable::StateVar,
-        unit::{Distance, DistanceUnit, Energy, EnergyUnit, Time, TimeUnit},
+        unit::{Distance, DistanceUnit, Energy, EnergyUnit, Speed, SpeedUnit, Time, TimeUnit},
     },
     util::compact_ordered_hash_map::IndexedEntry,
 };
@@ -36,39 +39,48 @@ impl StateModel {
 
     /// extends a state model by adding additional key/value pairs to the model mapping.
     /// in the case of name collision, we compare old and new state features at that name.
-    /// if the state feature has the same unit (tested by StateFeature::Eq), then it can
-    /// overwrite the existing.
+    /// if the state feature has the same type (tested by StateFeature::Eq), then it is
+    /// treated as an intentional overwrite, which is logged if the unit or initial value
+    /// changes. if the types differ, the definitions conflict and an error is returned.
     ///
     /// this method is used when state models are updated by the user query as Services
     /// become Models in the SearchApp.
     ///
     /// # Arguments
-    /// * `query` - JSON search query contents containing state model information
+    /// * `entries` - state features to add to this model, in order
+    ///
+    /// # Returns
+    ///
+    /// the extended state model, or a DuplicateFeature error naming the first conflicting feature
     pub fn extend(&self, entries: Vec<(String, StateFeature)>) -> Result<StateModel, StateError> {
         let mut map = self
             .0
             .iter()
             .map(|(k, v)| (k.clone(), v.clone()))
             .collect::<CompactOrderedHashMap<_, _>>();
-        let overwrites = entries
-            .into_iter()
-            .flat_map(|(name, new)| match map.insert(name.clone(), new.clone()) {
-                Some(old) if old != new => Some((name.clone(), old, new)),
-                _ => None,
-            })
-            .collect::<Vec<_>>();
-        if overwrites.is_empty() {
-            Ok(StateModel(map))
-        } else {
-            let msg = overwrites
-                .iter()
-                .map(|(k, old, new)| format!("{} old: {} | new: {}", k, old, new))
-                .join(", ");
-            Err(StateError::BuildError(format!(
-                "new state features overwriting existing: {}",
-                msg
-            )))
+        for (name, new) in entries.into_iter() {
+            insert_feature(&mut map, name, new)?;
         }
+        Ok(StateModel(map))
+    }
+
+    /// appends a single feature to this state model without rebuilding it. a new
+    /// feature is placed at the next state vector index, so the indices of the
+    /// existing features are unchanged and state vectors of this model remain valid
+    /// as a prefix of the new state vectors. name collisions are handled as in
+    /// [`StateModel::extend`], replacing the feature at its existing index.
+    ///
+    /// # Arguments
+    /// * `name` - name of the feature to add
+    /// * `feature` - the feature to add
+    ///
+    /// # Returns
+    ///
+    /// the state model with the feature appended, or a DuplicateFeature error if the
+    /// feature conflicts with an existing feature of the same name
+    pub fn append(mut self, name: String, feature: StateFeature) -> Result<StateModel, StateError> {
+        insert_feature(&mut self.0, name, feature)?;
+        Ok(self)
     }
 
     pub fn len(&self) -> usize {
@@ -178,6 +190,27 @@ impl StateModel {
         let result = feature.get_energy_unit()?.convert(&value.into(), unit);
         Ok(result)
     }
+    /// retrieves a state variable that is expected to have a type of Speed
+    ///
+    /// # Arguments
+    /// * `state` - state vector to inspect
+    /// * `name`  - feature name to extract
+    /// * `unit`  - feature is converted to this unit before returning
+    ///
+    /// # Returns
+    ///
+    /// feature value in the expected unit type, or an error
+    pub fn get_speed(
+        &self,
+        state: &[StateVar],
+        name: &String,
+        unit: &SpeedUnit,
+    ) -> Result<Speed, StateError> {
+        let value = self.get_state_variable(state, name)?;
+        let feature = self.get_feature(name)?;
+        let result = feature.get_speed_unit()?.convert(&value.into(), unit);
+        Ok(result)
+    }
     /// retrieves a state variable that is expected to have a type of f64.
     ///
     /// # Arguments
@@ -279,6 +312,35 @@ impl StateModel {
         Ok(next_val - prev_val)
     }
 
+    /// gets the difference from some previous state to some next state for every
+    /// feature, in state vector order. deltas are computed on the raw state
+    /// variables, in the storage unit of each feature, so the delta of a custom
+    /// feature is the difference of its encoded values.
+    ///
+    /// # Arguments
+    ///
+    /// * `prev` - the previous state to inspect
+    /// * `next` - the next state to inspect
+    ///
+    /// # Result
+    ///
+    /// the name and delta of each feature, or an error if either state vector
+    /// does not match this state model
+    pub fn delta_vec(
+        &self,
+        prev: &[StateVar],
+        next: &[StateVar],
+    ) -> Result<Vec<(String, StateVar)>, StateError> {
+        self.validate_state_length(prev)?;
+        self.validate_state_length(next)?;
+        let deltas = self
+            .iter()
+            .zip(prev.iter().zip(next.iter()))
+            .map(|((name, _), (prev_var, next_var))| (name.clone(), *next_var - *prev_var))
+            .collect();
+        Ok(deltas)
+    }
+
     /// adds a distance value with distance unit to this feature vector
     pub fn add_distance(
         &self,
@@ -305,7 +367,22 @@ impl StateModel {
         self.set_time(state, name, &next_time, from_unit)
     }
 
-    /// adds a energy value with energy unit to this feature vector
+    /// adds a speed value with speed unit to this feature vector
+    pub fn add_speed(
+        &self,
+        state: &mut [StateVar],
+        name: &String,
+        speed: &Speed,
+        from_unit: &SpeedUnit,
+    ) -> Result<(), StateError> {
+        let prev_speed = self.get_speed(state, name, from_unit)?;
+        let next_speed = prev_speed + *speed;
+        self.set_speed(state, name, &next_speed, from_unit)
+    }
+
+    /// adds a energy value with energy unit to this feature vector.
+    /// only the added value is converted to the storage unit of the feature,
+    /// so that the accumulated energy is not repeatedly converted between units.
     pub fn add_energy(
         &self,
         state: &mut [StateVar],
@@ -313,9 +390,11 @@ impl StateModel {
         energy: &Energy,
         from_unit: &EnergyUnit,
     ) -> Result<(), StateError> {
-        let prev_energy = self.get_energy(state, name, from_unit)?;
-        let next_energy = prev_energy + *energy;
-        self.set_energy(state, name, &next_energy, from_unit)
+        let feature = self.get_feature(name)?;
+        let to_unit = feature.get_energy_unit()?;
+        let prev_energy: Energy = self.get_state_variable(state, name)?.into();
+        let next_energy = prev_energy + from_unit.convert(energy, &to_unit);
+        self.update_state(state, name, &next_energy.into(), UpdateOperation::Replace)
     }
 
     pub fn set_distance(
@@ -357,6 +436,19 @@ impl StateModel {
         self.update_state(state, name, &value.into(), UpdateOperation::Replace)
     }
 
+    pub fn set_speed(
+        &self,
+        state: &mut [StateVar],
+        name: &String,
+        speed: &Speed,
+        from_unit: &SpeedUnit,
+    ) -> Result<(), StateError> {
+        let feature = self.get_feature(name)?;
+        let to_unit = feature.get_speed_unit()?;
+        let value = from_unit.convert(speed, &to_unit);
+        self.update_state(state, name, &value.into(), UpdateOperation::Replace)
+    }
+
     pub fn set_custom_f64(
         &self,
         state: &mut [StateVar],
@@ -405,7 +497,99 @@ impl StateModel {
         self.update_state(state, name, &encoded_value, UpdateOperation::Replace)
     }
 
-    /// uses the state model to pretty print a state instance as a JSON object
+    /// combines two state vectors feature-wise, such as the accumulated states of
+    /// two consecutive legs of a multi-leg route. distance, time and energy features
+    /// are additive and are summed. speed and custom features have no accumulation
+    /// semantics, so the value from `b`, the later state, is kept.
+    ///
+    /// # Arguments
+    /// * `a` - first state vector
+    /// * `b` - second state vector, following `a`
+    ///
+    /// # Returns
+    ///
+    /// the combined state vector, or an error if either vector does not match this state model
+    pub fn combine(&self, a: &[StateVar], b: &[StateVar]) -> Result<Vec<StateVar>, StateError> {
+        if a.len() != self.len() || b.len() != self.len() {
+            return Err(StateError::RuntimeError(format!(
+                "cannot combine state vectors with lengths {} and {} using state model with {} features",
+                a.len(),
+                b.len(),
+                self.len()
+            )));
+        }
+        let combined = self
+            .iter()
+            .zip(a.iter().zip(b.iter()))
+            .map(|((_, feature), (a_var, b_var))| match feature {
+                StateFeature::Distance { .. }
+                | StateFeature::Time { .. }
+                | StateFeature::Energy { .. } => *a_var + *b_var,
+                StateFeature::Speed { .. } | StateFeature::Custom { .. } => *b_var,
+            })
+            .collect();
+        Ok(combined)
+    }
+
+    /// tests whether state vectors built by this model can be interpreted by another
+    /// model, which requires both models to have the same feature names, in the same
+    /// state vector order, with the same feature types and units.
+    ///
+    /// # Arguments
+    /// * `other` - the state model that would interpret this model's state vectors
+    ///
+    /// # Returns
+    ///
+    /// nothing if the models are compatible, otherwise an IncompatibleStateModels error
+    /// describing the first difference found
+    pub fn is_compatible_with(&self, other: &StateModel) -> Result<(), StateError> {
+        if self.len() != other.len() {
+            return Err(StateError::IncompatibleStateModels(format!(
+                "expected {} features [{}] but found {} features [{}]",
+                self.len(),
+                self.get_names(),
+                other.len(),
+                other.get_names()
+            )));
+        }
+        for (idx, ((name, feature), (other_name, other_feature))) in
+            self.iter().zip(other.iter()).enumerate()
+        {
+            if name != other_name {
+                let reason = match other.0.get_index(name) {
+                    Some(other_idx) => format!(
+                        "feature '{}' is at index {} but found at index {}",
+                        name, idx, other_idx
+                    ),
+                    None => format!(
+                        "expected feature '{}' at index {} but found '{}'",
+                        name, idx, other_name
+                    ),
+                };
+                return Err(StateError::IncompatibleStateModels(reason));
+            }
+            if feature.get_feature_type() != other_feature.get_feature_type() {
+                return Err(StateError::IncompatibleStateModels(format!(
+                    "feature '{}' has type '{}' but found '{}'",
+                    name,
+                    feature.get_feature_type(),
+                    other_feature.get_feature_type()
+                )));
+            }
+            if feature.get_feature_unit_name() != other_feature.get_feature_unit_name() {
+                return Err(StateError::IncompatibleStateModels(format!(
+                    "feature '{}' has unit '{}' but found '{}'",
+                    name,
+                    feature.get_feature_unit_name(),
+                    other_feature.get_feature_unit_name()
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// uses the state model to pretty print a state instance as a JSON object.
+    /// each feature is keyed by its output alias, if set, or else by its name.
     ///
     /// # Arguments
     /// * `state` - any (valid) state vector instance
@@ -416,7 +600,9 @@ impl StateModel {
         let output = self
             .iter()
             .zip(state.iter())
-            .map(|((name, _), state_var)| (name, state_var))
+            .map(|((name, feature), state_var)| {
+                (feature.get_output_alias().unwrap_or(name), state_var)
+            })
             .collect::<HashMap<_, _>>();
         json![output]
     }
@@ -426,23 +612,70 @@ impl StateModel {
         json![self.iter().collect::<HashMap<_, _>>()]
     }
 
+    /// lists each feature of this state model with its metadata, in state vector order.
+    /// unlike [`StateModel::serialize_state_model`], this is a typed description intended
+    /// for introspection, such as by a client rendering the features of a profile.
+    ///
+    /// # Returns
+    ///
+    /// the schema of each feature, or an error if an initial value cannot be encoded
+    pub fn schema(&self) -> Result<Vec<FeatureSchema>, StateError> {
+        self.indexed_iter()
+            .map(|(index, (name, feature))| {
+                let (kind, format) = match feature {
+                    StateFeature::Distance { .. } => (FeatureKind::Distance, None),
+                    StateFeature::Time { .. } => (FeatureKind::Time, None),
+                    StateFeature::Energy { .. } => (FeatureKind::Energy, None),
+                    StateFeature::Speed { .. } => (FeatureKind::Speed, None),
+                    StateFeature::Custom { format, .. } => {
+                        (FeatureKind::Custom, Some(format.name()))
+                    }
+                };
+                Ok(FeatureSchema {
+                    name: name.clone(),
+                    index,
+                    kind,
+                    feature_type: feature.get_feature_type(),
+                    unit: feature.get_feature_unit_name(),
+                    format,
+                    initial: feature.get_initial()?.0,
+                    output_alias: feature.get_output_alias().cloned(),
+                })
+            })
+            .collect()
+    }
+
     /// lists the names of the state variables in order
     pub fn get_names(&self) -> String {
         self.0.iter().map(|(k, _)| k.clone()).join(",")
     }
 
+    /// confirms that a state vector has an entry for each feature of this model. a
+    /// state vector built before the model was extended has fewer entries, and
+    /// reading it by index could return the value of another feature.
+    fn validate_state_length(&self, state: &[StateVar]) -> Result<(), StateError> {
+        if state.len() != self.len() {
+            return Err(StateError::InvalidStateLength {
+                expected: self.len(),
+                found: state.len(),
+            });
+        }
+        Ok(())
+    }
+
     fn get_feature(&self, feature_name: &String) -> Result<&StateFeature, StateError> {
         self.0.get(feature_name).ok_or_else(|| {
             StateError::UnknownStateVariableName(feature_name.clone(), self.get_names())
         })
     }
 
-    /// gets a state variable from a state vector by name
-    fn get_state_variable(
+    /// gets a state variable from a state vector by name, in the unit of its feature
+    pub fn get_state_variable(
         &self,
         state: &[StateVar],
         name: &String,
     ) -> Result<StateVar, StateError> {
+        self.validate_state_length(state)?;
         let idx = self
             .0
             .get_index(name)
@@ -465,6 +698,7 @@ impl StateModel {
         value: &StateVar,
         op: UpdateOperation,
     ) -> Result<(), StateError> {
+        self.validate_state_length(state)?;
         let index = self
             .0
             .get_index(name)
@@ -495,7 +729,7 @@ impl<'a> TryFrom<&'a serde_json::Value> for StateModel {
     /// ```toml
     /// [state]
     /// distance = { "distance_unit" = "kilometers", initial = 0.0 },
-    /// time = { "time_unit" = "minutes", initial = 0.0 },
+    /// time = { "time_unit" = "minutes", initial = 0.0, output_alias = "travel_time" },
     /// battery_soc = { name = "soc", unit = "percent", format = { type = "floating_point", initial = 0.0 } }
     ///
     /// the same example as JSON (convert '=' into ':', and enquote object keys):
@@ -503,7 +737,7 @@ impl<'a> TryFrom<&'a serde_json::Value> for StateModel {
     /// ```json
     /// {
     ///   "distance": { "distance_unit": "kilometers", "initial": 0.0 },
-    ///   "time": { "time_unit": "minutes", "initial": 0.0 },
+    ///   "time": { "time_unit": "minutes", "initial": 0.0, "output_alias": "travel_time" },
     ///   "battery_soc": {
     ///     "name": "soc",
     ///     "unit": "percent",
@@ -546,3 +780,508 @@ impl From<Vec<(String, StateFeature)>> for StateModel {
         StateModel::new(value)
     }
 }
+
+/// inserts a feature into a state model mapping, keeping the output alias of an
+/// existing feature of the same name. a same-typed feature overwrites the existing
+/// feature, which is logged if the unit or initial value changes, while a feature
+/// of a different type is a conflict.
+fn insert_feature(
+    map: &mut CompactOrderedHashMap<String, StateFeature>,
+    name: String,
+    mut new: StateFeature,
+) -> Result<(), StateError> {
+    // features added by models do not set an output alias, so a configured alias is kept
+    if new.get_output_alias().is_none() {
+        if let Some(alias) = map.get(&name).and_then(|old| old.get_output_alias()) {
+            new.set_output_alias(Some(alias.clone()));
+        }
+    }
+    match map.insert(name.clone(), new.clone()) {
+        Some(old) if old != new => Err(StateError::DuplicateFeature(name)),
+        Some(old) if old.to_string() != new.to_string() => {
+            log::warn!(
+                "overwriting state feature '{}' old: {} | new: {}",
+                name,
+                old,
+                new
+            );
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::unit::{as_f64::AsF64, Distance, DistanceUnit, Time, TimeUnit};
+
+    fn distance_feature(distance_unit: DistanceUnit) -> StateFeature {
+        StateFeature::Distance {
+            distance_unit,
+            initial: Distance::ZERO,
+            output_alias: None,
+        }
+    }
+
+    #[test]
+    fn test_speed_feature_unit_conversion() {
+        let feature: StateFeature = serde_json::from_value(json!({
+            "speed_unit": "kilometers_per_hour",
+            "initial": 0.0
+        }))
+        .unwrap();
+        let model = StateModel::new(vec![
+            (String::from("speed"), feature),
+            (
+                String::from("distance"),
+                distance_feature(DistanceUnit::Kilometers),
+            ),
+        ]);
+        let speed = String::from("speed");
+        let mut state = model.initial_state().unwrap();
+
+        // stored in kilometers per hour, converted on the way in and out
+        model
+            .set_speed(
+                &mut state,
+                &speed,
+                &Speed::new(60.0),
+                &SpeedUnit::MilesPerHour,
+            )
+            .unwrap();
+        assert!((state[0].0 - 96.5604).abs() < 1e-9, "{}", state[0].0);
+        model
+            .add_speed(
+                &mut state,
+                &speed,
+                &Speed::new(10.0),
+                &SpeedUnit::KilometersPerHour,
+            )
+            .unwrap();
+        let kph = model
+            .get_speed(&state, &speed, &SpeedUnit::KilometersPerHour)
+            .unwrap();
+        assert!((kph.as_f64() - 106.5604).abs() < 1e-9, "{}", kph);
+        let mph = model
+            .get_speed(&state, &speed, &SpeedUnit::MilesPerHour)
+            .unwrap();
+        assert!((mph.as_f64() - 66.213538).abs() < 1e-5, "{}", mph);
+
+        // a feature that is not a speed cannot be read or written as one
+        let distance = String::from("distance");
+        assert!(matches!(
+            model.get_speed(&state, &distance, &SpeedUnit::MilesPerHour),
+            Err(StateError::UnexpectedFeatureUnit(expected, found)) if expected == "speed" && found == "distance"
+        ));
+        assert!(model
+            .set_speed(
+                &mut state,
+                &distance,
+                &Speed::new(1.0),
+                &SpeedUnit::MilesPerHour
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_delta_vec() {
+        let model = StateModel::new(vec![
+            (
+                String::from("distance"),
+                distance_feature(DistanceUnit::Kilometers),
+            ),
+            (
+                String::from("time"),
+                StateFeature::Time {
+                    time_unit: TimeUnit::Minutes,
+                    initial: Time::ZERO,
+                    output_alias: None,
+                },
+            ),
+        ]);
+        let prev = vec![StateVar(1.0), StateVar(10.0)];
+        let next = vec![StateVar(3.5), StateVar(12.0)];
+        let deltas = model.delta_vec(&prev, &next).unwrap();
+        // names align with the order of the state vector
+        let expected = model
+            .iter()
+            .map(|(name, _)| {
+                let delta = model.get_delta(&prev, &next, name).unwrap();
+                (name.clone(), delta)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(deltas, expected);
+        let distance_delta = deltas.iter().find(|(name, _)| name == "distance");
+        assert_eq!(distance_delta.map(|(_, delta)| *delta), Some(StateVar(2.5)));
+
+        assert!(model.delta_vec(&prev, &next[..1]).is_err());
+    }
+
+    #[test]
+    fn test_stale_state_length() {
+        let model = StateModel::new(vec![
+            (
+                String::from("distance"),
+                distance_feature(DistanceUnit::Kilometers),
+            ),
+            (
+                String::from("time"),
+                StateFeature::Time {
+                    time_unit: TimeUnit::Minutes,
+                    initial: Time::ZERO,
+                    output_alias: None,
+                },
+            ),
+            (
+                String::from("trip_distance"),
+                distance_feature(DistanceUnit::Miles),
+            ),
+        ]);
+        // a state vector built for a model with only two of the features
+        let mut stale = vec![StateVar(1.0), StateVar(2.0)];
+        let distance = String::from("distance");
+        let result = model.get_distance(&stale, &distance, &DistanceUnit::Kilometers);
+        assert!(
+            matches!(
+                result,
+                Err(StateError::InvalidStateLength {
+                    expected: 3,
+                    found: 2
+                })
+            ),
+            "{:?}",
+            result
+        );
+        let result = model.set_distance(
+            &mut stale,
+            &distance,
+            &Distance::new(1.0),
+            &DistanceUnit::Kilometers,
+        );
+        assert!(matches!(result, Err(StateError::InvalidStateLength { .. })));
+        assert_eq!(stale, vec![StateVar(1.0), StateVar(2.0)]);
+    }
+
+    #[test]
+    fn test_combine_mixed_features() {
+        let model = StateModel::new(vec![
+            (
+                String::from("distance"),
+                distance_feature(DistanceUnit::Kilometers),
+            ),
+            (
+                String::from("time"),
+                StateFeature::Time {
+                    time_unit: TimeUnit::Minutes,
+                    initial: Time::ZERO,
+                    output_alias: None,
+                },
+            ),
+            (
+                String::from("soc"),
+                StateFeature::Custom {
+                    r#type: String::from("soc"),
+                    unit: String::from("percent"),
+                    format: CustomFeatureFormat::FloatingPoint {
+                        initial: ordered_float::OrderedFloat(100.0),
+                    },
+                    output_alias: None,
+                },
+            ),
+            (
+                String::from("toll"),
+                StateFeature::Custom {
+                    r#type: String::from("toll"),
+                    unit: String::from("boolean"),
+                    format: CustomFeatureFormat::Boolean { initial: false },
+                    output_alias: None,
+                },
+            ),
+        ]);
+        let mut first = model.initial_state().unwrap();
+        model
+            .add_distance(
+                &mut first,
+                &String::from("distance"),
+                &Distance::new(2.0),
+                &DistanceUnit::Kilometers,
+            )
+            .unwrap();
+        model
+            .add_time(
+                &mut first,
+                &String::from("time"),
+                &Time::new(3.0),
+                &TimeUnit::Minutes,
+            )
+            .unwrap();
+        model
+            .set_custom_f64(&mut first, &String::from("soc"), &80.0)
+            .unwrap();
+        let mut second = model.initial_state().unwrap();
+        model
+            .add_distance(
+                &mut second,
+                &String::from("distance"),
+                &Distance::new(500.0),
+                &DistanceUnit::Meters,
+            )
+            .unwrap();
+        model
+            .set_custom_f64(&mut second, &String::from("soc"), &65.0)
+            .unwrap();
+        model
+            .set_custom_bool(&mut second, &String::from("toll"), &true)
+            .unwrap();
+
+        let combined = model.combine(&first, &second).unwrap();
+        let distance = model
+            .get_distance(
+                &combined,
+                &String::from("distance"),
+                &DistanceUnit::Kilometers,
+            )
+            .unwrap();
+        let time = model
+            .get_time(&combined, &String::from("time"), &TimeUnit::Minutes)
+            .unwrap();
+        let soc = model
+            .get_custom_f64(&combined, &String::from("soc"))
+            .unwrap();
+        let toll = model
+            .get_custom_bool(&combined, &String::from("toll"))
+            .unwrap();
+        assert_eq!(distance, Distance::new(2.5));
+        assert_eq!(time, Time::new(3.0));
+        assert_eq!(soc, 65.0);
+        assert!(toll);
+
+        let result = model.combine(&first, &second[0..2]);
+        assert!(matches!(result, Err(StateError::RuntimeError(_))));
+    }
+
+    #[test]
+    fn test_extend_overwrite_same_type() {
+        let model = StateModel::new(vec![(
+            String::from("distance"),
+            distance_feature(DistanceUnit::Miles),
+        )]);
+        let extended = model
+            .extend(vec![(
+                String::from("distance"),
+                distance_feature(DistanceUnit::Kilometers),
+            )])
+            .unwrap();
+        assert_eq!(extended.len(), 1);
+        let (_, feature) = extended.iter().next().unwrap();
+        // StateFeature::Eq ignores units, so compare the full representation
+        assert_eq!(
+            feature.to_string(),
+            distance_feature(DistanceUnit::Kilometers).to_string()
+        );
+    }
+
+    #[test]
+    fn test_extend_conflicting_definitions() {
+        let model = StateModel::new(vec![(
+            String::from("distance"),
+            distance_feature(DistanceUnit::Miles),
+        )]);
+        let result = model.extend(vec![(
+            String::from("distance"),
+            StateFeature::Time {
+                time_unit: TimeUnit::Hours,
+                initial: Time::ZERO,
+                output_alias: None,
+            },
+        )]);
+        assert!(matches!(result, Err(StateError::DuplicateFeature(name)) if name == "distance"));
+    }
+
+    #[test]
+    fn test_append_preserves_indices() {
+        let model = StateModel::new(
+            (0..6)
+                .map(|i| {
+                    (
+                        format!("distance_{}", i),
+                        distance_feature(DistanceUnit::Miles),
+                    )
+                })
+                .collect(),
+        );
+        let before = model
+            .indexed_iter()
+            .map(|(index, (name, _))| (name.clone(), index))
+            .collect::<Vec<_>>();
+        let time = StateFeature::Time {
+            time_unit: TimeUnit::Hours,
+            initial: Time::ZERO,
+            output_alias: None,
+        };
+        let appended = model.append(String::from("time"), time).unwrap();
+        assert_eq!(appended.len(), 7);
+        for (name, index) in before.iter() {
+            assert_eq!(appended.0.get_index(name), Some(*index));
+        }
+        assert_eq!(appended.0.get_index(&String::from("time")), Some(6));
+        assert_eq!(appended.iter().count(), 7);
+
+        // a collision replaces the feature at its existing index
+        let replaced = appended
+            .append(
+                String::from("distance_2"),
+                distance_feature(DistanceUnit::Kilometers),
+            )
+            .unwrap();
+        assert_eq!(replaced.len(), 7);
+        assert_eq!(replaced.0.get_index(&String::from("distance_2")), Some(2));
+        let (_, feature) = replaced.iter().nth(2).unwrap();
+        assert_eq!(
+            feature.to_string(),
+            distance_feature(DistanceUnit::Kilometers).to_string()
+        );
+    }
+
+    #[test]
+    fn test_serialize_state_with_output_alias() {
+        let config = serde_json::json!({
+            "distance": {
+                "distance_unit": "kilometers",
+                "initial": 0.0,
+                "output_alias": "trip_distance"
+            }
+        });
+        let configured = StateModel::try_from(&config).unwrap();
+        // a traversal model extends the configured feature without an alias
+        let model = configured
+            .extend(vec![(
+                String::from("distance"),
+                distance_feature(DistanceUnit::Kilometers),
+            )])
+            .unwrap();
+        let mut state = model.initial_state().unwrap();
+        let distance = String::from("distance");
+        model
+            .add_distance(
+                &mut state,
+                &distance,
+                &Distance::new(2.5),
+                &DistanceUnit::Kilometers,
+            )
+            .unwrap();
+
+        let serialized = model.serialize_state(&state);
+        assert_eq!(serialized, serde_json::json!({ "trip_distance": 2.5 }));
+        let result = model
+            .get_distance(&state, &distance, &DistanceUnit::Kilometers)
+            .unwrap();
+        assert_eq!(result, Distance::new(2.5));
+    }
+
+    #[test]
+    fn test_schema_of_mixed_features() {
+        let config = serde_json::json!({
+            "distance": { "distance_unit": "miles", "initial": 0.0 },
+            "time": { "time_unit": "minutes", "initial": 5.0, "output_alias": "trip_time" },
+            "soc": {
+                "type": "soc",
+                "unit": "percent",
+                "format": { "floating_point": { "initial": 100.0 } }
+            },
+            "toll": {
+                "type": "toll",
+                "unit": "boolean",
+                "format": { "boolean": { "initial": true } }
+            }
+        });
+        let model = StateModel::try_from(&config).unwrap();
+        let schema = model.schema().unwrap();
+        let by_name = schema
+            .iter()
+            .map(|s| (s.name.as_str(), s))
+            .collect::<HashMap<_, _>>();
+        assert_eq!(schema.len(), 4);
+        for (index, feature) in schema.iter().enumerate() {
+            assert_eq!(feature.index, index);
+        }
+
+        let distance = by_name["distance"];
+        assert_eq!(distance.kind, FeatureKind::Distance);
+        assert_eq!(distance.unit, "miles");
+        assert_eq!(distance.format, None);
+        assert_eq!(distance.output_alias, None);
+
+        let time = by_name["time"];
+        assert_eq!(time.kind, FeatureKind::Time);
+        assert_eq!(time.initial, 5.0);
+        assert_eq!(time.output_alias, Some(String::from("trip_time")));
+
+        let soc = by_name["soc"];
+        assert_eq!(soc.kind, FeatureKind::Custom);
+        assert_eq!(soc.feature_type, "soc");
+        assert_eq!(soc.unit, "percent");
+        assert_eq!(soc.format, Some(String::from("floating_point")));
+        assert_eq!(soc.initial, 100.0);
+
+        let toll = by_name["toll"];
+        assert_eq!(toll.kind, FeatureKind::Custom);
+        assert_eq!(toll.format, Some(String::from("boolean")));
+        assert_eq!(toll.initial, 1.0);
+    }
+
+    #[test]
+    fn test_is_compatible_with() {
+        let time_feature = StateFeature::Time {
+            time_unit: TimeUnit::Minutes,
+            initial: Time::ZERO,
+            output_alias: None,
+        };
+        let model = StateModel::new(vec![
+            (
+                String::from("distance"),
+                distance_feature(DistanceUnit::Kilometers),
+            ),
+            (String::from("time"), time_feature.clone()),
+        ]);
+        let same = StateModel::new(vec![
+            (
+                String::from("distance"),
+                distance_feature(DistanceUnit::Kilometers),
+            ),
+            (String::from("time"), time_feature.clone()),
+        ]);
+        assert!(model.is_compatible_with(&same).is_ok());
+
+        let reordered = StateModel::new(vec![
+            (String::from("time"), time_feature.clone()),
+            (
+                String::from("distance"),
+                distance_feature(DistanceUnit::Kilometers),
+            ),
+        ]);
+        let reordered_err = model.is_compatible_with(&reordered).unwrap_err();
+        assert!(
+            reordered_err
+                .to_string()
+                .contains("'distance' is at index 0"),
+            "{}",
+            reordered_err
+        );
+
+        let other_unit = StateModel::new(vec![
+            (
+                String::from("distance"),
+                distance_feature(DistanceUnit::Miles),
+            ),
+            (String::from("time"), time_feature),
+        ]);
+        let unit_err = model.is_compatible_with(&other_unit).unwrap_err();
+        assert!(
+            matches!(unit_err, StateError::IncompatibleStateModels(ref msg) if msg.contains("unit")),
+            "{}",
+            unit_err
+        );
+    }
+}
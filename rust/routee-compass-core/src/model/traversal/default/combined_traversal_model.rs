@@ -0,0 +1,231 @@
+use crate::model::property::{edge::Edge, vertex::Vertex};
+use crate::model::state::state_feature::StateFeature;
+use crate::model::state::state_model::StateModel;
+use crate::model::traversal::state::state_variable::StateVar;
+use crate::model::traversal::traversal_model::TraversalModel;
+use crate::model::traversal::traversal_model_error::TraversalModelError;
+use crate::model::traversal::traversal_model_service::TraversalModelService;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+pub struct CombinedTraversalService {
+    pub services: Vec<Arc<dyn TraversalModelService>>,
+}
+
+impl CombinedTraversalService {
+    /// combines traversal model services, confirming at load time that no two of their
+    /// models list the same state feature. each service is built without query
+    /// parameters for this check. a service that requires query parameters cannot be
+    /// built here, and its features are checked when a query builds the combined model.
+    pub fn new(
+        services: Vec<Arc<dyn TraversalModelService>>,
+    ) -> Result<CombinedTraversalService, TraversalModelError> {
+        let empty_query = serde_json::json!({});
+        let mut models = vec![];
+        for (index, service) in services.iter().enumerate() {
+            match service.build(&empty_query) {
+                Ok(model) => models.push(model),
+                Err(e) => log::debug!(
+                    "combined traversal model {} requires query parameters, its state features are checked per query: {}",
+                    index,
+                    e
+                ),
+            }
+        }
+        CombinedTraversalModel::new(models)?;
+        Ok(CombinedTraversalService { services })
+    }
+}
+
+/// A traversal model that runs several traversal models on each edge, such as a
+/// distance model alongside a model of some other feature, so that all of their
+/// features are tracked in one search.
+///
+/// state features are looked up by name in the state model, so each model reads and
+/// writes only its own features of the combined state vector. the models must write
+/// distinct features, since two models adding to the same feature would count each
+/// edge twice. the estimate of the combined model applies the estimate of every model
+/// to its own features, which is admissible if each estimate is.
+///
+/// # Example
+///
+/// ### Deserialization
+///
+/// ```toml
+/// [traversal]
+/// type = "combined"
+/// [[traversal.traversal_models]]
+/// type = "distance"
+/// distance_unit = "miles"
+/// [[traversal.traversal_models]]
+/// type = "constant"
+/// cost = 1.0
+/// ```
+pub struct CombinedTraversalModel {
+    models: Vec<Arc<dyn TraversalModel>>,
+}
+
+impl CombinedTraversalModel {
+    /// combines traversal models, confirming that no two of them list the same state feature.
+    pub fn new(
+        models: Vec<Arc<dyn TraversalModel>>,
+    ) -> Result<CombinedTraversalModel, TraversalModelError> {
+        let mut owners: HashMap<String, usize> = HashMap::new();
+        for (index, model) in models.iter().enumerate() {
+            for (name, _) in model.state_features() {
+                if let Some(other) = owners.insert(name.clone(), index) {
+                    return Err(TraversalModelError::BuildError(format!(
+                        "combined traversal models {} and {} both write the state feature '{}'",
+                        other, index, name
+                    )));
+                }
+            }
+        }
+        Ok(CombinedTraversalModel { models })
+    }
+}
+
+impl TraversalModelService for CombinedTraversalService {
+    fn build(
+        &self,
+        query: &serde_json::Value,
+    ) -> Result<Arc<dyn TraversalModel>, TraversalModelError> {
+        let models = self
+            .services
+            .iter()
+            .map(|s| s.build(query))
+            .collect::<Result<_, _>>()?;
+        Ok(Arc::new(CombinedTraversalModel::new(models)?))
+    }
+}
+
+impl TraversalModel for CombinedTraversalModel {
+    /// the features of each model, in the order of the models
+    fn state_features(&self) -> Vec<(String, StateFeature)> {
+        self.models
+            .iter()
+            .flat_map(|m| m.state_features())
+            .collect()
+    }
+
+    fn traverse_edge(
+        &self,
+        trajectory: (&Vertex, &Edge, &Vertex),
+        state: &mut Vec<StateVar>,
+        state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        for model in self.models.iter() {
+            model.traverse_edge(trajectory, state, state_model)?;
+        }
+        Ok(())
+    }
+
+    fn estimate_traversal(
+        &self,
+        od: (&Vertex, &Vertex),
+        state: &mut Vec<StateVar>,
+        state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        for model in self.models.iter() {
+            model.estimate_traversal(od, state, state_model)?;
+        }
+        Ok(())
+    }
+
+    /// static only if every model is static
+    fn is_static(&self) -> bool {
+        self.models.iter().all(|m| m.is_static())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::traversal::default::{
+        constant_traversal_model::ConstantTraversalModel,
+        distance_traversal_model::DistanceTraversalModel,
+        distance_traversal_service::DistanceTraversalService,
+        grade_distance_traversal_model::GradeDistanceTraversalModel,
+    };
+    use crate::model::unit::{as_f64::AsF64, DistanceUnit, Grade};
+
+    #[test]
+    fn test_combined_features() {
+        let model = CombinedTraversalModel::new(vec![
+            Arc::new(DistanceTraversalModel::new(DistanceUnit::Kilometers)),
+            Arc::new(ConstantTraversalModel::new(2.0).unwrap()),
+        ])
+        .unwrap();
+        assert!(model.is_static());
+        let state_model = StateModel::empty().extend(model.state_features()).unwrap();
+        assert_eq!(state_model.len(), 2);
+
+        let mut state = state_model.initial_state().unwrap();
+        let (v0, v1) = (Vertex::new(0, 0.0, 0.0), Vertex::new(1, 0.0, 0.0));
+        let edge = Edge::new(0, 0, 1, 1500.0);
+        for _ in 0..2 {
+            model
+                .traverse_edge((&v0, &edge, &v1), &mut state, &state_model)
+                .unwrap();
+        }
+        let distance = state_model
+            .get_distance(&state, &String::from("distance"), &DistanceUnit::Kilometers)
+            .unwrap();
+        let edge_cost = state_model
+            .get_custom_f64(&state, &String::from(ConstantTraversalModel::EDGE_COST))
+            .unwrap();
+        // each model updates only its own feature
+        assert!((distance.as_f64() - 3.0).abs() < 1e-9);
+        assert_eq!(edge_cost, 4.0);
+
+        // the distance estimate is added, while the constant model estimates nothing
+        let mut estimate = state_model.initial_state().unwrap();
+        let far = Vertex::new(2, 0.01, 0.0);
+        model
+            .estimate_traversal((&v0, &far), &mut estimate, &state_model)
+            .unwrap();
+        let estimated_distance = state_model
+            .get_distance(
+                &estimate,
+                &String::from("distance"),
+                &DistanceUnit::Kilometers,
+            )
+            .unwrap();
+        assert!(estimated_distance.as_f64() > 1.0);
+    }
+
+    #[test]
+    fn test_shared_feature_fails() {
+        let grade_distance = GradeDistanceTraversalModel::new(
+            vec![Grade::ZERO].into_boxed_slice(),
+            1.0,
+            0.0,
+            DistanceUnit::Meters,
+        )
+        .unwrap();
+        // both models write the "distance" feature
+        let result = CombinedTraversalModel::new(vec![
+            Arc::new(DistanceTraversalModel::new(DistanceUnit::Meters)),
+            Arc::new(grade_distance),
+        ]);
+        assert!(matches!(result, Err(TraversalModelError::BuildError(_))));
+    }
+
+    #[test]
+    fn test_shared_feature_fails_on_load() {
+        let grade_distance = GradeDistanceTraversalModel::new(
+            vec![Grade::ZERO].into_boxed_slice(),
+            1.0,
+            0.0,
+            DistanceUnit::Meters,
+        )
+        .unwrap();
+        let result = CombinedTraversalService::new(vec![
+            Arc::new(DistanceTraversalService {
+                distance_unit: DistanceUnit::Meters,
+            }),
+            Arc::new(grade_distance),
+        ]);
+        assert!(matches!(result, Err(TraversalModelError::BuildError(_))));
+    }
+}
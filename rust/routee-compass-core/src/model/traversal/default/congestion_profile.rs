@@ -0,0 +1,163 @@
+use crate::model::road_network::edge_id::EdgeId;
+use crate::model::traversal::traversal_model_error::TraversalModelError;
+use crate::util::linear_interp::LinearInterp1D;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// time-of-day speed factors by road class, which scale the speed of each edge
+/// for the departure time of a query, such as to slow down arterials during the
+/// morning peak.
+///
+/// each profile maps hours since midnight to a speed factor, interpolated linearly
+/// between sample points. edges of a road class without a profile keep their speed.
+/// the factors are selected once by the departure time and applied to the whole
+/// route, so the time spent traveling does not move a route into another hour.
+pub struct CongestionProfile {
+    road_classes: Arc<Box<[u8]>>,
+    class_profiles: HashMap<u8, LinearInterp1D>,
+}
+
+impl CongestionProfile {
+    pub const HOURS_PER_DAY: usize = 24;
+
+    /// builds the profile from the road class of each edge, ordered by edge id, and
+    /// the time-of-day profile of each congested road class. it is an error if a
+    /// profile has a factor of zero or less, since the speed of an edge must be positive.
+    pub fn new(
+        road_classes: Box<[u8]>,
+        class_profiles: HashMap<u8, LinearInterp1D>,
+    ) -> Result<CongestionProfile, TraversalModelError> {
+        for (road_class, profile) in class_profiles.iter() {
+            if let Some(factor) = profile.y.iter().find(|f| **f <= 0.0) {
+                return Err(TraversalModelError::BuildError(format!(
+                    "congestion profile for road class {} must have positive speed factors, found {}",
+                    road_class, factor
+                )));
+            }
+        }
+        Ok(CongestionProfile {
+            road_classes: Arc::new(road_classes),
+            class_profiles,
+        })
+    }
+
+    /// builds a time-of-day profile from 24 hourly speed factors, where the factor
+    /// at index `i` applies at hour `i`. the last hour interpolates back to the
+    /// factor of midnight.
+    pub fn hourly(factors: &[f64]) -> Result<LinearInterp1D, String> {
+        if factors.len() != Self::HOURS_PER_DAY {
+            return Err(format!(
+                "hourly profile must have {} entries, found {}",
+                Self::HOURS_PER_DAY,
+                factors.len()
+            ));
+        }
+        let x = (0..=Self::HOURS_PER_DAY).map(|h| h as f64).collect();
+        let y = factors.iter().chain(factors.first()).copied().collect();
+        LinearInterp1D::new(x, y)
+    }
+
+    /// selects the speed factor of each road class for a departure time.
+    ///
+    /// # Arguments
+    ///
+    /// * `departure_hour` - hours since midnight, within [0, 24)
+    pub fn factors_at(
+        &self,
+        departure_hour: f64,
+    ) -> Result<CongestionFactors, TraversalModelError> {
+        if !(0.0..Self::HOURS_PER_DAY as f64).contains(&departure_hour) {
+            return Err(TraversalModelError::BuildError(format!(
+                "departure hour must be within [0, {}), found {}",
+                Self::HOURS_PER_DAY,
+                departure_hour
+            )));
+        }
+        let class_factors = self
+            .class_profiles
+            .iter()
+            .map(|(road_class, profile)| (*road_class, profile.interpolate(departure_hour)))
+            .collect::<HashMap<_, _>>();
+        let max_factor = class_factors.values().copied().fold(1.0, f64::max);
+        Ok(CongestionFactors {
+            road_classes: self.road_classes.clone(),
+            class_factors,
+            max_factor,
+        })
+    }
+}
+
+/// the speed factors of a congestion profile at the departure time of a query
+pub struct CongestionFactors {
+    road_classes: Arc<Box<[u8]>>,
+    class_factors: HashMap<u8, f64>,
+    /// the largest factor of any edge, which scales the maximum speed of an estimate
+    pub max_factor: f64,
+}
+
+impl CongestionFactors {
+    /// the speed factor of an edge, which is 1.0 if its road class has no profile
+    pub fn get(&self, edge_id: EdgeId) -> Result<f64, TraversalModelError> {
+        let road_class = self.road_classes.get(edge_id.as_usize()).ok_or_else(|| {
+            TraversalModelError::MissingIdInTabularCostFunction(
+                format!("{}", edge_id),
+                String::from("EdgeId"),
+                String::from("road class table"),
+            )
+        })?;
+        Ok(self.class_factors.get(road_class).copied().unwrap_or(1.0))
+    }
+}
+
+/// reads a departure time from a query value, either as hours since midnight,
+/// such as `17.5`, or as an "HH:MM" string, such as "17:30".
+pub fn parse_departure_hour(value: &serde_json::Value) -> Result<f64, TraversalModelError> {
+    let invalid = || {
+        TraversalModelError::BuildError(format!(
+            "departure_time must be hours since midnight or an \"HH:MM\" string, found {}",
+            value
+        ))
+    };
+    match value {
+        serde_json::Value::Number(n) => n.as_f64().ok_or_else(invalid),
+        serde_json::Value::String(s) => {
+            let (hours, minutes) = s.trim().split_once(':').ok_or_else(invalid)?;
+            let hours = hours.parse::<u32>().map_err(|_| invalid())?;
+            let minutes = minutes.parse::<u32>().map_err(|_| invalid())?;
+            if minutes >= 60 {
+                return Err(invalid());
+            }
+            Ok(hours as f64 + minutes as f64 / 60.0)
+        }
+        _ => Err(invalid()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_hourly_profile_interpolates() {
+        let mut factors = vec![1.0; 24];
+        factors[8] = 0.5;
+        factors[23] = 0.8;
+        let profile = CongestionProfile::hourly(&factors).unwrap();
+        assert_eq!(profile.interpolate(8.0), 0.5);
+        assert_eq!(profile.interpolate(7.5), 0.75);
+        // the last hour wraps around to midnight
+        assert!((profile.interpolate(23.5) - 0.9).abs() < 1e-9);
+        assert!(CongestionProfile::hourly(&factors[0..12]).is_err());
+
+        assert_eq!(
+            parse_departure_hour(&serde_json::json!("07:30")).unwrap(),
+            7.5
+        );
+        assert_eq!(
+            parse_departure_hour(&serde_json::json!(17.25)).unwrap(),
+            17.25
+        );
+        assert!(parse_departure_hour(&serde_json::json!("7:75")).is_err());
+        assert!(parse_departure_hour(&serde_json::json!(true)).is_err());
+    }
+}
@@ -0,0 +1,212 @@
+use crate::model::property::{edge::Edge, vertex::Vertex};
+use crate::model::state::custom_feature_format::CustomFeatureFormat;
+use crate::model::state::state_feature::StateFeature;
+use crate::model::state::state_model::StateModel;
+use crate::model::traversal::state::state_variable::StateVar;
+use crate::model::traversal::traversal_model::TraversalModel;
+use crate::model::traversal::traversal_model_builder::TraversalModelBuilder;
+use crate::model::traversal::traversal_model_error::TraversalModelError;
+use crate::model::traversal::traversal_model_service::TraversalModelService;
+use ordered_float::OrderedFloat;
+use std::sync::Arc;
+
+/// A trivial traversal model that adds the same fixed cost for every edge traversed,
+/// independent of edge attributes, and makes no other changes to the search state.
+/// as it requires no input files, it is useful for testing plugins and search
+/// algorithms, and serves as a minimal reference implementation of [`TraversalModel`].
+///
+/// the accumulated cost is tracked in the "edge_cost" state feature, which should be
+/// given a weight in the cost model in order to have an effect on the search.
+///
+/// # Example
+///
+/// ### Deserialization
+///
+/// ```toml
+/// [traversal]
+/// type = "constant"
+/// cost = 1.0
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct ConstantTraversalModel {
+    pub cost: f64,
+}
+
+impl ConstantTraversalModel {
+    pub const EDGE_COST: &'static str = "edge_cost";
+
+    pub fn new(cost: f64) -> Result<ConstantTraversalModel, TraversalModelError> {
+        if !cost.is_finite() || cost < 0.0 {
+            return Err(TraversalModelError::BuildError(format!(
+                "constant traversal cost must be a non-negative number, found {}",
+                cost
+            )));
+        }
+        Ok(ConstantTraversalModel { cost })
+    }
+}
+
+impl TraversalModel for ConstantTraversalModel {
+    /// tracks the accumulated edge cost
+    fn state_features(&self) -> Vec<(String, StateFeature)> {
+        vec![(
+            String::from(Self::EDGE_COST),
+            StateFeature::Custom {
+                r#type: String::from(Self::EDGE_COST),
+                unit: String::from("cost"),
+                format: CustomFeatureFormat::FloatingPoint {
+                    initial: OrderedFloat(0.0),
+                },
+                output_alias: None,
+            },
+        )]
+    }
+
+    fn traverse_edge(
+        &self,
+        _trajectory: (&Vertex, &Edge, &Vertex),
+        state: &mut Vec<StateVar>,
+        state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        let name = String::from(Self::EDGE_COST);
+        let prev = state_model.get_custom_f64(state, &name)?;
+        state_model.set_custom_f64(state, &name, &(prev + self.cost))?;
+        Ok(())
+    }
+
+    /// the number of edges remaining is unknown, so no cost is estimated,
+    /// which keeps the estimate admissible for a-star search.
+    fn estimate_traversal(
+        &self,
+        _od: (&Vertex, &Vertex),
+        _state: &mut Vec<StateVar>,
+        _state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        Ok(())
+    }
+
+    /// every edge adds the same cost
+    fn is_static(&self) -> bool {
+        true
+    }
+}
+
+impl TraversalModelService for ConstantTraversalModel {
+    fn build(
+        &self,
+        _query: &serde_json::Value,
+    ) -> Result<Arc<dyn TraversalModel>, TraversalModelError> {
+        let model: Arc<dyn TraversalModel> = Arc::new(*self);
+        Ok(model)
+    }
+}
+
+/// builds a [`ConstantTraversalModel`] from the "cost" key of the traversal
+/// configuration, which defaults to 1.0 when not provided.
+pub struct ConstantTraversalBuilder {}
+
+impl TraversalModelBuilder for ConstantTraversalBuilder {
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+    ) -> Result<Arc<dyn TraversalModelService>, TraversalModelError> {
+        let cost = match parameters.get("cost") {
+            None => 1.0,
+            Some(value) => value.as_f64().ok_or_else(|| {
+                TraversalModelError::BuildError(format!(
+                    "expected 'cost' of constant traversal model to be a number, found {}",
+                    value
+                ))
+            })?,
+        };
+        let service: Arc<dyn TraversalModelService> = Arc::new(ConstantTraversalModel::new(cost)?);
+        Ok(service)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm::search::a_star::a_star_algorithm::run_a_star;
+    use crate::algorithm::search::backtrack::vertex_oriented_route;
+    use crate::algorithm::search::direction::Direction;
+    use crate::algorithm::search::search_instance::SearchInstance;
+    use crate::model::cost::cost_aggregation::CostAggregation;
+    use crate::model::cost::cost_model::CostModel;
+    use crate::model::cost::vehicle::vehicle_cost_rate::VehicleCostRate;
+    use crate::model::road_network::{edge_id::EdgeId, graph::Graph, vertex_id::VertexId};
+    use crate::util::test_util::{distance_search_instance, graph_from_edges};
+    use std::collections::HashMap;
+
+    /// a line of three short edges from (0) to (3) along with a single long edge
+    /// (0) -[3]-> (3), which is the fewest-edge route despite its distance.
+    fn build_mock_graph() -> Graph {
+        let vertices = vec![
+            Vertex::new(0, 0.0, 0.0),
+            Vertex::new(1, 0.0, 0.0),
+            Vertex::new(2, 0.0, 0.0),
+            Vertex::new(3, 0.0, 0.0),
+        ];
+        let edges = vec![
+            Edge::new(0, 0, 1, 1.0),
+            Edge::new(1, 1, 2, 1.0),
+            Edge::new(2, 2, 3, 1.0),
+            Edge::new(3, 0, 3, 100.0),
+        ];
+        graph_from_edges(vertices, edges)
+    }
+
+    #[test]
+    fn test_search_with_constant_cost() {
+        let service = ConstantTraversalBuilder {}
+            .build(&serde_json::json!({"type": "constant", "cost": 2.5}))
+            .unwrap();
+        let traversal_model = service.build(&serde_json::json!({})).unwrap();
+        let state_model = Arc::new(
+            StateModel::empty()
+                .extend(traversal_model.state_features())
+                .unwrap(),
+        );
+        let cost_model = CostModel::new(
+            Arc::new(HashMap::from([(
+                String::from(ConstantTraversalModel::EDGE_COST),
+                1.0,
+            )])),
+            Arc::new(HashMap::from([(
+                String::from(ConstantTraversalModel::EDGE_COST),
+                VehicleCostRate::Raw,
+            )])),
+            Arc::new(HashMap::new()),
+            CostAggregation::Sum,
+            Arc::new(vec![]),
+            state_model.clone(),
+        )
+        .unwrap();
+        let si = SearchInstance {
+            state_model: state_model.clone(),
+            traversal_model,
+            cost_model,
+            ..distance_search_instance(build_mock_graph())
+        };
+
+        let (o, d) = (VertexId(0), VertexId(3));
+        let result = run_a_star(o, Some(d), &Direction::Forward, None, &si).unwrap();
+        let route = vertex_oriented_route(o, d, &result.tree).unwrap();
+        let route_edges: Vec<EdgeId> = route.iter().map(|r| r.edge_id).collect();
+        assert_eq!(route_edges, vec![EdgeId(3)]);
+
+        let edge_cost = state_model
+            .get_custom_f64(
+                &route[0].result_state,
+                &String::from(ConstantTraversalModel::EDGE_COST),
+            )
+            .unwrap();
+        assert_eq!(edge_cost, 2.5);
+    }
+
+    #[test]
+    fn test_negative_cost_fails() {
+        let result = ConstantTraversalBuilder {}.build(&serde_json::json!({"cost": -1.0}));
+        assert!(matches!(result, Err(TraversalModelError::BuildError(_))));
+    }
+}
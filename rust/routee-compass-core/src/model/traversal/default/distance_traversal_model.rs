@@ -1,8 +1,8 @@
 use crate::model::state::state_feature::StateFeature;
 use crate::model::state::state_model::StateModel;
 use crate::model::traversal::traversal_model::TraversalModel;
-use crate::model::unit::DistanceUnit;
 use crate::model::unit::BASE_DISTANCE_UNIT;
+use crate::model::unit::{Distance, DistanceUnit};
 use crate::model::{
     property::{edge::Edge, vertex::Vertex},
     traversal::{state::state_variable::StateVar, traversal_model_error::TraversalModelError},
@@ -59,8 +59,20 @@ impl TraversalModel for DistanceTraversalModel {
         Ok(())
     }
 
-    /// no additional state features are needed
+    /// the distance feature written by this model, in the model's distance unit
     fn state_features(&self) -> Vec<(String, StateFeature)> {
-        vec![]
+        vec![(
+            String::from(Self::DISTANCE),
+            StateFeature::Distance {
+                distance_unit: self.distance_unit,
+                initial: Distance::ZERO,
+                output_alias: None,
+            },
+        )]
+    }
+
+    /// edge distances do not depend on the search state
+    fn is_static(&self) -> bool {
+        true
     }
 }
@@ -0,0 +1,323 @@
+use crate::model::property::{edge::Edge, vertex::Vertex};
+use crate::model::state::state_feature::StateFeature;
+use crate::model::state::state_model::StateModel;
+use crate::model::traversal::state::state_variable::StateVar;
+use crate::model::traversal::traversal_model::TraversalModel;
+use crate::model::traversal::traversal_model_error::TraversalModelError;
+use crate::model::traversal::traversal_model_service::TraversalModelService;
+use crate::model::unit::{
+    as_f64::AsF64, Distance, DistanceUnit, Grade, GradeUnit, BASE_DISTANCE_UNIT,
+};
+use crate::util::fs::{read_decoders, read_utils};
+use crate::util::geo::haversine;
+use std::path::Path;
+use std::sync::Arc;
+
+/// A traversal model for cyclists and pedestrians that penalizes uphill grades by
+/// lengthening the distance of each edge by a factor of its grade. an edge with a
+/// positive grade `g` has its distance multiplied by `1 + uphill_factor * g`, and an
+/// edge with a negative grade by `1 + downhill_factor * g`, which is a bonus for a
+/// positive downhill factor and no change for the default of zero. grades are read
+/// from a table with a row for each edge, and the factors apply to grades in the
+/// configured grade unit, which defaults to the grade unit of the table.
+///
+/// the edge distance is tracked in the "distance" state feature and the penalized
+/// distance in the "grade_distance" state feature, which should be given a weight in
+/// the cost model in order to have an effect on the search. a flat edge adds its
+/// distance to both.
+///
+/// # Example
+///
+/// ### Deserialization
+///
+/// ```toml
+/// [traversal]
+/// type = "grade_distance"
+/// grade_table_input_file = "edges-grade-enumerated.txt.gz"
+/// grade_table_grade_unit = "decimal"
+/// grade_unit = "percent"
+/// uphill_factor = 0.1
+/// downhill_factor = 0.02
+/// ```
+#[derive(Clone)]
+pub struct GradeDistanceTraversalModel {
+    grade_table: Arc<Box<[Grade]>>,
+    uphill_factor: f64,
+    downhill_factor: f64,
+    distance_unit: DistanceUnit,
+    /// the smallest factor of any edge, which scales the estimate to keep it admissible
+    min_factor: f64,
+}
+
+impl GradeDistanceTraversalModel {
+    pub const DISTANCE: &'static str = "distance";
+    pub const GRADE_DISTANCE: &'static str = "grade_distance";
+
+    /// builds the model from the grade of each edge, confirming that the factors are
+    /// non-negative and that no edge has a distance factor of zero or less.
+    pub fn new(
+        grade_table: Box<[Grade]>,
+        uphill_factor: f64,
+        downhill_factor: f64,
+        distance_unit: DistanceUnit,
+    ) -> Result<GradeDistanceTraversalModel, TraversalModelError> {
+        for (name, factor) in [
+            ("uphill_factor", uphill_factor),
+            ("downhill_factor", downhill_factor),
+        ] {
+            if !factor.is_finite() || factor < 0.0 {
+                return Err(TraversalModelError::BuildError(format!(
+                    "grade distance {} must be a non-negative number, found {}",
+                    name, factor
+                )));
+            }
+        }
+        let mut model = GradeDistanceTraversalModel {
+            grade_table: Arc::new(grade_table),
+            uphill_factor,
+            downhill_factor,
+            distance_unit,
+            min_factor: 1.0,
+        };
+        for grade in model.grade_table.iter() {
+            let factor = model.factor(*grade);
+            if factor <= 0.0 {
+                return Err(TraversalModelError::BuildError(format!(
+                    "grade distance downhill_factor {} leaves no distance for an edge with grade {}",
+                    downhill_factor, grade
+                )));
+            }
+            model.min_factor = model.min_factor.min(factor);
+        }
+        Ok(model)
+    }
+
+    /// reads the grade of each edge from a file with a row for each edge id, converting
+    /// grades from the grade unit of the file to the grade unit of the factors.
+    pub fn from_file<P: AsRef<Path>>(
+        grade_table_path: P,
+        grade_table_grade_unit: GradeUnit,
+        grade_unit: GradeUnit,
+        uphill_factor: f64,
+        downhill_factor: f64,
+        distance_unit: DistanceUnit,
+    ) -> Result<GradeDistanceTraversalModel, TraversalModelError> {
+        let grade_table: Box<[Grade]> =
+            read_utils::read_raw_file(grade_table_path.as_ref(), read_decoders::default, None)
+                .map_err(|e| {
+                    TraversalModelError::FileReadError(
+                        grade_table_path.as_ref().to_path_buf(),
+                        e.to_string(),
+                    )
+                })?
+                .iter()
+                .map(|grade| grade_table_grade_unit.convert(grade, &grade_unit))
+                .collect();
+        GradeDistanceTraversalModel::new(grade_table, uphill_factor, downhill_factor, distance_unit)
+    }
+
+    /// the multiplier on the distance of an edge with this grade
+    fn factor(&self, grade: Grade) -> f64 {
+        let g = grade.as_f64();
+        if g > 0.0 {
+            1.0 + self.uphill_factor * g
+        } else {
+            1.0 + self.downhill_factor * g
+        }
+    }
+}
+
+impl TraversalModel for GradeDistanceTraversalModel {
+    /// tracks the edge distance and the grade-penalized distance, in the model's distance unit
+    fn state_features(&self) -> Vec<(String, StateFeature)> {
+        [Self::DISTANCE, Self::GRADE_DISTANCE]
+            .iter()
+            .map(|name| {
+                (
+                    String::from(*name),
+                    StateFeature::Distance {
+                        distance_unit: self.distance_unit,
+                        initial: Distance::ZERO,
+                        output_alias: None,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    fn traverse_edge(
+        &self,
+        trajectory: (&Vertex, &Edge, &Vertex),
+        state: &mut Vec<StateVar>,
+        state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        let (_, edge, _) = trajectory;
+        let grade = self
+            .grade_table
+            .get(edge.edge_id.as_usize())
+            .ok_or_else(|| {
+                TraversalModelError::MissingIdInTabularCostFunction(
+                    format!("{}", edge.edge_id),
+                    String::from("EdgeId"),
+                    String::from("grade table"),
+                )
+            })?;
+        let distance = BASE_DISTANCE_UNIT.convert(&edge.distance, &self.distance_unit);
+        let grade_distance = distance * self.factor(*grade);
+        state_model.add_distance(
+            state,
+            &Self::DISTANCE.into(),
+            &distance,
+            &self.distance_unit,
+        )?;
+        state_model.add_distance(
+            state,
+            &Self::GRADE_DISTANCE.into(),
+            &grade_distance,
+            &self.distance_unit,
+        )?;
+        Ok(())
+    }
+
+    /// estimates with the straight-line distance, scaled by the smallest factor of any
+    /// edge so that a downhill bonus never leads to an overestimate.
+    fn estimate_traversal(
+        &self,
+        od: (&Vertex, &Vertex),
+        state: &mut Vec<StateVar>,
+        state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        let (src, dst) = od;
+        let distance =
+            haversine::coord_distance(&src.coordinate, &dst.coordinate, self.distance_unit)
+                .map_err(TraversalModelError::NumericError)?;
+        let grade_distance = distance * self.min_factor;
+        state_model.add_distance(
+            state,
+            &Self::DISTANCE.into(),
+            &distance,
+            &self.distance_unit,
+        )?;
+        state_model.add_distance(
+            state,
+            &Self::GRADE_DISTANCE.into(),
+            &grade_distance,
+            &self.distance_unit,
+        )?;
+        Ok(())
+    }
+
+    /// edge grades and distances do not depend on the search state
+    fn is_static(&self) -> bool {
+        true
+    }
+}
+
+impl TraversalModelService for GradeDistanceTraversalModel {
+    fn build(
+        &self,
+        _query: &serde_json::Value,
+    ) -> Result<Arc<dyn TraversalModel>, TraversalModelError> {
+        let model: Arc<dyn TraversalModel> = Arc::new(self.clone());
+        Ok(model)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// traverses a 100 meter edge with the grade of the given edge id, returning
+    /// the distance and grade distance in meters.
+    fn traverse(model: &GradeDistanceTraversalModel, edge_id: usize) -> (f64, f64) {
+        let state_model = StateModel::empty().extend(model.state_features()).unwrap();
+        let mut state = state_model.initial_state().unwrap();
+        let (v0, v1) = (Vertex::new(0, 0.0, 0.0), Vertex::new(1, 0.0, 0.0));
+        let edge = Edge::new(edge_id, 0, 1, 100.0);
+        model
+            .traverse_edge((&v0, &edge, &v1), &mut state, &state_model)
+            .unwrap();
+        let get = |name: &str| {
+            state_model
+                .get_distance(&state, &String::from(name), &DistanceUnit::Meters)
+                .unwrap()
+                .as_f64()
+        };
+        (
+            get(GradeDistanceTraversalModel::DISTANCE),
+            get(GradeDistanceTraversalModel::GRADE_DISTANCE),
+        )
+    }
+
+    /// estimates the distance and grade distance in meters between two vertices
+    /// 0.001 degrees of longitude apart
+    fn estimate(model: &GradeDistanceTraversalModel) -> (f64, f64) {
+        let state_model = StateModel::empty().extend(model.state_features()).unwrap();
+        let mut state = state_model.initial_state().unwrap();
+        let (v0, v1) = (Vertex::new(0, 0.0, 0.0), Vertex::new(1, 0.001, 0.0));
+        model
+            .estimate_traversal((&v0, &v1), &mut state, &state_model)
+            .unwrap();
+        let get = |name: &str| {
+            state_model
+                .get_distance(&state, &String::from(name), &DistanceUnit::Meters)
+                .unwrap()
+                .as_f64()
+        };
+        (
+            get(GradeDistanceTraversalModel::DISTANCE),
+            get(GradeDistanceTraversalModel::GRADE_DISTANCE),
+        )
+    }
+
+    #[test]
+    fn test_grade_distance_factors() {
+        // flat, 5% uphill and 5% downhill edges, with grades in decimal
+        let grades = vec![Grade::ZERO, Grade::new(0.05), Grade::new(-0.05)];
+        let model = GradeDistanceTraversalModel::new(
+            grades.clone().into_boxed_slice(),
+            10.0,
+            0.0,
+            DistanceUnit::Meters,
+        )
+        .unwrap();
+
+        // a flat edge is its plain distance
+        assert_eq!(traverse(&model, 0), (100.0, 100.0));
+        let (distance, uphill) = traverse(&model, 1);
+        assert_eq!(distance, 100.0);
+        assert!((uphill - 150.0).abs() < 1e-9, "{}", uphill);
+        // no downhill bonus by default
+        assert_eq!(traverse(&model, 2), (100.0, 100.0));
+        // without a bonus, the estimate is the plain distance
+        let (distance, grade_distance) = estimate(&model);
+        assert!(distance > 0.0);
+        assert!((grade_distance - distance).abs() < 1e-9);
+
+        // with a downhill bonus, the estimate is scaled by the smallest factor
+        let model = GradeDistanceTraversalModel::new(
+            grades.into_boxed_slice(),
+            10.0,
+            2.0,
+            DistanceUnit::Meters,
+        )
+        .unwrap();
+        assert_eq!(traverse(&model, 0), (100.0, 100.0));
+        let (_, downhill) = traverse(&model, 2);
+        assert!((downhill - 90.0).abs() < 1e-9, "{}", downhill);
+        let (distance, grade_distance) = estimate(&model);
+        assert!((grade_distance - 0.9 * distance).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_invalid_factors() {
+        let grades = vec![Grade::new(-0.5)].into_boxed_slice();
+        let negative =
+            GradeDistanceTraversalModel::new(grades.clone(), -1.0, 0.0, DistanceUnit::Meters);
+        assert!(negative.is_err());
+        // a bonus of 3 per unit of grade would give a -50% grade edge negative distance
+        let excessive_bonus =
+            GradeDistanceTraversalModel::new(grades, 1.0, 3.0, DistanceUnit::Meters);
+        assert!(excessive_bonus.is_err());
+    }
+}
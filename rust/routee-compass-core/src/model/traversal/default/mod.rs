@@ -1,5 +1,10 @@
+pub mod combined_traversal_model;
+pub mod congestion_profile;
+pub mod constant_traversal_model;
 pub mod distance_traversal_model;
 pub mod distance_traversal_service;
+pub mod grade_distance_traversal_model;
 pub mod speed_traversal_engine;
 pub mod speed_traversal_model;
 pub mod speed_traversal_service;
+pub mod toll_traversal_model;
@@ -1,10 +1,12 @@
 use crate::model::unit::DistanceUnit;
 use crate::model::unit::{SpeedUnit, TimeUnit, BASE_DISTANCE_UNIT, BASE_TIME_UNIT};
-use crate::util::fs::read_decoders;
+use crate::util::fs::{fs_utils, read_decoders};
+use crate::util::linear_interp::LinearInterp1D;
 use crate::{
     model::{traversal::traversal_model_error::TraversalModelError, unit::Speed},
     util::fs::read_utils,
 };
+use std::collections::HashMap;
 use std::path::Path;
 
 pub struct SpeedTraversalEngine {
@@ -16,21 +18,53 @@ pub struct SpeedTraversalEngine {
 }
 
 impl SpeedTraversalEngine {
+    /// column names expected in a Parquet speed table
+    pub const EDGE_ID_COLUMN: &'static str = "edge_id";
+    pub const SPEED_COLUMN: &'static str = "speed";
+
+    /// loads a speed table. files with a ".parquet" extension are read as
+    /// Parquet with `edge_id` and `speed` columns (requires the "parquet" feature).
+    /// otherwise, the file has one speed per line, ordered by edge id, optionally
+    /// preceded by a header line naming the speed unit of the file, such as
+    /// `miles_per_hour`.
+    ///
+    /// the speed unit of the engine is the configured speed unit if provided,
+    /// otherwise the unit declared in the file header. when the header declares
+    /// a different unit than the configured one, speeds are converted to the
+    /// configured unit. it is an error if neither provides a speed unit.
     pub fn new<P: AsRef<Path>>(
         speed_table_path: &P,
-        speed_unit: SpeedUnit,
+        speed_unit_opt: Option<SpeedUnit>,
         distance_unit_opt: Option<DistanceUnit>,
         time_unit_opt: Option<TimeUnit>,
     ) -> Result<SpeedTraversalEngine, TraversalModelError> {
-        let speed_table: Box<[Speed]> =
-            read_utils::read_raw_file(speed_table_path, read_decoders::default, None).map_err(
-                |e| {
-                    TraversalModelError::FileReadError(
-                        speed_table_path.as_ref().to_path_buf(),
-                        e.to_string(),
-                    )
-                },
-            )?;
+        let read_result = if fs_utils::is_parquet(speed_table_path) {
+            read_parquet_speed_table(speed_table_path).map(|speeds| (None, speeds))
+        } else {
+            read_speed_file(speed_table_path)
+        };
+        let (file_speed_unit, file_speed_table) = read_result.map_err(|e| {
+            TraversalModelError::FileReadError(
+                speed_table_path.as_ref().to_path_buf(),
+                e.to_string(),
+            )
+        })?;
+        let (speed_unit, speed_table) = match (speed_unit_opt, file_speed_unit) {
+            (None, None) => {
+                return Err(TraversalModelError::BuildError(format!(
+                    "no speed unit provided for speed table {}, must be configured or declared in a header line",
+                    speed_table_path.as_ref().to_string_lossy()
+                )))
+            }
+            (Some(unit), None) | (None, Some(unit)) => (unit, file_speed_table),
+            (Some(unit), Some(file_unit)) => {
+                let converted = file_speed_table
+                    .iter()
+                    .map(|s| file_unit.convert(s, &unit))
+                    .collect::<Box<[Speed]>>();
+                (unit, converted)
+            }
+        };
         let max_speed = get_max_speed(&speed_table)?;
         let time_unit = time_unit_opt.unwrap_or(BASE_TIME_UNIT);
         let distance_unit = distance_unit_opt.unwrap_or(BASE_DISTANCE_UNIT);
@@ -43,6 +77,162 @@ impl SpeedTraversalEngine {
         };
         Ok(model)
     }
+
+    /// builds a speed table by interpolating a speed for each edge from its road
+    /// class and an edge attribute, such as a congestion index or lane count.
+    ///
+    /// both files have one row per edge, ordered by edge id. each road class is
+    /// backed by a [`LinearInterp1D`] from attribute values to speeds in
+    /// `speed_unit`, so that edges of the same class may get different speeds.
+    /// it is an error if an edge has a road class without an interpolation.
+    pub fn new_interpolated<P: AsRef<Path>>(
+        road_class_path: &P,
+        attribute_path: &P,
+        class_speeds: &HashMap<u8, LinearInterp1D>,
+        speed_unit: SpeedUnit,
+        distance_unit_opt: Option<DistanceUnit>,
+        time_unit_opt: Option<TimeUnit>,
+    ) -> Result<SpeedTraversalEngine, TraversalModelError> {
+        let file_error = |path: &P, e: std::io::Error| {
+            TraversalModelError::FileReadError(path.as_ref().to_path_buf(), e.to_string())
+        };
+        let road_classes = read_road_classes(road_class_path)?;
+        let attributes: Box<[f64]> =
+            read_utils::read_raw_file(attribute_path, read_decoders::f64, None)
+                .map_err(|e| file_error(attribute_path, e))?;
+        if road_classes.len() != attributes.len() {
+            return Err(TraversalModelError::BuildError(format!(
+                "road class file has {} rows but attribute file has {} rows",
+                road_classes.len(),
+                attributes.len()
+            )));
+        }
+        let speed_table = road_classes
+            .iter()
+            .zip(attributes.iter())
+            .enumerate()
+            .map(|(edge_id, (road_class, attribute))| {
+                let interp = class_speeds.get(road_class).ok_or_else(|| {
+                    TraversalModelError::BuildError(format!(
+                        "edge {} has road class {} which has no configured speeds",
+                        edge_id, road_class
+                    ))
+                })?;
+                Ok(Speed::new(interp.interpolate(*attribute)))
+            })
+            .collect::<Result<Box<[Speed]>, TraversalModelError>>()?;
+        let max_speed = get_max_speed(&speed_table)?;
+        Ok(SpeedTraversalEngine {
+            speed_table,
+            distance_unit: distance_unit_opt.unwrap_or(BASE_DISTANCE_UNIT),
+            time_unit: time_unit_opt.unwrap_or(BASE_TIME_UNIT),
+            speed_unit,
+            max_speed,
+        })
+    }
+
+    /// clamps the speed of each edge to the speed cap of its road class, such as
+    /// to keep free-flow speeds within posted limits. caps are in the speed unit of
+    /// this engine, and edges of a road class without a cap keep their speed.
+    ///
+    /// # Arguments
+    ///
+    /// * `road_classes` - the road class of each edge, ordered by edge id
+    /// * `speed_caps` - the maximum speed of each capped road class
+    pub fn with_speed_caps(
+        mut self,
+        road_classes: &[u8],
+        speed_caps: &HashMap<u8, Speed>,
+    ) -> Result<SpeedTraversalEngine, TraversalModelError> {
+        if road_classes.len() != self.speed_table.len() {
+            return Err(TraversalModelError::BuildError(format!(
+                "road class file has {} rows but speed table has {} rows",
+                road_classes.len(),
+                self.speed_table.len()
+            )));
+        }
+        if let Some((road_class, cap)) = speed_caps.iter().find(|(_, cap)| **cap <= Speed::ZERO) {
+            return Err(TraversalModelError::BuildError(format!(
+                "speed cap for road class {} must be positive, found {}",
+                road_class, cap
+            )));
+        }
+        for (speed, road_class) in self.speed_table.iter_mut().zip(road_classes.iter()) {
+            if let Some(cap) = speed_caps.get(road_class) {
+                *speed = (*speed).min(*cap);
+            }
+        }
+        self.max_speed = get_max_speed(&self.speed_table)?;
+        Ok(self)
+    }
+}
+
+/// reads a file with the road class of each edge, one per line, ordered by edge id
+pub fn read_road_classes<P: AsRef<Path>>(
+    road_class_path: &P,
+) -> Result<Box<[u8]>, TraversalModelError> {
+    read_utils::read_raw_file(road_class_path, read_decoders::u8, None).map_err(|e| {
+        TraversalModelError::FileReadError(road_class_path.as_ref().to_path_buf(), e.to_string())
+    })
+}
+
+/// a row of a speed file, which is either a speed or the header line
+enum SpeedRow {
+    Header(SpeedUnit),
+    Speed(Speed),
+}
+
+/// reads a speed file with one speed per line. a first line that is not a number
+/// must name the speed unit of the file.
+fn read_speed_file<P: AsRef<Path>>(
+    path: &P,
+) -> Result<(Option<SpeedUnit>, Box<[Speed]>), std::io::Error> {
+    let decode = |idx: usize, row: String| -> Result<SpeedRow, std::io::Error> {
+        if idx == 0 {
+            if let Err(speed_error) = row.parse::<Speed>() {
+                let unit = row.trim().parse::<SpeedUnit>().map_err(|_| {
+                    let msg = format!(
+                        "first row '{}' is neither a speed ({}) nor a speed unit header",
+                        row, speed_error
+                    );
+                    std::io::Error::new(std::io::ErrorKind::InvalidData, msg)
+                })?;
+                return Ok(SpeedRow::Header(unit));
+            }
+        }
+        read_decoders::default(idx, row).map(SpeedRow::Speed)
+    };
+    let rows = read_utils::read_raw_file(path, decode, None)?;
+    let mut speed_unit = None;
+    let speeds = rows
+        .iter()
+        .filter_map(|row| match row {
+            SpeedRow::Header(unit) => {
+                speed_unit = Some(*unit);
+                None
+            }
+            SpeedRow::Speed(speed) => Some(*speed),
+        })
+        .collect();
+    Ok((speed_unit, speeds))
+}
+
+#[cfg(feature = "parquet")]
+fn read_parquet_speed_table<P: AsRef<Path>>(path: &P) -> Result<Box<[Speed]>, std::io::Error> {
+    let speeds = crate::util::fs::read_parquet::read_edge_values(
+        path,
+        SpeedTraversalEngine::EDGE_ID_COLUMN,
+        SpeedTraversalEngine::SPEED_COLUMN,
+    )?;
+    Ok(speeds.iter().map(|s| Speed::new(*s)).collect())
+}
+
+#[cfg(not(feature = "parquet"))]
+fn read_parquet_speed_table<P: AsRef<Path>>(_path: &P) -> Result<Box<[Speed]>, std::io::Error> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "reading Parquet speed tables requires the 'parquet' feature",
+    ))
 }
 
 pub fn get_max_speed(speed_table: &[Speed]) -> Result<Speed, TraversalModelError> {
@@ -64,3 +254,147 @@ pub fn get_max_speed(speed_table: &[Speed]) -> Result<Speed, TraversalModelError
         Ok(max_speed)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::unit::as_f64::AsF64;
+    use std::path::PathBuf;
+
+    fn test_file(filename: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join("model")
+            .join("traversal")
+            .join("default")
+            .join("test")
+            .join(filename)
+    }
+
+    #[test]
+    fn test_header_speed_units_are_normalized() {
+        // both files hold the same speeds, in miles and kilometers per hour
+        let build = |filename: &str, speed_unit: Option<SpeedUnit>| {
+            SpeedTraversalEngine::new(&test_file(filename), speed_unit, None, None).unwrap()
+        };
+        let configured = Some(SpeedUnit::KilometersPerHour);
+        let mph_engine = build("velocities_mph.txt", configured);
+        let kph_engine = build("velocities_kph.txt", configured);
+        assert_eq!(mph_engine.speed_table.len(), 3);
+        for (mph, kph) in mph_engine
+            .speed_table
+            .iter()
+            .zip(kph_engine.speed_table.iter())
+        {
+            assert!(
+                (mph.as_f64() - kph.as_f64()).abs() < 1e-9,
+                "{} != {}",
+                mph,
+                kph
+            );
+        }
+
+        // without a configured unit, the header unit is used as-is
+        let header_engine = build("velocities_mph.txt", None);
+        assert!(matches!(header_engine.speed_unit, SpeedUnit::MilesPerHour));
+        assert_eq!(header_engine.speed_table[0], Speed::new(25.0));
+    }
+
+    #[test]
+    fn test_missing_speed_unit() {
+        let result = SpeedTraversalEngine::new(&test_file("velocities.txt"), None, None, None);
+        assert!(matches!(result, Err(TraversalModelError::BuildError(_))));
+    }
+
+    #[test]
+    fn test_interpolated_speeds_by_road_class() {
+        // edges 0 and 1 are both class 1, with different attribute values
+        let class_speeds = HashMap::from([
+            (
+                1,
+                LinearInterp1D::from_points(&[(0.0, 20.0), (1.0, 60.0)]).unwrap(),
+            ),
+            (2, LinearInterp1D::from_points(&[(0.0, 100.0)]).unwrap()),
+        ]);
+        let engine = SpeedTraversalEngine::new_interpolated(
+            &test_file("road_classes.txt"),
+            &test_file("speed_attributes.txt"),
+            &class_speeds,
+            SpeedUnit::KilometersPerHour,
+            None,
+            None,
+        )
+        .unwrap();
+        let speeds = engine
+            .speed_table
+            .iter()
+            .map(|s| s.as_f64())
+            .collect::<Vec<_>>();
+        assert_eq!(speeds, vec![30.0, 50.0, 100.0]);
+        assert_eq!(engine.max_speed, Speed::new(100.0));
+
+        let missing_class = HashMap::from([(1, class_speeds[&1].clone())]);
+        let result = SpeedTraversalEngine::new_interpolated(
+            &test_file("road_classes.txt"),
+            &test_file("speed_attributes.txt"),
+            &missing_class,
+            SpeedUnit::KilometersPerHour,
+            None,
+            None,
+        );
+        assert!(matches!(result, Err(TraversalModelError::BuildError(_))));
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn test_parquet_speed_table_matches_csv() {
+        let build = |filename: &str| {
+            SpeedTraversalEngine::new(
+                &test_file(filename),
+                Some(SpeedUnit::KilometersPerHour),
+                None,
+                None,
+            )
+            .unwrap()
+        };
+        // the parquet rows are shuffled, and are placed by their edge_id column
+        let csv_engine = build("velocities.txt");
+        let parquet_engine = build("velocities.parquet");
+        assert_eq!(csv_engine.speed_table, parquet_engine.speed_table);
+        assert_eq!(csv_engine.max_speed, parquet_engine.max_speed);
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn test_parquet_schema_mismatch() {
+        let result = SpeedTraversalEngine::new(
+            &test_file("velocities_bad_schema.parquet"),
+            Some(SpeedUnit::KilometersPerHour),
+            None,
+            None,
+        );
+        match result {
+            Err(TraversalModelError::FileReadError(_, msg)) => {
+                assert!(msg.contains("expected columns [edge_id, speed]"), "{}", msg);
+                assert!(msg.contains("found [id, kph]"), "{}", msg);
+            }
+            Err(e) => panic!("expected file read error, found {}", e),
+            Ok(_) => panic!("expected schema mismatch error"),
+        }
+    }
+
+    #[cfg(not(feature = "parquet"))]
+    #[test]
+    fn test_parquet_requires_feature() {
+        let result = SpeedTraversalEngine::new(
+            &test_file("velocities.parquet"),
+            Some(SpeedUnit::KilometersPerHour),
+            None,
+            None,
+        );
+        assert!(matches!(
+            result,
+            Err(TraversalModelError::FileReadError(_, msg)) if msg.contains("'parquet' feature")
+        ));
+    }
+}
@@ -1,3 +1,4 @@
+use super::congestion_profile::CongestionFactors;
 use super::speed_traversal_engine::SpeedTraversalEngine;
 use crate::model::road_network::edge_id::EdgeId;
 use crate::model::state::state_feature::StateFeature;
@@ -14,11 +15,21 @@ use std::sync::Arc;
 
 pub struct SpeedTraversalModel {
     engine: Arc<SpeedTraversalEngine>,
+    congestion: Option<CongestionFactors>,
 }
 
 impl SpeedTraversalModel {
     pub fn new(engine: Arc<SpeedTraversalEngine>) -> SpeedTraversalModel {
-        SpeedTraversalModel { engine }
+        SpeedTraversalModel {
+            engine,
+            congestion: None,
+        }
+    }
+
+    /// scales the speed of each edge by its congestion factor at the departure time of a query
+    pub fn with_congestion(mut self, congestion: CongestionFactors) -> SpeedTraversalModel {
+        self.congestion = Some(congestion);
+        self
     }
     const DISTANCE: &'static str = "distance";
     const TIME: &'static str = "time";
@@ -33,7 +44,10 @@ impl TraversalModel for SpeedTraversalModel {
     ) -> Result<(), TraversalModelError> {
         let (_, edge, _) = trajectory;
         let distance = BASE_DISTANCE_UNIT.convert(&edge.distance, &self.engine.distance_unit);
-        let speed = get_speed(&self.engine.speed_table, edge.edge_id)?;
+        let mut speed = get_speed(&self.engine.speed_table, edge.edge_id)?;
+        if let Some(congestion) = &self.congestion {
+            speed = speed * congestion.get(edge.edge_id)?;
+        }
         let edge_time = Time::create(
             &speed,
             &self.engine.speed_unit,
@@ -72,8 +86,13 @@ impl TraversalModel for SpeedTraversalModel {
             return Ok(());
         }
 
+        // congestion factors above 1.0 raise the maximum speed, keeping the estimate admissible
+        let max_speed = match &self.congestion {
+            Some(congestion) => self.engine.max_speed * congestion.max_factor,
+            None => self.engine.max_speed,
+        };
         let estimated_time = Time::create(
-            &self.engine.max_speed,
+            &max_speed,
             &self.engine.speed_unit,
             &distance,
             &self.engine.distance_unit,
@@ -102,6 +121,7 @@ impl TraversalModel for SpeedTraversalModel {
                 StateFeature::Time {
                     time_unit: self.engine.time_unit,
                     initial: Time::ZERO,
+                    output_alias: None,
                 },
             ),
             (
@@ -109,10 +129,18 @@ impl TraversalModel for SpeedTraversalModel {
                 StateFeature::Distance {
                     distance_unit: self.engine.distance_unit,
                     initial: Distance::ZERO,
+                    output_alias: None,
                 },
             ),
         ]
     }
+
+    /// edge speeds are read from a fixed table and do not depend on the search state.
+    /// with congestion factors, speeds depend on the departure time of the query, so
+    /// edge costs may not be shared with queries that depart at other times.
+    fn is_static(&self) -> bool {
+        self.congestion.is_none()
+    }
 }
 
 /// look up a speed from the speed table
@@ -130,13 +158,14 @@ pub fn get_speed(speed_table: &[Speed], edge_id: EdgeId) -> Result<Speed, Traver
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::model::unit::{Distance, DistanceUnit, SpeedUnit, TimeUnit};
+    use crate::model::unit::{as_f64::AsF64, Distance, DistanceUnit, SpeedUnit, TimeUnit};
     use crate::model::{
         property::{edge::Edge, vertex::Vertex},
         road_network::{edge_id::EdgeId, vertex_id::VertexId},
     };
     use crate::util::geo::coord::InternalCoord;
     use geo::coord;
+    use std::collections::HashMap;
     use std::path::PathBuf;
 
     fn mock_vertex() -> Vertex {
@@ -181,7 +210,7 @@ mod tests {
         let file = filepath();
         let engine = SpeedTraversalEngine::new(
             &file,
-            SpeedUnit::KilometersPerHour,
+            Some(SpeedUnit::KilometersPerHour),
             None,
             Some(TimeUnit::Seconds),
         )
@@ -194,6 +223,7 @@ mod tests {
                         StateFeature::Distance {
                             distance_unit: DistanceUnit::Kilometers,
                             initial: Distance::new(0.0),
+                            output_alias: None,
                         },
                     ),
                     (
@@ -201,6 +231,7 @@ mod tests {
                         StateFeature::Time {
                             time_unit: TimeUnit::Seconds,
                             initial: Time::new(0.0),
+                            output_alias: None,
                         },
                     ),
                 ])
@@ -221,12 +252,112 @@ mod tests {
         approx_eq(state[1].into(), expected, 0.001);
     }
 
+    #[test]
+    fn test_speed_cap_by_road_class() {
+        // both edges have a speed of 120 kph, and edge 1 is on a residential road class
+        let engine = SpeedTraversalEngine {
+            speed_table: vec![Speed::new(120.0), Speed::new(120.0)].into_boxed_slice(),
+            speed_unit: SpeedUnit::KilometersPerHour,
+            time_unit: TimeUnit::Seconds,
+            distance_unit: DistanceUnit::Kilometers,
+            max_speed: Speed::new(120.0),
+        };
+        let residential = 5;
+        let caps = HashMap::from([(residential, Speed::new(80.0))]);
+        let engine = engine.with_speed_caps(&[1, residential], &caps).unwrap();
+        assert_eq!(engine.speed_table[0], Speed::new(120.0));
+        assert_eq!(engine.speed_table[1], Speed::new(80.0));
+
+        let model = SpeedTraversalModel::new(Arc::new(engine));
+        let state_model = StateModel::empty().extend(model.state_features()).unwrap();
+        let v = mock_vertex();
+        let edge_time = |edge_id: usize| {
+            let mut state = state_model.initial_state().unwrap();
+            model
+                .traverse_edge((&v, &mock_edge(edge_id), &v), &mut state, &state_model)
+                .unwrap();
+            state_model
+                .get_time(&state, &String::from("time"), &TimeUnit::Seconds)
+                .unwrap()
+                .as_f64()
+        };
+        // 100 meters takes 3 seconds at 120 kph and 4.5 seconds at the 80 kph cap
+        approx_eq(edge_time(0), 3.0, 0.001);
+        approx_eq(edge_time(1), 4.5, 0.001);
+
+        let invalid = HashMap::from([(residential, Speed::ZERO)]);
+        let engine =
+            SpeedTraversalEngine::new(&filepath(), Some(SpeedUnit::KilometersPerHour), None, None)
+                .unwrap();
+        assert!(engine.with_speed_caps(&[1, 1, 1, 1], &invalid).is_err());
+    }
+
+    #[test]
+    fn test_congestion_slows_peak_hour_departure() {
+        use crate::model::traversal::default::{
+            congestion_profile::CongestionProfile, speed_traversal_service::SpeedLookupService,
+        };
+        use crate::model::traversal::traversal_model_service::TraversalModelService;
+
+        // a route of two 100 meter edges at 60 kph, where edge 0 is on an arterial
+        // road class that slows to half speed at 8am
+        let engine = SpeedTraversalEngine {
+            speed_table: vec![Speed::new(60.0), Speed::new(60.0)].into_boxed_slice(),
+            speed_unit: SpeedUnit::KilometersPerHour,
+            time_unit: TimeUnit::Seconds,
+            distance_unit: DistanceUnit::Kilometers,
+            max_speed: Speed::new(60.0),
+        };
+        let arterial = 3;
+        let mut factors = vec![1.0; 24];
+        factors[8] = 0.5;
+        let profile = CongestionProfile::new(
+            vec![arterial, 1].into_boxed_slice(),
+            HashMap::from([(arterial, CongestionProfile::hourly(&factors).unwrap())]),
+        )
+        .unwrap();
+        let service = SpeedLookupService {
+            e: Arc::new(engine),
+            congestion: Some(Arc::new(profile)),
+        };
+        let v = mock_vertex();
+        let route_time = |query: serde_json::Value| {
+            let model = service.build(&query).unwrap();
+            let state_model = StateModel::empty().extend(model.state_features()).unwrap();
+            let mut state = state_model.initial_state().unwrap();
+            for edge_id in 0..2 {
+                model
+                    .traverse_edge((&v, &mock_edge(edge_id), &v), &mut state, &state_model)
+                    .unwrap();
+            }
+            let time = state_model
+                .get_time(&state, &String::from("time"), &TimeUnit::Seconds)
+                .unwrap();
+            (time.as_f64(), model.is_static())
+        };
+        // each edge takes 6 seconds at free flow
+        let (free_flow, is_static) = route_time(serde_json::json!({}));
+        approx_eq(free_flow, 12.0, 0.001);
+        assert!(is_static);
+        let (off_peak, _) = route_time(serde_json::json!({ "departure_time": "03:00" }));
+        approx_eq(off_peak, 12.0, 0.001);
+        // the arterial edge takes 12 seconds at 8am, and 8 seconds at 7:30am
+        let (peak, is_static) = route_time(serde_json::json!({ "departure_time": "08:00" }));
+        approx_eq(peak, 18.0, 0.001);
+        assert!(!is_static);
+        let (shoulder, _) = route_time(serde_json::json!({ "departure_time": 7.5 }));
+        approx_eq(shoulder, 14.0, 0.001);
+
+        let invalid = service.build(&serde_json::json!({ "departure_time": 24.0 }));
+        assert!(invalid.is_err());
+    }
+
     #[test]
     fn test_edge_cost_lookup_with_milliseconds_time_unit() {
         let file = filepath();
         let engine = SpeedTraversalEngine::new(
             &file,
-            SpeedUnit::KilometersPerHour,
+            Some(SpeedUnit::KilometersPerHour),
             None,
             Some(TimeUnit::Milliseconds),
         )
@@ -239,6 +370,7 @@ mod tests {
                         StateFeature::Distance {
                             distance_unit: DistanceUnit::Kilometers,
                             initial: Distance::new(0.0),
+                            output_alias: None,
                         },
                     ),
                     (
@@ -246,6 +378,7 @@ mod tests {
                         StateFeature::Time {
                             time_unit: TimeUnit::Milliseconds,
                             initial: Time::new(0.0),
+                            output_alias: None,
                         },
                     ),
                 ])
@@ -1,5 +1,7 @@
 use super::{
-    speed_traversal_engine::SpeedTraversalEngine, speed_traversal_model::SpeedTraversalModel,
+    congestion_profile::{parse_departure_hour, CongestionProfile},
+    speed_traversal_engine::SpeedTraversalEngine,
+    speed_traversal_model::SpeedTraversalModel,
 };
 use crate::model::traversal::{
     traversal_model::TraversalModel, traversal_model_error::TraversalModelError,
@@ -9,13 +11,29 @@ use std::sync::Arc;
 
 pub struct SpeedLookupService {
     pub e: Arc<SpeedTraversalEngine>,
+    /// optional time-of-day speed factors, applied to queries with a departure time
+    pub congestion: Option<Arc<CongestionProfile>>,
+}
+
+impl SpeedLookupService {
+    /// query key with the departure time that selects the congestion factors
+    pub const DEPARTURE_TIME: &'static str = "departure_time";
 }
 
 impl TraversalModelService for SpeedLookupService {
     fn build(
         &self,
-        _parameters: &serde_json::Value,
+        parameters: &serde_json::Value,
     ) -> Result<Arc<dyn TraversalModel>, TraversalModelError> {
-        Ok(Arc::new(SpeedTraversalModel::new(self.e.clone())))
+        let model = SpeedTraversalModel::new(self.e.clone());
+        let departure_time = parameters.get(Self::DEPARTURE_TIME);
+        match (&self.congestion, departure_time) {
+            (Some(profile), Some(departure_time)) => {
+                let departure_hour = parse_departure_hour(departure_time)?;
+                let factors = profile.factors_at(departure_hour)?;
+                Ok(Arc::new(model.with_congestion(factors)))
+            }
+            _ => Ok(Arc::new(model)),
+        }
     }
 }
@@ -0,0 +1,285 @@
+use crate::model::property::{edge::Edge, vertex::Vertex};
+use crate::model::road_network::edge_id::EdgeId;
+use crate::model::state::custom_feature_format::CustomFeatureFormat;
+use crate::model::state::state_feature::StateFeature;
+use crate::model::state::state_model::StateModel;
+use crate::model::traversal::state::state_variable::StateVar;
+use crate::model::traversal::traversal_model::TraversalModel;
+use crate::model::traversal::traversal_model_error::TraversalModelError;
+use crate::model::traversal::traversal_model_service::TraversalModelService;
+use crate::util::fs::read_utils;
+use ordered_float::OrderedFloat;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+/// A traversal model that adds the toll of each tolled edge to a monetary state
+/// feature, so that tolls can be weighed against other route costs, such as for
+/// commercial vehicles. tolls are read from a CSV table with `edge_id` and `toll`
+/// columns. most edges are free, so the table lists only tolled edges, and any
+/// edge missing from it has no toll.
+///
+/// the accumulated toll is tracked in the "toll" state feature, with the configured
+/// currency as its unit, which should be given a weight in the cost model in order
+/// to have an effect on the search. the model tracks no other features, so it is
+/// usually combined with a distance or speed model.
+///
+/// # Example
+///
+/// ### Deserialization
+///
+/// ```toml
+/// [traversal]
+/// type = "combined"
+/// [[traversal.traversal_models]]
+/// type = "distance"
+/// [[traversal.traversal_models]]
+/// type = "toll"
+/// toll_table_input_file = "edges-tolls.csv"
+/// currency = "USD"
+/// ```
+#[derive(Clone)]
+pub struct TollTraversalModel {
+    tolls: Arc<HashMap<EdgeId, f64>>,
+    currency: String,
+}
+
+/// a row of a toll table
+#[derive(Deserialize)]
+struct TollRow {
+    edge_id: EdgeId,
+    toll: f64,
+}
+
+impl TollTraversalModel {
+    pub const TOLL: &'static str = "toll";
+    pub const DEFAULT_CURRENCY: &'static str = "USD";
+
+    /// builds the model from the toll of each tolled edge, confirming that every
+    /// toll is a non-negative number.
+    pub fn new(
+        tolls: HashMap<EdgeId, f64>,
+        currency: String,
+    ) -> Result<TollTraversalModel, TraversalModelError> {
+        if let Some((edge_id, toll)) = tolls.iter().find(|(_, t)| !t.is_finite() || **t < 0.0) {
+            return Err(TraversalModelError::BuildError(format!(
+                "toll of edge {} must be a non-negative number, found {}",
+                edge_id, toll
+            )));
+        }
+        Ok(TollTraversalModel {
+            tolls: Arc::new(tolls),
+            currency,
+        })
+    }
+
+    /// reads tolls from a CSV file with a header row and `edge_id` and `toll`
+    /// columns. it is an error for an edge to be listed more than once.
+    pub fn from_file<P: AsRef<Path>>(
+        toll_table_path: P,
+        currency: String,
+    ) -> Result<TollTraversalModel, TraversalModelError> {
+        let rows: Box<[TollRow]> = read_utils::from_csv(&toll_table_path.as_ref(), true, None)
+            .map_err(|e| {
+                TraversalModelError::FileReadError(
+                    toll_table_path.as_ref().to_path_buf(),
+                    e.to_string(),
+                )
+            })?;
+        let mut tolls = HashMap::with_capacity(rows.len());
+        for row in rows.iter() {
+            if tolls.insert(row.edge_id, row.toll).is_some() {
+                return Err(TraversalModelError::BuildError(format!(
+                    "toll table lists edge {} more than once",
+                    row.edge_id
+                )));
+            }
+        }
+        TollTraversalModel::new(tolls, currency)
+    }
+
+    /// the toll of an edge, which is zero for edges without a toll
+    pub fn toll(&self, edge_id: EdgeId) -> f64 {
+        self.tolls.get(&edge_id).copied().unwrap_or(0.0)
+    }
+}
+
+impl TraversalModel for TollTraversalModel {
+    /// tracks the accumulated toll, in the model's currency
+    fn state_features(&self) -> Vec<(String, StateFeature)> {
+        vec![(
+            String::from(Self::TOLL),
+            StateFeature::Custom {
+                r#type: String::from(Self::TOLL),
+                unit: self.currency.clone(),
+                format: CustomFeatureFormat::FloatingPoint {
+                    initial: OrderedFloat(0.0),
+                },
+                output_alias: None,
+            },
+        )]
+    }
+
+    fn traverse_edge(
+        &self,
+        trajectory: (&Vertex, &Edge, &Vertex),
+        state: &mut Vec<StateVar>,
+        state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        let (_, edge, _) = trajectory;
+        let toll = self.toll(edge.edge_id);
+        if toll > 0.0 {
+            let name = String::from(Self::TOLL);
+            let prev = state_model.get_custom_f64(state, &name)?;
+            state_model.set_custom_f64(state, &name, &(prev + toll))?;
+        }
+        Ok(())
+    }
+
+    /// tolls cannot be predicted from the coordinates of the remaining trip, so no
+    /// toll is estimated, which keeps the estimate admissible for a-star search.
+    fn estimate_traversal(
+        &self,
+        _od: (&Vertex, &Vertex),
+        _state: &mut Vec<StateVar>,
+        _state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        Ok(())
+    }
+
+    /// tolls depend only on the edge
+    fn is_static(&self) -> bool {
+        true
+    }
+}
+
+impl TraversalModelService for TollTraversalModel {
+    fn build(
+        &self,
+        _query: &serde_json::Value,
+    ) -> Result<Arc<dyn TraversalModel>, TraversalModelError> {
+        let model: Arc<dyn TraversalModel> = Arc::new(self.clone());
+        Ok(model)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm::search::a_star::a_star_algorithm::run_a_star;
+    use crate::algorithm::search::backtrack::vertex_oriented_route;
+    use crate::algorithm::search::direction::Direction;
+    use crate::algorithm::search::search_instance::SearchInstance;
+    use crate::model::cost::cost_aggregation::CostAggregation;
+    use crate::model::cost::cost_model::CostModel;
+    use crate::model::cost::vehicle::vehicle_cost_rate::VehicleCostRate;
+    use crate::model::road_network::{graph::Graph, vertex_id::VertexId};
+    use crate::model::traversal::default::{
+        combined_traversal_model::CombinedTraversalModel,
+        distance_traversal_model::DistanceTraversalModel,
+    };
+    use crate::model::unit::DistanceUnit;
+    use crate::util::test_util::{distance_search_instance, graph_from_edges};
+    use std::path::PathBuf;
+
+    /// a tolled 10 meter edge (0) -[0]-> (1) and a free detour of 50 meters
+    /// (0) -[1]-> (2) -[2]-> (1)
+    fn build_mock_graph() -> Graph {
+        let vertices = (0..3).map(|i| Vertex::new(i, 0.0, 0.0)).collect::<Vec<_>>();
+        let edges = vec![
+            Edge::new(0, 0, 1, 10.0),
+            Edge::new(1, 0, 2, 25.0),
+            Edge::new(2, 2, 1, 25.0),
+        ];
+        graph_from_edges(vertices, edges)
+    }
+
+    /// finds the route from (0) to (1) with the given weight on each meter and
+    /// each unit of toll, returning its edges and its total toll
+    fn route_with_toll_weight(toll_weight: f64) -> (Vec<EdgeId>, f64) {
+        let tolls = TollTraversalModel::new(
+            HashMap::from([(EdgeId(0), 2.5)]),
+            String::from(TollTraversalModel::DEFAULT_CURRENCY),
+        )
+        .unwrap();
+        let traversal_model = Arc::new(
+            CombinedTraversalModel::new(vec![
+                Arc::new(DistanceTraversalModel::new(DistanceUnit::Meters)),
+                Arc::new(tolls),
+            ])
+            .unwrap(),
+        );
+        let state_model = Arc::new(
+            StateModel::empty()
+                .extend(traversal_model.state_features())
+                .unwrap(),
+        );
+        let toll = String::from(TollTraversalModel::TOLL);
+        let distance = String::from("distance");
+        let cost_model = CostModel::new(
+            Arc::new(HashMap::from([
+                (distance.clone(), 1.0),
+                (toll.clone(), toll_weight),
+            ])),
+            Arc::new(HashMap::from([
+                (distance, VehicleCostRate::Raw),
+                (toll.clone(), VehicleCostRate::Raw),
+            ])),
+            Arc::new(HashMap::new()),
+            CostAggregation::Sum,
+            Arc::new(vec![]),
+            state_model.clone(),
+        )
+        .unwrap();
+        let si = SearchInstance {
+            state_model: state_model.clone(),
+            traversal_model,
+            cost_model,
+            ..distance_search_instance(build_mock_graph())
+        };
+
+        let (o, d) = (VertexId(0), VertexId(1));
+        let result = run_a_star(o, Some(d), &Direction::Forward, None, &si).unwrap();
+        let route = vertex_oriented_route(o, d, &result.tree).unwrap();
+        let final_state = &route.last().unwrap().result_state;
+        let total_toll = state_model.get_custom_f64(final_state, &toll).unwrap();
+        (route.iter().map(|r| r.edge_id).collect(), total_toll)
+    }
+
+    #[test]
+    fn test_heavy_toll_weight_avoids_tolls() {
+        // at 1 per unit of toll, the tolled edge is cheaper than the 50 meter detour
+        assert_eq!(route_with_toll_weight(1.0), (vec![EdgeId(0)], 2.5));
+        // at 100 per unit of toll, the detour is cheaper
+        assert_eq!(
+            route_with_toll_weight(100.0),
+            (vec![EdgeId(1), EdgeId(2)], 0.0)
+        );
+    }
+
+    #[test]
+    fn test_toll_table_from_file() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join("model")
+            .join("traversal")
+            .join("default")
+            .join("test")
+            .join("tolls.csv");
+        let model = TollTraversalModel::from_file(&path, String::from("EUR")).unwrap();
+        assert_eq!(model.toll(EdgeId(1)), 3.75);
+        // edges missing from the table are free
+        assert_eq!(model.toll(EdgeId(0)), 0.0);
+        assert!(matches!(
+            &model.state_features()[0].1,
+            StateFeature::Custom { unit, .. } if unit == "EUR"
+        ));
+
+        let negative = TollTraversalModel::new(
+            HashMap::from([(EdgeId(0), -1.0)]),
+            String::from(TollTraversalModel::DEFAULT_CURRENCY),
+        );
+        assert!(negative.is_err());
+    }
+}
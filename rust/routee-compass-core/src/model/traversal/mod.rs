@@ -1,5 +1,6 @@
 pub mod default;
 pub mod state;
+pub mod strict_mode;
 pub mod traversal_model;
 pub mod traversal_model_builder;
 pub mod traversal_model_error;
@@ -1,4 +1,4 @@
-use crate::model::unit::{as_f64::AsF64, Distance, Energy, Time};
+use crate::model::unit::{as_f64::AsF64, Distance, Energy, Speed, Time};
 use allocative::Allocative;
 use derive_more::{Add, Div, Mul, Neg, Sub, Sum};
 use serde::{Deserialize, Serialize};
@@ -55,3 +55,8 @@ impl From<Energy> for StateVar {
         StateVar(value.as_f64())
     }
 }
+impl From<Speed> for StateVar {
+    fn from(value: Speed) -> Self {
+        StateVar(value.as_f64())
+    }
+}
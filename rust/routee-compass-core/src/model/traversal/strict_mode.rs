@@ -0,0 +1,45 @@
+use super::traversal_model_error::TraversalModelError;
+
+/// query key of the strict mode flag.
+///
+/// in strict mode, sites that would otherwise fall back to a substitute value
+/// when data is missing or out of range, such as a missing grade or a clamped
+/// prediction model input, fail the query with a
+/// [`TraversalModelError::StrictModeFallback`] instead. this supports data
+/// validation runs, where any fallback points at an issue in the input data.
+pub const STRICT_MODE_KEY: &str = "strict";
+
+/// reads the strict mode flag of a query, which defaults to false.
+///
+/// # Arguments
+///
+/// * `query` - the search query
+///
+/// # Returns
+///
+/// whether the query runs in strict mode, or an error if the flag is not a boolean
+pub fn is_strict(query: &serde_json::Value) -> Result<bool, TraversalModelError> {
+    match query.get(STRICT_MODE_KEY) {
+        None | Some(serde_json::Value::Null) => Ok(false),
+        Some(value) => value.as_bool().ok_or_else(|| {
+            TraversalModelError::BuildError(format!(
+                "expected '{}' value to be a boolean, found {}",
+                STRICT_MODE_KEY, value
+            ))
+        }),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_is_strict() {
+        assert!(!is_strict(&json!({})).unwrap());
+        assert!(!is_strict(&json!({ "strict": false })).unwrap());
+        assert!(is_strict(&json!({ "strict": true })).unwrap());
+        assert!(is_strict(&json!({ "strict": "yes" })).is_err());
+    }
+}
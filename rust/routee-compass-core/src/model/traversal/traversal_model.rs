@@ -13,14 +13,18 @@ use crate::model::state::state_model::StateModel;
 /// [DistanceModel]: super::default::distance::DistanceModel
 /// [SpeedLookupModel]: super::default::speed_lookup_model::SpeedLookupModel
 pub trait TraversalModel: Send + Sync {
-    /// lists the state variables expected by this traversal model that are not
-    /// defined on the base configuration. for example, if this traversal model
-    /// has state variables that differ based on the query, they can be injected
-    /// into the state model by listing them here.
+    /// lists the state variables written by this traversal model. the state model
+    /// for a search is derived from these features, so any feature this model reads
+    /// or writes must be listed here, or the search fails with a missing state
+    /// variable. features that differ based on the query, such as a vehicle's
+    /// energy feature, can be injected into the state model by listing them here.
     fn state_features(&self) -> Vec<(String, StateFeature)>;
 
     /// Updates the traversal state by traversing an edge.
     ///
+    /// The trajectory is always in the direction of travel along the edge, from its
+    /// source to its destination vertex, including during reverse searches.
+    ///
     /// # Arguments
     ///
     /// * `src` - source vertex
@@ -41,6 +45,12 @@ pub trait TraversalModel: Send + Sync {
     /// Estimates the traversal state by traversing between two vertices without
     /// performing any graph traversals.
     ///
+    /// The cost of the estimated state is the heuristic of the a* search, which
+    /// finds optimal routes only if the estimate never overestimates the cost of
+    /// any route between the vertices, for instance by using the haversine distance
+    /// between them. An estimate of zero is always valid and makes the search
+    /// behave as dijkstra.
+    ///
     /// # Arguments
     ///
     /// * `src` - source vertex
@@ -56,4 +66,12 @@ pub trait TraversalModel: Send + Sync {
         state: &mut Vec<StateVar>,
         state_model: &StateModel,
     ) -> Result<(), TraversalModelError>;
+
+    /// true if the change in state from traversing an edge depends only on the
+    /// edge itself and not on the state when arriving at it, which allows edge
+    /// costs to be computed once and cached. models that depend on the search
+    /// state, such as energy or time-of-day models, must return false.
+    fn is_static(&self) -> bool {
+        false
+    }
 }
@@ -29,4 +29,6 @@ pub enum TraversalModelError {
     StateError(#[from] StateError),
     #[error("prediction model failed with error {0}")]
     PredictionModel(String),
+    #[error("strict mode does not allow fallback: {0}")]
+    StrictModeFallback(String),
 }
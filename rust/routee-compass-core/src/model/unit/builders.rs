@@ -6,6 +6,8 @@ use super::{
 pub const BASE_DISTANCE_UNIT: DistanceUnit = DistanceUnit::Meters;
 pub const BASE_TIME_UNIT: TimeUnit = TimeUnit::Seconds;
 pub const BASE_SPEED_UNIT: SpeedUnit = SpeedUnit::MetersPerSecond;
+/// canonical unit for storing energy state when energy is accumulated across fuel types.
+pub const BASE_ENERGY_UNIT: EnergyUnit = EnergyUnit::KilowattHours;
 
 // these functions are accessible via the associated unit namespace. they are
 // implemented here as they are coupled with the base units listed above.
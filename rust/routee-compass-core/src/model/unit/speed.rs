@@ -2,6 +2,7 @@ use super::{
     as_f64::AsF64, builders, internal_float::InternalFloat, Distance, DistanceUnit, SpeedUnit,
     Time, TimeUnit, UnitError,
 };
+use crate::model::traversal::state::state_variable::StateVar;
 use allocative::Allocative;
 use derive_more::{Add, Div, Mul, Neg, Sub, Sum};
 use serde::{Deserialize, Serialize};
@@ -41,6 +42,12 @@ impl From<(Distance, Time)> for Speed {
     }
 }
 
+impl From<StateVar> for Speed {
+    fn from(value: StateVar) -> Self {
+        Speed::new(value.0)
+    }
+}
+
 impl PartialOrd for Speed {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.0.cmp(&other.0))
@@ -1,2 +1,3 @@
 pub mod cache_error;
 pub mod float_cache_policy;
+pub mod query_cache_policy;
@@ -0,0 +1,175 @@
+use std::{
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+};
+
+use lru::LruCache;
+use serde::{Deserialize, Serialize};
+
+use super::cache_error::CacheError;
+
+#[derive(Serialize, Deserialize)]
+pub struct QueryCachePolicyConfig {
+    pub cache_size: usize,
+    /// decimal places of the coordinates in the cache key
+    pub coordinate_precision: i32,
+}
+
+/// A cache policy that stores a value for each normalized JSON query.
+///
+/// Queries are normalized by removing the ignored keys, such as request
+/// identifiers, rounding the values of the coordinate keys to the coordinate
+/// precision and writing the query with its object keys sorted, so that queries
+/// which differ only in these ways share a cache entry. Up to `cache_size` of
+/// the most recently used entries are kept. The number of lookups that found
+/// an entry (hits) and that did not (misses) are counted.
+///
+/// # Example
+///
+/// ```
+/// use routee_compass_core::util::cache_policy::query_cache_policy::{QueryCachePolicy, QueryCachePolicyConfig};
+/// use serde_json::json;
+///
+/// let config = QueryCachePolicyConfig {
+///    cache_size: 100,
+///    coordinate_precision: 3,
+/// };
+///
+/// let cache_policy = QueryCachePolicy::from_config(config, &["origin_x"], &["request_id"]).unwrap();
+///
+/// cache_policy.update(&json!({ "origin_x": 1.2345, "model_name": "car", "request_id": "a" }), 3.456).unwrap();
+///
+/// // key order and request ids do not matter, and 1.2348 rounds to 1.235
+/// let value = cache_policy.get(&json!({ "model_name": "car", "origin_x": 1.2348, "request_id": "b" })).unwrap();
+/// assert_eq!(value, Some(3.456));
+///
+/// // other values are compared exactly
+/// let value = cache_policy.get(&json!({ "origin_x": 1.2345, "model_name": "truck" })).unwrap();
+/// assert_eq!(value, None);
+/// assert_eq!((cache_policy.hits(), cache_policy.misses()), (1, 1));
+/// ```
+pub struct QueryCachePolicy<V> {
+    cache: Mutex<LruCache<String, V>>,
+    coordinate_keys: Vec<String>,
+    ignored_keys: Vec<String>,
+    coordinate_precision: i32,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+}
+
+impl<V: Clone> QueryCachePolicy<V> {
+    /// builds a query cache.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - the cache size and coordinate precision
+    /// * `coordinate_keys` - query keys holding coordinates, which are quantized in the cache key
+    /// * `ignored_keys` - query keys left out of the cache key, which do not affect the value
+    pub fn from_config(
+        config: QueryCachePolicyConfig,
+        coordinate_keys: &[&str],
+        ignored_keys: &[&str],
+    ) -> Result<Self, CacheError> {
+        let size = NonZeroUsize::new(config.cache_size).ok_or_else(|| {
+            CacheError::BuildError("maximum_cache_size must be greater than 0".to_string())
+        })?;
+        if (config.coordinate_precision > 10) || (config.coordinate_precision < -10) {
+            return Err(CacheError::BuildError(
+                "coordinate_precision must be between -10 and 10".to_string(),
+            ));
+        }
+        Ok(Self {
+            cache: Mutex::new(LruCache::new(size)),
+            coordinate_keys: coordinate_keys.iter().map(|k| k.to_string()).collect(),
+            ignored_keys: ignored_keys.iter().map(|k| k.to_string()).collect(),
+            coordinate_precision: config.coordinate_precision,
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+        })
+    }
+
+    /// the normalized form of a query used as its cache key. ignored keys are removed
+    /// and coordinate values are replaced by integers of the coordinate scaled to
+    /// the coordinate precision.
+    pub fn query_key(&self, query: &serde_json::Value) -> String {
+        let mut normalized = query.clone();
+        if let Some(object) = normalized.as_object_mut() {
+            for key in self.ignored_keys.iter() {
+                object.remove(key);
+            }
+            let multiplier = 10f64.powi(self.coordinate_precision);
+            for key in self.coordinate_keys.iter() {
+                if let Some(coordinate) = object.get(key).and_then(|v| v.as_f64()) {
+                    let quantized = (coordinate * multiplier).round() as i64;
+                    object.insert(key.clone(), serde_json::json!(quantized));
+                }
+            }
+        }
+        // objects are stored with sorted keys, so the key does not depend on key order
+        normalized.to_string()
+    }
+
+    pub fn get(&self, query: &serde_json::Value) -> Result<Option<V>, CacheError> {
+        let key = self.query_key(query);
+        let mut cache = self.cache.lock().map_err(|e| {
+            CacheError::RuntimeError(format!("Could not get lock on cache due to {}", e))
+        })?;
+        let value = cache.get(&key).cloned();
+        match value {
+            Some(_) => self.hits.fetch_add(1, Ordering::Relaxed),
+            None => self.misses.fetch_add(1, Ordering::Relaxed),
+        };
+        Ok(value)
+    }
+
+    /// number of lookups that found a value in the cache
+    pub fn hits(&self) -> usize {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// number of lookups that found no value in the cache
+    pub fn misses(&self) -> usize {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    pub fn update(&self, query: &serde_json::Value, value: V) -> Result<(), CacheError> {
+        let key = self.query_key(query);
+        let mut cache = self.cache.lock().map_err(|e| {
+            CacheError::RuntimeError(format!("Could not get lock on cache due to {}", e))
+        })?;
+        cache.put(key, value);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_query_key_normalization() {
+        let config = QueryCachePolicyConfig {
+            cache_size: 10,
+            coordinate_precision: 2,
+        };
+        let cache_policy: QueryCachePolicy<f64> =
+            QueryCachePolicy::from_config(config, &["origin_x", "origin_y"], &["query_index"])
+                .unwrap();
+
+        let a = json!({ "query_index": 0, "origin_x": -105.001, "origin_y": 39.7, "weights": { "time": 1.0, "distance": 0.5 } });
+        let b = json!({ "query_index": 1, "weights": { "distance": 0.5, "time": 1.0 }, "origin_y": 39.704, "origin_x": -104.999 });
+        let c = json!({ "origin_x": -105.001, "origin_y": 39.7, "weights": { "time": 1.0, "distance": 0.6 } });
+        assert_eq!(cache_policy.query_key(&a), cache_policy.query_key(&b));
+        assert_ne!(cache_policy.query_key(&a), cache_policy.query_key(&c));
+
+        let invalid = QueryCachePolicyConfig {
+            cache_size: 0,
+            coordinate_precision: 2,
+        };
+        assert!(QueryCachePolicy::<f64>::from_config(invalid, &[], &[]).is_err());
+    }
+}
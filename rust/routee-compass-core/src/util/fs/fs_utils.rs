@@ -43,3 +43,14 @@ where
         }
     }
 }
+
+/// true if the file has a ".parquet" extension.
+pub fn is_parquet<P>(filepath: P) -> bool
+where
+    P: AsRef<Path>,
+{
+    filepath
+        .as_ref()
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("parquet"))
+}
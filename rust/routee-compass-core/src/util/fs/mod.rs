@@ -1,3 +1,5 @@
 pub mod fs_utils;
 pub mod read_decoders;
+#[cfg(feature = "parquet")]
+pub mod read_parquet;
 pub mod read_utils;
@@ -20,3 +20,10 @@ pub fn u8(_idx: usize, row: String) -> Result<u8, std::io::Error> {
         std::io::Error::new(std::io::ErrorKind::InvalidData, msg)
     })
 }
+
+pub fn f64(_idx: usize, row: String) -> Result<f64, std::io::Error> {
+    row.parse::<f64>().map_err(|e| {
+        let msg = format!("failure decoding row {} due to: {:}", row, e);
+        std::io::Error::new(std::io::ErrorKind::InvalidData, msg)
+    })
+}
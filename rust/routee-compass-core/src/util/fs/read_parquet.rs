@@ -0,0 +1,121 @@
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use parquet::record::Field;
+use std::{fs::File, io, path::Path};
+
+/// reads a Parquet file with one row per edge into a table of values indexed by
+/// edge id. the rows may appear in any order, but the edge ids must cover each
+/// id from zero to the number of rows exactly once. other columns are ignored.
+///
+/// # Arguments
+///
+/// * `filepath` - the Parquet file to read
+/// * `id_column` - name of the integer edge id column
+/// * `value_column` - name of the numeric value column
+///
+/// # Returns
+///
+/// the values indexed by edge id, or an error if the file cannot be read, the
+/// expected columns are missing, or the edge ids are not a complete enumeration
+pub fn read_edge_values<P: AsRef<Path>>(
+    filepath: &P,
+    id_column: &str,
+    value_column: &str,
+) -> Result<Box<[f64]>, io::Error> {
+    let file = File::open(filepath)?;
+    let reader = SerializedFileReader::new(file).map_err(invalid_data)?;
+
+    let found_columns = reader
+        .metadata()
+        .file_metadata()
+        .schema_descr()
+        .columns()
+        .iter()
+        .map(|c| c.name().to_string())
+        .collect::<Vec<_>>();
+    let has_column = |name: &str| found_columns.iter().any(|c| c == name);
+    if !has_column(id_column) || !has_column(value_column) {
+        return Err(invalid_data(format!(
+            "expected columns [{}, {}] but found [{}]",
+            id_column,
+            value_column,
+            found_columns.join(", ")
+        )));
+    }
+
+    let n_rows = reader.metadata().file_metadata().num_rows() as usize;
+    let mut values: Vec<Option<f64>> = vec![None; n_rows];
+    for (row_idx, row_result) in reader.into_iter().enumerate() {
+        let row = row_result.map_err(invalid_data)?;
+        let mut edge_id = None;
+        let mut value = None;
+        for (name, field) in row.get_column_iter() {
+            if name == id_column {
+                edge_id = Some(field_as_index(field, row_idx, name)?);
+            } else if name == value_column {
+                value = Some(field_as_f64(field, row_idx, name)?);
+            }
+        }
+        let (edge_id, value) = edge_id.zip(value).ok_or_else(|| {
+            invalid_data(format!(
+                "row {} is missing a value for {} or {}",
+                row_idx, id_column, value_column
+            ))
+        })?;
+        match values.get_mut(edge_id) {
+            None => {
+                return Err(invalid_data(format!(
+                    "edge id {} in row {} is out of range for a table with {} rows",
+                    edge_id, row_idx, n_rows
+                )))
+            }
+            Some(Some(_)) => {
+                return Err(invalid_data(format!(
+                    "edge id {} appears more than once",
+                    edge_id
+                )))
+            }
+            Some(slot) => *slot = Some(value),
+        }
+    }
+
+    values
+        .into_iter()
+        .enumerate()
+        .map(|(edge_id, value)| {
+            value.ok_or_else(|| invalid_data(format!("missing value for edge id {}", edge_id)))
+        })
+        .collect()
+}
+
+fn field_as_index(field: &Field, row_idx: usize, name: &str) -> Result<usize, io::Error> {
+    let index = match field {
+        Field::Int(v) => usize::try_from(*v).ok(),
+        Field::Long(v) => usize::try_from(*v).ok(),
+        Field::UInt(v) => usize::try_from(*v).ok(),
+        Field::ULong(v) => usize::try_from(*v).ok(),
+        _ => None,
+    };
+    index.ok_or_else(|| {
+        invalid_data(format!(
+            "expected non-negative integer for {} in row {}, found {}",
+            name, row_idx, field
+        ))
+    })
+}
+
+fn field_as_f64(field: &Field, row_idx: usize, name: &str) -> Result<f64, io::Error> {
+    match field {
+        Field::Double(v) => Ok(*v),
+        Field::Float(v) => Ok(*v as f64),
+        Field::Int(v) => Ok(*v as f64),
+        Field::Long(v) => Ok(*v as f64),
+        _ => Err(invalid_data(format!(
+            "expected number for {} in row {}, found {}",
+            name, row_idx, field
+        ))),
+    }
+}
+
+fn invalid_data<E: ToString>(e: E) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e.to_string())
+}
@@ -1,6 +1,7 @@
 use super::fs_utils;
-use csv::ReaderBuilder;
+use csv::{ReaderBuilder, StringRecord};
 use flate2::read::GzDecoder;
+use rayon::prelude::*;
 
 use std::{
     fs::File,
@@ -23,16 +24,7 @@ where
     F: AsRef<Path>,
     T: serde::de::DeserializeOwned + 'a,
 {
-    let f = File::open(filepath.as_ref())?;
-    let r: Box<dyn io::Read> = if fs_utils::is_gzip(filepath) {
-        Box::new(BufReader::new(GzDecoder::new(f)))
-    } else {
-        Box::new(f)
-    };
-    let reader = ReaderBuilder::new()
-        .has_headers(has_headers)
-        .trim(csv::Trim::Fields)
-        .from_reader(r)
+    let reader = csv_reader(filepath, has_headers)?
         .into_deserialize::<T>()
         .map(move |r| {
             if let Ok(t) = &r {
@@ -53,16 +45,132 @@ pub fn from_csv<'a, T>(
     has_headers: bool,
     row_callback: RowCallback<'a, T>,
 ) -> Result<Box<[T]>, csv::Error>
+where
+    T: serde::de::DeserializeOwned + 'a,
+{
+    from_csv_with_capacity(filepath, has_headers, 0, row_callback)
+}
+
+/// reads a csv file into a vector preallocated to hold `capacity` rows, for files
+/// whose number of rows is known ahead of time, so that the vector is not grown
+/// while reading.
+pub fn from_csv_with_capacity<'a, T>(
+    filepath: &dyn AsRef<Path>,
+    has_headers: bool,
+    capacity: usize,
+    row_callback: RowCallback<'a, T>,
+) -> Result<Box<[T]>, csv::Error>
 where
     T: serde::de::DeserializeOwned + 'a,
 {
     let iter: Box<dyn Iterator<Item = Result<T, csv::Error>>> =
         iterator_from_csv(filepath, has_headers, row_callback)?;
-    let result = iter
-        .into_iter()
-        .collect::<Result<Vec<T>, csv::Error>>()?
-        .into_boxed_slice();
-    Ok(result)
+    let mut result: Vec<T> = Vec::with_capacity(capacity);
+    for row in iter {
+        result.push(row?);
+    }
+    Ok(result.into_boxed_slice())
+}
+
+/// number of csv rows read at a time by [`from_csv_par`] before they are deserialized in parallel
+const PAR_CSV_CHUNK_SIZE: usize = 65_536;
+
+/// reads a csv file into a vector preallocated to hold `capacity` rows, deserializing
+/// rows in parallel. rows are read from the file in chunks, and each chunk is decoded
+/// in parallel into a buffer indexed by row position, so rows keep the order of the
+/// file without sorting or locking. the row callback is called in file order once
+/// each chunk is decoded. as with [`from_csv`], the first row that fails to read or
+/// deserialize is returned as an error with its position in the file.
+pub fn from_csv_par<'a, T>(
+    filepath: &dyn AsRef<Path>,
+    has_headers: bool,
+    capacity: usize,
+    row_callback: RowCallback<'a, T>,
+) -> Result<Box<[T]>, csv::Error>
+where
+    T: serde::de::DeserializeOwned + Send + 'a,
+{
+    from_csv_par_chunked(
+        filepath,
+        has_headers,
+        capacity,
+        PAR_CSV_CHUNK_SIZE,
+        row_callback,
+    )
+}
+
+fn from_csv_par_chunked<'a, T>(
+    filepath: &dyn AsRef<Path>,
+    has_headers: bool,
+    capacity: usize,
+    chunk_size: usize,
+    mut row_callback: RowCallback<'a, T>,
+) -> Result<Box<[T]>, csv::Error>
+where
+    T: serde::de::DeserializeOwned + Send + 'a,
+{
+    let mut reader = csv_reader(filepath, has_headers)?;
+    let headers = match has_headers {
+        true => Some(reader.headers()?.clone()),
+        false => None,
+    };
+    let mut result: Vec<T> = Vec::with_capacity(capacity);
+    // record buffers are reused across chunks, so rows are not allocated one at a time
+    let mut chunk: Vec<StringRecord> = Vec::new();
+    let mut decoded: Vec<Result<T, csv::Error>> = Vec::with_capacity(chunk_size);
+    loop {
+        let mut n_read = 0;
+        let mut read_error = None;
+        while n_read < chunk_size {
+            if n_read == chunk.len() {
+                chunk.push(StringRecord::new());
+            }
+            match reader.read_record(&mut chunk[n_read]) {
+                Ok(true) => n_read += 1,
+                Ok(false) => break,
+                Err(e) => {
+                    read_error = Some(e);
+                    break;
+                }
+            }
+        }
+        chunk[..n_read]
+            .par_iter()
+            .map(|record| record.deserialize::<T>(headers.as_ref()))
+            .collect_into_vec(&mut decoded);
+        for row in decoded.drain(..) {
+            let row = row?;
+            if let Some(cb) = &mut row_callback {
+                cb(&row);
+            }
+            result.push(row);
+        }
+        if let Some(e) = read_error {
+            return Err(e);
+        }
+        if n_read < chunk_size {
+            break;
+        }
+    }
+    Ok(result.into_boxed_slice())
+}
+
+/// opens a csv reader over a file, decompressing it if it is gzipped
+fn csv_reader<F: AsRef<Path>>(
+    filepath: F,
+    has_headers: bool,
+) -> Result<csv::Reader<Box<dyn io::Read>>, io::Error> {
+    let f = File::open(filepath.as_ref())?;
+    let r: Box<dyn io::Read> = if fs_utils::is_gzip(filepath) {
+        Box::new(BufReader::new(GzDecoder::new(f)))
+    } else {
+        Box::new(f)
+    };
+    let reader = ReaderBuilder::new()
+        .has_headers(has_headers)
+        .trim(csv::Trim::Fields)
+        .from_reader(r);
+    Ok(reader)
 }
 
 /// reads in a raw file and deserializes each line of the file into a type T
@@ -136,7 +244,8 @@ where
 mod tests {
     use std::path::PathBuf;
 
-    use super::read_raw_file;
+    use super::{from_csv, from_csv_par_chunked, read_raw_file};
+    use crate::model::property::edge::Edge;
 
     #[test]
     fn test_read_raw_file() {
@@ -187,4 +296,28 @@ mod tests {
             "result should include each row from the source file along with the bonus word"
         );
     }
+
+    #[test]
+    fn test_from_csv_par_matches_from_csv() {
+        let filepath = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join("model")
+            .join("road_network")
+            .join("test")
+            .join("edges.csv");
+        let expected: Box<[Edge]> = from_csv(&filepath, true, None).unwrap();
+        // chunks smaller than the file keep the rows in file order
+        let mut visited = vec![];
+        let cb = Box::new(|edge: &Edge| visited.push(edge.edge_id));
+        let result: Box<[Edge]> =
+            from_csv_par_chunked(&filepath, true, expected.len(), 2, Some(cb)).unwrap();
+        assert_eq!(result.len(), expected.len());
+        for (edge, expected_edge) in result.iter().zip(expected.iter()) {
+            assert_eq!(edge.edge_id, expected_edge.edge_id);
+            assert_eq!(edge.src_vertex_id, expected_edge.src_vertex_id);
+            assert_eq!(edge.dst_vertex_id, expected_edge.dst_vertex_id);
+        }
+        let ids = expected.iter().map(|e| e.edge_id).collect::<Vec<_>>();
+        assert_eq!(visited, ids);
+    }
 }
@@ -0,0 +1,100 @@
+use geo::{Coord, LineString};
+
+/// computes the initial bearing (forward azimuth) of the great circle path
+/// between two coordinates, in degrees clockwise from north.
+/// coordinates are assumed to be in the WGS84 Coordinate System.
+///
+/// # Arguments
+///
+/// * `src` - start of the path
+/// * `dst` - end of the path
+///
+/// # Returns
+///
+/// the bearing in degrees, normalized to [0, 360)
+pub fn initial_bearing(src: &Coord<f32>, dst: &Coord<f32>) -> f32 {
+    let lat1 = src.y.to_radians();
+    let lat2 = dst.y.to_radians();
+    let d_lon = (dst.x - src.x).to_radians();
+    let y = d_lon.sin() * lat2.cos();
+    let x = lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * d_lon.cos();
+    normalize_degrees(y.atan2(x).to_degrees())
+}
+
+/// normalizes an angle in degrees to the range [0, 360).
+pub fn normalize_degrees(degrees: f32) -> f32 {
+    let normalized = degrees.rem_euclid(360.0);
+    // rem_euclid may round up to exactly 360.0 for tiny negative inputs
+    if normalized >= 360.0 {
+        0.0
+    } else {
+        normalized
+    }
+}
+
+/// the bearing at the start of a linestring, from its first two points.
+///
+/// # Returns
+///
+/// the bearing in degrees in [0, 360), or None if the linestring has fewer than two points
+pub fn start_bearing(linestring: &LineString<f32>) -> Option<f32> {
+    match linestring.0.as_slice() {
+        [first, second, ..] => Some(initial_bearing(first, second)),
+        _ => None,
+    }
+}
+
+/// the bearing at the end of a linestring, from its last two points.
+///
+/// # Returns
+///
+/// the bearing in degrees in [0, 360), or None if the linestring has fewer than two points
+pub fn end_bearing(linestring: &LineString<f32>) -> Option<f32> {
+    match linestring.0.as_slice() {
+        [.., second_last, last] => Some(initial_bearing(second_last, last)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geo::coord;
+
+    /// great circle paths heading east or west away from the equator curve slightly
+    /// toward the pole, so their initial bearing is only approximately 90 or 270.
+    fn approx_eq(a: f32, b: f32) {
+        assert!((a - b).abs() < 0.05, "{} ~= {} is not true", a, b);
+    }
+
+    #[test]
+    fn test_cardinal_bearings() {
+        let origin = coord! {x: -105.0, y: 39.7};
+        approx_eq(initial_bearing(&origin, &coord! {x: -105.0, y: 39.8}), 0.0);
+        approx_eq(initial_bearing(&origin, &coord! {x: -104.9, y: 39.7}), 90.0);
+        approx_eq(
+            initial_bearing(&origin, &coord! {x: -105.0, y: 39.6}),
+            180.0,
+        );
+        approx_eq(
+            initial_bearing(&origin, &coord! {x: -105.1, y: 39.7}),
+            270.0,
+        );
+    }
+
+    #[test]
+    fn test_linestring_bearings() {
+        // north, then east
+        let linestring = LineString::from(vec![(0.0, 0.0), (0.0, 1.0), (1.0, 1.0)]);
+        approx_eq(start_bearing(&linestring).unwrap(), 0.0);
+        approx_eq(end_bearing(&linestring).unwrap(), 90.0);
+        assert_eq!(start_bearing(&LineString::from(vec![(0.0, 0.0)])), None);
+    }
+
+    #[test]
+    fn test_normalize_degrees() {
+        approx_eq(normalize_degrees(-90.0), 270.0);
+        approx_eq(normalize_degrees(360.0), 0.0);
+        approx_eq(normalize_degrees(725.0), 5.0);
+    }
+}
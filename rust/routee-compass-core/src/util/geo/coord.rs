@@ -26,6 +26,30 @@ impl<T: CoordNum> DerefMut for InternalCoord<T> {
     }
 }
 
+/// computes the bounding box of a collection of coordinates.
+///
+/// # Arguments
+///
+/// * `coords` - coordinates to bound
+///
+/// # Returns
+///
+/// the (min, max) corners of the bounding box, or None if no coordinates were provided
+pub fn bounding_box<T: CoordNum>(
+    coords: impl IntoIterator<Item = Coord<T>>,
+) -> Option<(Coord<T>, Coord<T>)> {
+    coords.into_iter().fold(None, |acc, c| match acc {
+        None => Some((c, c)),
+        Some((min, max)) => {
+            let min_x = if c.x < min.x { c.x } else { min.x };
+            let min_y = if c.y < min.y { c.y } else { min.y };
+            let max_x = if c.x > max.x { c.x } else { max.x };
+            let max_y = if c.y > max.y { c.y } else { max.y };
+            Some((Coord { x: min_x, y: min_y }, Coord { x: max_x, y: max_y }))
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -39,4 +63,17 @@ mod tests {
         // should only have two f64s at 8 bytes each
         assert_eq!(memory_bytes, 16);
     }
+
+    #[test]
+    fn test_bounding_box() {
+        let coords = vec![
+            coord! {x: 1.0, y: -2.0},
+            coord! {x: -3.0, y: 4.0},
+            coord! {x: 0.5, y: 0.5},
+        ];
+        let (min, max) = bounding_box(coords).unwrap();
+        assert_eq!(min, coord! {x: -3.0, y: -2.0});
+        assert_eq!(max, coord! {x: 1.0, y: 4.0});
+        assert!(bounding_box(Vec::<Coord<f32>>::new()).is_none());
+    }
 }
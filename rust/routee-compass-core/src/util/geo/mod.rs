@@ -1,3 +1,4 @@
+pub mod bearing;
 pub mod coord;
 pub mod geo_io_utils;
 pub mod haversine;
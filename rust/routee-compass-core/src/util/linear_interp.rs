@@ -0,0 +1,77 @@
+/// a piecewise linear function of one variable, defined by sample points.
+///
+/// values between two sample points are linearly interpolated. values outside
+/// of the sampled range are clamped to the value of the nearest end point.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinearInterp1D {
+    pub x: Vec<f64>,
+    pub y: Vec<f64>,
+}
+
+impl LinearInterp1D {
+    pub fn new(x: Vec<f64>, y: Vec<f64>) -> Result<Self, String> {
+        if x.is_empty() {
+            return Err("Supplied `x` must have at least one coordinate".to_string());
+        }
+        if x.len() != y.len() {
+            return Err("Supplied `x` must have same dimensionality as `y`".to_string());
+        }
+        if x.iter().chain(y.iter()).any(|v| !v.is_finite()) {
+            return Err("Supplied coordinates must be finite".to_string());
+        }
+        if !x.windows(2).all(|w| w[0] < w[1]) {
+            return Err("Supplied `x` coordinates must be sorted and non-repeating".to_string());
+        }
+        Ok(LinearInterp1D { x, y })
+    }
+
+    /// builds an interpolation from (x, y) sample points, which must be sorted by x.
+    pub fn from_points(points: &[(f64, f64)]) -> Result<Self, String> {
+        let (x, y) = points.iter().cloned().unzip();
+        LinearInterp1D::new(x, y)
+    }
+
+    /// Interpolate a value at a given point x
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - x coordinate to interpolate at
+    ///
+    /// # Returns
+    ///
+    /// the interpolated value, clamped to the end points outside of the sampled range
+    pub fn interpolate(&self, x: f64) -> f64 {
+        let last = self.x.len() - 1;
+        if x <= self.x[0] {
+            return self.y[0];
+        }
+        if x >= self.x[last] {
+            return self.y[last];
+        }
+        // index of the first sample point greater than x, which is within 1..=last
+        let upper = self.x.partition_point(|v| *v <= x);
+        let (x0, x1) = (self.x[upper - 1], self.x[upper]);
+        let (y0, y1) = (self.y[upper - 1], self.y[upper]);
+        y0 + (x - x0) / (x1 - x0) * (y1 - y0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_linear_interp_1d() {
+        let interp = LinearInterp1D::from_points(&[(0.0, 20.0), (1.0, 60.0), (3.0, 70.0)]).unwrap();
+        assert_eq!(interp.interpolate(0.5), 40.0);
+        assert_eq!(interp.interpolate(1.0), 60.0);
+        assert_eq!(interp.interpolate(2.0), 65.0);
+        // clamped outside of the sampled range
+        assert_eq!(interp.interpolate(-1.0), 20.0);
+        assert_eq!(interp.interpolate(10.0), 70.0);
+
+        assert!(LinearInterp1D::new(vec![1.0, 0.0], vec![0.0, 1.0]).is_err());
+        assert!(LinearInterp1D::new(vec![0.0], vec![]).is_err());
+        assert!(LinearInterp1D::new(vec![], vec![]).is_err());
+    }
+}
@@ -5,7 +5,11 @@ pub mod duration_extension;
 pub mod fs;
 pub mod geo;
 pub mod io_utils;
+pub mod linear_interp;
 pub mod multiset;
 pub mod priority_queue;
 pub mod read_only_lock;
 pub mod serde;
+pub mod t_digest;
+#[cfg(test)]
+pub(crate) mod test_util;
@@ -0,0 +1,170 @@
+/// a t-digest, which estimates quantiles of a stream of values in bounded memory.
+///
+/// values are summarized as weighted centroids. centroids near the median may
+/// absorb many values while centroids near the tails stay small, so extreme
+/// quantiles such as the 95th percentile remain accurate. while fewer values
+/// than the compression have been added, every value is kept and quantiles
+/// are exact up to interpolation.
+#[derive(Debug, Clone)]
+pub struct TDigest {
+    compression: f64,
+    centroids: Vec<Centroid>,
+    buffer: Vec<f64>,
+    min: f64,
+    max: f64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Centroid {
+    mean: f64,
+    weight: f64,
+}
+
+impl Default for TDigest {
+    fn default() -> Self {
+        TDigest::new(TDigest::DEFAULT_COMPRESSION)
+    }
+}
+
+impl TDigest {
+    pub const DEFAULT_COMPRESSION: f64 = 100.0;
+
+    /// creates an empty digest. higher compression keeps more centroids, which
+    /// improves accuracy at the cost of memory.
+    pub fn new(compression: f64) -> TDigest {
+        TDigest {
+            compression,
+            centroids: vec![],
+            buffer: vec![],
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+
+    /// number of values added to the digest
+    pub fn count(&self) -> usize {
+        let merged: f64 = self.centroids.iter().map(|c| c.weight).sum();
+        merged as usize + self.buffer.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.centroids.is_empty() && self.buffer.is_empty()
+    }
+
+    /// adds a value to the digest. NaN values are ignored.
+    pub fn add(&mut self, value: f64) {
+        if value.is_nan() {
+            return;
+        }
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        self.buffer.push(value);
+        if self.buffer.len() as f64 >= self.compression * 5.0 {
+            self.centroids = self.merged();
+            self.buffer.clear();
+        }
+    }
+
+    /// estimates the value at quantile `q`, which must be within [0, 1].
+    ///
+    /// # Returns
+    ///
+    /// the estimated value, or None if the digest is empty or `q` is out of range
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        if !(0.0..=1.0).contains(&q) || self.is_empty() {
+            return None;
+        }
+        let centroids = self.merged();
+        let total: f64 = centroids.iter().map(|c| c.weight).sum();
+        let target = q * total;
+
+        // each centroid is centered on the middle of the weight it covers. the
+        // estimate interpolates between neighboring centers, and between the
+        // outermost centers and the min and max values
+        let mut cumulative = 0.0;
+        let mut prev = (0.0, self.min);
+        for centroid in centroids.iter() {
+            let center = cumulative + centroid.weight / 2.0;
+            if target < center {
+                let (prev_center, prev_mean) = prev;
+                let t = (target - prev_center) / (center - prev_center);
+                return Some(prev_mean + t * (centroid.mean - prev_mean));
+            }
+            prev = (center, centroid.mean);
+            cumulative += centroid.weight;
+        }
+        let (prev_center, prev_mean) = prev;
+        let t = (target - prev_center) / (total - prev_center);
+        Some(prev_mean + t.min(1.0) * (self.max - prev_mean))
+    }
+
+    /// merges the buffered values into the centroids, combining neighboring
+    /// centroids while their weight stays within the size bound for their
+    /// quantile, which shrinks toward the tails.
+    fn merged(&self) -> Vec<Centroid> {
+        let mut all = self.centroids.clone();
+        all.extend(self.buffer.iter().map(|v| Centroid {
+            mean: *v,
+            weight: 1.0,
+        }));
+        all.sort_by(|a, b| a.mean.total_cmp(&b.mean));
+        let total: f64 = all.iter().map(|c| c.weight).sum();
+
+        let mut result: Vec<Centroid> = Vec::with_capacity(all.len());
+        let mut cumulative = 0.0;
+        for next in all {
+            if let Some(last) = result.last_mut() {
+                let weight = last.weight + next.weight;
+                let q = (cumulative - last.weight + weight / 2.0) / total;
+                let max_weight = 4.0 * total * q * (1.0 - q) / self.compression;
+                if weight <= max_weight {
+                    last.mean += (next.mean - last.mean) * next.weight / weight;
+                    last.weight = weight;
+                    cumulative += next.weight;
+                    continue;
+                }
+            }
+            cumulative += next.weight;
+            result.push(next);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_quantiles_of_uniform_values() {
+        // a small stream is summarized exactly
+        let mut digest = TDigest::default();
+        for v in 1..=20 {
+            digest.add(v as f64);
+        }
+        assert_eq!(digest.count(), 20);
+        assert_eq!(digest.quantile(0.5), Some(10.5));
+        assert_eq!(digest.quantile(0.0), Some(1.0));
+        assert_eq!(digest.quantile(1.0), Some(20.0));
+
+        // a large stream is compressed but stays within 1% of the exact quantiles
+        let mut digest = TDigest::default();
+        for v in (0..100_000).rev() {
+            digest.add(v as f64);
+        }
+        assert_eq!(digest.count(), 100_000);
+        assert!(digest.centroids.len() < 1_000);
+        for q in [0.05, 0.5, 0.95, 0.99] {
+            let expected = q * 100_000.0;
+            let estimate = digest.quantile(q).unwrap();
+            assert!(
+                (estimate - expected).abs() < 1_000.0,
+                "q={} estimate={} expected={}",
+                q,
+                estimate,
+                expected
+            );
+        }
+        assert_eq!(TDigest::default().quantile(0.5), None);
+    }
+}
@@ -0,0 +1,80 @@
+use crate::algorithm::search::search_instance::SearchInstance;
+use crate::model::access::default::no_access_model::NoAccessModel;
+use crate::model::cost::{
+    cost_aggregation::CostAggregation, cost_model::CostModel,
+    vehicle::vehicle_cost_rate::VehicleCostRate,
+};
+use crate::model::frontier::default::no_restriction::NoRestriction;
+use crate::model::property::{edge::Edge, vertex::Vertex};
+use crate::model::road_network::graph::Graph;
+use crate::model::state::state_model::StateModel;
+use crate::model::termination::termination_model::TerminationModel;
+use crate::model::traversal::{
+    default::distance_traversal_model::DistanceTraversalModel, traversal_model::TraversalModel,
+};
+use crate::model::unit::DistanceUnit;
+use crate::util::compact_ordered_hash_map::CompactOrderedHashMap;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// builds a graph of the vertices and edges, with the adjacency lists of the edges.
+/// vertex and edge ids must match their positions in the lists.
+pub(crate) fn graph_from_edges(vertices: Vec<Vertex>, edges: Vec<Edge>) -> Graph {
+    let mut adj = vec![CompactOrderedHashMap::empty(); vertices.len()];
+    let mut rev = vec![CompactOrderedHashMap::empty(); vertices.len()];
+    for edge in &edges {
+        adj[edge.src_vertex_id.0].insert(edge.edge_id, edge.dst_vertex_id);
+        rev[edge.dst_vertex_id.0].insert(edge.edge_id, edge.src_vertex_id);
+    }
+    Graph {
+        adj: adj.into_boxed_slice(),
+        rev: rev.into_boxed_slice(),
+        edges: edges.into_boxed_slice(),
+        vertices: vertices.into_boxed_slice(),
+    }
+}
+
+/// builds a search instance over the graph that minimizes the distance in meters,
+/// without access or frontier restrictions. other fields can be replaced with
+/// struct update syntax.
+pub(crate) fn distance_search_instance(graph: impl Into<Arc<Graph>>) -> SearchInstance {
+    let traversal_model = Arc::new(DistanceTraversalModel::new(DistanceUnit::Meters));
+    traversal_search_instance(graph, traversal_model)
+}
+
+/// builds a search instance over the graph with the traversal model, minimizing its
+/// "distance" state feature as [distance_search_instance] does.
+pub(crate) fn traversal_search_instance(
+    graph: impl Into<Arc<Graph>>,
+    traversal_model: Arc<dyn TraversalModel>,
+) -> SearchInstance {
+    let state_model = Arc::new(
+        StateModel::empty()
+            .extend(traversal_model.state_features())
+            .unwrap(),
+    );
+    SearchInstance {
+        directed_graph: graph.into(),
+        cost_model: distance_cost_model(state_model.clone()),
+        state_model,
+        traversal_model,
+        access_model: Arc::new(NoAccessModel {}),
+        frontier_model: Arc::new(NoRestriction {}),
+        termination_model: Arc::new(TerminationModel::IterationsLimit { limit: 1000 }),
+        edge_cost_cache: None,
+    }
+}
+
+/// builds a cost model that sums the raw value of the "distance" state feature.
+pub(crate) fn distance_cost_model(state_model: Arc<StateModel>) -> CostModel {
+    let distance = String::from("distance");
+    CostModel::new(
+        Arc::new(HashMap::from([(distance.clone(), 1.0)])),
+        Arc::new(HashMap::from([(distance, VehicleCostRate::Raw)])),
+        Arc::new(HashMap::new()),
+        CostAggregation::Sum,
+        Arc::new(vec![]),
+        state_model,
+    )
+    .unwrap()
+}
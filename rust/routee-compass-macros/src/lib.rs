@@ -65,6 +65,14 @@ pub fn pybindings(_attr: TokenStream, item: TokenStream) -> TokenStream {
                     ))
                 })
             }
+            fn graph_nearest_vertices(&self, x: f32, y: f32, k: usize, distance_unit: Option<String>) -> PyResult<Vec<(usize, f64)>> {
+                CompassAppBindings::graph_nearest_vertices(self, x, y, k, distance_unit).map_err(|e| {
+                    PyException::new_err(format!(
+                        "error retrieving nearest vertices to ({}, {}): {}",
+                        x, y, e
+                    ))
+                })
+            }
             #[classmethod]
             pub fn _from_config_toml_string(
                 _cls: &PyType,
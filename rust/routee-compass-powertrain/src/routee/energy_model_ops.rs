@@ -1,19 +1,48 @@
 use routee_compass_core::model::{
-    access::default::turn_delays::edge_heading::EdgeHeading, road_network::edge_id::EdgeId,
-    traversal::traversal_model_error::TraversalModelError, unit::Grade,
+    access::default::turn_delays::edge_heading::EdgeHeading,
+    road_network::edge_id::EdgeId,
+    traversal::traversal_model_error::TraversalModelError,
+    unit::{as_f64::AsF64, Grade},
 };
+use routee_compass_core::util::fs::read_decoders;
 
 pub const ZERO_ENERGY: f64 = 1e-9;
 
-/// look up the grade from the grade table
+/// values in a grade table row that mark the grade of an edge as unknown
+const MISSING_GRADE_VALUES: [&str; 4] = ["", "nan", "na", "null"];
+
+/// read decoder for a grade table row. empty rows and missing-value markers
+/// (nan, na, null) are decoded as an unknown grade.
+pub fn decode_grade(idx: usize, row: String) -> Result<Option<Grade>, std::io::Error> {
+    let trimmed = row.trim();
+    if MISSING_GRADE_VALUES.contains(&trimmed.to_lowercase().as_str()) {
+        Ok(None)
+    } else {
+        read_decoders::default(idx, trimmed.to_string()).map(Some)
+    }
+}
+
+/// the mean of all known grades in a grade table, or zero if none are known
+pub fn network_average_grade(grade_table: &[Option<Grade>]) -> Grade {
+    let known = grade_table.iter().flatten().collect::<Vec<_>>();
+    if known.is_empty() {
+        Grade::ZERO
+    } else {
+        let total = known.iter().map(|g| g.as_f64()).sum::<f64>();
+        Grade::new(total / known.len() as f64)
+    }
+}
+
+/// look up the grade from the grade table. without a grade table, every edge
+/// is flat. returns None when the grade table row for the edge is unknown.
 pub fn get_grade(
-    grade_table: &Option<Box<[Grade]>>,
+    grade_table: &Option<Box<[Option<Grade>]>>,
     edge_id: EdgeId,
-) -> Result<Grade, TraversalModelError> {
+) -> Result<Option<Grade>, TraversalModelError> {
     match grade_table {
-        None => Ok(Grade::ZERO),
+        None => Ok(Some(Grade::ZERO)),
         Some(gt) => {
-            let grade: &Grade = gt.get(edge_id.as_usize()).ok_or_else(|| {
+            let grade: &Option<Grade> = gt.get(edge_id.as_usize()).ok_or_else(|| {
                 TraversalModelError::MissingIdInTabularCostFunction(
                     format!("{}", edge_id),
                     String::from("EdgeId"),
@@ -1,10 +1,11 @@
+use super::energy_model_ops;
 use super::energy_traversal_model::EnergyTraversalModel;
+use super::missing_grade_policy::MissingGradePolicy;
 use super::vehicle::VehicleType;
 use routee_compass_core::model::traversal::traversal_model::TraversalModel;
 use routee_compass_core::model::traversal::traversal_model_error::TraversalModelError;
 use routee_compass_core::model::traversal::traversal_model_service::TraversalModelService;
 use routee_compass_core::model::unit::*;
-use routee_compass_core::util::fs::read_decoders;
 use routee_compass_core::util::fs::read_utils;
 use std::collections::HashMap;
 use std::path::Path;
@@ -14,11 +15,17 @@ use std::sync::Arc;
 pub struct EnergyModelService {
     pub time_model_service: Arc<dyn TraversalModelService>,
     pub time_model_speed_unit: SpeedUnit,
-    pub grade_table: Arc<Option<Box<[Grade]>>>,
+    pub grade_table: Arc<Option<Box<[Option<Grade>]>>>,
     pub grade_table_grade_unit: GradeUnit,
+    pub missing_grade_policy: MissingGradePolicy,
+    pub network_average_grade: Grade,
     pub time_unit: TimeUnit,
     pub distance_unit: DistanceUnit,
     pub vehicle_library: HashMap<String, Arc<dyn VehicleType>>,
+    /// grams of CO2 emitted per unit of each energy feature, by feature name
+    pub emission_factors: Arc<HashMap<String, f64>>,
+    /// when true, the effective speed used to predict energy on each edge is reported
+    pub report_edge_speed: bool,
 }
 
 impl EnergyModelService {
@@ -28,6 +35,7 @@ impl EnergyModelService {
         time_model_speed_unit: SpeedUnit,
         grade_table_path_option: &Option<P>,
         grade_table_grade_unit: GradeUnit,
+        missing_grade_policy: MissingGradePolicy,
         output_time_unit_option: Option<TimeUnit>,
         output_distance_unit_option: Option<DistanceUnit>,
         vehicle_library: HashMap<String, Arc<dyn VehicleType>>,
@@ -35,25 +43,76 @@ impl EnergyModelService {
         let output_time_unit = output_time_unit_option.unwrap_or(BASE_TIME_UNIT);
         let output_distance_unit = output_distance_unit_option.unwrap_or(BASE_DISTANCE_UNIT);
 
-        let grade_table: Arc<Option<Box<[Grade]>>> = match grade_table_path_option {
-            Some(gtp) => Arc::new(Some(
-                read_utils::read_raw_file(gtp, read_decoders::default, None).map_err(|e| {
-                    TraversalModelError::FileReadError(gtp.as_ref().to_path_buf(), e.to_string())
-                })?,
-            )),
-            None => Arc::new(None),
+        let grade_table: Option<Box<[Option<Grade>]>> = match grade_table_path_option {
+            Some(gtp) => Some(
+                read_utils::read_raw_file(gtp, energy_model_ops::decode_grade, None).map_err(
+                    |e| {
+                        TraversalModelError::FileReadError(
+                            gtp.as_ref().to_path_buf(),
+                            e.to_string(),
+                        )
+                    },
+                )?,
+            ),
+            None => None,
         };
+        let network_average_grade = grade_table
+            .as_deref()
+            .map(energy_model_ops::network_average_grade)
+            .unwrap_or(Grade::ZERO);
 
         Ok(EnergyModelService {
             time_model_service,
             time_model_speed_unit,
-            grade_table,
+            grade_table: Arc::new(grade_table),
             grade_table_grade_unit,
+            missing_grade_policy,
+            network_average_grade,
             time_unit: output_time_unit,
             distance_unit: output_distance_unit,
             vehicle_library,
+            emission_factors: Arc::new(HashMap::new()),
+            report_edge_speed: false,
         })
     }
+
+    /// sets the emission factors used to accumulate the "emissions" state feature,
+    /// in grams of CO2 per unit of energy of each energy feature, such as a fuel
+    /// emission factor for "energy_liquid" or a grid intensity for "energy_electric".
+    /// when no factors are set, emissions are not tracked.
+    ///
+    /// # Arguments
+    ///
+    /// * `emission_factors` - grams of CO2 per unit of energy, by energy feature name
+    ///
+    /// # Returns
+    ///
+    /// the service with emission factors set, or an error if a factor is negative
+    pub fn with_emission_factors(
+        mut self,
+        emission_factors: HashMap<String, f64>,
+    ) -> Result<Self, TraversalModelError> {
+        for (feature, factor) in emission_factors.iter() {
+            if !factor.is_finite() || *factor < 0.0 {
+                return Err(TraversalModelError::BuildError(format!(
+                    "emission factor for {} must be non-negative, found {}",
+                    feature, factor
+                )));
+            }
+        }
+        self.emission_factors = Arc::new(emission_factors);
+        Ok(self)
+    }
+
+    /// sets whether the "edge_speed" state feature is reported. when set, each
+    /// edge traversal records the speed the vehicle prediction model received on
+    /// that edge, in the time model speed unit. this is the speed of the time
+    /// model after the input bounds of the prediction model are applied, which
+    /// may differ from the speed found in the speed table.
+    pub fn with_report_edge_speed(mut self, report_edge_speed: bool) -> Self {
+        self.report_edge_speed = report_edge_speed;
+        self
+    }
 }
 
 impl TraversalModelService for EnergyModelService {
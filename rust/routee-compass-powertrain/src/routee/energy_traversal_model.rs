@@ -1,13 +1,18 @@
 use super::energy_model_ops::get_grade;
 use super::energy_model_service::EnergyModelService;
 use super::vehicle::vehicle_type::VehicleType;
+use ordered_float::OrderedFloat;
 use routee_compass_core::model::property::edge::Edge;
 use routee_compass_core::model::property::vertex::Vertex;
+use routee_compass_core::model::road_network::edge_id::EdgeId;
+use routee_compass_core::model::state::custom_feature_format::CustomFeatureFormat;
 use routee_compass_core::model::state::state_feature::StateFeature;
 use routee_compass_core::model::state::state_model::StateModel;
 use routee_compass_core::model::traversal::state::state_variable::StateVar;
+use routee_compass_core::model::traversal::strict_mode;
 use routee_compass_core::model::traversal::traversal_model::TraversalModel;
 use routee_compass_core::model::traversal::traversal_model_error::TraversalModelError;
+use routee_compass_core::model::unit::as_f64::AsF64;
 use routee_compass_core::model::unit::*;
 use routee_compass_core::util::geo::haversine;
 use std::sync::Arc;
@@ -16,13 +21,43 @@ pub struct EnergyTraversalModel {
     pub energy_model_service: Arc<EnergyModelService>,
     pub time_model: Arc<dyn TraversalModel>,
     pub vehicle: Arc<dyn VehicleType>,
+    /// when true, a missing grade is an error instead of resolved by the missing grade policy
+    pub strict: bool,
 }
 
 impl TraversalModel for EnergyTraversalModel {
-    /// inject the state features required by the VehicleType
+    /// inject the state features required by the VehicleType, along with
+    /// emissions when emission factors are configured and the edge speed
+    /// when it is reported
     fn state_features(&self) -> Vec<(String, StateFeature)> {
         let mut features = self.vehicle.state_features();
         features.extend(self.time_model.state_features());
+        if !self.energy_model_service.emission_factors.is_empty() {
+            features.push((
+                String::from(Self::EMISSIONS),
+                StateFeature::Custom {
+                    r#type: String::from(Self::EMISSIONS),
+                    unit: String::from("grams_co2"),
+                    format: CustomFeatureFormat::FloatingPoint {
+                        initial: OrderedFloat(0.0),
+                    },
+                    output_alias: None,
+                },
+            ));
+        }
+        if self.energy_model_service.report_edge_speed {
+            features.push((
+                String::from(Self::EDGE_SPEED),
+                StateFeature::Custom {
+                    r#type: String::from("speed"),
+                    unit: self.energy_model_service.time_model_speed_unit.to_string(),
+                    format: CustomFeatureFormat::FloatingPoint {
+                        initial: OrderedFloat(0.0),
+                    },
+                    output_alias: None,
+                },
+            ));
+        }
         features
     }
 
@@ -57,10 +92,6 @@ impl TraversalModel for EnergyTraversalModel {
                 .associated_time_unit(),
         )?;
         let time_delta = current_time - prev_time;
-
-        // perform vehicle energy traversal
-        let grade = get_grade(&self.energy_model_service.grade_table, edge.edge_id)?;
-
         let distance_in_time_model_unit = BASE_DISTANCE_UNIT.convert(
             &edge.distance,
             &self
@@ -69,6 +100,17 @@ impl TraversalModel for EnergyTraversalModel {
                 .associated_distance_unit(),
         );
         let speed = Speed::from((distance_in_time_model_unit, time_delta));
+        if self.energy_model_service.report_edge_speed {
+            self.set_edge_speed(speed, state, state_model)?;
+        }
+
+        // perform vehicle energy traversal, unless the grade is unknown and
+        // the missing grade policy skips energy for the edge
+        let grade = match self.edge_grade(edge.edge_id)? {
+            Some(grade) => grade,
+            None => return Ok(()),
+        };
+
         self.vehicle.consume_energy(
             (speed, self.energy_model_service.time_model_speed_unit),
             (grade, self.energy_model_service.grade_table_grade_unit),
@@ -76,6 +118,7 @@ impl TraversalModel for EnergyTraversalModel {
             state,
             state_model,
         )?;
+        self.add_emissions(&prev, state, state_model)?;
 
         Ok(())
     }
@@ -98,12 +141,14 @@ impl TraversalModel for EnergyTraversalModel {
             return Ok(());
         }
 
+        let prev = state.to_vec();
         self.time_model.estimate_traversal(od, state, state_model)?;
         self.vehicle.best_case_energy_state(
             (distance, self.energy_model_service.distance_unit),
             state,
             state_model,
         )?;
+        self.add_emissions(&prev, state, state_model)?;
 
         Ok(())
     }
@@ -111,6 +156,143 @@ impl TraversalModel for EnergyTraversalModel {
 
 impl EnergyTraversalModel {
     const TIME: &'static str = "time";
+    const EMISSIONS: &'static str = "emissions";
+    const EDGE_SPEED: &'static str = "edge_speed";
+
+    /// records the speed the vehicle prediction model receives for the speed of
+    /// the time model on an edge, which replaces the edge speed of the previous edge.
+    fn set_edge_speed(
+        &self,
+        speed: Speed,
+        state: &mut [StateVar],
+        state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        let speed_unit = self.energy_model_service.time_model_speed_unit;
+        let (effective_speed, effective_unit) =
+            self.vehicle
+                .effective_speed((speed, speed_unit), state, state_model)?;
+        let edge_speed = effective_unit.convert(&effective_speed, &speed_unit);
+        state_model.set_custom_f64(state, &String::from(Self::EDGE_SPEED), &edge_speed.as_f64())?;
+        Ok(())
+    }
+
+    /// adds the emissions of the energy consumed since the previous state, using
+    /// the emission factor of each energy feature of the vehicle. factors for
+    /// energy features the vehicle does not have are ignored.
+    fn add_emissions(
+        &self,
+        prev: &[StateVar],
+        state: &mut [StateVar],
+        state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        let emission_factors = &self.energy_model_service.emission_factors;
+        if emission_factors.is_empty() {
+            return Ok(());
+        }
+        let mut emissions = 0.0;
+        for (feature, factor) in emission_factors.iter() {
+            if state_model.contains_key(feature) {
+                let energy = state_model.get_delta(prev, state, feature)?;
+                emissions += energy.0 * factor;
+            }
+        }
+        let name = String::from(Self::EMISSIONS);
+        let prev_emissions = state_model.get_custom_f64(state, &name)?;
+        state_model.set_custom_f64(state, &name, &(prev_emissions + emissions))?;
+        Ok(())
+    }
+
+    /// the grade of an edge from the grade table, falling back to the missing grade
+    /// policy when the edge has no grade, which fails in strict mode.
+    ///
+    /// # Returns
+    ///
+    /// the grade, in the grade table unit, or None if no energy should be
+    /// computed for the edge
+    fn edge_grade(&self, edge_id: EdgeId) -> Result<Option<Grade>, TraversalModelError> {
+        let grade_option = get_grade(&self.energy_model_service.grade_table, edge_id)?;
+        if self.strict && grade_option.is_none() {
+            return Err(TraversalModelError::StrictModeFallback(format!(
+                "edge {} has no grade",
+                edge_id
+            )));
+        }
+        Ok(self.energy_model_service.missing_grade_policy.resolve(
+            grade_option,
+            self.energy_model_service.network_average_grade,
+        ))
+    }
+
+    /// computes the energy consumed on each edge of a known route without running
+    /// a search, such as for reporting where along the route energy is spent.
+    /// energy is predicted by the vehicle the same way as during traversal, using
+    /// the grade table and missing grade policy of the service, which fails in
+    /// strict mode, but at the given speeds instead of the speeds of the time model.
+    ///
+    /// # Arguments
+    ///
+    /// * `edges`  - the edges of the route, in order
+    /// * `speeds` - the speed on each edge, in the time model speed unit of the service
+    ///
+    /// # Returns
+    ///
+    /// the energy consumed on each edge, in the energy unit of the vehicle, or an
+    /// error if the lengths of `edges` and `speeds` differ or the vehicle does not
+    /// have exactly one energy feature, as is the case for a PHEV
+    pub fn energy_profile(
+        &self,
+        edges: &[Edge],
+        speeds: &[Speed],
+    ) -> Result<Vec<f64>, TraversalModelError> {
+        if edges.len() != speeds.len() {
+            return Err(TraversalModelError::InternalError(format!(
+                "energy profile has {} edges but {} speeds",
+                edges.len(),
+                speeds.len()
+            )));
+        }
+        let features = self.vehicle.state_features();
+        let energy_features = features
+            .iter()
+            .filter(|(_, feature)| matches!(feature, StateFeature::Energy { .. }))
+            .map(|(name, _)| name.clone())
+            .collect::<Vec<_>>();
+        let energy_feature = match energy_features.as_slice() {
+            [name] => name.clone(),
+            _ => {
+                return Err(TraversalModelError::InternalError(format!(
+                "energy profile requires a vehicle with one energy feature, vehicle '{}' has {:?}",
+                self.vehicle.name(),
+                energy_features
+            )))
+            }
+        };
+        let state_model = StateModel::empty().extend(features)?;
+        let mut state = state_model.initial_state()?;
+
+        let mut profile = Vec::with_capacity(edges.len());
+        for (edge, speed) in edges.iter().zip(speeds.iter()) {
+            let grade = match self.edge_grade(edge.edge_id)? {
+                Some(grade) => grade,
+                None => {
+                    profile.push(0.0);
+                    continue;
+                }
+            };
+            let distance = BASE_DISTANCE_UNIT
+                .convert(&edge.distance, &self.energy_model_service.distance_unit);
+            let prev = state.to_vec();
+            self.vehicle.consume_energy(
+                (*speed, self.energy_model_service.time_model_speed_unit),
+                (grade, self.energy_model_service.grade_table_grade_unit),
+                (distance, self.energy_model_service.distance_unit),
+                &mut state,
+                &state_model,
+            )?;
+            profile.push(state_model.get_delta(&prev, &state, &energy_feature)?.0);
+        }
+        Ok(profile)
+    }
 
     pub fn new(
         energy_model_service: Arc<EnergyModelService>,
@@ -146,11 +328,13 @@ impl EnergyTraversalModel {
             Some(mr) => Ok(mr.clone()),
         }?
         .update_from_query(conf)?;
+        let strict = strict_mode::is_strict(conf)?;
 
         Ok(EnergyTraversalModel {
             energy_model_service,
             time_model,
             vehicle,
+            strict,
         })
     }
 }
@@ -159,7 +343,8 @@ impl EnergyTraversalModel {
 mod tests {
     use super::*;
     use crate::routee::{
-        prediction::load_prediction_model, prediction::model_type::ModelType,
+        missing_grade_policy::MissingGradePolicy,
+        test_util::{camry_record, test_file},
         vehicle::default::ice::ICE,
     };
     use geo::coord;
@@ -174,82 +359,81 @@ mod tests {
         },
         util::geo::coord::InternalCoord,
     };
-    use std::{collections::HashMap, path::PathBuf};
+    use std::collections::HashMap;
 
-    #[test]
-    fn test_edge_cost_lookup_from_file() {
-        let speed_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-            .join("src")
-            .join("routee")
-            .join("test")
-            .join("velocities.txt");
-        let grade_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-            .join("src")
-            .join("routee")
-            .join("test")
-            .join("grades.txt");
-        let model_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-            .join("src")
-            .join("routee")
-            .join("test")
-            .join("Toyota_Camry.bin");
-        let v = Vertex {
+    fn mock_vertex() -> Vertex {
+        Vertex {
             vertex_id: VertexId(0),
             coordinate: InternalCoord(coord! {x: -86.67, y: 36.12}),
-        };
-        fn mock_edge(edge_id: usize) -> Edge {
-            Edge {
-                edge_id: EdgeId(edge_id),
-                src_vertex_id: VertexId(0),
-                dst_vertex_id: VertexId(1),
-                distance: Distance::new(100.0),
-            }
         }
-        let model_record = load_prediction_model(
-            "Toyota_Camry".to_string(),
-            &model_file_path,
-            ModelType::Smartcore,
-            SpeedUnit::MilesPerHour,
-            GradeUnit::Decimal,
-            EnergyRateUnit::GallonsGasolinePerMile,
-            None,
-            None,
-            None,
-        )
-        .unwrap();
+    }
 
-        let state_model = Arc::new(StateModel::empty());
-        let camry = ICE::new("Toyota_Camry".to_string(), model_record).unwrap();
+    fn mock_edge(edge_id: usize) -> Edge {
+        Edge {
+            edge_id: EdgeId(edge_id),
+            src_vertex_id: VertexId(0),
+            dst_vertex_id: VertexId(1),
+            distance: Distance::new(100.0),
+        }
+    }
+
+    /// builds a Toyota Camry energy model service over the test speeds and the given grade table
+    fn build_camry_service(
+        grade_file: &str,
+        missing_grade_policy: MissingGradePolicy,
+    ) -> EnergyModelService {
+        let model_record = camry_record(GradeUnit::Decimal);
+        let camry = ICE::new("Toyota_Camry".to_string(), model_record, None).unwrap();
 
         let mut model_library: HashMap<String, Arc<dyn VehicleType>> = HashMap::new();
         model_library.insert("Toyota_Camry".to_string(), Arc::new(camry));
 
         let time_engine = Arc::new(
-            SpeedTraversalEngine::new(&speed_file_path, SpeedUnit::KilometersPerHour, None, None)
-                .unwrap(),
+            SpeedTraversalEngine::new(
+                &test_file("velocities.txt"),
+                Some(SpeedUnit::KilometersPerHour),
+                None,
+                None,
+            )
+            .unwrap(),
         );
-        let time_service = SpeedLookupService { e: time_engine };
+        let time_service = SpeedLookupService {
+            e: time_engine,
+            congestion: None,
+        };
 
-        let service = EnergyModelService::new(
+        EnergyModelService::new(
             Arc::new(time_service),
             SpeedUnit::MilesPerHour,
-            // &speed_file_path,
-            &Some(grade_file_path),
-            // SpeedUnit::KilometersPerHour,
+            &Some(test_file(grade_file)),
             GradeUnit::Millis,
+            missing_grade_policy,
             None,
             None,
             model_library,
         )
-        .unwrap();
-        let arc_service = Arc::new(service);
+        .unwrap()
+    }
+
+    fn build_camry_model(
+        grade_file: &str,
+        missing_grade_policy: MissingGradePolicy,
+    ) -> EnergyTraversalModel {
+        let service = build_camry_service(grade_file, missing_grade_policy);
         let conf = serde_json::json!({
             "model_name": "Toyota_Camry",
         });
-        let model = EnergyTraversalModel::new(arc_service, &conf).unwrap();
+        EnergyTraversalModel::new(Arc::new(service), &conf).unwrap()
+    }
+
+    #[test]
+    fn test_edge_cost_lookup_from_file() {
+        let model = build_camry_model("grades.txt", MissingGradePolicy::Zero);
+        let state_model = Arc::new(StateModel::empty());
         let updated_state_model = state_model.extend(model.state_features()).unwrap();
         println!("{:?}", updated_state_model.to_vec());
         let mut state = updated_state_model.initial_state().unwrap();
+        let v = mock_vertex();
         let e1 = mock_edge(0);
         // 100 meters @ 10kph should take 36 seconds ((0.1/10) * 3600)
         model
@@ -257,4 +441,203 @@ mod tests {
             .unwrap();
         println!("{:?}", state);
     }
+
+    #[test]
+    fn test_missing_grade_policy() {
+        // edge 3 has no grade. the known grades of 0, 3000 and 0 millis average to 1000 millis,
+        // which is steep enough to change the test model's prediction from that of a zero grade
+        let traverse_missing_grade_edge = |policy: MissingGradePolicy| {
+            let model = build_camry_model("grades_missing.txt", policy);
+            let state_model = StateModel::empty().extend(model.state_features()).unwrap();
+            let mut state = state_model.initial_state().unwrap();
+            let v = mock_vertex();
+            model
+                .traverse_edge((&v, &mock_edge(3), &v), &mut state, &state_model)
+                .unwrap();
+            state_model
+                .get_state_variable(&state, &String::from("energy_liquid"))
+                .unwrap()
+                .0
+        };
+        let service = build_camry_service("grades_missing.txt", MissingGradePolicy::Zero);
+        let network_average = service.network_average_grade.as_f64();
+        assert!(
+            (network_average - 1000.0).abs() < 1e-9,
+            "{}",
+            network_average
+        );
+        let zero = traverse_missing_grade_edge(MissingGradePolicy::Zero);
+        let average = traverse_missing_grade_edge(MissingGradePolicy::NetworkAverage);
+        let skip = traverse_missing_grade_edge(MissingGradePolicy::Skip);
+        assert!(zero > 0.0, "{}", zero);
+        assert!(average > zero, "{} should exceed {}", average, zero);
+        assert_eq!(skip, 0.0);
+    }
+
+    #[test]
+    fn test_energy_profile_sums_to_route_energy() {
+        let model = build_camry_model("grades.txt", MissingGradePolicy::Zero);
+        let state_model = StateModel::empty().extend(model.state_features()).unwrap();
+        let mut state = state_model.initial_state().unwrap();
+        let v = mock_vertex();
+        let edges = (0..3).map(mock_edge).collect::<Vec<_>>();
+
+        // traverse the route, recording the speed on each edge as the model does
+        let mut speeds = vec![];
+        for edge in edges.iter() {
+            let prev = state.to_vec();
+            model
+                .traverse_edge((&v, edge, &v), &mut state, &state_model)
+                .unwrap();
+            let time = state_model
+                .get_time(&state, &String::from("time"), &TimeUnit::Hours)
+                .unwrap()
+                - state_model
+                    .get_time(&prev, &String::from("time"), &TimeUnit::Hours)
+                    .unwrap();
+            let distance = BASE_DISTANCE_UNIT.convert(&edge.distance, &DistanceUnit::Miles);
+            speeds.push(Speed::from((distance, time)));
+        }
+        let route_energy = state_model
+            .get_state_variable(&state, &String::from("energy_liquid"))
+            .unwrap()
+            .0;
+
+        let profile = model.energy_profile(&edges, &speeds).unwrap();
+        assert_eq!(profile.len(), 3);
+        assert!(profile.iter().all(|e| *e > 0.0), "{:?}", profile);
+        let profile_energy: f64 = profile.iter().sum();
+        assert!(
+            (profile_energy - route_energy).abs() < 1e-12,
+            "{} != {}",
+            profile_energy,
+            route_energy
+        );
+
+        let mismatched = model.energy_profile(&edges, &speeds[..2]);
+        assert!(mismatched.is_err());
+    }
+
+    #[test]
+    fn test_strict_mode_missing_grade() {
+        // edge 3 has no grade, which falls back to the missing grade policy
+        // unless the query runs in strict mode
+        let service = Arc::new(build_camry_service(
+            "grades_missing.txt",
+            MissingGradePolicy::Zero,
+        ));
+        let traverse = |conf: serde_json::Value, edge_id: usize| {
+            let model = EnergyTraversalModel::new(service.clone(), &conf).unwrap();
+            let state_model = StateModel::empty().extend(model.state_features()).unwrap();
+            let mut state = state_model.initial_state().unwrap();
+            let v = mock_vertex();
+            model.traverse_edge((&v, &mock_edge(edge_id), &v), &mut state, &state_model)
+        };
+        let lenient = serde_json::json!({ "model_name": "Toyota_Camry" });
+        let strict = serde_json::json!({ "model_name": "Toyota_Camry", "strict": true });
+        assert!(traverse(lenient.clone(), 3).is_ok());
+        assert!(traverse(strict.clone(), 0).is_ok());
+        assert!(matches!(
+            traverse(strict.clone(), 3),
+            Err(TraversalModelError::StrictModeFallback(_))
+        ));
+
+        // the energy profile of a route applies the same strict handling
+        let profile = |conf: serde_json::Value| {
+            let model = EnergyTraversalModel::new(service.clone(), &conf).unwrap();
+            model.energy_profile(&[mock_edge(0), mock_edge(3)], &[Speed::new(20.0); 2])
+        };
+        assert!(profile(lenient).is_ok());
+        assert!(matches!(
+            profile(strict),
+            Err(TraversalModelError::StrictModeFallback(_))
+        ));
+    }
+
+    #[test]
+    fn test_emissions_scale_with_energy() {
+        // traverses the first three test edges, returning (energy, emissions) after each edge
+        let traverse = |factor: f64| {
+            let service = build_camry_service("grades.txt", MissingGradePolicy::Zero)
+                .with_emission_factors(HashMap::from([(String::from("energy_liquid"), factor)]))
+                .unwrap();
+            let conf = serde_json::json!({ "model_name": "Toyota_Camry" });
+            let model = EnergyTraversalModel::new(Arc::new(service), &conf).unwrap();
+            let state_model = StateModel::empty().extend(model.state_features()).unwrap();
+            let mut state = state_model.initial_state().unwrap();
+            let v = mock_vertex();
+            (0..3)
+                .map(|edge_id| {
+                    model
+                        .traverse_edge((&v, &mock_edge(edge_id), &v), &mut state, &state_model)
+                        .unwrap();
+                    let energy = state_model
+                        .get_state_variable(&state, &String::from("energy_liquid"))
+                        .unwrap()
+                        .0;
+                    let emissions = state_model
+                        .get_custom_f64(&state, &String::from("emissions"))
+                        .unwrap();
+                    (energy, emissions)
+                })
+                .collect::<Vec<_>>()
+        };
+        // grams of CO2 per gallon of gasoline
+        let factor = 8887.0;
+        let results = traverse(factor);
+        let doubled = traverse(factor * 2.0);
+        for ((energy, emissions), (_, doubled_emissions)) in results.iter().zip(doubled.iter()) {
+            assert!(*energy > 0.0);
+            assert!((emissions - energy * factor).abs() < 1e-9, "{}", emissions);
+            assert!((doubled_emissions - emissions * 2.0).abs() < 1e-9);
+        }
+        assert!(results[2].1 > results[0].1);
+    }
+
+    #[test]
+    fn test_edge_speed_reports_clamped_speed() {
+        // the test speeds of edges 0 and 1 are 10 and 20 kph, about 6.2 and 12.4 mph.
+        // the Camry model here only accepts speeds of at least 10 mph.
+        let mut service = build_camry_service("grades.txt", MissingGradePolicy::Zero)
+            .with_report_edge_speed(true);
+        let model_record = camry_record(GradeUnit::Percent)
+            .with_input_bounds(Some((Speed::new(10.0), Speed::new(80.0))), None)
+            .unwrap();
+        let camry = ICE::new("Toyota_Camry".to_string(), model_record, None).unwrap();
+        service
+            .vehicle_library
+            .insert("Toyota_Camry".to_string(), Arc::new(camry));
+        let conf = serde_json::json!({ "model_name": "Toyota_Camry" });
+        let model = EnergyTraversalModel::new(Arc::new(service), &conf).unwrap();
+        let state_model = StateModel::empty().extend(model.state_features()).unwrap();
+        let mut state = state_model.initial_state().unwrap();
+        let v = mock_vertex();
+        let edge_speed = String::from("edge_speed");
+
+        model
+            .traverse_edge((&v, &mock_edge(0), &v), &mut state, &state_model)
+            .unwrap();
+        let clamped = state_model.get_custom_f64(&state, &edge_speed).unwrap();
+        assert!((clamped - 10.0).abs() < 1e-9, "{}", clamped);
+
+        model
+            .traverse_edge((&v, &mock_edge(1), &v), &mut state, &state_model)
+            .unwrap();
+        let unclamped = state_model.get_custom_f64(&state, &edge_speed).unwrap();
+        assert!((unclamped - 12.43).abs() < 1e-2, "{}", unclamped);
+    }
+
+    #[test]
+    fn test_edge_speed_not_reported_by_default() {
+        let model = build_camry_model("grades.txt", MissingGradePolicy::Zero);
+        let features = model.state_features();
+        assert!(!features.iter().any(|(name, _)| name == "edge_speed"));
+    }
+
+    #[test]
+    fn test_negative_emission_factor() {
+        let result = build_camry_service("grades.txt", MissingGradePolicy::Zero)
+            .with_emission_factors(HashMap::from([(String::from("energy_liquid"), -1.0)]));
+        assert!(matches!(result, Err(TraversalModelError::BuildError(_))));
+    }
 }
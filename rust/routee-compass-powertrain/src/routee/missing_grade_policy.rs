@@ -0,0 +1,37 @@
+use routee_compass_core::model::unit::Grade;
+use serde::{Deserialize, Serialize};
+
+/// declares how the energy model treats edges whose grade is unknown, which
+/// are rows of the grade table that are empty or hold a missing-value marker.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MissingGradePolicy {
+    /// treat the edge as flat ground
+    #[default]
+    Zero,
+    /// use the mean of all known grades in the grade table
+    NetworkAverage,
+    /// do not compute energy for the edge
+    Skip,
+}
+
+impl MissingGradePolicy {
+    /// resolves the grade used for energy estimation on an edge.
+    ///
+    /// # Arguments
+    ///
+    /// * `grade`           - the grade of the edge, if known
+    /// * `network_average` - the mean of all known grades in the grade table
+    ///
+    /// # Returns
+    ///
+    /// the grade to use, or None if no energy should be computed for the edge
+    pub fn resolve(&self, grade: Option<Grade>, network_average: Grade) -> Option<Grade> {
+        match (grade, self) {
+            (Some(g), _) => Some(g),
+            (None, MissingGradePolicy::Zero) => Some(Grade::ZERO),
+            (None, MissingGradePolicy::NetworkAverage) => Some(network_average),
+            (None, MissingGradePolicy::Skip) => None,
+        }
+    }
+}
@@ -1,5 +1,8 @@
 pub mod energy_model_ops;
 pub mod energy_model_service;
 pub mod energy_traversal_model;
+pub mod missing_grade_policy;
 pub mod prediction;
+#[cfg(test)]
+pub(crate) mod test_util;
 pub mod vehicle;
@@ -1,15 +1,26 @@
 use ordered_float::OrderedFloat;
 
-use super::utils::find_nearest_index;
+use super::{extrapolation_mode::ExtrapolationMode, utils::find_nearest_index};
 
 pub struct BilinearInterp {
     pub x: Vec<OrderedFloat<f64>>,
     pub y: Vec<OrderedFloat<f64>>,
     pub values: Vec<Vec<f64>>,
+    pub mode: ExtrapolationMode,
 }
 
 impl BilinearInterp {
     pub fn new(x: Vec<f64>, y: Vec<f64>, values: Vec<Vec<f64>>) -> Result<Self, String> {
+        BilinearInterp::new_with_mode(x, y, values, ExtrapolationMode::Error)
+    }
+
+    /// builds an interpolation with the given handling of points outside of the grid.
+    pub fn new_with_mode(
+        x: Vec<f64>,
+        y: Vec<f64>,
+        values: Vec<Vec<f64>>,
+        mode: ExtrapolationMode,
+    ) -> Result<Self, String> {
         if x.len() != values.len() {
             return Err("Supplied `x` must have same dimensionality as `values`".to_string());
         }
@@ -24,7 +35,7 @@ impl BilinearInterp {
         }
         let x = x.into_iter().map(OrderedFloat::from).collect();
         let y = y.into_iter().map(OrderedFloat::from).collect();
-        Ok(BilinearInterp { x, y, values })
+        Ok(BilinearInterp { x, y, values, mode })
     }
 
     /// Interpolate a value at a given point (x, y) using bilinear interpolation
@@ -38,8 +49,13 @@ impl BilinearInterp {
     /// # Returns
     ///
     /// * `Ok(f64)` - interpolated value
-    /// * `Err(&str)` - An error if the point is outside of the grid bounds
+    /// * `Err(&str)` - An error if the point is outside of the grid bounds, unless
+    ///   the extrapolation mode clamps the point into the grid
     pub fn interpolate(&self, x: f64, y: f64) -> Result<f64, &'static str> {
+        let (x, y) = match self.mode {
+            ExtrapolationMode::Error => (x, y),
+            ExtrapolationMode::Clamp => (clamp_to_axis(x, &self.x), clamp_to_axis(y, &self.y)),
+        };
         let x_index = find_nearest_index(&self.x, OrderedFloat(x));
         let y_index = find_nearest_index(&self.y, OrderedFloat(y));
 
@@ -63,9 +79,19 @@ impl BilinearInterp {
     }
 }
 
+/// clamps a coordinate into the range of an axis.
+fn clamp_to_axis(value: f64, axis: &[OrderedFloat<f64>]) -> f64 {
+    match (axis.first(), axis.last()) {
+        (Some(first), Some(last)) => value.clamp(first.into_inner(), last.into_inner()),
+        _ => value,
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::routee::prediction::interpolation::bilinear_interp::BilinearInterp;
+    use crate::routee::prediction::interpolation::{
+        bilinear_interp::BilinearInterp, extrapolation_mode::ExtrapolationMode,
+    };
 
     // test targets found using https://www.omnicalculator.com/math/bilinear-interpolation
     #[test]
@@ -90,4 +116,26 @@ mod tests {
         // errors out for values greater than bounds
         assert!(interp.interpolate(3.0, 3.0).is_err());
     }
+
+    #[test]
+    fn test_multilinear_2d_clamp() {
+        let x = vec![0.0, 1.0, 2.0];
+        let y = vec![0.0, 1.0, 2.0];
+        let values = vec![
+            vec![0.0, 2.0, 1.9],
+            vec![2.0, 4.0, 3.1],
+            vec![5.0, 0.0, 1.4],
+        ];
+
+        let interp =
+            BilinearInterp::new_with_mode(x, y, values.clone(), ExtrapolationMode::Clamp).unwrap();
+
+        // points beyond the grid return the value at the nearest edge
+        assert_eq!(interp.interpolate(3.0, 3.0).unwrap(), values[2][2]);
+        assert_eq!(interp.interpolate(-1.0, -1.0).unwrap(), values[0][0]);
+        assert_eq!(interp.interpolate(3.0, 0.5).unwrap(), 2.5);
+
+        // points within the grid are interpolated as usual
+        assert_eq!(interp.interpolate(1.52, 0.36).unwrap(), 2.9696);
+    }
 }
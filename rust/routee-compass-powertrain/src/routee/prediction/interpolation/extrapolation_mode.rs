@@ -0,0 +1,9 @@
+/// how an interpolation handles points outside of its grid.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExtrapolationMode {
+    /// points beyond the upper edge of the grid are an error
+    #[default]
+    Error,
+    /// points are clamped to the nearest edge of the grid, returning the boundary value
+    Clamp,
+}
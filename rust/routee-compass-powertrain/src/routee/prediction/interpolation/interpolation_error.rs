@@ -0,0 +1,9 @@
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum InterpolationError {
+    #[error("Cannot interpolate outside of grid bounds")]
+    OutOfBounds,
+    #[error("Supplied `{0}` must have same dimensionality as `values`")]
+    DimensionMismatch(&'static str),
+    #[error("Supplied `{0}` coordinates must be sorted and non-repeating")]
+    UnsortedCoordinates(&'static str),
+}
@@ -1,7 +1,8 @@
 use std::path::Path;
 
 use crate::routee::prediction::{
-    load_prediction_model, model_type::ModelType, prediction_model::PredictionModel,
+    model_input_feature::ModelInputFeature, model_type::ModelType,
+    prediction_model::PredictionModel, prediction_model_ops::PredictionModelLoader,
 };
 
 use routee_compass_core::{
@@ -58,6 +59,7 @@ impl InterpolationSpeedGradeModel {
     pub fn new<P: AsRef<Path>>(
         underlying_model_path: &P,
         underlying_model_type: ModelType,
+        underlying_input_features: Vec<ModelInputFeature>,
         underlying_model_name: String,
         speed_unit: SpeedUnit,
         speed_bounds: (Speed, Speed),
@@ -68,7 +70,7 @@ impl InterpolationSpeedGradeModel {
         energy_rate_unit: EnergyRateUnit,
     ) -> Result<Self, TraversalModelError> {
         // load underlying model to build the interpolation grid
-        let model = load_prediction_model(
+        let model = PredictionModelLoader::new(
             underlying_model_name,
             underlying_model_path,
             underlying_model_type,
@@ -78,7 +80,9 @@ impl InterpolationSpeedGradeModel {
             None,
             None,
             None,
-        )?;
+        )
+        .with_input_features(underlying_input_features)
+        .load()?;
 
         // Create a linear grid of speed and grade values
         let speed_values = linspace(speed_bounds.0.as_f64(), speed_bounds.1.as_f64(), speed_bins);
@@ -142,6 +146,7 @@ mod test {
         let model = InterpolationSpeedGradeModel::new(
             &model_path,
             ModelType::Smartcore,
+            ModelInputFeature::DEFAULT_ORDER.to_vec(),
             "Toyota Camry".to_string(),
             SpeedUnit::MilesPerHour,
             (Speed::new(0.0), Speed::new(100.0)),
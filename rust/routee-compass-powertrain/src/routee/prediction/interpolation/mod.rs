@@ -1,3 +1,6 @@
 pub mod bilinear_interp;
+pub mod extrapolation_mode;
+pub mod interpolation_error;
 pub mod interpolation_speed_grade_model;
+pub mod trilinear_interp;
 pub mod utils;
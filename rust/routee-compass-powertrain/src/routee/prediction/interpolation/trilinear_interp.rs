@@ -0,0 +1,161 @@
+use ordered_float::OrderedFloat;
+
+use super::{interpolation_error::InterpolationError, utils::find_nearest_index};
+
+pub struct TrilinearInterp {
+    pub x: Vec<OrderedFloat<f64>>,
+    pub y: Vec<OrderedFloat<f64>>,
+    pub z: Vec<OrderedFloat<f64>>,
+    pub values: Vec<Vec<Vec<f64>>>,
+}
+
+impl TrilinearInterp {
+    pub fn new(
+        x: Vec<f64>,
+        y: Vec<f64>,
+        z: Vec<f64>,
+        values: Vec<Vec<Vec<f64>>>,
+    ) -> Result<Self, InterpolationError> {
+        if x.is_empty() || x.len() != values.len() {
+            return Err(InterpolationError::DimensionMismatch("x"));
+        }
+        if y.is_empty() || values.iter().any(|plane| plane.len() != y.len()) {
+            return Err(InterpolationError::DimensionMismatch("y"));
+        }
+        if z.is_empty() || values.iter().flatten().any(|row| row.len() != z.len()) {
+            return Err(InterpolationError::DimensionMismatch("z"));
+        }
+        if !x.windows(2).all(|w| w[0] < w[1]) {
+            return Err(InterpolationError::UnsortedCoordinates("x"));
+        }
+        if !y.windows(2).all(|w| w[0] < w[1]) {
+            return Err(InterpolationError::UnsortedCoordinates("y"));
+        }
+        if !z.windows(2).all(|w| w[0] < w[1]) {
+            return Err(InterpolationError::UnsortedCoordinates("z"));
+        }
+        let x = x.into_iter().map(OrderedFloat::from).collect();
+        let y = y.into_iter().map(OrderedFloat::from).collect();
+        let z = z.into_iter().map(OrderedFloat::from).collect();
+        Ok(TrilinearInterp { x, y, z, values })
+    }
+
+    /// Interpolate a value at a given point (x, y, z) using trilinear interpolation
+    /// Based on https://en.wikipedia.org/wiki/Trilinear_interpolation
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - x coordinate to interpolate at
+    /// * `y` - y coordinate to interpolate at
+    /// * `z` - z coordinate to interpolate at
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(f64)` - interpolated value
+    /// * `Err(InterpolationError)` - An error if the point is outside of the grid bounds
+    pub fn interpolate(&self, x: f64, y: f64, z: f64) -> Result<f64, InterpolationError> {
+        // the nearest index of a coordinate below the grid is 0, so those are checked here
+        if x < self.x[0].into_inner() || y < self.y[0].into_inner() || z < self.z[0].into_inner() {
+            return Err(InterpolationError::OutOfBounds);
+        }
+        let x_index = find_nearest_index(&self.x, OrderedFloat(x));
+        let y_index = find_nearest_index(&self.y, OrderedFloat(y));
+        let z_index = find_nearest_index(&self.z, OrderedFloat(z));
+
+        if x_index >= self.x.len() - 1 || y_index >= self.y.len() - 1 || z_index >= self.z.len() - 1
+        {
+            return Err(InterpolationError::OutOfBounds);
+        }
+        let x0 = self.x[x_index].into_inner();
+        let x1 = self.x[x_index + 1].into_inner();
+        let y0 = self.y[y_index].into_inner();
+        let y1 = self.y[y_index + 1].into_inner();
+        let z0 = self.z[z_index].into_inner();
+        let z1 = self.z[z_index + 1].into_inner();
+
+        let xd = (x - x0) / (x1 - x0);
+        let yd = (y - y0) / (y1 - y0);
+        let zd = (z - z0) / (z1 - z0);
+
+        let c = |i: usize, j: usize, k: usize| self.values[x_index + i][y_index + j][z_index + k];
+
+        // interpolate along x, then y, then z
+        let c00 = c(0, 0, 0) * (1.0 - xd) + c(1, 0, 0) * xd;
+        let c01 = c(0, 0, 1) * (1.0 - xd) + c(1, 0, 1) * xd;
+        let c10 = c(0, 1, 0) * (1.0 - xd) + c(1, 1, 0) * xd;
+        let c11 = c(0, 1, 1) * (1.0 - xd) + c(1, 1, 1) * xd;
+
+        let c0 = c00 * (1.0 - yd) + c10 * yd;
+        let c1 = c01 * (1.0 - yd) + c11 * yd;
+
+        Ok(c0 * (1.0 - zd) + c1 * zd)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::routee::prediction::interpolation::{
+        interpolation_error::InterpolationError, trilinear_interp::TrilinearInterp,
+    };
+
+    #[test]
+    fn test_multilinear_3d() {
+        // a unit cube with the values of f(x, y, z) = x + 2y + 4z + xyz at its
+        // corners, which trilinear interpolation reproduces exactly
+        let x = vec![0.0, 1.0];
+        let y = vec![0.0, 1.0];
+        let z = vec![0.0, 1.0];
+        let values = vec![
+            vec![vec![0.0, 4.0], vec![2.0, 6.0]], // (x0, y0, z0), (x0, y0, z1), (x0, y1, z0), (x0, y1, z1)
+            vec![vec![1.0, 5.0], vec![3.0, 8.0]], // (x1, y0, z0), (x1, y0, z1), (x1, y1, z0), (x1, y1, z1)
+        ];
+
+        let interp = TrilinearInterp::new(x, y, z, values.clone()).unwrap();
+
+        assert_eq!(interp.interpolate(0.5, 0.5, 0.5).unwrap(), 3.625);
+
+        assert_eq!(interp.interpolate(0.25, 0.5, 0.75).unwrap(), 4.34375);
+
+        // returns value at (x1, y1, z1)
+        assert_eq!(interp.interpolate(1.0, 1.0, 1.0).unwrap(), values[1][1][1]);
+
+        // errors out for values greater than bounds on any axis
+        assert_eq!(
+            interp.interpolate(2.0, 0.5, 0.5),
+            Err(InterpolationError::OutOfBounds)
+        );
+        assert_eq!(
+            interp.interpolate(0.5, 0.5, 2.0),
+            Err(InterpolationError::OutOfBounds)
+        );
+
+        // errors out for values less than bounds on any axis
+        for (x, y, z) in [(-0.5, 0.5, 0.5), (0.5, -0.5, 0.5), (0.5, 0.5, -0.5)] {
+            assert_eq!(
+                interp.interpolate(x, y, z),
+                Err(InterpolationError::OutOfBounds)
+            );
+        }
+    }
+
+    #[test]
+    fn test_trilinear_invalid_grid() {
+        let values = vec![vec![vec![0.0, 1.0], vec![0.0, 1.0]]; 2];
+        let result = TrilinearInterp::new(
+            vec![0.0, 1.0],
+            vec![0.0, 1.0],
+            vec![0.0, 1.0, 2.0],
+            values.clone(),
+        );
+        assert_eq!(
+            result.err(),
+            Some(InterpolationError::DimensionMismatch("z"))
+        );
+
+        let result = TrilinearInterp::new(vec![1.0, 0.0], vec![0.0, 1.0], vec![0.0, 1.0], values);
+        assert_eq!(
+            result.err(),
+            Some(InterpolationError::UnsortedCoordinates("x"))
+        );
+    }
+}
@@ -1,9 +1,12 @@
 pub mod interpolation;
+pub mod model_input_feature;
 pub mod model_type;
+pub mod nan_prediction_policy;
 pub mod prediction_model;
 pub mod prediction_model_ops;
 pub mod prediction_model_record;
 pub mod smartcore;
+pub mod sweep_config;
 
 #[cfg(feature = "onnx")]
 pub mod onnx;
@@ -0,0 +1,69 @@
+use routee_compass_core::model::traversal::traversal_model_error::TraversalModelError;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// the features that can be fed into a prediction model. the order of a list
+/// of these features determines the column order of the model input row, which
+/// must match the order of the features the model was trained on.
+///
+/// # Example
+///
+/// ### Deserialization
+///
+/// a model trained with grade in the first column and speed in the second:
+///
+/// ```toml
+/// [[traversal.vehicles]]
+/// input_features = ["grade", "speed"]
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelInputFeature {
+    Speed,
+    Grade,
+}
+
+impl ModelInputFeature {
+    /// the feature order assumed when none is configured
+    pub const DEFAULT_ORDER: [ModelInputFeature; 2] =
+        [ModelInputFeature::Speed, ModelInputFeature::Grade];
+
+    /// confirms that a list of input features is non-empty and contains no duplicates.
+    pub fn validate(features: &[ModelInputFeature]) -> Result<(), TraversalModelError> {
+        if features.is_empty() {
+            return Err(TraversalModelError::BuildError(String::from(
+                "prediction model input features cannot be empty",
+            )));
+        }
+        let mut seen = HashSet::new();
+        for feature in features.iter() {
+            if !seen.insert(feature) {
+                return Err(TraversalModelError::BuildError(format!(
+                    "prediction model input feature '{}' appears more than once",
+                    feature
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// assembles a model input row with values in the order of the provided features.
+    pub fn build_row(features: &[ModelInputFeature], speed: f64, grade: f64) -> Vec<f64> {
+        features
+            .iter()
+            .map(|f| match f {
+                ModelInputFeature::Speed => speed,
+                ModelInputFeature::Grade => grade,
+            })
+            .collect()
+    }
+}
+
+impl std::fmt::Display for ModelInputFeature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModelInputFeature::Speed => write!(f, "speed"),
+            ModelInputFeature::Grade => write!(f, "grade"),
+        }
+    }
+}
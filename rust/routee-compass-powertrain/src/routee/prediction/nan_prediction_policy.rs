@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+/// declares how a prediction model record treats a NaN energy rate returned by
+/// its prediction model, such as from bad inputs or a corrupted model file.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NanPredictionPolicy {
+    /// fail the traversal with a prediction model error
+    #[default]
+    Error,
+    /// use the ideal energy rate of the model and log a warning
+    IdealEnergyRate,
+}
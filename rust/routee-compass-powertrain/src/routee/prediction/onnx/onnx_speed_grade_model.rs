@@ -1,8 +1,10 @@
 use std::path::Path;
 
-use crate::routee::prediction::prediction_model::PredictionModel;
+use crate::routee::prediction::{
+    model_input_feature::ModelInputFeature, prediction_model::PredictionModel,
+};
 
-use ort::{GraphOptimizationLevel, Session, Value};
+use ort::{GraphOptimizationLevel, Session, Value, ValueType};
 use routee_compass_core::{
     model::traversal::traversal_model_error::TraversalModelError,
     model::unit::{as_f64::AsF64, EnergyRate, EnergyRateUnit, Grade, GradeUnit, Speed, SpeedUnit},
@@ -10,6 +12,7 @@ use routee_compass_core::{
 
 pub struct OnnxSpeedGradeModel {
     session: Session,
+    input_features: Vec<ModelInputFeature>,
     speed_unit: SpeedUnit,
     grade_unit: GradeUnit,
     energy_rate_unit: EnergyRateUnit,
@@ -24,10 +27,16 @@ impl PredictionModel for OnnxSpeedGradeModel {
         let (speed, speed_unit) = speed;
         let (grade, grade_unit) = grade;
 
-        let speed_value: f32 = speed_unit.convert(speed, self.speed_unit).as_f64() as f32;
-        let grade_value: f32 = grade_unit.convert(grade, self.grade_unit).as_f64() as f32;
-        let array = ndarray::Array1::from(vec![speed_value, grade_value])
-            .into_shape((1, 2))
+        let speed_value = speed_unit.convert(speed, self.speed_unit).as_f64();
+        let grade_value = grade_unit.convert(grade, self.grade_unit).as_f64();
+        let row: Vec<f32> =
+            ModelInputFeature::build_row(&self.input_features, speed_value, grade_value)
+                .into_iter()
+                .map(|v| v as f32)
+                .collect();
+        let n_features = row.len();
+        let array = ndarray::Array1::from(row)
+            .into_shape((1, n_features))
             .map_err(|e| {
                 TraversalModelError::PredictionModel(format!(
                     "Failed to reshape input for prediction: {}",
@@ -60,6 +69,7 @@ impl PredictionModel for OnnxSpeedGradeModel {
 impl OnnxSpeedGradeModel {
     pub fn new<P: AsRef<Path>>(
         onnx_model_path: &P,
+        input_features: Vec<ModelInputFeature>,
         speed_unit: SpeedUnit,
         grade_unit: GradeUnit,
         energy_rate_unit: EnergyRateUnit,
@@ -73,8 +83,27 @@ impl OnnxSpeedGradeModel {
             .with_model_from_file(onnx_model_path)
             .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
 
+        ModelInputFeature::validate(&input_features)?;
+        // the last dimension of the model input is the number of features per row.
+        // dynamic dimensions are reported as negative values and are not validated.
+        if let Some(ValueType::Tensor { dimensions, .. }) =
+            session.inputs.first().map(|i| &i.input_type)
+        {
+            if let Some(n_model_features) = dimensions.last().filter(|d| **d > 0) {
+                if *n_model_features as usize != input_features.len() {
+                    return Err(TraversalModelError::BuildError(format!(
+                        "model file {} expects {} input features but {} input features were configured",
+                        onnx_model_path.as_ref().to_string_lossy(),
+                        n_model_features,
+                        input_features.len()
+                    )));
+                }
+            }
+        }
+
         Ok(OnnxSpeedGradeModel {
             session,
+            input_features,
             speed_unit,
             grade_unit,
             energy_rate_unit,
@@ -87,6 +116,7 @@ mod test {
     use std::path::PathBuf;
 
     use crate::routee::{
+        prediction::model_input_feature::ModelInputFeature,
         prediction::onnx::onnx_speed_grade_model::OnnxSpeedGradeModel, prediction::PredictionModel,
     };
     use rayon::prelude::*;
@@ -107,6 +137,7 @@ mod test {
         let model: Box<dyn PredictionModel> = Box::new(
             OnnxSpeedGradeModel::new(
                 &model_file_path,
+                ModelInputFeature::DEFAULT_ORDER.to_vec(),
                 SpeedUnit::MilesPerHour,
                 GradeUnit::Decimal,
                 routee_compass_core::model::unit::EnergyRateUnit::GallonsGasolinePerMile,
@@ -1,3 +1,4 @@
+use super::prediction_model_record::ModelInputs;
 use routee_compass_core::{
     model::traversal::traversal_model_error::TraversalModelError,
     model::unit::{EnergyRate, EnergyRateUnit, Grade, GradeUnit, Speed, SpeedUnit},
@@ -9,4 +10,17 @@ pub trait PredictionModel: Send + Sync {
         speed: (Speed, SpeedUnit),
         grade: (Grade, GradeUnit),
     ) -> Result<(EnergyRate, EnergyRateUnit), TraversalModelError>;
+
+    /// predicts the energy rate of each (speed, grade) input. models that can
+    /// evaluate many rows at once should override this, which by default calls
+    /// [`PredictionModel::predict`] once per input.
+    fn predict_batch(
+        &self,
+        inputs: &[ModelInputs],
+    ) -> Result<Vec<(EnergyRate, EnergyRateUnit)>, TraversalModelError> {
+        inputs
+            .iter()
+            .map(|(speed, grade)| self.predict(*speed, *grade))
+            .collect()
+    }
 }
@@ -8,13 +8,16 @@ use routee_compass_core::{
 
 use super::{
     interpolation::interpolation_speed_grade_model::InterpolationSpeedGradeModel,
-    model_type::ModelType, smartcore::smartcore_speed_grade_model::SmartcoreSpeedGradeModel,
+    model_input_feature::ModelInputFeature, model_type::ModelType,
+    smartcore::smartcore_speed_grade_model::SmartcoreSpeedGradeModel, sweep_config::SweepConfig,
     PredictionModel, PredictionModelRecord,
 };
 
 #[cfg(feature = "onnx")]
 use crate::routee::prediction::onnx::onnx_speed_grade_model::OnnxSpeedGradeModel;
 
+/// loads a prediction model and wraps it in a record with its associated metadata.
+/// see [`PredictionModelLoader`] to set the input features or the minimum energy sweep.
 #[allow(clippy::too_many_arguments)]
 pub fn load_prediction_model<P: AsRef<Path>>(
     name: String,
@@ -27,107 +30,344 @@ pub fn load_prediction_model<P: AsRef<Path>>(
     real_world_energy_adjustment_option: Option<f64>,
     cache: Option<FloatCachePolicy>,
 ) -> Result<PredictionModelRecord, TraversalModelError> {
-    let prediction_model: Arc<dyn PredictionModel> = match model_type.clone() {
-        ModelType::Smartcore => {
-            let model = SmartcoreSpeedGradeModel::new(
-                model_path,
-                speed_unit,
-                grade_unit,
-                energy_rate_unit,
-            )?;
-            Arc::new(model)
-        }
-        ModelType::Onnx => {
-            #[cfg(feature = "onnx")]
-            {
-                let model =
-                    OnnxSpeedGradeModel::new(model_path, speed_unit, grade_unit, energy_rate_unit)?;
-                Arc::new(model)
-            }
-            #[cfg(not(feature = "onnx"))]
-            {
-                return Err(TraversalModelError::BuildError(
-                    "Cannot build Onnx model without `onnx` feature enabled for compass-powertrain"
-                        .to_string(),
-                ));
-            }
-        }
-        ModelType::Interpolate {
-            underlying_model_type: underlying_model,
-            speed_lower_bound,
-            speed_upper_bound,
-            speed_bins: speed_bin_size,
-            grade_lower_bound,
-            grade_upper_bound,
-            grade_bins: grade_bin_size,
-        } => {
-            let model = InterpolationSpeedGradeModel::new(
-                model_path,
-                *underlying_model,
-                name.clone(),
-                speed_unit,
-                (speed_lower_bound, speed_upper_bound),
-                speed_bin_size,
-                grade_unit,
-                (grade_lower_bound, grade_upper_bound),
-                grade_bin_size,
-                energy_rate_unit,
-            )?;
-            Arc::new(model)
-        }
-    };
-    let ideal_energy_rate = match ideal_energy_rate_option {
-        None => find_min_energy_rate(&prediction_model, &energy_rate_unit)?,
-        Some(ier) => ier,
-    };
-
-    let real_world_energy_adjustment = real_world_energy_adjustment_option.unwrap_or(1.0);
-
-    Ok(PredictionModelRecord {
+    PredictionModelLoader::new(
         name,
-        prediction_model,
+        model_path,
         model_type,
         speed_unit,
         grade_unit,
         energy_rate_unit,
-        ideal_energy_rate,
-        real_world_energy_adjustment,
+        ideal_energy_rate_option,
+        real_world_energy_adjustment_option,
         cache,
-    })
+    )
+    .load()
+}
+
+/// loads a prediction model as [`load_prediction_model`] does, with settings that
+/// most models leave at their defaults set through the `with_*` methods.
+pub struct PredictionModelLoader<'a, P: AsRef<Path>> {
+    name: String,
+    model_path: &'a P,
+    model_type: ModelType,
+    speed_unit: SpeedUnit,
+    grade_unit: GradeUnit,
+    energy_rate_unit: EnergyRateUnit,
+    ideal_energy_rate_option: Option<EnergyRate>,
+    real_world_energy_adjustment_option: Option<f64>,
+    cache: Option<FloatCachePolicy>,
+    input_features: Vec<ModelInputFeature>,
+    min_energy_sweep: SweepConfig,
+}
+
+impl<'a, P: AsRef<Path>> PredictionModelLoader<'a, P> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        name: String,
+        model_path: &'a P,
+        model_type: ModelType,
+        speed_unit: SpeedUnit,
+        grade_unit: GradeUnit,
+        energy_rate_unit: EnergyRateUnit,
+        ideal_energy_rate_option: Option<EnergyRate>,
+        real_world_energy_adjustment_option: Option<f64>,
+        cache: Option<FloatCachePolicy>,
+    ) -> Self {
+        PredictionModelLoader {
+            name,
+            model_path,
+            model_type,
+            speed_unit,
+            grade_unit,
+            energy_rate_unit,
+            ideal_energy_rate_option,
+            real_world_energy_adjustment_option,
+            cache,
+            input_features: ModelInputFeature::DEFAULT_ORDER.to_vec(),
+            min_energy_sweep: SweepConfig::default(),
+        }
+    }
+
+    /// sets the order of features in the model input row, which must match the order
+    /// used when training the model. defaults to `[speed, grade]`. an interpolation
+    /// model passes it to its underlying model.
+    pub fn with_input_features(mut self, input_features: Vec<ModelInputFeature>) -> Self {
+        self.input_features = input_features;
+        self
+    }
+
+    /// sets the speed and grade values swept to find the ideal energy rate when none
+    /// is provided. defaults to [`SweepConfig::default`].
+    pub fn with_min_energy_sweep(mut self, min_energy_sweep: SweepConfig) -> Self {
+        self.min_energy_sweep = min_energy_sweep;
+        self
+    }
+
+    /// loads the model file and builds its record.
+    pub fn load(self) -> Result<PredictionModelRecord, TraversalModelError> {
+        let features = self.input_features.clone();
+        let prediction_model: Arc<dyn PredictionModel> = match self.model_type.clone() {
+            ModelType::Smartcore => {
+                let model = SmartcoreSpeedGradeModel::new(
+                    self.model_path,
+                    features,
+                    self.speed_unit,
+                    self.grade_unit,
+                    self.energy_rate_unit,
+                )?;
+                Arc::new(model)
+            }
+            ModelType::Onnx => {
+                #[cfg(feature = "onnx")]
+                {
+                    let model = OnnxSpeedGradeModel::new(
+                        self.model_path,
+                        features,
+                        self.speed_unit,
+                        self.grade_unit,
+                        self.energy_rate_unit,
+                    )?;
+                    Arc::new(model)
+                }
+                #[cfg(not(feature = "onnx"))]
+                {
+                    return Err(TraversalModelError::BuildError(
+                        "Cannot build Onnx model without `onnx` feature enabled for compass-powertrain"
+                            .to_string(),
+                    ));
+                }
+            }
+            ModelType::Interpolate {
+                underlying_model_type: underlying_model,
+                speed_lower_bound,
+                speed_upper_bound,
+                speed_bins: speed_bin_size,
+                grade_lower_bound,
+                grade_upper_bound,
+                grade_bins: grade_bin_size,
+            } => {
+                let model = InterpolationSpeedGradeModel::new(
+                    self.model_path,
+                    *underlying_model,
+                    features,
+                    self.name.clone(),
+                    self.speed_unit,
+                    (speed_lower_bound, speed_upper_bound),
+                    speed_bin_size,
+                    self.grade_unit,
+                    (grade_lower_bound, grade_upper_bound),
+                    grade_bin_size,
+                    self.energy_rate_unit,
+                )?;
+                Arc::new(model)
+            }
+        };
+        let ideal_energy_rate = match self.ideal_energy_rate_option {
+            None => find_min_energy_rate(
+                &prediction_model,
+                &self.energy_rate_unit,
+                &self.min_energy_sweep,
+            )?,
+            Some(ier) => ier,
+        };
+
+        let real_world_energy_adjustment = self.real_world_energy_adjustment_option.unwrap_or(1.0);
+
+        Ok(PredictionModelRecord::new(
+            self.name,
+            prediction_model,
+            self.model_type,
+            self.speed_unit,
+            self.grade_unit,
+            self.energy_rate_unit,
+            ideal_energy_rate,
+            real_world_energy_adjustment,
+            self.cache,
+        ))
+    }
+}
+
+/// runs a number of representative predictions against a freshly-loaded model so that
+/// any one-time initialization in the underlying framework happens at load time instead
+/// of during the first user query. predictions cycle over a spread of speed and grade
+/// values and their results are discarded.
+///
+/// # Arguments
+///
+/// * `model`      - the model to warm up
+/// * `iterations` - number of predictions to run
+pub fn warm_up_prediction_model(
+    model: &Arc<dyn PredictionModel>,
+    iterations: usize,
+) -> Result<(), TraversalModelError> {
+    let start_time = std::time::Instant::now();
+    for i in 0..iterations {
+        let speed = Speed::new(5.0 + (i % 16) as f64 * 5.0);
+        let grade = Grade::new((i % 5) as f64 * 2.0 - 4.0);
+        model.predict(
+            (speed, SpeedUnit::MilesPerHour),
+            (grade, GradeUnit::Percent),
+        )?;
+    }
+    log::debug!(
+        "warmed up prediction model with {} predictions in {} milliseconds",
+        iterations,
+        start_time.elapsed().as_millis()
+    );
+    Ok(())
 }
 
-/// sweep a fixed set of speed and grade values to find the minimum energy per mile rate from the incoming rf model
+/// sweeps a grid of speed and grade values to find the minimum energy rate of a
+/// prediction model. the grid is predicted as a single batch.
+///
+/// # Arguments
+///
+/// * `model`                         - the model to sweep
+/// * `energy_model_energy_rate_unit` - energy rate unit of the model, used for logging
+/// * `sweep`                         - the speed and grade values to sweep
 pub fn find_min_energy_rate(
     model: &Arc<dyn PredictionModel>,
     energy_model_energy_rate_unit: &EnergyRateUnit,
+    sweep: &SweepConfig,
 ) -> Result<EnergyRate, TraversalModelError> {
-    // sweep a fixed set of speed and grade values to find the minimum energy per mile rate from the incoming rf model
-    let mut minimum_energy_rate = EnergyRate::new(f64::MAX);
     let start_time = std::time::Instant::now();
 
-    let grade = Grade::ZERO;
-    for speed_i32 in 20..80 {
-        let speed = Speed::new(speed_i32 as f64);
-        let (energy_rate, _) = model
-            .predict(
-                (speed, SpeedUnit::MilesPerHour),
-                (grade, GradeUnit::Percent),
-            )
-            .map_err(|e| TraversalModelError::PredictionModel(e.to_string()))?;
-        if energy_rate < minimum_energy_rate {
-            minimum_energy_rate = energy_rate;
-        }
-    }
+    let inputs = sweep.points()?;
+    let minimum_energy_rate = model
+        .predict_batch(&inputs)
+        .map_err(|e| TraversalModelError::PredictionModel(e.to_string()))?
+        .into_iter()
+        .map(|(energy_rate, _)| energy_rate)
+        .fold(EnergyRate::new(f64::MAX), |min, energy_rate| {
+            if energy_rate < min {
+                energy_rate
+            } else {
+                min
+            }
+        });
 
-    let end_time = std::time::Instant::now();
-    let search_time = end_time - start_time;
+    let search_time = start_time.elapsed();
 
     log::debug!(
-        "found minimum energy: {}/{} in {} milliseconds",
+        "found minimum energy: {}/{} over speeds {:?} mph and grades {:?} percent with step {} in {} milliseconds",
         minimum_energy_rate,
         energy_model_energy_rate_unit,
+        sweep.speed_range,
+        sweep.grade_range,
+        sweep.step,
         search_time.as_millis()
     );
 
     Ok(minimum_energy_rate)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::routee::test_util::{camry_record, test_file};
+
+    #[test]
+    fn test_warm_up_preserves_predictions() {
+        let record = camry_record(GradeUnit::Decimal);
+        let inputs = [(30.0, 0.0), (45.0, 0.02), (65.0, -0.03)].map(|(speed, grade)| {
+            (
+                (Speed::new(speed), SpeedUnit::MilesPerHour),
+                (Grade::new(grade), GradeUnit::Decimal),
+            )
+        });
+        let predict_all = || {
+            inputs
+                .iter()
+                .map(|(speed, grade)| record.prediction_model.predict(*speed, *grade).unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        let before = predict_all();
+        warm_up_prediction_model(&record.prediction_model, 100).unwrap();
+        let after = predict_all();
+        assert_eq!(before, after);
+    }
+
+    #[test]
+    fn test_find_min_energy_rate_sweep() {
+        let model: Arc<dyn PredictionModel> = Arc::new(
+            SmartcoreSpeedGradeModel::new(
+                &test_file("Toyota_Camry.bin"),
+                ModelInputFeature::DEFAULT_ORDER.to_vec(),
+                SpeedUnit::MilesPerHour,
+                GradeUnit::Decimal,
+                EnergyRateUnit::GallonsGasolinePerMile,
+            )
+            .unwrap(),
+        );
+        let unit = EnergyRateUnit::GallonsGasolinePerMile;
+
+        // the batch prediction matches predicting each input on its own
+        let default_sweep = SweepConfig::default();
+        let inputs = default_sweep.points().unwrap();
+        let batch = model.predict_batch(&inputs).unwrap();
+        let single = inputs
+            .iter()
+            .map(|(speed, grade)| model.predict(*speed, *grade).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(batch, single);
+        let expected = single
+            .iter()
+            .map(|(rate, _)| *rate)
+            .fold(EnergyRate::new(f64::MAX), |a, b| if b < a { b } else { a });
+        let default_min = find_min_energy_rate(&model, &unit, &default_sweep).unwrap();
+        assert_eq!(default_min, expected);
+
+        // a sweep covering the default sweep finds a minimum at least as low
+        let wide_sweep = SweepConfig {
+            speed_range: (10.0, 90.0),
+            grade_range: (-4.0, 4.0),
+            step: 1.0,
+        };
+        let wide_min = find_min_energy_rate(&model, &unit, &wide_sweep).unwrap();
+        assert!(wide_min <= default_min);
+    }
+
+    #[test]
+    fn test_loader_settings() {
+        let model_file_path = test_file("Toyota_Camry.bin");
+        let loader = || {
+            PredictionModelLoader::new(
+                "Toyota_Camry".to_string(),
+                &model_file_path,
+                ModelType::Smartcore,
+                SpeedUnit::MilesPerHour,
+                GradeUnit::Decimal,
+                EnergyRateUnit::GallonsGasolinePerMile,
+                None,
+                None,
+                None,
+            )
+        };
+        let default = loader().load().unwrap();
+
+        // the ideal energy rate is the minimum over the configured sweep
+        let wide_sweep = SweepConfig {
+            speed_range: (10.0, 90.0),
+            grade_range: (-4.0, 4.0),
+            step: 1.0,
+        };
+        let wide = loader().with_min_energy_sweep(wide_sweep).load().unwrap();
+        let unit = EnergyRateUnit::GallonsGasolinePerMile;
+        let expected = find_min_energy_rate(&wide.prediction_model, &unit, &wide_sweep).unwrap();
+        assert_eq!(wide.ideal_energy_rate, expected);
+        assert!(wide.ideal_energy_rate <= default.ideal_energy_rate);
+
+        // the input features set the column order of the model input row
+        let reversed = loader()
+            .with_input_features(vec![ModelInputFeature::Grade, ModelInputFeature::Speed])
+            .load()
+            .unwrap();
+        let speed = (Speed::new(45.0), SpeedUnit::MilesPerHour);
+        let grade = (Grade::new(0.02), GradeUnit::Decimal);
+        assert_ne!(
+            reversed.prediction_model.predict(speed, grade).unwrap(),
+            default.prediction_model.predict(speed, grade).unwrap()
+        );
+    }
+}
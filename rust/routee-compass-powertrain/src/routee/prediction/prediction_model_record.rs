@@ -1,4 +1,7 @@
-use std::sync::Arc;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
 
 use routee_compass_core::{
     model::traversal::traversal_model_error::TraversalModelError,
@@ -9,7 +12,10 @@ use routee_compass_core::{
     util::cache_policy::float_cache_policy::FloatCachePolicy,
 };
 
-use super::{model_type::ModelType, PredictionModel};
+use super::{model_type::ModelType, nan_prediction_policy::NanPredictionPolicy, PredictionModel};
+/// speed and grade inputs to a prediction model, along with their units
+pub type ModelInputs = ((Speed, SpeedUnit), (Grade, GradeUnit));
+
 /// A struct to hold the prediction model and associated metadata
 pub struct PredictionModelRecord {
     pub name: String,
@@ -20,10 +26,224 @@ pub struct PredictionModelRecord {
     pub energy_rate_unit: EnergyRateUnit,
     pub ideal_energy_rate: EnergyRate,
     pub real_world_energy_adjustment: f64,
-    pub cache: Option<FloatCachePolicy>,
+    /// scales predicted energy rates along with the ideal energy rate, defaults to 1.0
+    pub correction_factor: f64,
+    pub cache: Option<Arc<FloatCachePolicy>>,
+    /// optional valid range of speed values, in the speed unit of the model
+    pub speed_bounds: Option<(Speed, Speed)>,
+    /// optional valid range of grade values, in the grade unit of the model
+    pub grade_bounds: Option<(Grade, Grade)>,
+    /// how a NaN energy rate from the prediction model is handled
+    pub nan_prediction_policy: NanPredictionPolicy,
+    /// when true, clamping an input or falling back from a NaN prediction is an error
+    pub strict: bool,
+    clamp_warned: AtomicBool,
+    nan_warned: AtomicBool,
 }
 
 impl PredictionModelRecord {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        name: String,
+        prediction_model: Arc<dyn PredictionModel>,
+        model_type: ModelType,
+        speed_unit: SpeedUnit,
+        grade_unit: GradeUnit,
+        energy_rate_unit: EnergyRateUnit,
+        ideal_energy_rate: EnergyRate,
+        real_world_energy_adjustment: f64,
+        cache: Option<FloatCachePolicy>,
+    ) -> PredictionModelRecord {
+        PredictionModelRecord {
+            name,
+            prediction_model,
+            model_type,
+            speed_unit,
+            grade_unit,
+            energy_rate_unit,
+            ideal_energy_rate,
+            real_world_energy_adjustment,
+            correction_factor: 1.0,
+            cache: cache.map(Arc::new),
+            speed_bounds: None,
+            grade_bounds: None,
+            nan_prediction_policy: NanPredictionPolicy::default(),
+            strict: false,
+            clamp_warned: AtomicBool::new(false),
+            nan_warned: AtomicBool::new(false),
+        }
+    }
+
+    /// sets the valid range of model inputs. speed and grade values outside of
+    /// these ranges are clamped into range before prediction, keeping predictions
+    /// within the domain the model was trained on.
+    ///
+    /// # Arguments
+    ///
+    /// * `speed_bounds` - optional (lower, upper) speed bounds in the speed unit of the model
+    /// * `grade_bounds` - optional (lower, upper) grade bounds in the grade unit of the model
+    ///
+    /// # Returns
+    ///
+    /// the record with bounds set, or an error if a lower bound exceeds its upper bound
+    pub fn with_input_bounds(
+        mut self,
+        speed_bounds: Option<(Speed, Speed)>,
+        grade_bounds: Option<(Grade, Grade)>,
+    ) -> Result<PredictionModelRecord, TraversalModelError> {
+        if let Some((lower, upper)) = speed_bounds {
+            if lower > upper {
+                return Err(TraversalModelError::BuildError(format!(
+                    "speed bounds lower value {} exceeds upper value {}",
+                    lower, upper
+                )));
+            }
+        }
+        if let Some((lower, upper)) = grade_bounds {
+            if lower > upper {
+                return Err(TraversalModelError::BuildError(format!(
+                    "grade bounds lower value {} exceeds upper value {}",
+                    lower, upper
+                )));
+            }
+        }
+        self.speed_bounds = speed_bounds;
+        self.grade_bounds = grade_bounds;
+        Ok(self)
+    }
+
+    /// sets a correction factor applied to each predicted energy rate. the ideal
+    /// energy rate is scaled by the same factor so that best-case energy estimates
+    /// remain a lower bound on the corrected per-edge energy.
+    ///
+    /// # Arguments
+    ///
+    /// * `correction_factor` - positive multiplier on the predicted energy rate
+    ///
+    /// # Returns
+    ///
+    /// the record with the correction applied, or an error if the factor is not positive
+    pub fn with_correction_factor(
+        mut self,
+        correction_factor: f64,
+    ) -> Result<PredictionModelRecord, TraversalModelError> {
+        if !(correction_factor > 0.0 && correction_factor.is_finite()) {
+            return Err(TraversalModelError::BuildError(format!(
+                "correction factor must be a positive number, found {}",
+                correction_factor
+            )));
+        }
+        self.ideal_energy_rate =
+            self.ideal_energy_rate * (correction_factor / self.correction_factor);
+        self.correction_factor = correction_factor;
+        Ok(self)
+    }
+
+    /// sets how a NaN energy rate returned by the prediction model is handled.
+    pub fn with_nan_prediction_policy(
+        mut self,
+        nan_prediction_policy: NanPredictionPolicy,
+    ) -> PredictionModelRecord {
+        self.nan_prediction_policy = nan_prediction_policy;
+        self
+    }
+
+    /// gets this record for a query, which is a copy of the record in strict mode
+    /// when `strict` is true (see [`routee_compass_core::model::traversal::strict_mode`]).
+    /// a strict copy shares the prediction model and cache of this record.
+    pub fn with_strict_mode(self: &Arc<Self>, strict: bool) -> Arc<PredictionModelRecord> {
+        if strict == self.strict {
+            return self.clone();
+        }
+        Arc::new(PredictionModelRecord {
+            name: self.name.clone(),
+            prediction_model: self.prediction_model.clone(),
+            model_type: self.model_type.clone(),
+            speed_unit: self.speed_unit,
+            grade_unit: self.grade_unit,
+            energy_rate_unit: self.energy_rate_unit,
+            ideal_energy_rate: self.ideal_energy_rate,
+            real_world_energy_adjustment: self.real_world_energy_adjustment,
+            correction_factor: self.correction_factor,
+            cache: self.cache.clone(),
+            speed_bounds: self.speed_bounds,
+            grade_bounds: self.grade_bounds,
+            nan_prediction_policy: self.nan_prediction_policy,
+            strict,
+            clamp_warned: AtomicBool::new(false),
+            nan_warned: AtomicBool::new(false),
+        })
+    }
+
+    /// the speed the prediction model receives for an input speed, which is the
+    /// input speed clamped into the configured speed bounds, in the unit of the
+    /// model. unlike [`PredictionModelRecord::clamp_inputs`], this neither logs
+    /// nor fails in strict mode, so it can be used to report the speed used.
+    pub fn bound_speed(&self, speed: (Speed, SpeedUnit)) -> (Speed, SpeedUnit) {
+        match self.speed_bounds {
+            None => speed,
+            Some((lower, upper)) => {
+                let (value, unit) = speed;
+                let model_value = unit.convert(&value, &self.speed_unit);
+                (model_value.clamp(lower, upper), self.speed_unit)
+            }
+        }
+    }
+
+    /// clamps speed and grade into the configured input bounds. when a value is
+    /// clamped, it is returned in the unit of the model. a warning is logged the
+    /// first time clamping occurs for this model. in strict mode, clamping
+    /// fails instead.
+    pub fn clamp_inputs(
+        &self,
+        speed: (Speed, SpeedUnit),
+        grade: (Grade, GradeUnit),
+    ) -> Result<ModelInputs, TraversalModelError> {
+        let (input_speed, input_grade) = (speed, grade);
+        let mut clamped = false;
+        let speed = match self.speed_bounds {
+            None => speed,
+            Some((lower, upper)) => {
+                let (value, unit) = speed;
+                let model_value = unit.convert(&value, &self.speed_unit);
+                let bounded = model_value.clamp(lower, upper);
+                if bounded != model_value {
+                    clamped = true;
+                }
+                (bounded, self.speed_unit)
+            }
+        };
+        let grade = match self.grade_bounds {
+            None => grade,
+            Some((lower, upper)) => {
+                let (value, unit) = grade;
+                let model_value = unit.convert(&value, &self.grade_unit);
+                let bounded = model_value.clamp(lower, upper);
+                if bounded != model_value {
+                    clamped = true;
+                }
+                (bounded, self.grade_unit)
+            }
+        };
+        if clamped && self.strict {
+            return Err(TraversalModelError::StrictModeFallback(format!(
+                "prediction model '{}' received inputs outside of its valid range (speed: {} {}, grade: {} {})",
+                self.name, input_speed.0, input_speed.1, input_grade.0, input_grade.1
+            )));
+        }
+        if clamped && !self.clamp_warned.swap(true, Ordering::Relaxed) {
+            log::warn!(
+                "prediction model '{}' received inputs outside of its valid range, which are being clamped (speed: {} {}, grade: {} {}). further occurrences will not be logged.",
+                self.name,
+                input_speed.0,
+                input_speed.1,
+                input_grade.0,
+                input_grade.1
+            );
+        }
+        Ok((speed, grade))
+    }
+
     pub fn predict(
         &self,
         speed: (Speed, SpeedUnit),
@@ -31,36 +251,383 @@ impl PredictionModelRecord {
         distance: (Distance, DistanceUnit),
     ) -> Result<(Energy, EnergyUnit), TraversalModelError> {
         let (distance, distance_unit) = distance;
+        let energy_rate_real_world = self.predict_energy_rate(speed, grade)?;
+
+        let (energy, energy_unit) = Energy::create(
+            &energy_rate_real_world,
+            &self.energy_rate_unit,
+            &distance,
+            &distance_unit,
+        )?;
+
+        Ok((energy, energy_unit))
+    }
+
+    /// predicts the energy rate at some speed and grade, in the energy rate unit of
+    /// the model, the same way as during search: inputs are clamped into bounds,
+    /// the cache is used if present, and the real world adjustment and correction
+    /// factor are applied.
+    pub fn predict_energy_rate(
+        &self,
+        speed: (Speed, SpeedUnit),
+        grade: (Grade, GradeUnit),
+    ) -> Result<EnergyRate, TraversalModelError> {
+        let (speed, grade) = self.clamp_inputs(speed, grade)?;
 
         let energy_rate = match &self.cache {
             Some(cache) => {
                 let key = vec![speed.0.as_f64(), grade.0.as_f64()];
                 match cache.get(&key)? {
-                    Some(er) => EnergyRate::new(er),
+                    Some(er) => Some(EnergyRate::new(er)),
                     None => {
-                        let (energy_rate, _energy_rate_unit) =
-                            self.prediction_model.predict(speed, grade)?;
-                        cache.update(&key, energy_rate.as_f64())?;
+                        let energy_rate = self.predict_model_energy_rate(speed, grade)?;
+                        if let Some(er) = energy_rate {
+                            cache.update(&key, er.as_f64())?;
+                        }
                         energy_rate
                     }
                 }
             }
-            None => {
-                let (energy_rate, _energy_rate_unit) =
-                    self.prediction_model.predict(speed, grade)?;
-                energy_rate
-            }
+            None => self.predict_model_energy_rate(speed, grade)?,
         };
 
-        let energy_rate_real_world = energy_rate * self.real_world_energy_adjustment;
+        let energy_rate_real_world = match energy_rate {
+            Some(er) => er * self.real_world_energy_adjustment * self.correction_factor,
+            // the ideal energy rate is already scaled by the correction factor
+            None => self.ideal_energy_rate * self.real_world_energy_adjustment,
+        };
+        Ok(energy_rate_real_world)
+    }
 
-        let (energy, energy_unit) = Energy::create(
-            &energy_rate_real_world,
-            &self.energy_rate_unit,
-            &distance,
-            &distance_unit,
-        )?;
+    /// runs the prediction model, checking for a NaN energy rate, which would
+    /// otherwise pass through comparisons such as cost ordering unnoticed.
+    ///
+    /// # Returns
+    ///
+    /// the predicted energy rate, None if the prediction was NaN and the ideal energy
+    /// rate should be used instead, or an error if the prediction was NaN and the
+    /// policy is to fail
+    fn predict_model_energy_rate(
+        &self,
+        speed: (Speed, SpeedUnit),
+        grade: (Grade, GradeUnit),
+    ) -> Result<Option<EnergyRate>, TraversalModelError> {
+        let (energy_rate, _energy_rate_unit) = self.prediction_model.predict(speed, grade)?;
+        if !energy_rate.as_f64().is_nan() {
+            return Ok(Some(energy_rate));
+        }
+        match self.nan_prediction_policy {
+            NanPredictionPolicy::Error => Err(TraversalModelError::PredictionModel(format!(
+                "prediction model '{}' returned NaN for speed {} {} and grade {} {}",
+                self.name, speed.0, speed.1, grade.0, grade.1
+            ))),
+            NanPredictionPolicy::IdealEnergyRate if self.strict => {
+                Err(TraversalModelError::StrictModeFallback(format!(
+                    "prediction model '{}' returned NaN for speed {} {} and grade {} {}",
+                    self.name, speed.0, speed.1, grade.0, grade.1
+                )))
+            }
+            NanPredictionPolicy::IdealEnergyRate => {
+                if !self.nan_warned.swap(true, Ordering::Relaxed) {
+                    log::warn!(
+                        "prediction model '{}' returned NaN for speed {} {} and grade {} {}, using the ideal energy rate instead. further occurrences will not be logged.",
+                        self.name,
+                        speed.0,
+                        speed.1,
+                        grade.0,
+                        grade.1
+                    );
+                }
+                Ok(None)
+            }
+        }
+    }
 
-        Ok((energy, energy_unit))
+    /// samples the predicted energy rate over a grid of speed and grade values,
+    /// such as for plotting the behavior of the model. predictions are made as
+    /// during search (see [`PredictionModelRecord::predict_energy_rate`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `speeds` - speed values to sample, in the speed unit of the model
+    /// * `grades` - grade values to sample, in the grade unit of the model
+    ///
+    /// # Returns
+    ///
+    /// a (speed, grade, energy rate) triple for each combination of speed and grade,
+    /// ordered by speed and then by grade, with energy rates in the energy rate unit
+    /// of the model
+    pub fn sample_surface(
+        &self,
+        speeds: &[Speed],
+        grades: &[Grade],
+    ) -> Result<Vec<(Speed, Grade, EnergyRate)>, TraversalModelError> {
+        speeds
+            .iter()
+            .flat_map(|speed| grades.iter().map(move |grade| (*speed, *grade)))
+            .map(|(speed, grade)| {
+                let energy_rate =
+                    self.predict_energy_rate((speed, self.speed_unit), (grade, self.grade_unit))?;
+                Ok((speed, grade, energy_rate))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{NanPredictionPolicy, PredictionModel, PredictionModelRecord};
+    use crate::routee::prediction::{load_prediction_model, model_type::ModelType};
+    use crate::routee::test_util::{camry_record, test_file};
+    use routee_compass_core::model::traversal::traversal_model_error::TraversalModelError;
+    use routee_compass_core::model::unit::{
+        as_f64::AsF64, Distance, DistanceUnit, Energy, EnergyRate, EnergyRateUnit, Grade,
+        GradeUnit, Speed, SpeedUnit,
+    };
+    use routee_compass_core::util::cache_policy::float_cache_policy::{
+        FloatCachePolicy, FloatCachePolicyConfig,
+    };
+    use std::sync::Arc;
+
+    /// a prediction model that always predicts NaN
+    struct NanModel;
+
+    impl PredictionModel for NanModel {
+        fn predict(
+            &self,
+            _speed: (Speed, SpeedUnit),
+            _grade: (Grade, GradeUnit),
+        ) -> Result<(EnergyRate, EnergyRateUnit), TraversalModelError> {
+            Ok((
+                EnergyRate::new(f64::NAN),
+                EnergyRateUnit::GallonsGasolinePerMile,
+            ))
+        }
+    }
+
+    #[test]
+    fn test_nan_prediction_policy() {
+        let record = PredictionModelRecord::new(
+            String::from("nan"),
+            Arc::new(NanModel),
+            ModelType::Smartcore,
+            SpeedUnit::MilesPerHour,
+            GradeUnit::Percent,
+            EnergyRateUnit::GallonsGasolinePerMile,
+            EnergyRate::new(0.02),
+            1.0,
+            None,
+        );
+        let speed = (Speed::new(40.0), SpeedUnit::MilesPerHour);
+        let grade = (Grade::ZERO, GradeUnit::Percent);
+        let distance = (Distance::new(1.0), DistanceUnit::Miles);
+
+        // by default, a NaN prediction is an error rather than a NaN energy
+        let result = record.predict(speed, grade, distance);
+        assert!(matches!(
+            result,
+            Err(TraversalModelError::PredictionModel(_))
+        ));
+
+        // with the fallback policy, the ideal energy rate is used
+        let record = record.with_nan_prediction_policy(NanPredictionPolicy::IdealEnergyRate);
+        let energy_rate = record.predict_energy_rate(speed, grade).unwrap();
+        assert_eq!(energy_rate, EnergyRate::new(0.02));
+        let (energy, _) = record.predict(speed, grade, distance).unwrap();
+        assert_eq!(energy, Energy::new(0.02));
+
+        // unless the record is in strict mode
+        let strict = Arc::new(record).with_strict_mode(true);
+        assert!(matches!(
+            strict.predict(speed, grade, distance),
+            Err(TraversalModelError::StrictModeFallback(_))
+        ));
+    }
+
+    /// a prediction model that predicts NaN below 10 mph and a constant rate otherwise
+    struct NanBelowTenMphModel;
+
+    impl PredictionModel for NanBelowTenMphModel {
+        fn predict(
+            &self,
+            speed: (Speed, SpeedUnit),
+            _grade: (Grade, GradeUnit),
+        ) -> Result<(EnergyRate, EnergyRateUnit), TraversalModelError> {
+            let energy_rate = if speed.0 < Speed::new(10.0) {
+                f64::NAN
+            } else {
+                0.02
+            };
+            Ok((
+                EnergyRate::new(energy_rate),
+                EnergyRateUnit::GallonsGasolinePerMile,
+            ))
+        }
+    }
+
+    #[test]
+    fn test_nan_fallback_applies_real_world_adjustment() {
+        // the ideal energy rate equals the predicted rate, so both paths must agree
+        let record = PredictionModelRecord::new(
+            String::from("nan_below_ten_mph"),
+            Arc::new(NanBelowTenMphModel),
+            ModelType::Smartcore,
+            SpeedUnit::MilesPerHour,
+            GradeUnit::Percent,
+            EnergyRateUnit::GallonsGasolinePerMile,
+            EnergyRate::new(0.02),
+            1.5,
+            None,
+        )
+        .with_nan_prediction_policy(NanPredictionPolicy::IdealEnergyRate)
+        .with_correction_factor(2.0)
+        .unwrap();
+        let grade = (Grade::ZERO, GradeUnit::Percent);
+        let predicted = record
+            .predict_energy_rate((Speed::new(40.0), SpeedUnit::MilesPerHour), grade)
+            .unwrap();
+        let fallback = record
+            .predict_energy_rate((Speed::new(5.0), SpeedUnit::MilesPerHour), grade)
+            .unwrap();
+        assert!((predicted.as_f64() - 0.06).abs() < 1e-12);
+        assert!((fallback.as_f64() - 0.06).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_out_of_range_grade_is_clamped() {
+        let record = camry_record(GradeUnit::Percent)
+            .with_input_bounds(None, Some((Grade::new(-10.0), Grade::new(10.0))))
+            .unwrap();
+
+        // a 25% grade, provided in decimal units, is clamped to the 10% upper bound
+        let speed = (Speed::new(40.0), SpeedUnit::MilesPerHour);
+        let (clamped_speed, clamped_grade) = record
+            .clamp_inputs(speed, (Grade::new(0.25), GradeUnit::Decimal))
+            .unwrap();
+        assert_eq!(clamped_speed.0, speed.0);
+        assert_eq!(clamped_grade.0, Grade::new(10.0));
+        assert!(matches!(clamped_grade.1, GradeUnit::Percent));
+
+        let distance = (Distance::new(1.0), DistanceUnit::Miles);
+        let steep = record
+            .predict(speed, (Grade::new(25.0), GradeUnit::Percent), distance)
+            .unwrap();
+        let bound = record
+            .predict(speed, (Grade::new(10.0), GradeUnit::Percent), distance)
+            .unwrap();
+        assert_eq!(steep, bound);
+
+        // in-range values are untouched
+        let (_, unclamped_grade) = record
+            .clamp_inputs(speed, (Grade::new(5.0), GradeUnit::Percent))
+            .unwrap();
+        assert_eq!(unclamped_grade.0, Grade::new(5.0));
+
+        // in strict mode, clamping is an error
+        let strict = Arc::new(record).with_strict_mode(true);
+        let result = strict.predict(speed, (Grade::new(25.0), GradeUnit::Percent), distance);
+        assert!(matches!(
+            result,
+            Err(TraversalModelError::StrictModeFallback(_))
+        ));
+        assert!(strict
+            .predict(speed, (Grade::new(5.0), GradeUnit::Percent), distance)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_invalid_bounds_fail() {
+        let result = camry_record(GradeUnit::Percent)
+            .with_input_bounds(Some((Speed::new(80.0), Speed::new(0.0))), None);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_surface_minimum_matches_ideal_energy_rate() {
+        // without an ideal energy rate, it is found by sweeping speeds on flat ground
+        let record = camry_record(GradeUnit::Percent);
+
+        let speeds = (20..80).map(|s| Speed::new(s as f64)).collect::<Vec<_>>();
+        let surface = record.sample_surface(&speeds, &[Grade::ZERO]).unwrap();
+        assert_eq!(surface.len(), speeds.len());
+        let minimum = surface
+            .iter()
+            .map(|(_, _, energy_rate)| *energy_rate)
+            .min()
+            .unwrap();
+        assert_eq!(minimum, record.ideal_energy_rate);
+
+        // a grid of speeds and grades is sampled in speed-major order
+        let grades = [Grade::new(-2.0), Grade::ZERO, Grade::new(2.0)];
+        let grid = record.sample_surface(&speeds[..2], &grades).unwrap();
+        let inputs = grid.iter().map(|(s, g, _)| (*s, *g)).collect::<Vec<_>>();
+        assert_eq!(
+            inputs,
+            vec![
+                (speeds[0], grades[0]),
+                (speeds[0], grades[1]),
+                (speeds[0], grades[2]),
+                (speeds[1], grades[0]),
+                (speeds[1], grades[1]),
+                (speeds[1], grades[2]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cached_predictions_match_uncached() {
+        let load = |cache: Option<FloatCachePolicy>| {
+            load_prediction_model(
+                "Toyota_Camry".to_string(),
+                &test_file("Toyota_Camry.bin"),
+                ModelType::Smartcore,
+                SpeedUnit::MilesPerHour,
+                GradeUnit::Percent,
+                EnergyRateUnit::GallonsGasolinePerMile,
+                Some(EnergyRate::new(0.02)),
+                None,
+                cache,
+            )
+            .unwrap()
+        };
+        let uncached = load(None);
+        // keys are rounded to 0.1 mph and 0.1 percent grade
+        let cache = FloatCachePolicy::from_config(FloatCachePolicyConfig {
+            cache_size: 1000,
+            key_precisions: vec![1, 1],
+        })
+        .unwrap();
+        let cached = load(Some(cache));
+
+        // speeds and grades repeat, as they do across the edges of a graph
+        let inputs = (0..2000)
+            .map(|i| {
+                let speed = Speed::new(20.0 + (i % 41) as f64);
+                let grade = Grade::new((i % 9) as f64 - 4.0);
+                (
+                    (speed, SpeedUnit::MilesPerHour),
+                    (grade, GradeUnit::Percent),
+                )
+            })
+            .collect::<Vec<_>>();
+        for (speed, grade) in inputs.iter() {
+            let expected = uncached.predict_energy_rate(*speed, *grade).unwrap();
+            let found = cached.predict_energy_rate(*speed, *grade).unwrap();
+            assert!(
+                (expected.as_f64() - found.as_f64()).abs() < 1e-12,
+                "expected {} but found {}",
+                expected,
+                found
+            );
+        }
+
+        // inputs within the key resolution share the cached prediction
+        let speed = (Speed::new(45.0), SpeedUnit::MilesPerHour);
+        let nearby_speed = (Speed::new(45.02), SpeedUnit::MilesPerHour);
+        let grade = (Grade::new(1.0), GradeUnit::Percent);
+        assert_eq!(
+            cached.predict_energy_rate(nearby_speed, grade).unwrap(),
+            cached.predict_energy_rate(speed, grade).unwrap()
+        );
     }
 }
@@ -1,6 +1,9 @@
 use std::path::Path;
 
-use crate::routee::prediction::prediction_model::PredictionModel;
+use crate::routee::prediction::{
+    model_input_feature::ModelInputFeature, prediction_model::PredictionModel,
+    prediction_model_record::ModelInputs,
+};
 use routee_compass_core::{
     model::traversal::traversal_model_error::TraversalModelError,
     model::unit::{as_f64::AsF64, EnergyRate, EnergyRateUnit, Grade, GradeUnit, Speed, SpeedUnit},
@@ -11,6 +14,7 @@ use smartcore::{
 
 pub struct SmartcoreSpeedGradeModel {
     rf: RandomForestRegressor<f64, f64, DenseMatrix<f64>, Vec<f64>>,
+    input_features: Vec<ModelInputFeature>,
     speed_unit: SpeedUnit,
     grade_unit: GradeUnit,
     energy_rate_unit: EnergyRateUnit,
@@ -26,7 +30,8 @@ impl PredictionModel for SmartcoreSpeedGradeModel {
         let (grade, grade_unit) = grade;
         let speed_value = speed_unit.convert(&speed, &self.speed_unit).as_f64();
         let grade_value = grade_unit.convert(&grade, &self.grade_unit).as_f64();
-        let x = DenseMatrix::from_2d_vec(&vec![vec![speed_value, grade_value]]);
+        let row = ModelInputFeature::build_row(&self.input_features, speed_value, grade_value);
+        let x = DenseMatrix::from_2d_vec(&vec![row]);
         let y = self
             .rf
             .predict(&x)
@@ -35,11 +40,38 @@ impl PredictionModel for SmartcoreSpeedGradeModel {
         let energy_rate = EnergyRate::new(y[0]);
         Ok((energy_rate, self.energy_rate_unit))
     }
+
+    fn predict_batch(
+        &self,
+        inputs: &[ModelInputs],
+    ) -> Result<Vec<(EnergyRate, EnergyRateUnit)>, TraversalModelError> {
+        if inputs.is_empty() {
+            return Ok(vec![]);
+        }
+        let rows = inputs
+            .iter()
+            .map(|((speed, speed_unit), (grade, grade_unit))| {
+                let speed_value = speed_unit.convert(speed, &self.speed_unit).as_f64();
+                let grade_value = grade_unit.convert(grade, &self.grade_unit).as_f64();
+                ModelInputFeature::build_row(&self.input_features, speed_value, grade_value)
+            })
+            .collect::<Vec<_>>();
+        let x = DenseMatrix::from_2d_vec(&rows);
+        let y = self
+            .rf
+            .predict(&x)
+            .map_err(|e| TraversalModelError::PredictionModel(e.to_string()))?;
+        Ok(y.into_iter()
+            .map(|rate| (EnergyRate::new(rate), self.energy_rate_unit))
+            .collect())
+    }
 }
 
 impl SmartcoreSpeedGradeModel {
+    /// loads a smartcore random forest model from a bincode file.
     pub fn new<P: AsRef<Path>>(
         routee_model_path: &P,
+        input_features: Vec<ModelInputFeature>,
         speed_unit: SpeedUnit,
         grade_unit: GradeUnit,
         energy_rate_unit: EnergyRateUnit,
@@ -51,18 +83,175 @@ impl SmartcoreSpeedGradeModel {
                 e.to_string(),
             )
         })?;
+        Self::from_bytes(
+            &rf_binary,
+            input_features,
+            speed_unit,
+            grade_unit,
+            energy_rate_unit,
+        )
+        .map_err(|e| match e {
+            TraversalModelError::BuildError(msg) => TraversalModelError::BuildError(format!(
+                "model file {}: {}",
+                routee_model_path.as_ref().to_string_lossy(),
+                msg
+            )),
+            other => other,
+        })
+    }
+
+    /// loads a smartcore random forest model from a buffer containing the bincode
+    /// model, such as a model fetched from remote storage instead of the filesystem.
+    pub fn from_bytes(
+        bytes: &[u8],
+        input_features: Vec<ModelInputFeature>,
+        speed_unit: SpeedUnit,
+        grade_unit: GradeUnit,
+        energy_rate_unit: EnergyRateUnit,
+    ) -> Result<Self, TraversalModelError> {
         let rf: RandomForestRegressor<f64, f64, DenseMatrix<f64>, Vec<f64>> =
-            bincode::deserialize(&rf_binary).map_err(|e| {
-                TraversalModelError::FileReadError(
-                    routee_model_path.as_ref().to_path_buf(),
-                    e.to_string(),
-                )
+            bincode::deserialize(bytes).map_err(|e| {
+                TraversalModelError::BuildError(format!(
+                    "failed to deserialize smartcore model: {}",
+                    e
+                ))
             })?;
+        ModelInputFeature::validate(&input_features)?;
+        if let Some(n_model_features) = min_input_arity(&rf)? {
+            if n_model_features > input_features.len() {
+                return Err(TraversalModelError::BuildError(format!(
+                    "model splits on at least {} input features but {} input features were configured",
+                    n_model_features,
+                    input_features.len()
+                )));
+            }
+        }
         Ok(SmartcoreSpeedGradeModel {
             rf,
+            input_features,
             speed_unit,
             grade_unit,
             energy_rate_unit,
         })
     }
 }
+
+/// finds the smallest number of input features the random forest can accept,
+/// which is one more than the largest feature index any tree splits on. smartcore
+/// does not expose the training arity directly, so the trees are inspected through
+/// their serialized form.
+///
+/// # Returns
+///
+/// the minimum input arity, or None if the forest contains no trees
+fn min_input_arity(
+    rf: &RandomForestRegressor<f64, f64, DenseMatrix<f64>, Vec<f64>>,
+) -> Result<Option<usize>, TraversalModelError> {
+    let value = serde_json::to_value(rf).map_err(|e| {
+        TraversalModelError::BuildError(format!("failed to inspect random forest: {}", e))
+    })?;
+    let trees = match value.get("trees").and_then(|t| t.as_array()) {
+        Some(trees) if !trees.is_empty() => trees,
+        _ => return Ok(None),
+    };
+    let max_split_feature = trees
+        .iter()
+        .filter_map(|tree| tree.get("nodes").and_then(|n| n.as_array()))
+        .flatten()
+        .filter(|node| !node["split_value"].is_null())
+        .filter_map(|node| node["split_feature"].as_u64())
+        .max();
+    Ok(max_split_feature.map(|f| f as usize + 1))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::routee::test_util::test_file;
+
+    #[test]
+    fn test_swapped_input_feature_order() {
+        // model trained on rows of [grade (percent), speed (mph)] with target
+        // energy rate = 0.001 * speed + 0.01 * grade
+        let file = test_file("grade_speed_model.bin");
+        let correct = SmartcoreSpeedGradeModel::new(
+            &file,
+            vec![ModelInputFeature::Grade, ModelInputFeature::Speed],
+            SpeedUnit::MilesPerHour,
+            GradeUnit::Percent,
+            EnergyRateUnit::GallonsGasolinePerMile,
+        )
+        .unwrap();
+        let default_order = SmartcoreSpeedGradeModel::new(
+            &file,
+            ModelInputFeature::DEFAULT_ORDER.to_vec(),
+            SpeedUnit::MilesPerHour,
+            GradeUnit::Percent,
+            EnergyRateUnit::GallonsGasolinePerMile,
+        )
+        .unwrap();
+
+        let speed = (Speed::new(60.0), SpeedUnit::MilesPerHour);
+        let grade = (Grade::new(-4.0), GradeUnit::Percent);
+        let expected = 0.001 * 60.0 + 0.01 * -4.0;
+        let (correct_rate, _) = correct.predict(speed, grade).unwrap();
+        let (default_rate, _) = default_order.predict(speed, grade).unwrap();
+        assert!(
+            (correct_rate.as_f64() - expected).abs() < 0.005,
+            "expected {} but found {}",
+            expected,
+            correct_rate
+        );
+        assert!((default_rate.as_f64() - expected).abs() > 0.005);
+    }
+
+    #[test]
+    fn test_too_few_input_features_fails() {
+        let result = SmartcoreSpeedGradeModel::new(
+            &test_file("grade_speed_model.bin"),
+            vec![ModelInputFeature::Speed],
+            SpeedUnit::MilesPerHour,
+            GradeUnit::Percent,
+            EnergyRateUnit::GallonsGasolinePerMile,
+        );
+        assert!(matches!(result, Err(TraversalModelError::BuildError(_))));
+    }
+
+    #[test]
+    fn test_from_bytes_matches_file() {
+        let file = test_file("Toyota_Camry.bin");
+        let bytes = std::fs::read(&file).unwrap();
+        let from_bytes = SmartcoreSpeedGradeModel::from_bytes(
+            &bytes,
+            ModelInputFeature::DEFAULT_ORDER.to_vec(),
+            SpeedUnit::MilesPerHour,
+            GradeUnit::Decimal,
+            EnergyRateUnit::GallonsGasolinePerMile,
+        )
+        .unwrap();
+        let from_file = SmartcoreSpeedGradeModel::new(
+            &file,
+            ModelInputFeature::DEFAULT_ORDER.to_vec(),
+            SpeedUnit::MilesPerHour,
+            GradeUnit::Decimal,
+            EnergyRateUnit::GallonsGasolinePerMile,
+        )
+        .unwrap();
+
+        let speed = (Speed::new(45.0), SpeedUnit::MilesPerHour);
+        let grade = (Grade::new(0.02), GradeUnit::Decimal);
+        assert_eq!(
+            from_bytes.predict(speed, grade).unwrap(),
+            from_file.predict(speed, grade).unwrap()
+        );
+
+        let invalid = SmartcoreSpeedGradeModel::from_bytes(
+            &bytes[0..16],
+            ModelInputFeature::DEFAULT_ORDER.to_vec(),
+            SpeedUnit::MilesPerHour,
+            GradeUnit::Decimal,
+            EnergyRateUnit::GallonsGasolinePerMile,
+        );
+        assert!(matches!(invalid, Err(TraversalModelError::BuildError(_))));
+    }
+}
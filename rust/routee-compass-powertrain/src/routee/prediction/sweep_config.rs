@@ -0,0 +1,115 @@
+use routee_compass_core::model::{
+    traversal::traversal_model_error::TraversalModelError,
+    unit::{Grade, GradeUnit, Speed, SpeedUnit},
+};
+use serde::{Deserialize, Serialize};
+
+use super::prediction_model_record::ModelInputs;
+
+/// the grid of speed and grade values swept to find the minimum energy rate
+/// of a prediction model when no ideal energy rate is configured. ranges are
+/// inclusive and both axes advance by the same step.
+///
+/// # Example
+///
+/// ### Deserialization
+///
+/// ```toml
+/// [traversal.vehicles.min_energy_sweep]
+/// speed_range = [10.0, 70.0]
+/// grade_range = [-4.0, 4.0]
+/// step = 2.0
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct SweepConfig {
+    /// lower and upper speed of the sweep in miles per hour
+    pub speed_range: (f64, f64),
+    /// lower and upper grade of the sweep in percent
+    pub grade_range: (f64, f64),
+    /// distance between swept values along each axis
+    pub step: f64,
+}
+
+impl Default for SweepConfig {
+    /// speeds of 20 to 79 mph on flat ground
+    fn default() -> Self {
+        SweepConfig {
+            speed_range: (20.0, 79.0),
+            grade_range: (0.0, 0.0),
+            step: 1.0,
+        }
+    }
+}
+
+impl SweepConfig {
+    /// lists every (speed, grade) pair of the sweep, with speed as the outer axis.
+    pub fn points(&self) -> Result<Vec<ModelInputs>, TraversalModelError> {
+        let speeds = self.axis("speed_range", self.speed_range)?;
+        let grades = self.axis("grade_range", self.grade_range)?;
+        let points = speeds
+            .iter()
+            .flat_map(|speed| {
+                grades.iter().map(move |grade| {
+                    (
+                        (Speed::new(*speed), SpeedUnit::MilesPerHour),
+                        (Grade::new(*grade), GradeUnit::Percent),
+                    )
+                })
+            })
+            .collect();
+        Ok(points)
+    }
+
+    fn axis(&self, name: &str, range: (f64, f64)) -> Result<Vec<f64>, TraversalModelError> {
+        let (lower, upper) = range;
+        if !(self.step.is_finite() && self.step > 0.0) {
+            return Err(TraversalModelError::BuildError(format!(
+                "sweep step must be a positive number, found {}",
+                self.step
+            )));
+        }
+        if !(lower.is_finite() && upper.is_finite()) || lower > upper {
+            return Err(TraversalModelError::BuildError(format!(
+                "sweep {} must be finite with lower <= upper, found [{}, {}]",
+                name, lower, upper
+            )));
+        }
+        // tolerance keeps an upper bound reached by accumulated steps in the sweep
+        let n = ((upper - lower) / self.step + 1e-9).floor() as usize + 1;
+        Ok((0..n).map(|i| lower + i as f64 * self.step).collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_sweep_points() {
+        let default_points = SweepConfig::default().points().unwrap();
+        assert_eq!(default_points.len(), 60);
+        assert_eq!(default_points[0].0 .0, Speed::new(20.0));
+        assert_eq!(default_points[59].0 .0, Speed::new(79.0));
+        assert!(default_points.iter().all(|(_, g)| g.0 == Grade::ZERO));
+
+        let sweep = SweepConfig {
+            speed_range: (10.0, 20.0),
+            grade_range: (-0.2, 0.2),
+            step: 0.2,
+        };
+        let points = sweep.points().unwrap();
+        // 51 speeds by 3 grades
+        assert_eq!(points.len(), 153);
+
+        let invalid_step = SweepConfig {
+            step: 0.0,
+            ..Default::default()
+        };
+        assert!(invalid_step.points().is_err());
+        let invalid_range = SweepConfig {
+            speed_range: (80.0, 20.0),
+            ..Default::default()
+        };
+        assert!(invalid_range.points().is_err());
+    }
+}
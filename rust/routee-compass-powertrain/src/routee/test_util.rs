@@ -0,0 +1,29 @@
+use super::prediction::{load_prediction_model, model_type::ModelType, PredictionModelRecord};
+use routee_compass_core::model::unit::{EnergyRateUnit, GradeUnit, SpeedUnit};
+use std::path::PathBuf;
+
+/// the path to a file in the routee test directory
+pub(crate) fn test_file(filename: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src")
+        .join("routee")
+        .join("test")
+        .join(filename)
+}
+
+/// loads the Toyota Camry smartcore model, which predicts gallons of gasoline per mile
+/// from speeds in miles per hour and grades in the given unit
+pub(crate) fn camry_record(grade_unit: GradeUnit) -> PredictionModelRecord {
+    load_prediction_model(
+        "Toyota_Camry".to_string(),
+        &test_file("Toyota_Camry.bin"),
+        ModelType::Smartcore,
+        SpeedUnit::MilesPerHour,
+        grade_unit,
+        EnergyRateUnit::GallonsGasolinePerMile,
+        None,
+        None,
+        None,
+    )
+    .unwrap()
+}
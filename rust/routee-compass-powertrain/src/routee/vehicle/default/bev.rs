@@ -2,6 +2,7 @@ use crate::routee::{
     prediction::PredictionModelRecord,
     vehicle::{vehicle_ops, vehicle_type::VehicleType},
 };
+use routee_compass_core::model::traversal::strict_mode;
 use routee_compass_core::model::{
     state::{
         custom_feature_format::CustomFeatureFormat, state_feature::StateFeature,
@@ -58,6 +59,7 @@ impl VehicleType for BEV {
                 StateFeature::Energy {
                     energy_unit: self.battery_energy_unit,
                     initial: Energy::ZERO,
+                    output_alias: None,
                 },
             ),
             (
@@ -68,6 +70,7 @@ impl VehicleType for BEV {
                     format: CustomFeatureFormat::FloatingPoint {
                         initial: initial_soc.into(),
                     },
+                    output_alias: None,
                 },
             ),
         ]
@@ -141,6 +144,15 @@ impl VehicleType for BEV {
         Ok(())
     }
 
+    fn effective_speed(
+        &self,
+        speed: (Speed, SpeedUnit),
+        _state: &[StateVar],
+        _state_model: &StateModel,
+    ) -> Result<(Speed, SpeedUnit), TraversalModelError> {
+        Ok(self.prediction_model_record.bound_speed(speed))
+    }
+
     fn update_from_query(
         &self,
         query: &serde_json::Value,
@@ -162,9 +174,11 @@ impl VehicleType for BEV {
         let starting_battery_energy =
             Energy::new(starting_soc_percent * self.battery_capacity.as_f64());
 
+        let strict = strict_mode::is_strict(query)?;
+
         let new_bev = BEV {
             name: self.name.clone(),
-            prediction_model_record: self.prediction_model_record.clone(),
+            prediction_model_record: self.prediction_model_record.with_strict_mode(strict),
             battery_capacity: self.battery_capacity,
             starting_battery_energy,
             battery_energy_unit: self.battery_energy_unit,
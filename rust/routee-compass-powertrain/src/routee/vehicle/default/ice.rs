@@ -1,4 +1,5 @@
 use crate::routee::{prediction::PredictionModelRecord, vehicle::VehicleType};
+use routee_compass_core::model::traversal::strict_mode;
 use routee_compass_core::model::{
     state::{state_feature::StateFeature, state_model::StateModel},
     traversal::{state::state_variable::StateVar, traversal_model_error::TraversalModelError},
@@ -9,17 +10,36 @@ use std::sync::Arc;
 pub struct ICE {
     pub name: String,
     pub prediction_model_record: Arc<PredictionModelRecord>,
+    /// unit used to store the accumulated energy state, which may differ from
+    /// the energy unit of the prediction model.
+    pub energy_unit: EnergyUnit,
 }
 
 impl ICE {
     const ENERGY_FEATURE_NAME: &'static str = "energy_liquid";
+
+    /// creates a conventional vehicle.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - vehicle name
+    /// * `prediction_model_record` - energy prediction model for this vehicle
+    /// * `energy_unit` - optional storage unit for the energy state, defaulting to the energy
+    ///   unit of the prediction model. predicted energy is converted to this unit as it is accumulated.
     pub fn new(
         name: String,
         prediction_model_record: PredictionModelRecord,
+        energy_unit: Option<EnergyUnit>,
     ) -> Result<Self, TraversalModelError> {
+        let energy_unit = energy_unit.unwrap_or_else(|| {
+            prediction_model_record
+                .energy_rate_unit
+                .associated_energy_unit()
+        });
         Ok(Self {
             name,
             prediction_model_record: Arc::new(prediction_model_record),
+            energy_unit,
         })
     }
 }
@@ -29,15 +49,12 @@ impl VehicleType for ICE {
         self.name.clone()
     }
     fn state_features(&self) -> Vec<(String, StateFeature)> {
-        let energy_unit = self
-            .prediction_model_record
-            .energy_rate_unit
-            .associated_energy_unit();
         vec![(
             String::from(ICE::ENERGY_FEATURE_NAME),
             StateFeature::Energy {
-                energy_unit,
+                energy_unit: self.energy_unit,
                 initial: Energy::ZERO,
+                output_alias: None,
             },
         )]
     }
@@ -97,14 +114,100 @@ impl VehicleType for ICE {
         Ok(())
     }
 
+    fn effective_speed(
+        &self,
+        speed: (Speed, SpeedUnit),
+        _state: &[StateVar],
+        _state_model: &StateModel,
+    ) -> Result<(Speed, SpeedUnit), TraversalModelError> {
+        Ok(self.prediction_model_record.bound_speed(speed))
+    }
+
     fn update_from_query(
         &self,
-        _query: &serde_json::Value,
+        query: &serde_json::Value,
     ) -> Result<Arc<dyn VehicleType>, TraversalModelError> {
-        // just return a clone of self
+        let strict = strict_mode::is_strict(query)?;
         Ok(Arc::new(ICE {
             name: self.name.clone(),
-            prediction_model_record: self.prediction_model_record.clone(),
+            prediction_model_record: self.prediction_model_record.with_strict_mode(strict),
+            energy_unit: self.energy_unit,
         }))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routee::test_util::camry_record;
+    use routee_compass_core::model::unit::{as_f64::AsF64, BASE_ENERGY_UNIT};
+
+    fn build_ice(energy_unit: Option<EnergyUnit>) -> ICE {
+        build_corrected_ice(energy_unit, 1.0)
+    }
+
+    fn build_corrected_ice(energy_unit: Option<EnergyUnit>, correction_factor: f64) -> ICE {
+        let model_record = camry_record(GradeUnit::Decimal)
+            .with_correction_factor(correction_factor)
+            .unwrap();
+        ICE::new("Toyota_Camry".to_string(), model_record, energy_unit).unwrap()
+    }
+
+    /// accumulates energy over a few edges, returning the total in gallons of gasoline
+    fn accumulate(vehicle: &ICE) -> Energy {
+        let state_model = StateModel::empty()
+            .extend(vehicle.state_features())
+            .unwrap();
+        let mut state = state_model.initial_state().unwrap();
+        for (speed, grade) in [(30.0, 0.0), (45.0, 0.02), (60.0, -0.01)] {
+            vehicle
+                .consume_energy(
+                    (Speed::new(speed), SpeedUnit::MilesPerHour),
+                    (Grade::new(grade), GradeUnit::Decimal),
+                    (Distance::new(1000.0), DistanceUnit::Meters),
+                    &mut state,
+                    &state_model,
+                )
+                .unwrap();
+        }
+        state_model
+            .get_energy(
+                &state,
+                &ICE::ENERGY_FEATURE_NAME.into(),
+                &EnergyUnit::GallonsGasoline,
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn test_energy_storage_unit_matches_native_accumulation() {
+        let native = accumulate(&build_ice(None));
+        let stored_in_base = accumulate(&build_ice(Some(BASE_ENERGY_UNIT)));
+        let (a, b) = (native.as_f64(), stored_in_base.as_f64());
+        assert!(a > 0.0);
+        // conversion factors between gallons and kilowatt-hours round-trip within 0.01%
+        assert!((a - b).abs() / a < 1e-4, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn test_correction_factor_scales_energy_and_estimate() {
+        let base = build_ice(None);
+        let corrected = build_corrected_ice(None, 1.5);
+
+        let (base_energy, corrected_energy) = (accumulate(&base), accumulate(&corrected));
+        let ratio = corrected_energy.as_f64() / base_energy.as_f64();
+        assert!((ratio - 1.5).abs() < 1e-9, "energy ratio {}", ratio);
+
+        let distance = (Distance::new(3000.0), DistanceUnit::Meters);
+        let (base_estimate, _) = base.best_case_energy(distance).unwrap();
+        let (corrected_estimate, _) = corrected.best_case_energy(distance).unwrap();
+        let ratio = corrected_estimate.as_f64() / base_estimate.as_f64();
+        assert!((ratio - 1.5).abs() < 1e-9, "estimate ratio {}", ratio);
+    }
+
+    #[test]
+    fn test_non_positive_correction_factor_fails() {
+        let model_record = camry_record(GradeUnit::Decimal);
+        assert!(model_record.with_correction_factor(0.0).is_err());
+    }
+}
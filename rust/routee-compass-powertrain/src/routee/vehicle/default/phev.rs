@@ -2,6 +2,7 @@ use crate::routee::{
     prediction::PredictionModelRecord,
     vehicle::{vehicle_ops, vehicle_type::VehicleType},
 };
+use routee_compass_core::model::traversal::strict_mode;
 use routee_compass_core::model::{
     state::{
         custom_feature_format::CustomFeatureFormat, state_feature::StateFeature,
@@ -69,6 +70,7 @@ impl VehicleType for PHEV {
                 StateFeature::Energy {
                     energy_unit: self.battery_energy_unit,
                     initial: Energy::ZERO,
+                    output_alias: None,
                 },
             ),
             (
@@ -79,6 +81,7 @@ impl VehicleType for PHEV {
                     format: CustomFeatureFormat::FloatingPoint {
                         initial: initial_soc.into(),
                     },
+                    output_alias: None,
                 },
             ),
             (
@@ -86,6 +89,7 @@ impl VehicleType for PHEV {
                 StateFeature::Energy {
                     energy_unit: liquid_energy_unit,
                     initial: Energy::ZERO,
+                    output_alias: None,
                 },
             ),
         ]
@@ -166,6 +170,22 @@ impl VehicleType for PHEV {
         Ok(())
     }
 
+    /// the speed received by the charge depleting model while there is battery
+    /// energy left, and otherwise by the charge sustaining model.
+    fn effective_speed(
+        &self,
+        speed: (Speed, SpeedUnit),
+        state: &[StateVar],
+        state_model: &StateModel,
+    ) -> Result<(Speed, SpeedUnit), TraversalModelError> {
+        let soc = state_model.get_custom_f64(state, &PHEV::SOC_FEATURE_NAME.into())?;
+        if soc > 0.0 {
+            Ok(self.charge_depleting_model.bound_speed(speed))
+        } else {
+            Ok(self.charge_sustain_model.bound_speed(speed))
+        }
+    }
+
     fn update_from_query(
         &self,
         query: &serde_json::Value,
@@ -191,10 +211,12 @@ impl VehicleType for PHEV {
         let starting_battery_energy =
             Energy::new(starting_soc_percent * self.battery_capacity.as_f64());
 
+        let strict = strict_mode::is_strict(query)?;
+
         let new_phev = PHEV {
             name: self.name.clone(),
-            charge_sustain_model: self.charge_sustain_model.clone(),
-            charge_depleting_model: self.charge_depleting_model.clone(),
+            charge_sustain_model: self.charge_sustain_model.with_strict_mode(strict),
+            charge_depleting_model: self.charge_depleting_model.with_strict_mode(strict),
             battery_capacity: self.battery_capacity,
             starting_battery_energy,
             battery_energy_unit: self.battery_energy_unit,
@@ -205,15 +227,15 @@ impl VehicleType for PHEV {
     }
 }
 
-/// Compute the energy for the PHEV by converting liquid_fuel to kWh.
-/// This uses a simplified operation in which we assume that if the battery
-/// SOC is greater than zero we can just operate on battery to traverse a link.
-/// This is not entirely realistic as it's possible to arrive at a link with
-/// 0.001% SOC and still need to use liquid_fuel to traverse the link.
+/// Compute the energy for the PHEV to traverse a link. The battery is used
+/// until it is depleted, after which the liquid_fuel engine is used.
 ///
-/// In the future we could make this more sophisticated by calculating
-/// the energy required to traverse the link using the battery and then
-/// finding the point at which we would have to switch to liquid_fuel
+/// When the battery holds less energy than is needed to traverse the whole
+/// link in charge depleting mode, the link is split at the point where the
+/// battery is depleted. This point is found as the fraction of the charge
+/// depleting energy of the link that the remaining battery energy covers.
+/// The battery energy is used up to that point and the remainder of the link
+/// is traversed in charge sustaining mode.
 ///
 /// Returns a tuple of (electrical_energy, electrical_energy_unit, liquid_fuel_energy, liquid_fuel_energy_unit)
 fn get_phev_energy(
@@ -232,29 +254,51 @@ fn get_phev_energy(
         .energy_rate_unit
         .associated_energy_unit();
 
-    if battery_soc_percent > 0.0 {
-        // assume we can just use the battery
-        let (electrical_energy, electrical_energy_unit) = vehicle
-            .charge_depleting_model
-            .predict(speed, grade, distance)?;
-        Ok((
-            electrical_energy,
-            electrical_energy_unit,
-            Energy::new(0.0),
-            liquid_fuel_energy_unit,
-        ))
-    } else {
+    if battery_soc_percent <= 0.0 {
         // just use the liquid_fuel engine
         let (liquid_fuel_energy, liquid_fuel_energy_unit) = vehicle
             .charge_sustain_model
             .predict(speed, grade, distance)?;
-        Ok((
+        return Ok((
             Energy::new(0.0),
             electrical_energy_unit,
             liquid_fuel_energy,
             liquid_fuel_energy_unit,
-        ))
+        ));
     }
+
+    let (electrical_energy, electrical_energy_unit) = vehicle
+        .charge_depleting_model
+        .predict(speed, grade, distance)?;
+    let remaining_battery =
+        Energy::new(vehicle.battery_capacity.as_f64() * battery_soc_percent / 100.0);
+    let required_battery =
+        electrical_energy_unit.convert(&electrical_energy, &vehicle.battery_energy_unit);
+    if required_battery <= remaining_battery {
+        // the battery covers the whole link
+        return Ok((
+            electrical_energy,
+            electrical_energy_unit,
+            Energy::new(0.0),
+            liquid_fuel_energy_unit,
+        ));
+    }
+
+    // the battery is depleted partway along the link
+    let electric_fraction = remaining_battery.as_f64() / required_battery.as_f64();
+    let (distance, distance_unit) = distance;
+    let liquid_fuel_distance = Distance::new(distance.as_f64() * (1.0 - electric_fraction));
+    let (liquid_fuel_energy, liquid_fuel_energy_unit) = vehicle.charge_sustain_model.predict(
+        speed,
+        grade,
+        (liquid_fuel_distance, distance_unit),
+    )?;
+    Ok((
+        Energy::new(electrical_energy.as_f64() * electric_fraction),
+        electrical_energy_unit,
+        liquid_fuel_energy,
+        liquid_fuel_energy_unit,
+    ))
 }
 
 #[cfg(test)]
@@ -407,9 +451,14 @@ mod tests {
             )
             .unwrap();
 
-        assert!(elec > Energy::ZERO, "elec energy {} should be > 0", elec);
+        // the battery is depleted partway along the link, and the rest of the link uses liquid_fuel
+        assert!(
+            (elec.as_f64() - 12.0).abs() < 1e-6,
+            "elec energy {} should be 12 kWh",
+            elec
+        );
         assert!(soc < 1e-9, "soc {} should be miniscule, < {}", soc, 1e-9);
-        assert!(liquid == Energy::ZERO, "should not have used liquid energy");
+        assert!(liquid > Energy::ZERO, "should have used liquid energy");
 
         // and then traverse the same distance but this time we should only use liquid_fuel energy
         vehicle
@@ -426,4 +475,68 @@ mod tests {
 
         assert!(liquid_energy_2 > Energy::ZERO);
     }
+
+    #[test]
+    fn test_phev_route_switches_to_liquid_fuel_when_battery_depleted() {
+        let vehicle = mock_vehicle();
+        let state_model = StateModel::empty()
+            .extend(vehicle.state_features())
+            .unwrap();
+        let mut state = state_model.initial_state().unwrap();
+        let distance = (Distance::new(5.0), DistanceUnit::Miles);
+        let speed = (Speed::new(60.0), SpeedUnit::MilesPerHour);
+        let grade = (Grade::new(0.0), GradeUnit::Decimal);
+        let get_energy = |state: &[StateVar]| {
+            let elec = state_model
+                .get_energy(
+                    state,
+                    &PHEV::ELECTRIC_FEATURE_NAME.into(),
+                    &EnergyUnit::KilowattHours,
+                )
+                .unwrap();
+            let liquid = state_model
+                .get_energy(
+                    state,
+                    &PHEV::LIQUID_FEATURE_NAME.into(),
+                    &EnergyUnit::GallonsGasoline,
+                )
+                .unwrap();
+            (elec.as_f64(), liquid.as_f64())
+        };
+
+        // a 100 mile route of 5 mile links, long enough to deplete the 12 kWh battery
+        let mut links = vec![];
+        for _ in 0..20 {
+            let (elec_before, liquid_before) = get_energy(&state);
+            vehicle
+                .consume_energy(speed, grade, distance, &mut state, &state_model)
+                .unwrap();
+            let (elec_after, liquid_after) = get_energy(&state);
+            links.push((elec_after - elec_before, liquid_after - liquid_before));
+        }
+
+        // the battery is used alone until it is depleted, then liquid_fuel alone,
+        // with a single link using both where the battery is depleted
+        let switch = links.iter().position(|(_, liquid)| *liquid > 0.0).unwrap();
+        assert!(switch > 0, "first link should be all electric");
+        assert!(links[..switch].iter().all(|(elec, _)| *elec > 0.0));
+        let (switch_elec, switch_liquid) = links[switch];
+        assert!(switch_elec > 0.0 && switch_liquid > 0.0);
+        assert!(switch_liquid < links[switch + 1].1);
+        assert!(links[switch + 1..]
+            .iter()
+            .all(|(elec, liquid)| *elec == 0.0 && *liquid > 0.0));
+
+        // the electric energy drawn is exactly the battery capacity
+        let (elec, _) = get_energy(&state);
+        assert!(
+            (elec - 12.0).abs() < 1e-6,
+            "elec energy {} should be 12 kWh",
+            elec
+        );
+        let soc = state_model
+            .get_custom_f64(&state, &PHEV::SOC_FEATURE_NAME.into())
+            .unwrap();
+        assert_eq!(soc, 0.0);
+    }
 }
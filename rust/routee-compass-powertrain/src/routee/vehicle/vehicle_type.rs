@@ -35,6 +35,25 @@ pub trait VehicleType: Send + Sync {
         state_model: &StateModel,
     ) -> Result<(), TraversalModelError>;
 
+    /// Return the speed the energy prediction model receives for an input speed,
+    /// after the input bounds of the model are applied. by default, the input
+    /// speed is used as-is.
+    ///
+    /// Arguments:
+    /// * `speed` - The speed at which the vehicle is traveling
+    /// * `state` - The state of the vehicle before traveling
+    ///
+    /// Returns:
+    /// * `(Speed, SpeedUnit)` - The effective speed used to predict energy
+    fn effective_speed(
+        &self,
+        speed: (Speed, SpeedUnit),
+        _state: &[StateVar],
+        _state_model: &StateModel,
+    ) -> Result<(Speed, SpeedUnit), TraversalModelError> {
+        Ok(speed)
+    }
+
     /// Return the best case scenario for traveling a certain distance.
     /// This is used in the a-star algorithm as a distance heuristic.
     ///
@@ -1,5 +1,6 @@
 use std::str::FromStr;
 
+use geo::coord;
 use routee_compass_core::{
     algorithm::search::direction::Direction,
     model::{
@@ -133,20 +134,7 @@ pub trait CompassAppBindings {
         edge_id: usize,
         distance_unit: Option<String>,
     ) -> Result<f64, CompassAppError> {
-        let du_internal: Option<DistanceUnit> = match distance_unit {
-            Some(du_str) => {
-                let du = DistanceUnit::from_str(du_str.as_str()).map_err(|_| {
-                    CompassAppError::InternalError(format!(
-                        "could not deserialize distance unit '{}'",
-                        du_str
-                    ))
-                })?;
-
-                Some(du)
-            }
-
-            None => None,
-        };
+        let du_internal = parse_distance_unit(distance_unit)?;
         let edge_id_internal = EdgeId(edge_id);
         self.app()
             .search_app
@@ -184,6 +172,30 @@ pub trait CompassAppBindings {
             .map(|es| es.iter().map(|e| e.0).collect())
     }
 
+    /// Get the nearest vertices to a coordinate by haversine distance, without running a search
+    ///
+    /// # Arguments
+    /// * `x` - the longitude of the coordinate
+    /// * `y` - the latitude of the coordinate
+    /// * `k` - the number of vertices to return
+    /// * `distance_unit` - the distance unit to use. If not provided, the default distance unit is meters
+    ///
+    /// # Returns
+    /// * up to `k` pairs of vertex id and distance to the coordinate, nearest first
+    fn graph_nearest_vertices(
+        &self,
+        x: f32,
+        y: f32,
+        k: usize,
+        distance_unit: Option<String>,
+    ) -> Result<Vec<(usize, f64)>, CompassAppError> {
+        let du_internal = parse_distance_unit(distance_unit)?;
+        self.app()
+            .search_app
+            .get_nearest_vertices(coord! {x: x, y: y}, k, du_internal)
+            .map(|vs| vs.iter().map(|(v, d)| (v.0, d.as_f64())).collect())
+    }
+
     /// Runs a set of queries and returns the results
     ///
     /// # Arguments
@@ -215,3 +227,21 @@ pub trait CompassAppBindings {
         Ok(string_results)
     }
 }
+
+/// deserializes an optional distance unit provided by a language binding
+fn parse_distance_unit(
+    distance_unit: Option<String>,
+) -> Result<Option<DistanceUnit>, CompassAppError> {
+    match distance_unit {
+        Some(du_str) => {
+            let du = DistanceUnit::from_str(du_str.as_str()).map_err(|_| {
+                CompassAppError::InternalError(format!(
+                    "could not deserialize distance unit '{}'",
+                    du_str
+                ))
+            })?;
+            Ok(Some(du))
+        }
+        None => Ok(None),
+    }
+}
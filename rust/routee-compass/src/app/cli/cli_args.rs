@@ -1,4 +1,5 @@
 use clap::Parser;
+use std::path::Path;
 
 use crate::app::compass::{
     compass_app_error::CompassAppError,
@@ -20,17 +21,37 @@ pub struct CliArgs {
     #[arg(long)]
     pub chunksize: Option<i64>,
 
-    /// Format of JSON queries file, if regular JSON or newline-delimited JSON
+    /// Format of JSON queries file, if regular JSON or newline-delimited JSON.
+    /// Files with a .jsonl or .ndjson extension are always newline-delimited
     #[arg(short, long)]
     pub newline_delimited: bool,
+
+    /// Log and skip lines of a newline-delimited queries file that are not valid JSON,
+    /// instead of stopping at the first one
+    #[arg(long)]
+    pub skip_bad_rows: bool,
 }
 
 impl CliArgs {
+    /// file extensions of newline-delimited JSON query files
+    pub const NEWLINE_DELIMITED_EXTENSIONS: [&'static str; 2] = ["jsonl", "ndjson"];
+
+    /// whether the query file is newline-delimited JSON, either by the
+    /// `newline_delimited` flag or by the extension of the file
+    pub fn is_newline_delimited(&self) -> bool {
+        let extension = Path::new(&self.query_file)
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_lowercase());
+        self.newline_delimited
+            || extension.is_some_and(|e| Self::NEWLINE_DELIMITED_EXTENSIONS.contains(&e.as_str()))
+    }
+
     pub fn validate(&self) -> Result<(), CompassAppError> {
-        match (self.chunksize, self.newline_delimited) {
+        match (self.chunksize, self.is_newline_delimited()) {
             (Some(_), false) => Err(CompassAppError::CompassConfigurationError(
                 CompassConfigurationError::UserConfigurationError(String::from(
-                    "chunksize must be set if newline_delimited_queries is true",
+                    "chunksize can only be set for newline-delimited queries",
                 )),
             )),
             (Some(chunksize), _) if chunksize < 1 => {
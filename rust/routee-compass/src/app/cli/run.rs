@@ -1,16 +1,21 @@
 use super::cli_args::CliArgs;
 use crate::app::compass::compass_app_ops as ops;
-use crate::app::compass::config::compass_configuration_error::CompassConfigurationError;
 use crate::app::compass::{
     compass_app::CompassApp, compass_app_error::CompassAppError,
     compass_json_extensions::CompassJsonExtensions, config::compass_app_builder::CompassAppBuilder,
+    response::batch_summary::BatchSummaryAggregator,
 };
-use itertools::{Either, Itertools};
-use log::{debug, error};
+use itertools::Itertools;
+use log::{debug, error, info};
 use serde_json::{json, Value};
 use std::io::BufRead;
+use std::sync::Mutex;
 use std::{fs::File, io::BufReader, path::Path};
 
+/// number of queries per chunk of a newline-delimited queries file when no
+/// chunksize is provided
+pub const DEFAULT_CHUNKSIZE: usize = 10_000;
+
 /// runs CompassApp from the command line using the provided app builder and optional
 /// additional CompassApp configuration overwrites.
 ///
@@ -22,6 +27,7 @@ use std::{fs::File, io::BufReader, path::Path};
 /// # Returns
 /// After executing all queries, returns nothing, or returns an un-handled application error.
 /// Any user errors are logged and optionally written to an output file depending on the file io policy.
+/// A summary of all responses, such as route counts and distance statistics, is logged at the end.
 pub fn command_line_runner(
     args: &CliArgs,
     builder: Option<CompassAppBuilder>,
@@ -47,19 +53,32 @@ pub fn command_line_runner(
     })?;
 
     // execute queries on app
-    match (args.chunksize, args.newline_delimited) {
-        (None, true) => Err(CompassAppError::InternalError(String::from(
-            "invalid argument combination should have been caught during CLI validation",
-        ))),
-        (None, false) => run_json(&query_file, &compass_app, run_config),
-        (Some(_), true) => {
+    let batch_summary = Mutex::new(BatchSummaryAggregator::default());
+    match (args.chunksize, args.is_newline_delimited()) {
+        (None, false) => run_json(&query_file, &compass_app, run_config, &batch_summary),
+        (_, true) => {
             let chunksize = args.get_chunksize_option()?;
-            run_newline_json(&query_file, chunksize, &compass_app, run_config)
+            run_newline_json(
+                &query_file,
+                chunksize,
+                args.skip_bad_rows,
+                &compass_app,
+                run_config,
+                &batch_summary,
+            )
         }
         (Some(_), false) => Err(CompassAppError::InternalError(String::from(
-            "not yet implemented",
+            "invalid argument combination should have been caught during CLI validation",
         ))),
-    }
+    }?;
+
+    let summary = batch_summary
+        .lock()
+        .map_err(|e| CompassAppError::InternalError(e.to_string()))?
+        .summary();
+    let summary_json = serde_json::to_string(&summary).map_err(CompassAppError::CodecError)?;
+    info!("batch summary: {}", summary_json);
+    Ok(())
 }
 
 /// parses a file as a valid JSON object and executes it as queries against
@@ -68,68 +87,149 @@ fn run_json(
     query_file: &File,
     compass_app: &CompassApp,
     run_config: Option<&Value>,
+    batch_summary: &Mutex<BatchSummaryAggregator>,
 ) -> Result<(), CompassAppError> {
     let reader = BufReader::new(query_file);
     let user_json: serde_json::Value =
         serde_json::from_reader(reader).map_err(CompassAppError::CodecError)?;
     let user_queries = user_json.get_queries()?;
-    let results = compass_app.run(user_queries, run_config)?;
+    let results = compass_app.run_with_summary(user_queries, run_config, batch_summary)?;
     for result in results.iter() {
         log_error(result);
     }
     Ok(())
 }
 
-/// parses a file as newline-delimited JSON which can be optionally chunked into sub-batches
-/// and each sub-batch run as queries against the CompassApp.run command.
-/// chunksize should be >> the configured CompassApp parallelism (from TOML file) for best
-/// performance.
+/// parses a file as newline-delimited JSON which is chunked into sub-batches and each
+/// sub-batch run as queries against the CompassApp.run command, so that only one chunk
+/// of queries is held in memory at a time. without a chunksize, chunks of
+/// [DEFAULT_CHUNKSIZE] queries are used. chunksize should be >> the configured
+/// CompassApp parallelism (from TOML file) for best performance.
+///
+/// blank lines are ignored. a line that is not valid JSON stops the run with an error
+/// naming its line number, after the queries of any earlier chunks have run. when
+/// `skip_bad_rows` is set, the error is logged instead and the remaining lines are run.
 fn run_newline_json(
     query_file: &File,
     chunksize_option: Option<usize>,
+    skip_bad_rows: bool,
     compass_app: &CompassApp,
     run_config: Option<&Value>,
+    batch_summary: &Mutex<BatchSummaryAggregator>,
 ) -> Result<(), CompassAppError> {
     let reader = BufReader::new(query_file);
-    let iterator = reader.lines();
-    let chunksize = chunksize_option.unwrap_or(usize::MAX);
+    let iterator = reader.lines().enumerate();
+    let chunksize = chunksize_option.unwrap_or(DEFAULT_CHUNKSIZE);
     let chunks = iterator.chunks(chunksize);
 
     for (iteration, chunk) in chunks.into_iter().enumerate() {
         debug!("executing batch {}", iteration + 1);
-        // parse JSON output
-        let (chunk_queries, errors): (Vec<Value>, Vec<CompassAppError>) =
-            chunk.partition_map(|row| match row {
-                Ok(string) => match serde_json::from_str(&string) {
-                    Ok(query) => Either::Left(query),
-                    Err(e) => Either::Right(CompassAppError::CompassConfigurationError(
-                        CompassConfigurationError::SerdeDeserializationError(e),
-                    )),
-                },
-                Err(e) => Either::Right(CompassAppError::IOError(e)),
-            });
+        let (chunk_queries, errors) = parse_chunk(chunk, skip_bad_rows)?;
 
         // run Compass on this chunk of queries
-        for result in compass_app.run(chunk_queries, run_config)?.iter() {
+        for result in compass_app
+            .run_with_summary(chunk_queries, run_config, batch_summary)?
+            .iter()
+        {
             log_error(result)
         }
 
         // report JSON parsing errors
         for error in errors {
-            let error_json = json!({
-                "request": "failed to parse",
-                "error": error.to_string()
-            });
-            log_error(&error_json)
+            log_error(&error);
+            if let Ok(mut summary) = batch_summary.lock() {
+                summary.add_response(&error, None);
+            }
         }
     }
 
     Ok(())
 }
 
+/// parses the lines of a chunk of a newline-delimited queries file, numbered from zero,
+/// into queries. lines that fail to parse are an error naming their line number in the
+/// file, or when `skip_bad_rows` is set, are returned as error responses with the same
+/// message.
+fn parse_chunk(
+    chunk: impl IntoIterator<Item = (usize, std::io::Result<String>)>,
+    skip_bad_rows: bool,
+) -> Result<(Vec<Value>, Vec<Value>), CompassAppError> {
+    let mut queries = vec![];
+    let mut errors = vec![];
+    for (index, row) in chunk {
+        let line_number = index + 1;
+        let parsed = match row {
+            Ok(string) if string.trim().is_empty() => continue,
+            Ok(string) => serde_json::from_str(&string).map_err(|e| {
+                // each line is parsed on its own, so serde reports every position as
+                // line 1. the position within the file is the line number and column.
+                let position = format!(" at line {} column {}", e.line(), e.column());
+                let message = e.to_string();
+                let message = message.strip_suffix(&position).unwrap_or(&message);
+                format!(
+                    "failed to parse query file line {} column {}: {}",
+                    line_number,
+                    e.column(),
+                    message
+                )
+            }),
+            Err(e) => Err(format!(
+                "failed to read query file line {}: {}",
+                line_number, e
+            )),
+        };
+        match parsed {
+            Ok(query) => queries.push(query),
+            Err(message) if skip_bad_rows => errors.push(json!({
+                "request": "failed to parse",
+                "line": line_number,
+                "error": message
+            })),
+            Err(message) => return Err(CompassAppError::InvalidInput(message)),
+        }
+    }
+    Ok((queries, errors))
+}
+
 fn log_error(result: &Value) {
     if let Some(error) = result.get("error") {
         let error_string = error.to_string().replace("\\n", "\n");
         error!("Error: {}", error_string);
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn lines(rows: &[&str]) -> Vec<(usize, std::io::Result<String>)> {
+        rows.iter()
+            .enumerate()
+            .map(|(index, row)| (index, Ok(String::from(*row))))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_chunk_reports_bad_line() {
+        let rows = [
+            r#"{"origin_x": 0.0}"#,
+            "",
+            r#"{"origin_x": "#,
+            r#"{"origin_x": 1.0}"#,
+        ];
+
+        let error = parse_chunk(lines(&rows), false).unwrap_err().to_string();
+        assert!(error.contains("line 3 column 13"), "{}", error);
+        assert!(!error.contains("line 1"), "{}", error);
+
+        // when skipping bad rows, the logged error names the line as well
+        let (queries, errors) = parse_chunk(lines(&rows), true).unwrap();
+        assert_eq!(queries.len(), 2);
+        assert_eq!(queries[1]["origin_x"], 1.0);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0]["line"], 3);
+        let logged = errors[0]["error"].as_str().unwrap();
+        assert!(logged.contains("line 3 column 13"), "{}", logged);
+        assert!(!logged.contains("line 1"), "{}", logged);
+    }
+}
@@ -1,3 +1,4 @@
+use super::response::batch_summary::BatchSummaryAggregator;
 use super::response::response_output_policy::ResponseOutputPolicy;
 use super::response::response_sink::ResponseSink;
 use super::{
@@ -11,6 +12,7 @@ use crate::{
             compass_app_error::CompassAppError,
             compass_input_field::CompassInputField,
             config::{
+                compass_configuration_error::CompassConfigurationError,
                 compass_configuration_field::CompassConfigurationField,
                 config_json_extension::ConfigJsonExtensions,
                 cost_model::cost_model_builder::CostModelBuilder,
@@ -18,7 +20,11 @@ use crate::{
                 termination_model_builder::TerminationModelBuilder,
             },
         },
-        search::{search_app::SearchApp, search_app_result::SearchAppResult},
+        search::{
+            search_app::SearchApp,
+            search_app_health::{HealthReport, PluginHealth},
+            search_app_result::SearchAppResult,
+        },
     },
     plugin::{
         input::{input_plugin::InputPlugin, input_plugin_ops as in_ops},
@@ -32,6 +38,7 @@ use kdam::{Bar, BarExt};
 use rayon::{current_num_threads, prelude::*};
 use routee_compass_core::algorithm::search::search_instance::SearchInstance;
 use routee_compass_core::model::state::state_model::StateModel;
+use routee_compass_core::model::traversal::strict_mode;
 use routee_compass_core::{
     algorithm::search::search_algorithm::SearchAlgorithm,
     util::duration_extension::DurationExtension,
@@ -146,6 +153,9 @@ impl TryFrom<(&Config, &CompassAppBuilder)> for CompassApp {
 
         let search_algorithm: SearchAlgorithm =
             config_json.get_config_serde(&CompassConfigurationField::Algorithm, &"TOML")?;
+        search_algorithm
+            .validate()
+            .map_err(|e| CompassConfigurationError::UserConfigurationError(e.to_string()))?;
 
         let state_model = match config_json.get(&CompassConfigurationField::State.to_string()) {
             Some(state_config) => Arc::new(StateModel::try_from(state_config)?),
@@ -257,7 +267,17 @@ impl TryFrom<(&Config, &CompassAppBuilder)> for CompassApp {
             frontier_model_service,
             termination_model,
         );
-
+        let cache_static_edge_costs = config_json
+            .get_config_serde_optional::<bool>(
+                &CompassConfigurationField::CacheStaticEdgeCosts,
+                &"TOML",
+            )?
+            .unwrap_or(false);
+        let search_app = if cache_static_edge_costs {
+            search_app.with_edge_cost_cache()?
+        } else {
+            search_app
+        };
         // build plugins
         let plugins_start = Local::now();
         let plugins_config =
@@ -272,6 +292,35 @@ impl TryFrom<(&Config, &CompassAppBuilder)> for CompassApp {
             plugins_duration.hhmmss()
         );
 
+        // routes of summary_only queries are reconstructed when an output plugin reads them
+        let summary_routes_required = output_plugins.iter().any(|p| p.uses_route_edges());
+        let search_app = search_app.with_summary_routes(summary_routes_required);
+
+        // searches without a destination are bounded by the reachable limits of the query
+        let reachable_feature = output_plugins
+            .iter()
+            .find_map(|p| p.reachable_feature().map(String::from));
+        let search_app = search_app.with_reachable_feature(reachable_feature);
+
+        let query_cache_size = config_json.get_config_serde_optional::<usize>(
+            &CompassConfigurationField::QueryCacheSize,
+            &"TOML",
+        )?;
+        let search_app = match query_cache_size {
+            Some(cache_size) => {
+                // search trees are only cached when an output plugin reads them
+                let keep_trees = output_plugins.iter().any(|p| p.uses_search_trees());
+                let precision = config_json
+                    .get_config_serde_optional::<i32>(
+                        &CompassConfigurationField::QueryCachePrecision,
+                        &"TOML",
+                    )?
+                    .unwrap_or(SearchApp::DEFAULT_QUERY_CACHE_PRECISION);
+                search_app.with_query_cache(cache_size, precision, keep_trees)?
+            }
+            None => search_app,
+        };
+
         // other parameters
         let parallelism = config.get::<usize>(CompassConfigurationField::Parallelism.to_str())?;
         let search_orientation = config
@@ -302,6 +351,23 @@ impl TryFrom<(&Config, &CompassAppBuilder)> for CompassApp {
 }
 
 impl CompassApp {
+    /// summarizes the loaded graph, search algorithm, state model and plugin
+    /// pipeline of this app, for introspection of a running service.
+    ///
+    /// # Returns
+    ///
+    /// the health report, or an error if the state model could not be described
+    pub fn health(&self) -> Result<HealthReport, CompassAppError> {
+        let mut report = self.search_app.health()?;
+        report.plugins = Some(PluginHealth {
+            input_plugin_count: self.input_plugins.len(),
+            output_plugin_count: self.output_plugins.len(),
+            parallelism: self.parallelism,
+            search_orientation: self.search_orientation.clone(),
+        });
+        Ok(report)
+    }
+
     /// runs a set of queries via this instance of CompassApp. this
     ///   1. processes each input query based on the InputPlugins
     ///   2. runs the search algorithm with each query via SearchApp
@@ -312,6 +378,10 @@ impl CompassApp {
     /// errors due to the user, they should be propagated along into the output
     /// JSON in an error format along with the request.
     ///
+    /// responses are not returned in the order of the queries. each query is tagged
+    /// with its position in `queries` as `query_index`, which is copied onto its
+    /// response(s) along with any client-supplied `request_id`.
+    ///
     /// # Arguments
     ///
     /// * `queries` - list of search queries to execute
@@ -324,6 +394,38 @@ impl CompassApp {
         &self,
         queries: Vec<serde_json::Value>,
         config: Option<&serde_json::Value>,
+    ) -> Result<Vec<serde_json::Value>, CompassAppError> {
+        self.run_batch(queries, config, None)
+    }
+
+    /// runs a set of queries as [`CompassApp::run`] does, adding each response to a
+    /// batch summary as it completes. responses are added whether or not they are
+    /// persisted in memory, so the same aggregator can summarize several runs, such as
+    /// the chunks of a large query file.
+    ///
+    /// # Arguments
+    ///
+    /// * `queries` - list of search queries to execute
+    /// * `config` - configuration for this run batch which may override default configurations
+    /// * `batch_summary` - aggregator that accumulates the summary of the responses
+    ///
+    /// # Result
+    ///
+    /// the JSON responses of the queries, or an error if the app failed
+    pub fn run_with_summary(
+        &self,
+        queries: Vec<serde_json::Value>,
+        config: Option<&serde_json::Value>,
+        batch_summary: &Mutex<BatchSummaryAggregator>,
+    ) -> Result<Vec<serde_json::Value>, CompassAppError> {
+        self.run_batch(queries, config, Some(batch_summary))
+    }
+
+    fn run_batch(
+        &self,
+        queries: Vec<serde_json::Value>,
+        config: Option<&serde_json::Value>,
+        batch_summary: Option<&Mutex<BatchSummaryAggregator>>,
     ) -> Result<Vec<serde_json::Value>, CompassAppError> {
         // allow the user to overwrite global configurations
         let parallelism: usize = get_optional_run_config(
@@ -344,7 +446,16 @@ impl CompassApp {
             config,
         )?
         .unwrap_or_else(|| self.response_output_policy.clone());
+        let strict: bool =
+            get_optional_run_config(&strict_mode::STRICT_MODE_KEY, &"run configuration", config)?
+                .unwrap_or(false);
         let response_writer = response_output_policy.build()?;
+        let queries = ops::tag_query_indices(queries);
+        let queries = if strict {
+            ops::apply_strict_mode(queries)
+        } else {
+            queries
+        };
 
         let input_pb = Bar::builder()
             .total(queries.len())
@@ -389,7 +500,17 @@ impl CompassApp {
             .collect();
         let load_balanced_inputs =
             ops::apply_load_balancing_policy(&processed_inputs, parallelism, 1.0)?;
-        let error_inputs: Vec<Value> = error_inputs_nested.into_iter().flatten().collect();
+        let error_inputs: Vec<Value> = error_inputs_nested
+            .into_iter()
+            .flatten()
+            .map(|mut response| {
+                ops::tag_response(&mut response);
+                if let Some(Ok(mut summary)) = batch_summary.map(|s| s.lock()) {
+                    summary.add_response(&response, None);
+                }
+                response
+            })
+            .collect();
         if load_balanced_inputs.is_empty() {
             return Ok(error_inputs);
         }
@@ -429,6 +550,7 @@ impl CompassApp {
                 &self.search_app,
                 &response_writer,
                 search_pb_shared,
+                batch_summary,
             )?,
             ResponsePersistencePolicy::DiscardResponseFromMemory => run_batch_without_responses(
                 &load_balanced_inputs,
@@ -437,12 +559,68 @@ impl CompassApp {
                 &self.search_app,
                 &response_writer,
                 search_pb_shared,
+                batch_summary,
             )?,
         };
 
         let run_result = run_query_result.chain(error_inputs).collect();
         Ok(run_result)
     }
+
+    /// runs a set of queries, blocking the calling thread until all searches
+    /// complete. this is the same as [`CompassApp::run`]. searches are CPU-bound
+    /// and run on the rayon thread pool, so this should not be called directly from
+    /// an async runtime. see [`CompassApp::search_async`] (requires the `tokio`
+    /// feature) for an adapter that runs the search on a blocking thread pool.
+    ///
+    /// # Arguments
+    ///
+    /// * `queries` - list of search queries to execute
+    /// * `config` - configuration for this run batch which may override default configurations
+    ///
+    /// # Result
+    ///
+    /// the JSON responses of the queries, or an error if the app failed
+    pub fn search_blocking(
+        &self,
+        queries: Vec<serde_json::Value>,
+        config: Option<&serde_json::Value>,
+    ) -> Result<Vec<serde_json::Value>, CompassAppError> {
+        self.run(queries, config)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl CompassApp {
+    /// runs a set of queries on the tokio blocking thread pool via
+    /// `spawn_blocking`, so that the search does not block the async runtime.
+    /// the search is started immediately, which requires a tokio runtime on the
+    /// calling thread, and the returned future resolves with its responses.
+    ///
+    /// # Arguments
+    ///
+    /// * `queries` - list of search queries to execute
+    /// * `config` - configuration for this run batch which may override default configurations
+    ///
+    /// # Result
+    ///
+    /// a future of the JSON responses of the queries, or an error if the app failed
+    pub fn search_async(
+        self: &Arc<Self>,
+        queries: Vec<serde_json::Value>,
+        config: Option<serde_json::Value>,
+    ) -> impl std::future::Future<Output = Result<Vec<serde_json::Value>, CompassAppError>>
+           + Send
+           + 'static {
+        let app = Arc::clone(self);
+        let handle =
+            tokio::task::spawn_blocking(move || app.search_blocking(queries, config.as_ref()));
+        async move {
+            handle.await.map_err(|e| {
+                CompassAppError::InternalError(format!("blocking search task failed: {}", e))
+            })?
+        }
+    }
 }
 
 pub fn get_optional_run_config<'a, K, T>(
@@ -470,6 +648,7 @@ where
 /// # Arguments
 ///
 /// * `query` - a single search query that has been processed by InputPlugins
+/// * `batch_summary` - optional aggregator to add the response to
 ///
 /// # Returns
 ///
@@ -479,9 +658,17 @@ pub fn run_single_query(
     search_orientation: &SearchOrientation,
     output_plugins: &[Arc<dyn OutputPlugin>],
     search_app: &SearchApp,
+    batch_summary: Option<&Mutex<BatchSummaryAggregator>>,
 ) -> Result<serde_json::Value, CompassAppError> {
-    let search_result = search_app.run(query, search_orientation);
-    let output = apply_output_processing(query, search_result, search_app, output_plugins);
+    let (origin, destination) = ops::query_span_fields(query);
+    let search_result = tracing::info_span!("search", %origin, %destination)
+        .in_scope(|| search_app.run(query, search_orientation));
+    let mut output = tracing::info_span!("plugin_output", %origin, %destination)
+        .in_scope(|| apply_output_processing(query, &search_result, search_app, output_plugins));
+    ops::tag_response(&mut output);
+    if let Some(Ok(mut summary)) = batch_summary.map(|s| s.lock()) {
+        summary.add_response(&output, search_result.as_ref().err());
+    }
     Ok(output)
 }
 
@@ -504,6 +691,7 @@ pub fn run_batch_with_responses(
     search_app: &SearchApp,
     response_writer: &ResponseSink,
     pb: Arc<Mutex<Bar>>,
+    batch_summary: Option<&Mutex<BatchSummaryAggregator>>,
 ) -> Result<Box<dyn Iterator<Item = Value>>, CompassAppError> {
     let run_query_result = load_balanced_inputs
         .par_iter()
@@ -511,8 +699,13 @@ pub fn run_batch_with_responses(
             queries
                 .iter()
                 .map(|q| {
-                    let mut response =
-                        run_single_query(q, search_orientation, output_plugins, search_app)?;
+                    let mut response = run_single_query(
+                        q,
+                        search_orientation,
+                        output_plugins,
+                        search_app,
+                        batch_summary,
+                    )?;
                     if let Ok(mut pb_local) = pb.lock() {
                         let _ = pb_local.update(1);
                     }
@@ -539,6 +732,7 @@ pub fn run_batch_without_responses(
     search_app: &SearchApp,
     response_writer: &ResponseSink,
     pb: Arc<Mutex<Bar>>,
+    batch_summary: Option<&Mutex<BatchSummaryAggregator>>,
 ) -> Result<Box<dyn Iterator<Item = Value>>, CompassAppError> {
     // run the computations, discard values that do not trigger an error
     let _ = load_balanced_inputs
@@ -549,8 +743,13 @@ pub fn run_batch_without_responses(
             // within a for loop or for_each call, and map creates more allocations. open to other ideas!
             let initial: Result<(), CompassAppError> = Ok(());
             let _ = queries.iter().fold(initial, |_, q| {
-                let mut response =
-                    run_single_query(q, search_orientation, output_plugins, search_app)?;
+                let mut response = run_single_query(
+                    q,
+                    search_orientation,
+                    output_plugins,
+                    search_app,
+                    batch_summary,
+                )?;
                 if let Ok(mut pb_local) = pb.lock() {
                     let _ = pb_local.update(1);
                 }
@@ -569,6 +768,8 @@ pub fn apply_input_plugins(
     query: &serde_json::Value,
     plugins: &Vec<Arc<dyn InputPlugin>>,
 ) -> Result<Vec<serde_json::Value>, serde_json::Value> {
+    let (origin, destination) = ops::query_span_fields(query);
+    let _span = tracing::info_span!("snap", %origin, %destination).entered();
     let mut plugin_state = serde_json::Value::Array(vec![query.clone()]);
     for plugin in plugins {
         let p = plugin.clone();
@@ -584,17 +785,17 @@ pub fn apply_input_plugins(
 // 2. applying the output plugins
 pub fn apply_output_processing(
     request_json: &serde_json::Value,
-    result: Result<(SearchAppResult, SearchInstance), CompassAppError>,
+    result: &Result<(SearchAppResult, SearchInstance), CompassAppError>,
     search_app: &SearchApp,
     output_plugins: &[Arc<dyn OutputPlugin>],
 ) -> serde_json::Value {
-    let mut initial: Value = match out_ops::create_initial_output(request_json, &result, search_app)
+    let mut initial: Value = match out_ops::create_initial_output(request_json, result, search_app)
     {
         Ok(value) => value,
         Err(error_value) => return error_value,
     };
     for output_plugin in output_plugins.iter() {
-        match output_plugin.process(&mut initial, &result) {
+        match output_plugin.process(&mut initial, result) {
             Ok(()) => {}
             Err(e) => return out_ops::package_error(request_json, e),
         }
@@ -606,16 +807,30 @@ pub fn apply_output_processing(
 #[cfg(test)]
 mod tests {
     use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
 
     use crate::app::compass::{
         compass_app_error::CompassAppError,
         config::compass_configuration_error::CompassConfigurationError,
+        response::batch_summary::BatchSummaryAggregator, search_orientation::SearchOrientation,
+    };
+    use crate::app::search::search_app_graph_ops::SearchAppGraphOps;
+    use crate::app::search::search_app_result::SearchAppResult;
+    use crate::plugin::input::{
+        default::vertex_rtree::plugin::RTreePlugin, input_plugin::InputPlugin,
+    };
+    use routee_compass_core::algorithm::search::{
+        edge_traversal::EdgeTraversal, search_error::SearchError,
+    };
+    use routee_compass_core::model::road_network::{
+        edge_id::EdgeId, graph_error::GraphError, vertex_id::VertexId,
     };
+    use routee_compass_core::model::unit::{as_f64::AsF64, DistanceUnit};
+    use routee_compass_core::util::geo::haversine;
 
     use super::CompassApp;
 
-    #[test]
-    fn test_speeds() {
+    fn build_speeds_test_app() -> CompassApp {
         let cwd_str = match std::env::current_dir() {
             Ok(cwd_path) => String::from(cwd_path.to_str().unwrap_or("<unknown>")),
             _ => String::from("<unknown>"),
@@ -643,7 +858,7 @@ mod tests {
             .join("speeds_test")
             .join("speeds_debug.toml");
 
-        let app = match CompassApp::try_from(conf_file_test.as_path()) {
+        match CompassApp::try_from(conf_file_test.as_path()) {
             Ok(a) => Ok(a),
             Err(CompassAppError::CompassConfigurationError(
                 CompassConfigurationError::FileNormalizationNotFound(_key, _f1, _f2),
@@ -654,7 +869,12 @@ mod tests {
             }
             Err(other) => panic!("{}", other),
         }
-        .unwrap();
+        .unwrap()
+    }
+
+    #[test]
+    fn test_speeds() {
+        let app = build_speeds_test_app();
         let query = serde_json::json!({
             "origin_vertex": 0,
             "destination_vertex": 2
@@ -668,6 +888,571 @@ mod tests {
         assert_eq!(path_0, &expected);
     }
 
+    #[test]
+    fn test_responses_tagged_with_query_index() {
+        let app = build_speeds_test_app();
+        let od_pairs = [(0, 2), (0, 1), (1, 2), (0, 2), (0, 1), (1, 2)];
+        let queries = od_pairs
+            .iter()
+            .enumerate()
+            .map(|(i, (o, d))| {
+                serde_json::json!({
+                    "origin_vertex": o,
+                    "destination_vertex": d,
+                    "request_id": format!("trip-{}", i),
+                })
+            })
+            .collect::<Vec<_>>();
+        // a query that fails is tagged as well
+        let mut all_queries = queries.clone();
+        all_queries.push(serde_json::json!({"origin_x": "bad"}));
+        let config = serde_json::json!({"parallelism": 3});
+        let result = app.run(all_queries, Some(&config)).unwrap();
+        assert_eq!(result.len(), od_pairs.len() + 1);
+
+        // queries are distributed across parallel batches, so responses come back out of order
+        let indices = result
+            .iter()
+            .map(|r| r["query_index"].as_u64().unwrap() as usize)
+            .collect::<Vec<_>>();
+        assert_ne!(indices, (0..result.len()).collect::<Vec<_>>());
+
+        for (response, index) in result.iter().zip(indices) {
+            match queries.get(index) {
+                Some(query) => {
+                    assert_eq!(response["request_id"], query["request_id"]);
+                    assert_eq!(response["request"]["origin_vertex"], query["origin_vertex"]);
+                    assert_eq!(
+                        response["request"]["destination_vertex"],
+                        query["destination_vertex"]
+                    );
+                }
+                None => {
+                    assert_eq!(index, od_pairs.len());
+                    assert!(response.get("error").is_some());
+                    assert!(response.get("request_id").is_none());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_run_with_summary() {
+        let app = build_speeds_test_app();
+        let queries = vec![
+            serde_json::json!({ "origin_vertex": 0, "destination_vertex": 2 }),
+            serde_json::json!({ "origin_vertex": 0, "destination_vertex": 1 }),
+            // vertex 0 cannot be reached from vertex 2
+            serde_json::json!({ "origin_vertex": 2, "destination_vertex": 0 }),
+            serde_json::json!({ "origin_x": "bad" }),
+        ];
+        let batch_summary = Mutex::new(BatchSummaryAggregator::default());
+        let result = app.run_with_summary(queries, None, &batch_summary).unwrap();
+        assert_eq!(result.len(), 4);
+        let summary = batch_summary.lock().unwrap().summary();
+        assert_eq!(summary.query_count, 4);
+        assert_eq!(summary.route_count, 2);
+        assert_eq!(summary.no_route_count, 1);
+        assert_eq!(summary.error_count, 1);
+        assert_eq!(summary.features["distance"].count, 2);
+    }
+
+    #[test]
+    fn test_search_counters_without_plugins() {
+        let app = build_speeds_test_app();
+        let query = serde_json::json!({ "origin_vertex": 0, "destination_vertex": 2 });
+        let output = super::run_single_query(
+            &query,
+            &SearchOrientation::Vertex,
+            &[],
+            &app.search_app,
+            None,
+        )
+        .unwrap();
+        // the search counters are written without the summary plugin
+        assert!(output["nodes_expanded"].as_u64().unwrap() > 0);
+        assert!(output["edges_relaxed"].as_u64().unwrap() > 0);
+        assert!(output["max_frontier_size"].as_u64().unwrap() > 0);
+    }
+
+    #[test]
+    fn test_summary_only() {
+        let app = build_speeds_test_app();
+        let query = serde_json::json!({
+            "origin_vertex": 0,
+            "destination_vertex": 2
+        });
+        let summary_query = serde_json::json!({
+            "origin_vertex": 0,
+            "destination_vertex": 2,
+            "summary_only": true
+        });
+        let result = app
+            .run(vec![query.clone(), summary_query.clone()], None)
+            .unwrap();
+        let (summary, full): (Vec<_>, Vec<_>) = result
+            .iter()
+            .partition(|r| r["request"].get("summary_only").is_some());
+        let full_route = full[0].get("route").unwrap();
+        let summary_route = summary[0].get("route").unwrap();
+        assert!(full_route.get("path").is_some());
+        assert!(summary_route.get("path").is_none());
+        assert_eq!(
+            full_route.get("traversal_summary"),
+            summary_route.get("traversal_summary")
+        );
+        assert_eq!(full_route.get("cost"), summary_route.get("cost"));
+        assert!(summary[0].get("route_runtime").is_some());
+        // the summary plugin counts the route edges, so the route is still reconstructed
+        assert!(app.search_app.summary_routes_required);
+        assert_eq!(summary[0]["route_edges"], full[0]["route_edges"]);
+        assert_ne!(summary[0]["route_edges"], serde_json::json!(0));
+
+        // without a plugin reading the route edges, the route is not reconstructed
+        // from the search tree, only its final state is read
+        let search_app = app.search_app.with_summary_routes(false);
+        let (summary_result, summary_si) = search_app
+            .run(&summary_query, &SearchOrientation::Vertex)
+            .unwrap();
+        let (full_result, full_si) = search_app.run(&query, &SearchOrientation::Vertex).unwrap();
+        assert!(summary_result.routes.is_empty());
+        let route_final_states = summary_result.route_final_states.unwrap();
+        assert_eq!(route_final_states.len(), 1);
+        assert_eq!(
+            summary_si
+                .state_model
+                .serialize_state(&route_final_states[0]),
+            full_si
+                .state_model
+                .serialize_state(&full_result.routes[0].last().unwrap().result_state)
+        );
+    }
+
+    #[test]
+    fn test_edge_endpoints_match_snapped_coordinates() {
+        let app = build_speeds_test_app();
+        let vertices_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join("app")
+            .join("compass")
+            .join("test")
+            .join("speeds_test")
+            .join("test_vertices.csv");
+        let rtree = RTreePlugin::new(&vertices_file, None, None, None).unwrap();
+        let mut snapped_query = serde_json::json!({
+            "origin_x": -105.1683038,
+            "origin_y": 39.7379033,
+            "destination_x": -111.9095014,
+            "destination_y": 40.7607176
+        });
+        rtree.process(&mut snapped_query).unwrap();
+        let mut edge_query = serde_json::json!({
+            "origin_edge": 0,
+            "destination_edge": 2
+        });
+        // snapping is skipped when edges are provided directly
+        rtree.process(&mut edge_query).unwrap();
+        assert!(edge_query.get("origin_vertex").is_none());
+
+        let snapped_result = app.run(vec![snapped_query], None).unwrap();
+        let edge_result = app.run(vec![edge_query], None).unwrap();
+        let snapped_path = snapped_result[0]["route"]["path"].clone();
+        let edge_path = edge_result[0]["route"]["path"].clone();
+        assert_eq!(snapped_path, serde_json::json!(vec![0, 2]));
+        assert_eq!(edge_path, snapped_path);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_search_async() {
+        let app = Arc::new(build_speeds_test_app());
+        let query = serde_json::json!({
+            "origin_vertex": 0,
+            "destination_vertex": 2
+        });
+        let result = app.search_async(vec![query], None).await.unwrap();
+        assert_eq!(result.len(), 1);
+        let path = result[0].get("route").unwrap().get("path").unwrap();
+        assert_eq!(path, &serde_json::json!(vec![0, 2]));
+    }
+
+    #[test]
+    fn test_nearest_vertices() {
+        let app = build_speeds_test_app();
+        let point = geo::coord! {x: -105.0, y: 40.5};
+        let nearest = app
+            .search_app
+            .get_nearest_vertices(point, 2, Some(DistanceUnit::Kilometers))
+            .unwrap();
+
+        // the two closest vertices by haversine distance, nearest first
+        let mut expected = app
+            .search_app
+            .directed_graph
+            .vertices
+            .iter()
+            .map(|v| {
+                let d = haversine::coord_distance(&point, &v.coordinate, DistanceUnit::Kilometers);
+                (v.vertex_id, d.unwrap())
+            })
+            .collect::<Vec<_>>();
+        expected.sort_by_key(|(_, d)| *d);
+        expected.truncate(2);
+        assert_eq!(nearest, expected);
+        assert_eq!(nearest[0].0, VertexId(1));
+    }
+
+    #[test]
+    fn test_health() {
+        use itertools::Itertools;
+        let app = build_speeds_test_app();
+        let report = app.health().unwrap();
+        assert_eq!(report.graph.vertex_count, 3);
+        assert_eq!(report.graph.edge_count, 3);
+        let bounds = report.graph.bounds.unwrap();
+        let expected_bounds = [-111.9095, 39.7379, -104.8086, 41.1475];
+        for (bound, expected) in bounds.iter().zip(expected_bounds) {
+            assert!((*bound as f64 - expected).abs() < 1e-4, "{:?}", bounds);
+        }
+        assert_eq!(report.search_algorithm["type"], serde_json::json!("a*"));
+
+        let features = report
+            .state_features
+            .iter()
+            .map(|f| (f.name.as_str(), f.unit.as_str()))
+            .sorted()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            features,
+            vec![("distance", "kilometers"), ("time", "seconds")]
+        );
+        assert!(!report.edge_cost_cache);
+
+        let plugins = report.plugins.unwrap();
+        assert_eq!(plugins.input_plugin_count, 0);
+        assert_eq!(plugins.output_plugin_count, 2);
+        assert!(matches!(
+            plugins.search_orientation,
+            SearchOrientation::Vertex
+        ));
+    }
+
+    #[test]
+    fn test_reachable_limit_bounds_search() {
+        let search_app = build_speeds_test_app()
+            .search_app
+            .with_reachable_feature(Some(String::from("time")));
+        let tree_size = |query: serde_json::Value| {
+            let (result, _) = search_app.run(&query, &SearchOrientation::Vertex).unwrap();
+            result.trees[0].len()
+        };
+        // without a limit the search reaches every vertex
+        assert_eq!(tree_size(serde_json::json!({ "origin_vertex": 0 })), 2);
+        // with a limit of zero no edge can be traversed
+        let bounded = tree_size(serde_json::json!({
+            "origin_vertex": 0,
+            "reachable_limits": [0.0],
+        }));
+        assert_eq!(bounded, 0);
+        // a query with a destination is not bounded
+        let (routed, _) = search_app
+            .run(
+                &serde_json::json!({
+                    "origin_vertex": 0,
+                    "destination_vertex": 2,
+                    "reachable_limit": 0.0,
+                }),
+                &SearchOrientation::Vertex,
+            )
+            .unwrap();
+        assert_eq!(routed.routes.len(), 1);
+
+        // a feature that is not in the state model is an error
+        let search_app = build_speeds_test_app()
+            .search_app
+            .with_reachable_feature(Some(String::from("energy")));
+        let query = serde_json::json!({ "origin_vertex": 0, "reachable_limit": 1.0 });
+        assert!(search_app.run(&query, &SearchOrientation::Vertex).is_err());
+    }
+
+    #[test]
+    fn test_query_cache() {
+        let search_app = build_speeds_test_app()
+            .search_app
+            .with_query_cache(10, 5, false)
+            .unwrap();
+        let lookups = || {
+            let cache = search_app.query_cache.as_ref().unwrap();
+            (cache.hits(), cache.misses())
+        };
+        let run = |query: serde_json::Value| {
+            let (result, _) = search_app.run(&query, &SearchOrientation::Vertex).unwrap();
+            result
+        };
+        let first = run(serde_json::json!({
+            "origin_vertex": 0,
+            "destination_vertex": 2,
+            "origin_x": -105.1,
+        }));
+        assert_eq!(lookups(), (0, 1));
+        // a repeated query, with its keys in another order, a request id and a
+        // coordinate that rounds to the same value, is answered from the cache: the result is the
+        // one stored by the first search, including its execution time
+        let repeated = run(serde_json::json!({
+            "origin_x": -105.100001,
+            "destination_vertex": 2,
+            "request_id": "repeat",
+            "origin_vertex": 0,
+        }));
+        assert_eq!(lookups(), (1, 1));
+        assert_eq!(repeated.search_executed_time, first.search_executed_time);
+        assert_eq!(repeated.iterations, first.iterations);
+        let edges =
+            |r: &SearchAppResult| r.routes[0].iter().map(|et| et.edge_id).collect::<Vec<_>>();
+        assert_eq!(edges(&repeated), edges(&first));
+        // search trees are not kept in the cache unless requested
+        assert!(!first.trees.is_empty());
+        assert!(repeated.trees.is_empty());
+
+        // a different query runs a new search
+        let _ = run(serde_json::json!({
+            "origin_vertex": 0,
+            "destination_vertex": 1,
+            "origin_x": -105.1,
+        }));
+        assert_eq!(lookups(), (1, 2));
+
+        // the same query in another search orientation runs a new search
+        let edge_query = serde_json::json!({ "origin_edge": 0, "destination_edge": 2 });
+        for orientation in [SearchOrientation::Vertex, SearchOrientation::Edge] {
+            let _ = search_app.run(&edge_query, &orientation).unwrap();
+        }
+        assert_eq!(lookups(), (1, 4));
+
+        // a query without a destination is not cached
+        let tree_query = serde_json::json!({ "origin_vertex": 0 });
+        let _ = run(tree_query.clone());
+        let repeated_tree = run(tree_query);
+        assert_eq!(lookups(), (1, 4));
+        assert!(!repeated_tree.trees.is_empty());
+    }
+
+    #[test]
+    fn test_query_cache_output_matches_search() {
+        // the summary plugin reads the search trees, so the cache keeps them
+        let mut app = build_speeds_test_app();
+        let keep_trees = app.output_plugins.iter().any(|p| p.uses_search_trees());
+        assert!(keep_trees);
+        app.search_app = app.search_app.with_query_cache(10, 5, keep_trees).unwrap();
+        let query = serde_json::json!({ "origin_vertex": 0, "destination_vertex": 2 });
+        let first = app.run(vec![query.clone()], None).unwrap();
+        let repeated = app.run(vec![query], None).unwrap();
+        for key in [
+            "search_executed_time",
+            "tree_size_count",
+            "search_result_size_mib",
+            "route",
+        ] {
+            assert_eq!(repeated[0].get(key), first[0].get(key), "{}", key);
+        }
+        assert_ne!(first[0]["tree_size_count"], serde_json::json!(0));
+    }
+
+    #[test]
+    fn test_invalid_edge_endpoint() {
+        let app = build_speeds_test_app();
+        let query = serde_json::json!({
+            "origin_edge": 0,
+            "destination_edge": 99
+        });
+        let result = app.run(vec![query], None).unwrap();
+        let expected = GraphError::EdgeAttributeNotFound {
+            edge_id: EdgeId(99),
+        }
+        .to_string();
+        let error = result[0]["error"].as_str().unwrap();
+        assert!(error.contains(&expected), "{}", error);
+    }
+
+    #[test]
+    fn test_same_origin_destination() {
+        let app = build_speeds_test_app();
+        let query = serde_json::json!({
+            "origin_vertex": 0,
+            "destination_vertex": 0
+        });
+        let result = app.run(vec![query], None).unwrap();
+        assert!(result[0].get("error").is_none(), "{}", result[0]);
+        let route = &result[0]["route"];
+        assert_eq!(route["path"], serde_json::json!([]));
+        assert_eq!(
+            route["traversal_summary"]["distance"],
+            serde_json::json!(0.0)
+        );
+        assert_eq!(route["traversal_summary"]["time"], serde_json::json!(0.0));
+        assert_eq!(route["cost"]["total_cost"], serde_json::json!(0.0));
+    }
+
+    #[test]
+    fn test_evaluate_path() {
+        let app = build_speeds_test_app();
+        let query = serde_json::json!({
+            "origin_vertex": 0,
+            "destination_vertex": 2
+        });
+        let total_cost = |route: &[EdgeTraversal]| -> f64 {
+            route.iter().map(|et| et.total_cost().as_f64()).sum()
+        };
+
+        let (optimal, optimal_si) = app
+            .search_app
+            .run(&query, &SearchOrientation::Vertex)
+            .unwrap();
+        let optimal_route = &optimal.routes[0];
+        let optimal_edges = optimal_route
+            .iter()
+            .map(|et| et.edge_id)
+            .collect::<Vec<_>>();
+
+        // evaluating the optimal route reproduces the search result
+        let (evaluated, evaluated_si) = app
+            .search_app
+            .evaluate_path(&query, VertexId(0), &optimal_edges)
+            .unwrap();
+        let evaluated_route = &evaluated.routes[0];
+        assert_eq!(evaluated_route.len(), optimal_route.len());
+        assert_eq!(total_cost(evaluated_route), total_cost(optimal_route));
+        // each search instance may order its state features differently, so
+        // states are compared by feature name
+        assert_eq!(
+            evaluated_si
+                .state_model
+                .serialize_state(&evaluated_route.last().unwrap().result_state),
+            optimal_si
+                .state_model
+                .serialize_state(&optimal_route.last().unwrap().result_state)
+        );
+
+        // the two routes from (0) to (2) are (0)-[1]->(2) and (0)-[0]->(1)-[2]->(2).
+        // the route not found by the search costs at least as much as the optimal route
+        let alternative = if optimal_edges == vec![EdgeId(1)] {
+            vec![EdgeId(0), EdgeId(2)]
+        } else {
+            vec![EdgeId(1)]
+        };
+        let (alternative_result, _) = app
+            .search_app
+            .evaluate_path(&query, VertexId(0), &alternative)
+            .unwrap();
+        assert!(total_cost(&alternative_result.routes[0]) >= total_cost(optimal_route));
+
+        // edges must form a connected path starting at the origin
+        let disconnected =
+            app.search_app
+                .evaluate_path(&query, VertexId(0), &[EdgeId(1), EdgeId(2)]);
+        assert!(matches!(
+            disconnected,
+            Err(CompassAppError::SearchError(SearchError::DisconnectedPath(
+                EdgeId(1),
+                EdgeId(2)
+            )))
+        ));
+        let wrong_origin = app
+            .search_app
+            .evaluate_path(&query, VertexId(1), &[EdgeId(1)]);
+        assert!(matches!(
+            wrong_origin,
+            Err(CompassAppError::SearchError(
+                SearchError::PathDoesNotStartAtOrigin(_, _)
+            ))
+        ));
+    }
+
+    /// records the name and fields of each span created while it is the active subscriber
+    struct SpanRecorder {
+        spans: Arc<Mutex<Vec<(String, String)>>>,
+        next_id: std::sync::atomic::AtomicU64,
+    }
+
+    struct FieldRecorder(String);
+
+    impl tracing::field::Visit for FieldRecorder {
+        fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+            self.0.push_str(&format!("{}={:?};", field.name(), value));
+        }
+    }
+
+    impl tracing::Subscriber for SpanRecorder {
+        fn enabled(&self, _metadata: &tracing::Metadata<'_>) -> bool {
+            true
+        }
+        fn new_span(&self, span: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+            let mut fields = FieldRecorder(String::new());
+            span.record(&mut fields);
+            if let Ok(mut spans) = self.spans.lock() {
+                spans.push((span.metadata().name().to_string(), fields.0));
+            }
+            let id = self
+                .next_id
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            tracing::span::Id::from_u64(id)
+        }
+        fn record(&self, _span: &tracing::span::Id, _values: &tracing::span::Record<'_>) {}
+        fn record_follows_from(&self, _span: &tracing::span::Id, _follows: &tracing::span::Id) {}
+        fn event(&self, _event: &tracing::Event<'_>) {}
+        fn enter(&self, _span: &tracing::span::Id) {}
+        fn exit(&self, _span: &tracing::span::Id) {}
+    }
+
+    #[test]
+    fn test_search_phase_spans() {
+        let spans = Arc::new(Mutex::new(vec![]));
+        let recorder = SpanRecorder {
+            spans: spans.clone(),
+            next_id: std::sync::atomic::AtomicU64::new(1),
+        };
+        let dispatch = tracing::Dispatch::new(recorder);
+
+        // queries run on a rayon thread pool, so the recorder is made the default
+        // subscriber of each thread of a dedicated pool rather than of the process
+        let pool_dispatch = dispatch.clone();
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(2)
+            .spawn_handler(move |thread| {
+                let dispatch = pool_dispatch.clone();
+                std::thread::spawn(move || {
+                    tracing::dispatcher::with_default(&dispatch, || thread.run())
+                });
+                Ok(())
+            })
+            .build()
+            .unwrap();
+        let app = build_speeds_test_app();
+        let query = serde_json::json!({
+            "origin_vertex": 0,
+            "destination_vertex": 2
+        });
+        let _ = tracing::dispatcher::with_default(&dispatch, || {
+            pool.install(|| app.run(vec![query], None))
+        })
+        .unwrap();
+
+        let spans = spans.lock().unwrap();
+        let query_fields = "origin=vertex 0;destination=vertex 2;";
+        for name in ["snap", "search", "plugin_output"] {
+            assert!(
+                spans
+                    .iter()
+                    .any(|(n, fields)| n == name && fields == query_fields),
+                "missing span '{}' with fields '{}' in {:?}",
+                name,
+                query_fields,
+                spans
+            );
+        }
+        assert!(spans.iter().any(|(n, _)| n == "route_reconstruction"));
+    }
+
     // #[test]
     // fn test_energy() {
     //     // rust runs test and debug at different locations, which breaks the URLs
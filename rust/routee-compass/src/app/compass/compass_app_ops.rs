@@ -1,7 +1,11 @@
 use super::{compass_app_error::CompassAppError, compass_input_field::CompassInputField};
-use crate::plugin::{input::input_json_extensions::InputJsonExtensions, plugin_error::PluginError};
+use crate::plugin::{
+    input::{input_field::InputField, input_json_extensions::InputJsonExtensions},
+    plugin_error::PluginError,
+};
 use config::Config;
 use ordered_float::OrderedFloat;
+use routee_compass_core::model::traversal::strict_mode;
 use std::path::Path;
 
 /// reads the compass configuration TOML file from a path
@@ -112,6 +116,113 @@ pub fn apply_load_balancing_policy(
     Ok(assignments)
 }
 
+/// tags each query with its position in the batch as the `query_index` field, so that
+/// responses can be correlated with their queries regardless of the order in which
+/// they complete. any existing `query_index` value is replaced.
+///
+/// # Arguments
+///
+/// * `queries` - user queries in the order they were submitted
+///
+/// # Returns
+///
+/// the queries, each tagged with its index. queries that are not JSON objects are unchanged
+pub fn tag_query_indices(queries: Vec<serde_json::Value>) -> Vec<serde_json::Value> {
+    queries
+        .into_iter()
+        .enumerate()
+        .map(|(index, mut query)| {
+            if let Some(obj) = query.as_object_mut() {
+                obj.insert(InputField::QueryIndex.to_string(), serde_json::json!(index));
+            }
+            query
+        })
+        .collect()
+}
+
+/// sets the strict mode flag on each query that does not set it, so that a
+/// run configured with `strict = true` fails on any fallback. queries that
+/// declare their own strict flag keep it.
+///
+/// # Arguments
+///
+/// * `queries` - user queries
+///
+/// # Returns
+///
+/// the queries in strict mode. queries that are not JSON objects are unchanged
+pub fn apply_strict_mode(queries: Vec<serde_json::Value>) -> Vec<serde_json::Value> {
+    queries
+        .into_iter()
+        .map(|mut query| {
+            if let Some(obj) = query.as_object_mut() {
+                obj.entry(strict_mode::STRICT_MODE_KEY)
+                    .or_insert(serde_json::json!(true));
+            }
+            query
+        })
+        .collect()
+}
+
+/// copies the `query_index` and client-supplied `request_id` of the request that
+/// produced a response onto the top level of that response, when present.
+///
+/// # Arguments
+///
+/// * `response` - a response object containing the originating `request`
+pub fn tag_response(response: &mut serde_json::Value) {
+    let tags = [InputField::QueryIndex, InputField::RequestId]
+        .into_iter()
+        .filter_map(|field| {
+            let value = response.get("request")?.get(field.to_str())?.clone();
+            Some((field, value))
+        })
+        .collect::<Vec<_>>();
+    if let Some(obj) = response.as_object_mut() {
+        for (field, value) in tags {
+            obj.insert(field.to_string(), value);
+        }
+    }
+}
+
+/// describes the origin and destination of a query for use as fields on tracing spans.
+/// the most specific location available is used, in order of vertex id, edge id, then
+/// coordinate.
+///
+/// # Arguments
+///
+/// * `query` - a search query in any stage of processing
+///
+/// # Returns
+///
+/// descriptions of the origin and destination, or "none" when not present
+pub fn query_span_fields(query: &serde_json::Value) -> (String, String) {
+    let describe = |vertex: InputField, edge: InputField, x: InputField, y: InputField| {
+        if let Some(v) = query.get(vertex.to_str()) {
+            format!("vertex {}", v)
+        } else if let Some(e) = query.get(edge.to_str()) {
+            format!("edge {}", e)
+        } else if let (Some(x), Some(y)) = (query.get(x.to_str()), query.get(y.to_str())) {
+            format!("({}, {})", x, y)
+        } else {
+            String::from("none")
+        }
+    };
+    let origin = describe(
+        InputField::OriginVertex,
+        InputField::OriginEdge,
+        InputField::OriginX,
+        InputField::OriginY,
+    );
+    let destination = describe(
+        InputField::DestinationVertex,
+        InputField::DestinationEdge,
+        InputField::DestinationX,
+        InputField::DestinationY,
+    );
+    (origin, destination)
+}
+
 fn min_bin(bins: &[f64]) -> Result<usize, PluginError> {
     bins.iter()
         .enumerate()
@@ -124,7 +235,7 @@ fn min_bin(bins: &[f64]) -> Result<usize, PluginError> {
 
 #[cfg(test)]
 mod test {
-    use super::apply_load_balancing_policy;
+    use super::{apply_load_balancing_policy, apply_strict_mode};
     use crate::plugin::input::input_field::InputField;
     use serde_json::json;
 
@@ -214,4 +325,22 @@ mod test {
         let expected = vec![vec![0], vec![1, 4, 7, 10], vec![2, 5, 8, 11], vec![3, 6, 9]];
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_apply_strict_mode() {
+        let queries = vec![
+            json!({}),
+            json!({ "strict": false }),
+            json!("not an object"),
+        ];
+        let result = apply_strict_mode(queries);
+        assert_eq!(
+            result,
+            vec![
+                json!({ "strict": true }),
+                json!({ "strict": false }),
+                json!("not an object")
+            ]
+        );
+    }
 }
@@ -0,0 +1,38 @@
+use crate::app::compass::config::config_json_extension::ConfigJsonExtensions;
+use routee_compass_core::{
+    model::access::{
+        access_model_builder::AccessModelBuilder, access_model_error::AccessModelError,
+        access_model_service::AccessModelService, default::road_reentry::RoadReentryAccessModel,
+    },
+    util::fs::{read_decoders, read_utils},
+};
+use std::sync::Arc;
+
+pub struct RoadReentryAccessModelBuilder {}
+
+impl AccessModelBuilder for RoadReentryAccessModelBuilder {
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+    ) -> Result<Arc<dyn AccessModelService>, AccessModelError> {
+        let parent_key = String::from("road re-entry access model");
+        let file_path = parameters
+            .get_config_path(&"edge_road_name_input_file", &parent_key)
+            .map_err(|e| AccessModelError::BuildError(e.to_string()))?;
+        let edge_road_names = read_utils::read_raw_file(&file_path, read_decoders::string, None)
+            .map_err(|e| {
+                AccessModelError::BuildError(format!(
+                    "error reading road names from file {:?}: {}",
+                    file_path, e
+                ))
+            })?;
+        let lookback = parameters
+            .get_config_serde::<usize>(&"lookback", &parent_key)
+            .map_err(|e| AccessModelError::BuildError(e.to_string()))?;
+        let penalty = parameters
+            .get_config_serde::<f64>(&"penalty", &parent_key)
+            .map_err(|e| AccessModelError::BuildError(e.to_string()))?;
+        let model = RoadReentryAccessModel::new(&edge_road_names, lookback, penalty)?;
+        Ok(Arc::new(model))
+    }
+}
@@ -1,6 +1,7 @@
 use super::{
     access_model::{
         combined_access_model_builder::CombinedAccessModelBuilder,
+        road_reentry_access_model_builder::RoadReentryAccessModelBuilder,
         turn_delay_access_model_builder::TurnDelayAccessModelBuilder,
     },
     builders::{InputPluginBuilder, OutputPluginBuilder},
@@ -8,14 +9,17 @@ use super::{
     compass_configuration_field::CompassConfigurationField,
     config_json_extension::ConfigJsonExtensions,
     frontier_model::{
-        combined::combined_builder::CombinedBuilder, no_restriction_builder::NoRestrictionBuilder,
+        combined::combined_builder::CombinedBuilder, energy_budget_builder::EnergyBudgetBuilder,
+        no_restriction_builder::NoRestrictionBuilder,
         road_class::road_class_builder::RoadClassBuilder,
         turn_restrictions::turn_restriction_builder::TurnRestrictionBuilder,
         vehicle_restrictions::vehicle_restriction_builder::VehicleRestrictionBuilder,
     },
     traversal_model::{
+        combined_traversal_builder::CombinedTraversalBuilder,
         distance_traversal_builder::DistanceTraversalBuilder,
-        energy_model_builder::EnergyModelBuilder, speed_lookup_builder::SpeedLookupBuilder,
+        energy_model_builder::EnergyModelBuilder, grade_distance_builder::GradeDistanceBuilder,
+        speed_lookup_builder::SpeedLookupBuilder, toll_traversal_builder::TollTraversalBuilder,
     },
 };
 use crate::plugin::{
@@ -30,6 +34,11 @@ use crate::plugin::{
     },
     output::{
         default::{
+            bearing::builder::BearingOutputPluginBuilder,
+            elevation::builder::ElevationOutputPluginBuilder,
+            formatting::plugin::OutputFormattingPlugin,
+            free_flow_time::builder::FreeFlowTimeOutputPluginBuilder,
+            reachable_edges::builder::ReachableEdgesOutputPluginBuilder,
             summary::builder::SummaryOutputPluginBuilder,
             traversal::builder::TraversalPluginBuilder, uuid::builder::UUIDOutputPluginBuilder,
         },
@@ -46,6 +55,7 @@ use routee_compass_core::model::{
         frontier_model_builder::FrontierModelBuilder, frontier_model_service::FrontierModelService,
     },
     traversal::{
+        default::constant_traversal_model::ConstantTraversalBuilder,
         traversal_model_builder::TraversalModelBuilder,
         traversal_model_service::TraversalModelService,
     },
@@ -133,28 +143,42 @@ impl CompassAppBuilder {
     fn default() -> CompassAppBuilder {
         // Traversal model builders
         let dist: Rc<dyn TraversalModelBuilder> = Rc::new(DistanceTraversalBuilder {});
+        let constant: Rc<dyn TraversalModelBuilder> = Rc::new(ConstantTraversalBuilder {});
+        let grade_distance: Rc<dyn TraversalModelBuilder> = Rc::new(GradeDistanceBuilder {});
         let speed: Rc<dyn TraversalModelBuilder> = Rc::new(SpeedLookupBuilder {});
+        let toll: Rc<dyn TraversalModelBuilder> = Rc::new(TollTraversalBuilder {});
         let energy: Rc<dyn TraversalModelBuilder> = Rc::new(EnergyModelBuilder::new(
             HashMap::from([(String::from("speed_table"), speed.clone())]),
         ));
-        let tm_builders: HashMap<String, Rc<dyn TraversalModelBuilder>> = HashMap::from([
+        let base_tm_builders: HashMap<String, Rc<dyn TraversalModelBuilder>> = HashMap::from([
             (String::from("distance"), dist),
+            (String::from("constant"), constant),
+            (String::from("grade_distance"), grade_distance),
             (String::from("speed_table"), speed),
             (String::from("energy_model"), energy),
+            (String::from("toll"), toll),
         ]);
+        let combined_tm = Rc::new(CombinedTraversalBuilder {
+            builders: base_tm_builders.clone(),
+        });
+        let mut tm_builders = base_tm_builders.clone();
+        tm_builders.insert(String::from("combined"), combined_tm);
 
         // Access model builders
         let no_access_model: Rc<dyn AccessModelBuilder> = Rc::new(NoAccessModel {});
         let turn_delay: Rc<dyn AccessModelBuilder> = Rc::new(TurnDelayAccessModelBuilder {});
+        let road_reentry: Rc<dyn AccessModelBuilder> = Rc::new(RoadReentryAccessModelBuilder {});
         let combined_am: Rc<dyn AccessModelBuilder> = Rc::new(CombinedAccessModelBuilder {
             builders: HashMap::from([
                 (String::from("no_access_model"), no_access_model.clone()),
                 (String::from("turn_delay"), turn_delay.clone()),
+                (String::from("road_reentry"), road_reentry.clone()),
             ]),
         });
         let am_builders: HashMap<String, Rc<dyn AccessModelBuilder>> = HashMap::from([
             (String::from("no_access_model"), no_access_model),
             (String::from("turn_delay"), turn_delay),
+            (String::from("road_reentry"), road_reentry),
             (String::from("combined"), combined_am),
         ]);
 
@@ -164,12 +188,14 @@ impl CompassAppBuilder {
         let turn_restriction: Rc<dyn FrontierModelBuilder> = Rc::new(TurnRestrictionBuilder {});
         let vehicle_restriction: Rc<dyn FrontierModelBuilder> =
             Rc::new(VehicleRestrictionBuilder {});
+        let energy_budget: Rc<dyn FrontierModelBuilder> = Rc::new(EnergyBudgetBuilder {});
         let base_frontier_builders: HashMap<String, Rc<dyn FrontierModelBuilder>> =
             HashMap::from([
                 (String::from("no_restriction"), no_restriction),
                 (String::from("road_class"), road_class),
                 (String::from("turn_restriction"), turn_restriction),
                 (String::from("vehicle_restriction"), vehicle_restriction),
+                (String::from("energy_budget"), energy_budget),
             ]);
         let combined = Rc::new(CombinedBuilder {
             builders: base_frontier_builders.clone(),
@@ -197,10 +223,20 @@ impl CompassAppBuilder {
         let traversal: Rc<dyn OutputPluginBuilder> = Rc::new(TraversalPluginBuilder {});
         let summary: Rc<dyn OutputPluginBuilder> = Rc::new(SummaryOutputPluginBuilder {});
         let uuid: Rc<dyn OutputPluginBuilder> = Rc::new(UUIDOutputPluginBuilder {});
+        let elevation: Rc<dyn OutputPluginBuilder> = Rc::new(ElevationOutputPluginBuilder {});
+        let free_flow_time: Rc<dyn OutputPluginBuilder> =
+            Rc::new(FreeFlowTimeOutputPluginBuilder {});
+        let bearing: Rc<dyn OutputPluginBuilder> = Rc::new(BearingOutputPluginBuilder {});
+        let reachable_edges: Rc<dyn OutputPluginBuilder> =
+            Rc::new(ReachableEdgesOutputPluginBuilder {});
         let output_plugin_builders = HashMap::from([
             (String::from("traversal"), traversal),
             (String::from("summary"), summary),
             (String::from("uuid"), uuid),
+            (String::from("elevation"), elevation),
+            (String::from("free_flow_time"), free_flow_time),
+            (String::from("bearing"), bearing),
+            (String::from("reachable_edges"), reachable_edges),
         ]);
 
         CompassAppBuilder {
@@ -333,6 +369,15 @@ impl CompassAppBuilder {
             let output_plugin = builder.build(&plugin_json)?;
             plugins.push(output_plugin);
         }
+
+        // output formatting runs after all other output plugins
+        let formatting = config.get_config_serde_optional::<OutputFormattingPlugin>(
+            &CompassConfigurationField::OutputFormatting,
+            &CompassConfigurationField::Plugins,
+        )?;
+        if let Some(formatting_plugin) = formatting {
+            plugins.push(Arc::new(formatting_plugin));
+        }
         Ok(plugins)
     }
 }
@@ -13,9 +13,13 @@ pub enum CompassConfigurationField {
     Plugins,
     InputPlugins,
     OutputPlugins,
+    OutputFormatting,
     Parallelism,
     QueryTimeoutMs,
     IncludeTree,
+    CacheStaticEdgeCosts,
+    QueryCacheSize,
+    QueryCachePrecision,
     ChargeDepleting,
     ChargeSustaining,
     SearchOrientation,
@@ -37,9 +41,13 @@ impl CompassConfigurationField {
             CompassConfigurationField::Parallelism => "parallelism",
             CompassConfigurationField::QueryTimeoutMs => "query_timeout_ms",
             CompassConfigurationField::IncludeTree => "include_tree",
+            CompassConfigurationField::CacheStaticEdgeCosts => "cache_static_edge_costs",
+            CompassConfigurationField::QueryCacheSize => "query_cache_size",
+            CompassConfigurationField::QueryCachePrecision => "query_cache_precision",
             CompassConfigurationField::Plugins => "plugin",
             CompassConfigurationField::InputPlugins => "input_plugins",
             CompassConfigurationField::OutputPlugins => "output_plugins",
+            CompassConfigurationField::OutputFormatting => "output_formatting",
             CompassConfigurationField::ChargeDepleting => "charge_depleting",
             CompassConfigurationField::ChargeSustaining => "charge_sustaining",
             CompassConfigurationField::SearchOrientation => "search_orientation",
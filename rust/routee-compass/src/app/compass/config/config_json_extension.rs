@@ -24,6 +24,11 @@ pub trait ConfigJsonExtensions {
         key: &dyn AsRef<str>,
         parent_key: &dyn AsRef<str>,
     ) -> Result<Option<PathBuf>, CompassConfigurationError>;
+    fn get_config_path_list(
+        &self,
+        key: &dyn AsRef<str>,
+        parent_key: &dyn AsRef<str>,
+    ) -> Result<Vec<PathBuf>, CompassConfigurationError>;
     fn get_config_string(
         &self,
         key: &dyn AsRef<str>,
@@ -121,6 +126,41 @@ impl ConfigJsonExtensions for serde_json::Value {
             ))
         }
     }
+    /// reads a key that may contain either a single file path or an array
+    /// of file paths, confirming that each file exists.
+    fn get_config_path_list(
+        &self,
+        key: &dyn AsRef<str>,
+        parent_key: &dyn AsRef<str>,
+    ) -> Result<Vec<PathBuf>, CompassConfigurationError> {
+        match self.get(key.as_ref()) {
+            Some(serde_json::Value::Array(values)) => values
+                .iter()
+                .map(|value| {
+                    let path_string = value.as_str().ok_or_else(|| {
+                        CompassConfigurationError::ExpectedFieldWithType(
+                            String::from(key.as_ref()),
+                            String::from("String or Array of Strings"),
+                        )
+                    })?;
+                    let path = PathBuf::from(path_string);
+                    if path.is_file() {
+                        Ok(path)
+                    } else {
+                        Err(CompassConfigurationError::FileNotFoundForComponent(
+                            String::from(path_string),
+                            String::from(key.as_ref()),
+                            String::from(parent_key.as_ref()),
+                        ))
+                    }
+                })
+                .collect(),
+            _ => {
+                let path = self.get_config_path(key, parent_key)?;
+                Ok(vec![path])
+            }
+        }
+    }
     fn get_config_string(
         &self,
         key: &dyn AsRef<str>,
@@ -379,6 +419,12 @@ impl ConfigJsonExtensions for serde_json::Value {
                         serde_json::Value::Object(_) => {
                             new_arr.push(value.normalize_file_paths(parent_key, root_config_path)?)
                         }
+                        // arrays of file paths, such as sharded input files
+                        serde_json::Value::String(_)
+                            if parent_key.as_ref().ends_with(FILE_NORMALIZATION_POSTFIX) =>
+                        {
+                            new_arr.push(value.normalize_file_paths(parent_key, root_config_path)?)
+                        }
                         _ => new_arr.push(value.clone()),
                     }
                 }
@@ -5,7 +5,8 @@ use crate::app::compass::config::{
     config_json_extension::ConfigJsonExtensions,
 };
 use routee_compass_core::model::cost::{
-    cost_aggregation::CostAggregation, network::network_cost_rate::NetworkCostRate,
+    cost_aggregation::CostAggregation, cost_aggregation_group::CostAggregationGroup,
+    edge_cost_cap::EdgeCostCap, network::network_cost_rate::NetworkCostRate,
     vehicle::vehicle_cost_rate::VehicleCostRate,
 };
 use std::{collections::HashMap, sync::Arc};
@@ -31,17 +32,30 @@ impl CostModelBuilder {
         let cost_aggregation: CostAggregation = config
             .get_config_serde_optional(&"cost_aggregation", &parent_key)?
             .unwrap_or_default();
+        let cost_aggregation_groups: Vec<CostAggregationGroup> = config
+            .get_config_serde_optional(&"cost_aggregation_groups", &parent_key)?
+            .unwrap_or_default();
 
         let ignore_unknown_weights = config
             .get_config_serde_optional(&"ignore_unknown_user_provided_weights", &parent_key)?
             .unwrap_or(true);
+        let tie_break: Option<String> =
+            config.get_config_serde_optional(&"tie_break", &parent_key)?;
+        let edge_cost_cap: Option<EdgeCostCap> =
+            config.get_config_serde_optional(&"edge_cost_cap", &parent_key)?;
+        let min_improvement: Option<f64> =
+            config.get_config_serde_optional(&"min_improvement", &parent_key)?;
 
         let model = CostModelService {
             vehicle_rates: Arc::new(vehicle_rates),
             network_rates: Arc::new(network_rates),
             weights: Arc::new(weights),
             cost_aggregation,
+            cost_aggregation_groups: Arc::new(cost_aggregation_groups),
             ignore_unknown_weights,
+            tie_break,
+            edge_cost_cap,
+            min_improvement,
         };
         Ok(model)
     }
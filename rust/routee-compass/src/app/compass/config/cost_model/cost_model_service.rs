@@ -2,7 +2,8 @@ use crate::app::compass::config::compass_configuration_error::CompassConfigurati
 use crate::app::compass::config::config_json_extension::ConfigJsonExtensions;
 use routee_compass_core::model::{
     cost::{
-        cost_aggregation::CostAggregation, cost_model::CostModel,
+        cost_aggregation::CostAggregation, cost_aggregation_group::CostAggregationGroup,
+        cost_model::CostModel, edge_cost_cap::EdgeCostCap,
         network::network_cost_rate::NetworkCostRate, vehicle::vehicle_cost_rate::VehicleCostRate,
     },
     state::state_model::StateModel,
@@ -17,10 +18,28 @@ pub struct CostModelService {
     pub network_rates: Arc<HashMap<String, NetworkCostRate>>,
     pub weights: Arc<HashMap<String, f64>>,
     pub cost_aggregation: CostAggregation,
+    pub cost_aggregation_groups: Arc<Vec<CostAggregationGroup>>,
     pub ignore_unknown_weights: bool,
+    pub tie_break: Option<String>,
+    pub edge_cost_cap: Option<EdgeCostCap>,
+    pub min_improvement: Option<f64>,
 }
 
 impl CostModelService {
+    /// query keys that override the configured cost model
+    pub const QUERY_KEYS: [&'static str; 4] = [
+        "weights",
+        "vehicle_rates",
+        "cost_aggregation",
+        "cost_aggregation_groups",
+    ];
+
+    /// true if the query provides any cost model parameters, in which case
+    /// the cost model built for the query may differ from the configured one.
+    pub fn query_overrides_costs(query: &serde_json::Value) -> bool {
+        Self::QUERY_KEYS.iter().any(|k| query.get(k).is_some())
+    }
+
     /// builds a CostModel based on the incoming query parameters along with the
     /// state variable names of the traversal model.
     ///
@@ -29,7 +48,10 @@ impl CostModelService {
     /// ```python
     /// {
     ///   "state_variable_names": [],  # list of state variables to convert to costs
-    ///   "cost_aggregation": ''     # operation for combining costs, 'sum' or 'mul'
+    ///   "cost_aggregation": '',    # operation for combining costs, 'sum' or 'mul'
+    ///   "cost_aggregation_groups": [],  # optional nested groups of features with their own operation
+    ///   "tie_break": '',  # optional state feature to minimize among equal-cost routes
+    ///   "min_improvement": 0.0  # optional least cost improvement that replaces a path
     /// }
     /// ```
     ///
@@ -97,14 +119,32 @@ impl CostModelService {
         let cost_aggregation: CostAggregation = query
             .get_config_serde_optional(&"cost_aggregation", &"cost_model")?
             .unwrap_or(self.cost_aggregation.to_owned());
+        let cost_aggregation_groups = query
+            .get_config_serde_optional::<Vec<CostAggregationGroup>>(
+                &"cost_aggregation_groups",
+                &"cost_model",
+            )?
+            .map(Arc::new)
+            .unwrap_or(self.cost_aggregation_groups.clone());
+
+        let tie_break = query
+            .get_config_serde_optional::<String>(&"tie_break", &"cost_model")?
+            .or(self.tie_break.clone());
+        let min_improvement = query
+            .get_config_serde_optional::<f64>(&"min_improvement", &"cost_model")?
+            .or(self.min_improvement);
 
         let model = CostModel::new(
             weights,
             vehicle_rates,
             self.network_rates.clone(),
             cost_aggregation,
+            cost_aggregation_groups,
             state_model,
         )
+        .and_then(|model| model.with_tie_break(tie_break.as_ref()))
+        .and_then(|model| model.with_edge_cost_cap(self.edge_cost_cap))
+        .and_then(|model| model.with_min_improvement(min_improvement))
         .map_err(|e| {
             CompassConfigurationError::UserConfigurationError(format!(
                 "failed to build cost model: {}",
@@ -27,4 +27,18 @@ impl FrontierModel for CombinedFrontierModel {
         }
         Ok(true)
     }
+
+    fn valid_result_state(
+        &self,
+        edge: &Edge,
+        result_state: &[StateVar],
+        state_model: &StateModel,
+    ) -> Result<bool, FrontierModelError> {
+        for frontier_model in self.inner_models.iter() {
+            if !frontier_model.valid_result_state(edge, result_state, state_model)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
 }
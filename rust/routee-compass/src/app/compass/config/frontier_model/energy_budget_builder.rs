@@ -0,0 +1,24 @@
+use crate::app::compass::config::{
+    compass_configuration_field::CompassConfigurationField,
+    config_json_extension::ConfigJsonExtensions,
+};
+use routee_compass_core::model::frontier::{
+    default::energy_budget::EnergyBudgetService, frontier_model_builder::FrontierModelBuilder,
+    frontier_model_error::FrontierModelError, frontier_model_service::FrontierModelService,
+};
+use std::sync::Arc;
+
+pub struct EnergyBudgetBuilder {}
+
+impl FrontierModelBuilder for EnergyBudgetBuilder {
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+    ) -> Result<Arc<dyn FrontierModelService>, FrontierModelError> {
+        let frontier_key = CompassConfigurationField::Frontier.to_string();
+        let energy_feature = parameters
+            .get_config_string(&"energy_feature", &frontier_key)
+            .map_err(|e| FrontierModelError::BuildError(e.to_string()))?;
+        Ok(Arc::new(EnergyBudgetService { energy_feature }))
+    }
+}
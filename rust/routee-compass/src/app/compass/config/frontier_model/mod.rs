@@ -1,4 +1,5 @@
 pub mod combined;
+pub mod energy_budget_builder;
 pub mod no_restriction_builder;
 pub mod road_class;
 pub mod turn_restrictions;
@@ -18,6 +18,15 @@ impl DefaultGraphBuilder {
     /// then we can build a Vec *once* and insert rows as we decode them without
     /// a sort.
     ///
+    /// the edge and vertex list input files may each be a single path or an
+    /// array of paths when the lists are split across multiple files. shards
+    /// are read in order and their ids must continue where the previous shard
+    /// ended.
+    ///
+    /// with `validate = true`, the loaded graph is checked for vertices without
+    /// out edges or in edges, and `validate_components = true` also counts its
+    /// weakly-connected components. problems are logged as warnings.
+    ///
     /// # Arguments
     ///
     /// * `params` - configuration JSON object for building a `Graph` instance
@@ -27,20 +36,30 @@ impl DefaultGraphBuilder {
     /// A graph instance, or an error if an IO error occurred.
     pub fn build(params: &serde_json::Value) -> Result<Graph, CompassConfigurationError> {
         let graph_key = CompassConfigurationField::Graph.to_string();
-        let edge_list_csv = params.get_config_path(&"edge_list_input_file", &graph_key)?;
-        let vertex_list_csv = params.get_config_path(&"vertex_list_input_file", &graph_key)?;
+        let edge_list_csvs = params.get_config_path_list(&"edge_list_input_file", &graph_key)?;
+        let vertex_list_csvs =
+            params.get_config_path_list(&"vertex_list_input_file", &graph_key)?;
         let n_edges = params.get_config_serde_optional(&"n_edges", &graph_key)?;
         let n_vertices = params.get_config_serde_optional(&"n_vertices", &graph_key)?;
         let verbose: Option<bool> = params.get_config_serde_optional(&"verbose", &graph_key)?;
+        let validate: Option<bool> = params.get_config_serde_optional(&"validate", &graph_key)?;
+        let validate_components: Option<bool> =
+            params.get_config_serde_optional(&"validate_components", &graph_key)?;
 
-        let graph = Graph::from_files(
-            &edge_list_csv,
-            &vertex_list_csv,
+        let graph = Graph::from_sharded_files(
+            &edge_list_csvs,
+            &vertex_list_csvs,
             n_edges,
             n_vertices,
             verbose,
         )?;
 
+        let validate_components = validate_components.unwrap_or(false);
+        if validate.unwrap_or(false) || validate_components {
+            let report = graph.validate(validate_components)?;
+            report.log_warnings();
+        }
+
         Ok(graph)
     }
 }
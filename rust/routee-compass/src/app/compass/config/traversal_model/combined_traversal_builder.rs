@@ -0,0 +1,51 @@
+use crate::app::compass::config::config_json_extension::ConfigJsonExtensions;
+use itertools::Itertools;
+use routee_compass_core::model::traversal::{
+    default::combined_traversal_model::CombinedTraversalService,
+    traversal_model_builder::TraversalModelBuilder, traversal_model_error::TraversalModelError,
+    traversal_model_service::TraversalModelService,
+};
+use std::{collections::HashMap, rc::Rc, sync::Arc};
+
+pub struct CombinedTraversalBuilder {
+    pub builders: HashMap<String, Rc<dyn TraversalModelBuilder>>,
+}
+
+impl TraversalModelBuilder for CombinedTraversalBuilder {
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+    ) -> Result<Arc<dyn TraversalModelService>, TraversalModelError> {
+        let model_params = parameters
+            .get_config_array(&"traversal_models", &"combined")
+            .map_err(|e| {
+                TraversalModelError::BuildError(format!(
+                    "unable to decode combined.traversal_models: {}",
+                    e
+                ))
+            })?;
+        let services = model_params
+            .iter()
+            .map(|params| {
+                let model_type = params
+                    .get_config_string(&"type", &"combined.traversal_models")
+                    .map_err(|e| {
+                        TraversalModelError::BuildError(format!(
+                            "unable to find 'type' of combined.traversal_models listing: {}",
+                            e
+                        ))
+                    })?;
+                let builder = self.builders.get(&model_type).ok_or_else(|| {
+                    let alts = self.builders.keys().join(",");
+                    TraversalModelError::BuildError(format!(
+                        "unregistered traversal model {}, should be one of: {{{}}}",
+                        model_type, alts
+                    ))
+                })?;
+                builder.build(params)
+            })
+            .collect::<Result<_, _>>()?;
+        let service = CombinedTraversalService::new(services)?;
+        Ok(Arc::new(service))
+    }
+}
@@ -9,6 +9,7 @@ use routee_compass_core::model::traversal::traversal_model_error::TraversalModel
 use routee_compass_core::model::traversal::traversal_model_service::TraversalModelService;
 use routee_compass_core::model::unit::{DistanceUnit, GradeUnit, SpeedUnit, TimeUnit};
 use routee_compass_powertrain::routee::energy_model_service::EnergyModelService;
+use routee_compass_powertrain::routee::missing_grade_policy::MissingGradePolicy;
 
 use super::energy_model_vehicle_builders::VehicleBuilder;
 
@@ -58,6 +59,11 @@ impl TraversalModelBuilder for EnergyModelBuilder {
             .get_config_serde::<GradeUnit>(&"grade_table_grade_unit", &parent_key)
             .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
 
+        let missing_grade_policy = params
+            .get_config_serde_optional::<MissingGradePolicy>(&"missing_grade_policy", &parent_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?
+            .unwrap_or_default();
+
         let vehicle_configs = params
             .get_config_array(&"vehicles", &parent_key)
             .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
@@ -89,11 +95,134 @@ impl TraversalModelBuilder for EnergyModelBuilder {
             time_model_speed_unit,
             &grade_table_path_option,
             grade_table_grade_unit,
+            missing_grade_policy,
             time_unit_option,
             distance_unit_option,
             vehicle_library,
         )?;
+        let service = match params
+            .get_config_serde_optional::<HashMap<String, f64>>(&"emission_factors", &parent_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?
+        {
+            Some(emission_factors) => service.with_emission_factors(emission_factors)?,
+            None => service,
+        };
+        let report_edge_speed = params
+            .get_config_serde_optional::<bool>(&"report_edge_speed", &parent_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?
+            .unwrap_or_default();
+        let service = service.with_report_edge_speed(report_edge_speed);
 
         Ok(Arc::new(service))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::compass::config::traversal_model::speed_lookup_builder::SpeedLookupBuilder;
+    use crate::app::search::search_app_ops::collect_features;
+    use routee_compass_core::model::access::default::no_access_model::NoAccessModel;
+    use routee_compass_core::model::state::state_error::StateError;
+    use std::path::PathBuf;
+
+    fn test_file(path: &[&str]) -> String {
+        let mut filepath = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        filepath.extend(path);
+        filepath.to_str().unwrap().to_string()
+    }
+
+    fn builder() -> EnergyModelBuilder {
+        let speed_table: Rc<dyn TraversalModelBuilder> = Rc::new(SpeedLookupBuilder {});
+        EnergyModelBuilder::new(HashMap::from([(String::from("speed_table"), speed_table)]))
+    }
+
+    /// a random forest (smartcore) vehicle configured from a `[traversal]` config block
+    fn random_forest_config() -> serde_json::Value {
+        serde_json::json!({
+            "type": "energy_model",
+            "grade_table_grade_unit": "decimal",
+            "time_unit": "minutes",
+            "time_model": {
+                "type": "speed_table",
+                "speed_table_input_file": test_file(&["src", "app", "compass", "test", "energy_test", "test_edge_speeds.csv"]),
+                "speed_unit": "kilometers_per_hour"
+            },
+            "vehicles": [{
+                "name": "Toyota_Camry",
+                "type": "ice",
+                "model_input_file": test_file(&["..", "routee-compass-powertrain", "src", "routee", "test", "Toyota_Camry.bin"]),
+                "model_type": "smartcore",
+                "speed_unit": "miles_per_hour",
+                "grade_unit": "decimal",
+                "energy_rate_unit": "gallons_gasoline_per_mile",
+                "ideal_energy_rate": 0.02857143,
+                "real_world_energy_adjustment": 1.166
+            }]
+        })
+    }
+
+    #[test]
+    fn test_build_random_forest_model_from_config() {
+        let service = builder().build(&random_forest_config()).unwrap();
+        let model = service
+            .build(&serde_json::json!({"model_name": "Toyota_Camry"}))
+            .unwrap();
+        let feature_names = model
+            .state_features()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>();
+        assert!(
+            feature_names.contains(&String::from("energy_liquid")),
+            "{:?}",
+            feature_names
+        );
+    }
+
+    #[test]
+    fn test_random_forest_state_features_validate_query() {
+        let service = builder().build(&random_forest_config()).unwrap();
+        let model = service
+            .build(&serde_json::json!({"model_name": "Toyota_Camry"}))
+            .unwrap();
+        let access_model = Arc::new(NoAccessModel {});
+
+        // the energy feature written by the vehicle can be given an initial value
+        let query = serde_json::json!({
+            "state_features": {
+                "energy_liquid": { "energy_unit": "gallons_gasoline", "initial": 0.5 }
+            }
+        });
+        assert!(collect_features(&query, model.clone(), access_model.clone()).is_ok());
+
+        // an energy feature that this vehicle does not write is rejected
+        let query = serde_json::json!({
+            "state_features": {
+                "energy_electric": { "energy_unit": "kilowatt_hours", "initial": 0.0 }
+            }
+        });
+        match collect_features(&query, model, access_model) {
+            Err(StateError::UnknownStateVariableName(name, _)) => {
+                assert_eq!(name, "energy_electric")
+            }
+            other => panic!("expected unknown state variable error, found {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_missing_key_names_key() {
+        let mut config = random_forest_config();
+        config["vehicles"][0]
+            .as_object_mut()
+            .unwrap()
+            .remove("model_input_file");
+        match builder().build(&config) {
+            Err(TraversalModelError::BuildError(msg)) => {
+                assert!(msg.contains("model_input_file"), "{}", msg)
+            }
+            Err(e) => panic!("expected build error, found {}", e),
+            Ok(_) => panic!("expected build error"),
+        }
+    }
+}
@@ -4,13 +4,20 @@ use crate::app::compass::config::{
     config_json_extension::ConfigJsonExtensions,
 };
 use routee_compass_core::model::unit::{
-    Energy, EnergyRate, EnergyRateUnit, EnergyUnit, GradeUnit, SpeedUnit,
+    Energy, EnergyRate, EnergyRateUnit, EnergyUnit, Grade, GradeUnit, Speed, SpeedUnit,
 };
 use routee_compass_core::util::cache_policy::float_cache_policy::{
     FloatCachePolicy, FloatCachePolicyConfig,
 };
 use routee_compass_powertrain::routee::{
-    prediction::{load_prediction_model, model_type::ModelType, PredictionModelRecord},
+    prediction::{
+        model_input_feature::ModelInputFeature,
+        model_type::ModelType,
+        nan_prediction_policy::NanPredictionPolicy,
+        prediction_model_ops::{warm_up_prediction_model, PredictionModelLoader},
+        sweep_config::SweepConfig,
+        PredictionModelRecord,
+    },
     vehicle::{
         default::{bev::BEV, ice::ICE, phev::PHEV},
         VehicleType,
@@ -55,8 +62,10 @@ fn build_conventional(
     let name = parameters.get_config_string(&"name", &vehicle_key)?;
 
     let model_record = get_model_record_from_params(parameters, &name)?;
+    let energy_unit =
+        parameters.get_config_serde_optional::<EnergyUnit>(&"energy_unit", &vehicle_key)?;
 
-    let vehicle = ICE::new(name, model_record)?;
+    let vehicle = ICE::new(name, model_record, energy_unit)?;
 
     Ok(Arc::new(vehicle))
 }
@@ -130,15 +139,32 @@ fn get_model_record_from_params(
     let name = parameters.get_config_string(&"name", &parent_key)?;
     let model_path = parameters.get_config_path(&"model_input_file", &parent_key)?;
     let model_type = parameters.get_config_serde::<ModelType>(&"model_type", &parent_key)?;
+    let input_features = parameters
+        .get_config_serde_optional::<Vec<ModelInputFeature>>(&"input_features", &parent_key)?;
     let speed_unit = parameters.get_config_serde::<SpeedUnit>(&"speed_unit", &parent_key)?;
     let ideal_energy_rate_option =
         parameters.get_config_serde_optional::<EnergyRate>(&"ideal_energy_rate", &parent_key)?;
+    let min_energy_sweep =
+        parameters.get_config_serde_optional::<SweepConfig>(&"min_energy_sweep", &parent_key)?;
     let grade_unit = parameters.get_config_serde::<GradeUnit>(&"grade_unit", &parent_key)?;
 
     let energy_rate_unit =
         parameters.get_config_serde::<EnergyRateUnit>(&"energy_rate_unit", &parent_key)?;
     let real_world_energy_adjustment_option = parameters
         .get_config_serde_optional::<f64>(&"real_world_energy_adjustment", &parent_key)?;
+    let correction_factor = parameters
+        .get_config_serde_optional::<f64>(&"correction_factor", &parent_key)?
+        .unwrap_or(1.0);
+    let nan_prediction_policy = parameters
+        .get_config_serde_optional::<NanPredictionPolicy>(&"nan_prediction_policy", &parent_key)?
+        .unwrap_or_default();
+
+    let warm_up_iterations =
+        parameters.get_config_serde_optional::<usize>(&"warm_up_iterations", &parent_key)?;
+    let speed_bounds =
+        parameters.get_config_serde_optional::<(Speed, Speed)>(&"speed_bounds", &parent_key)?;
+    let grade_bounds =
+        parameters.get_config_serde_optional::<(Grade, Grade)>(&"grade_bounds", &parent_key)?;
 
     let cache_config = parameters
         .get_config_serde_optional::<FloatCachePolicyConfig>(&"float_cache_policy", parent_key)?;
@@ -148,7 +174,7 @@ fn get_model_record_from_params(
         None => None,
     };
 
-    let model_record = load_prediction_model(
+    let mut loader = PredictionModelLoader::new(
         name.clone(),
         &model_path,
         model_type,
@@ -158,7 +184,22 @@ fn get_model_record_from_params(
         ideal_energy_rate_option,
         real_world_energy_adjustment_option,
         cache,
-    )?;
+    );
+    if let Some(input_features) = input_features {
+        loader = loader.with_input_features(input_features);
+    }
+    if let Some(min_energy_sweep) = min_energy_sweep {
+        loader = loader.with_min_energy_sweep(min_energy_sweep);
+    }
+    let model_record = loader
+        .load()?
+        .with_input_bounds(speed_bounds, grade_bounds)?
+        .with_correction_factor(correction_factor)?
+        .with_nan_prediction_policy(nan_prediction_policy);
+
+    if let Some(iterations) = warm_up_iterations {
+        warm_up_prediction_model(&model_record.prediction_model, iterations)?;
+    }
 
     Ok(model_record)
 }
@@ -0,0 +1,51 @@
+use crate::app::compass::config::compass_configuration_field::CompassConfigurationField;
+use crate::app::compass::config::config_json_extension::ConfigJsonExtensions;
+use routee_compass_core::model::traversal::default::grade_distance_traversal_model::GradeDistanceTraversalModel;
+use routee_compass_core::model::traversal::traversal_model_builder::TraversalModelBuilder;
+use routee_compass_core::model::traversal::traversal_model_error::TraversalModelError;
+use routee_compass_core::model::traversal::traversal_model_service::TraversalModelService;
+use routee_compass_core::model::unit::{DistanceUnit, GradeUnit, BASE_DISTANCE_UNIT};
+use std::sync::Arc;
+
+pub struct GradeDistanceBuilder {}
+
+impl TraversalModelBuilder for GradeDistanceBuilder {
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+    ) -> Result<Arc<dyn TraversalModelService>, TraversalModelError> {
+        let traversal_key = CompassConfigurationField::Traversal.to_string();
+        let grade_table_path = parameters
+            .get_config_path(&"grade_table_input_file", &traversal_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
+        let grade_table_grade_unit = parameters
+            .get_config_serde_optional::<GradeUnit>(&"grade_table_grade_unit", &traversal_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?
+            .unwrap_or(GradeUnit::Decimal);
+        let grade_unit = parameters
+            .get_config_serde_optional::<GradeUnit>(&"grade_unit", &traversal_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?
+            .unwrap_or(grade_table_grade_unit);
+        let uphill_factor = parameters
+            .get_config_serde::<f64>(&"uphill_factor", &traversal_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
+        let downhill_factor = parameters
+            .get_config_serde_optional::<f64>(&"downhill_factor", &traversal_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?
+            .unwrap_or(0.0);
+        let distance_unit = parameters
+            .get_config_serde_optional::<DistanceUnit>(&"distance_unit", &traversal_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?
+            .unwrap_or(BASE_DISTANCE_UNIT);
+        let model = GradeDistanceTraversalModel::from_file(
+            &grade_table_path,
+            grade_table_grade_unit,
+            grade_unit,
+            uphill_factor,
+            downhill_factor,
+            distance_unit,
+        )?;
+        let service: Arc<dyn TraversalModelService> = Arc::new(model);
+        Ok(service)
+    }
+}
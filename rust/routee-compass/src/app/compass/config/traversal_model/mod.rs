@@ -1,4 +1,7 @@
+pub mod combined_traversal_builder;
 pub mod distance_traversal_builder;
 pub mod energy_model_builder;
 pub mod energy_model_vehicle_builders;
+pub mod grade_distance_builder;
 pub mod speed_lookup_builder;
+pub mod toll_traversal_builder;
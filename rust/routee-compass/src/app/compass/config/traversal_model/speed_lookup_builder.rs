@@ -1,16 +1,31 @@
 use crate::app::compass::config::compass_configuration_field::CompassConfigurationField;
 use crate::app::compass::config::config_json_extension::ConfigJsonExtensions;
-use routee_compass_core::model::traversal::default::speed_traversal_engine::SpeedTraversalEngine;
+use routee_compass_core::model::traversal::default::congestion_profile::CongestionProfile;
+use routee_compass_core::model::traversal::default::speed_traversal_engine::{
+    read_road_classes, SpeedTraversalEngine,
+};
 
 use routee_compass_core::model::traversal::default::speed_traversal_service::SpeedLookupService;
 use routee_compass_core::model::traversal::traversal_model_builder::TraversalModelBuilder;
 use routee_compass_core::model::traversal::traversal_model_error::TraversalModelError;
 use routee_compass_core::model::traversal::traversal_model_service::TraversalModelService;
-use routee_compass_core::model::unit::{DistanceUnit, SpeedUnit, TimeUnit};
+use routee_compass_core::model::unit::{DistanceUnit, Speed, SpeedUnit, TimeUnit};
+use routee_compass_core::util::linear_interp::LinearInterp1D;
+use serde::Deserialize;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 pub struct SpeedLookupBuilder {}
 
+/// a time-of-day congestion profile, either as 24 hourly speed factors or as
+/// (hour, speed factor) points
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum CongestionProfileConfig {
+    Hourly(Vec<f64>),
+    Points(Vec<(f64, f64)>),
+}
+
 impl TraversalModelBuilder for SpeedLookupBuilder {
     fn build(
         &self,
@@ -18,11 +33,8 @@ impl TraversalModelBuilder for SpeedLookupBuilder {
     ) -> Result<Arc<dyn TraversalModelService>, TraversalModelError> {
         let traversal_key = CompassConfigurationField::Traversal.to_string();
         // todo: optional output time unit
-        let filename = params
-            .get_config_path(&"speed_table_input_file", &traversal_key)
-            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
         let speed_unit = params
-            .get_config_serde::<SpeedUnit>(&"speed_unit", &traversal_key)
+            .get_config_serde_optional::<SpeedUnit>(&"speed_unit", &traversal_key)
             .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
         let distance_unit = params
             .get_config_serde_optional::<DistanceUnit>(&"distance_unit", &traversal_key)
@@ -31,8 +43,124 @@ impl TraversalModelBuilder for SpeedLookupBuilder {
             .get_config_serde_optional::<TimeUnit>(&"time_unit", &traversal_key)
             .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
 
-        let e = SpeedTraversalEngine::new(&filename, speed_unit, distance_unit, time_unit)?;
-        let service = Arc::new(SpeedLookupService { e: Arc::new(e) });
+        let class_speeds = params
+            .get_config_serde_optional::<HashMap<u8, Vec<(f64, f64)>>>(
+                &"road_class_speeds",
+                &traversal_key,
+            )
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
+
+        let speed_caps = params
+            .get_config_serde_optional::<HashMap<u8, f64>>(&"road_class_speed_caps", &traversal_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
+
+        let congestion_profiles = params
+            .get_config_serde_optional::<HashMap<u8, CongestionProfileConfig>>(
+                &"road_class_congestion_profiles",
+                &traversal_key,
+            )
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
+
+        let read_road_class_file = || -> Result<Box<[u8]>, TraversalModelError> {
+            let road_class_file = params
+                .get_config_path(&"road_class_input_file", &traversal_key)
+                .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
+            read_road_classes(&road_class_file)
+        };
+
+        let e = match class_speeds {
+            None => {
+                let filename = params
+                    .get_config_path(&"speed_table_input_file", &traversal_key)
+                    .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
+                SpeedTraversalEngine::new(&filename, speed_unit, distance_unit, time_unit)?
+            }
+            Some(class_speeds) => {
+                let road_class_file = params
+                    .get_config_path(&"road_class_input_file", &traversal_key)
+                    .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
+                let attribute_file = params
+                    .get_config_path(&"speed_attribute_input_file", &traversal_key)
+                    .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
+                let speed_unit = speed_unit.ok_or_else(|| {
+                    TraversalModelError::BuildError(String::from(
+                        "speed_unit is required when road_class_speeds is configured",
+                    ))
+                })?;
+                let class_speeds = class_speeds
+                    .into_iter()
+                    .map(|(road_class, points)| {
+                        let interp = LinearInterp1D::from_points(&points).map_err(|e| {
+                            TraversalModelError::BuildError(format!(
+                                "invalid speeds for road class {}: {}",
+                                road_class, e
+                            ))
+                        })?;
+                        Ok((road_class, interp))
+                    })
+                    .collect::<Result<HashMap<_, _>, TraversalModelError>>()?;
+                SpeedTraversalEngine::new_interpolated(
+                    &road_class_file,
+                    &attribute_file,
+                    &class_speeds,
+                    speed_unit,
+                    distance_unit,
+                    time_unit,
+                )?
+            }
+        };
+        let e = match speed_caps {
+            None => e,
+            Some(caps) => {
+                let road_classes = read_road_class_file()?;
+                let caps = caps
+                    .into_iter()
+                    .map(|(road_class, cap)| (road_class, Speed::new(cap)))
+                    .collect::<HashMap<_, _>>();
+                e.with_speed_caps(&road_classes, &caps)?
+            }
+        };
+        let congestion = match congestion_profiles {
+            None => None,
+            Some(profiles) => {
+                let road_classes = read_road_class_file()?;
+                if road_classes.len() != e.speed_table.len() {
+                    return Err(TraversalModelError::BuildError(format!(
+                        "road class file has {} rows but speed table has {} rows",
+                        road_classes.len(),
+                        e.speed_table.len()
+                    )));
+                }
+                let class_profiles = profiles
+                    .into_iter()
+                    .map(|(road_class, profile)| {
+                        let interp = match profile {
+                            CongestionProfileConfig::Hourly(factors) => {
+                                CongestionProfile::hourly(&factors)
+                            }
+                            CongestionProfileConfig::Points(points) => {
+                                LinearInterp1D::from_points(&points)
+                            }
+                        }
+                        .map_err(|e| {
+                            TraversalModelError::BuildError(format!(
+                                "invalid congestion profile for road class {}: {}",
+                                road_class, e
+                            ))
+                        })?;
+                        Ok((road_class, interp))
+                    })
+                    .collect::<Result<HashMap<_, _>, TraversalModelError>>()?;
+                Some(Arc::new(CongestionProfile::new(
+                    road_classes,
+                    class_profiles,
+                )?))
+            }
+        };
+        let service = Arc::new(SpeedLookupService {
+            e: Arc::new(e),
+            congestion,
+        });
         Ok(service)
     }
 }
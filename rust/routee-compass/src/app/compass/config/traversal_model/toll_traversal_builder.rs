@@ -0,0 +1,28 @@
+use crate::app::compass::config::compass_configuration_field::CompassConfigurationField;
+use crate::app::compass::config::config_json_extension::ConfigJsonExtensions;
+use routee_compass_core::model::traversal::default::toll_traversal_model::TollTraversalModel;
+use routee_compass_core::model::traversal::traversal_model_builder::TraversalModelBuilder;
+use routee_compass_core::model::traversal::traversal_model_error::TraversalModelError;
+use routee_compass_core::model::traversal::traversal_model_service::TraversalModelService;
+use std::sync::Arc;
+
+pub struct TollTraversalBuilder {}
+
+impl TraversalModelBuilder for TollTraversalBuilder {
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+    ) -> Result<Arc<dyn TraversalModelService>, TraversalModelError> {
+        let traversal_key = CompassConfigurationField::Traversal.to_string();
+        let toll_table_path = parameters
+            .get_config_path(&"toll_table_input_file", &traversal_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
+        let currency = parameters
+            .get_config_serde_optional::<String>(&"currency", &traversal_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?
+            .unwrap_or_else(|| String::from(TollTraversalModel::DEFAULT_CURRENCY));
+        let model = TollTraversalModel::from_file(&toll_table_path, currency)?;
+        let service: Arc<dyn TraversalModelService> = Arc::new(model);
+        Ok(service)
+    }
+}
@@ -0,0 +1,188 @@
+use crate::app::compass::compass_app_error::CompassAppError;
+use routee_compass_core::algorithm::search::search_error::SearchError;
+use routee_compass_core::util::t_digest::TDigest;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// summary statistics of a batch of query responses, produced by a [`BatchSummaryAggregator`].
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BatchSummary {
+    /// number of responses aggregated
+    pub query_count: usize,
+    /// number of responses with a route
+    pub route_count: usize,
+    /// number of queries where no path exists between the origin and destination
+    pub no_route_count: usize,
+    /// number of queries that failed for any other reason
+    pub error_count: usize,
+    /// fraction of queries without a route, due to no path or an error
+    pub failure_rate: f64,
+    /// statistics of each traversal summary feature of the routes, by feature name
+    pub features: BTreeMap<String, FeatureSummary>,
+}
+
+/// statistics of one traversal summary feature over the routes of a batch
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct FeatureSummary {
+    pub count: usize,
+    pub mean: f64,
+    pub median: f64,
+    pub p95: f64,
+}
+
+/// accumulates summary statistics from query responses one at a time, so that
+/// large batches can be summarized without keeping their responses in memory.
+///
+/// the traversal summary of the route in each response, such as distance, time
+/// and energy, is accumulated by feature name. quantiles are estimated with a
+/// t-digest. when a response has several routes, the first route is used.
+/// responses without a route are tallied as no-route when the search failed with
+/// [`SearchError::NoPathExists`] and as errored otherwise. an aggregator can be
+/// passed to [`crate::app::compass::compass_app::CompassApp::run_with_summary`]
+/// to summarize a batch as it runs.
+#[derive(Default)]
+pub struct BatchSummaryAggregator {
+    query_count: usize,
+    route_count: usize,
+    no_route_count: usize,
+    error_count: usize,
+    features: BTreeMap<String, FeatureAggregate>,
+}
+
+#[derive(Default)]
+struct FeatureAggregate {
+    sum: f64,
+    digest: TDigest,
+}
+
+impl BatchSummaryAggregator {
+    /// adds a query response to the summary.
+    ///
+    /// # Arguments
+    ///
+    /// * `response` - the JSON response of a query
+    /// * `search_error` - the error of the search that produced the response, if it failed
+    pub fn add_response(
+        &mut self,
+        response: &serde_json::Value,
+        search_error: Option<&CompassAppError>,
+    ) {
+        self.query_count += 1;
+        if let Some(CompassAppError::SearchError(SearchError::NoPathExists(_, _))) = search_error {
+            self.no_route_count += 1;
+            return;
+        }
+        if response.get("error").is_some() {
+            self.error_count += 1;
+            return;
+        }
+        let route = match response.get("route") {
+            Some(serde_json::Value::Array(routes)) => routes.first(),
+            other => other,
+        };
+        let summary = match route.and_then(|r| r.get("traversal_summary")) {
+            Some(serde_json::Value::Object(summary)) => summary,
+            _ => {
+                self.no_route_count += 1;
+                return;
+            }
+        };
+        self.route_count += 1;
+        for (feature, value) in summary.iter() {
+            if let Some(v) = value.as_f64() {
+                let aggregate = self.features.entry(feature.clone()).or_default();
+                aggregate.sum += v;
+                aggregate.digest.add(v);
+            }
+        }
+    }
+
+    /// computes the summary of all responses added so far.
+    pub fn summary(&self) -> BatchSummary {
+        let failures = self.no_route_count + self.error_count;
+        let failure_rate = if self.query_count == 0 {
+            0.0
+        } else {
+            failures as f64 / self.query_count as f64
+        };
+        let features = self
+            .features
+            .iter()
+            .map(|(name, aggregate)| {
+                let count = aggregate.digest.count();
+                let summary = FeatureSummary {
+                    count,
+                    mean: aggregate.sum / count as f64,
+                    median: aggregate.digest.quantile(0.5).unwrap_or(f64::NAN),
+                    p95: aggregate.digest.quantile(0.95).unwrap_or(f64::NAN),
+                };
+                (name.clone(), summary)
+            })
+            .collect();
+        BatchSummary {
+            query_count: self.query_count,
+            route_count: self.route_count,
+            no_route_count: self.no_route_count,
+            error_count: self.error_count,
+            failure_rate,
+            features,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use routee_compass_core::model::road_network::vertex_id::VertexId;
+    use serde_json::json;
+
+    #[test]
+    fn test_batch_summary() {
+        let mut aggregator = BatchSummaryAggregator::default();
+        // 100 routes with distances 1..=100 and times 2..=200
+        for i in 1..=100 {
+            let distance = i as f64;
+            let response = json!({
+                "request": { "origin_vertex": 0, "destination_vertex": i },
+                "route": {
+                    "traversal_summary": { "distance": distance, "time": distance * 2.0 }
+                }
+            });
+            aggregator.add_response(&response, None);
+        }
+        let no_path = SearchError::NoPathExists(VertexId(0), VertexId(101));
+        let response = json!({
+            "request": { "origin_vertex": 0, "destination_vertex": 101 },
+            "error": no_path.to_string()
+        });
+        aggregator.add_response(&response, Some(&CompassAppError::SearchError(no_path)));
+        // other search errors and failures outside of the search are errors
+        let terminated = SearchError::QueryTerminated(String::from("exceeded iteration limit"));
+        let response = json!({ "error": terminated.to_string() });
+        aggregator.add_response(&response, Some(&CompassAppError::SearchError(terminated)));
+        for _ in 0..2 {
+            let response = json!({
+                "request": { "origin_x": 0.0 },
+                "error": "query missing origin_y"
+            });
+            aggregator.add_response(&response, None);
+        }
+
+        let summary = aggregator.summary();
+        assert_eq!(summary.query_count, 104);
+        assert_eq!(summary.route_count, 100);
+        assert_eq!(summary.no_route_count, 1);
+        assert_eq!(summary.error_count, 3);
+        assert_eq!(summary.failure_rate, 4.0 / 104.0);
+
+        let distance = summary.features["distance"];
+        assert_eq!(distance.count, 100);
+        assert_eq!(distance.mean, 50.5);
+        assert_eq!(distance.median, 50.5);
+        assert_eq!(distance.p95, 95.5);
+        let time = summary.features["time"];
+        assert_eq!(time.mean, 101.0);
+        assert_eq!(time.median, 101.0);
+        assert_eq!(time.p95, 191.0);
+    }
+}
@@ -1,4 +1,6 @@
+pub mod batch_summary;
 pub mod csv;
+pub mod od_matrix;
 pub mod response_output_format;
 pub mod response_output_format_json;
 pub mod response_output_policy;
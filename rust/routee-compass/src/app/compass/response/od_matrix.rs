@@ -0,0 +1,134 @@
+use crate::app::compass::compass_app_error::CompassAppError;
+use routee_compass_core::model::{road_network::vertex_id::VertexId, unit::Cost};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter},
+    path::Path,
+};
+
+/// an origin-destination cost matrix, where `costs[i][j]` is the cost of the
+/// route from `origins[i]` to `destinations[j]`, or None when the destination
+/// is not reachable from the origin.
+///
+/// matrices can be exported to and read from a compact bincode file, which is
+/// far smaller than a JSON export for large matrices.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct OdMatrix {
+    pub origins: Vec<VertexId>,
+    pub destinations: Vec<VertexId>,
+    pub costs: Vec<Vec<Option<Cost>>>,
+}
+
+impl OdMatrix {
+    /// builds a matrix, confirming that it has a row of costs for each origin
+    /// and a column for each destination.
+    pub fn new(
+        origins: Vec<VertexId>,
+        destinations: Vec<VertexId>,
+        costs: Vec<Vec<Option<Cost>>>,
+    ) -> Result<OdMatrix, CompassAppError> {
+        let matrix = OdMatrix {
+            origins,
+            destinations,
+            costs,
+        };
+        matrix.validate()?;
+        Ok(matrix)
+    }
+
+    /// writes this matrix to a bincode file.
+    pub fn write_bincode<P: AsRef<Path>>(&self, path: P) -> Result<(), CompassAppError> {
+        let file = File::create(path.as_ref())?;
+        bincode::serialize_into(BufWriter::new(file), self).map_err(|e| {
+            CompassAppError::InternalError(format!(
+                "failure writing od matrix to {}: {}",
+                path.as_ref().to_string_lossy(),
+                e
+            ))
+        })
+    }
+
+    /// reads a matrix from a bincode file written by [`OdMatrix::write_bincode`].
+    pub fn read_bincode<P: AsRef<Path>>(path: P) -> Result<OdMatrix, CompassAppError> {
+        let file = File::open(path.as_ref())?;
+        let matrix: OdMatrix = bincode::deserialize_from(BufReader::new(file)).map_err(|e| {
+            CompassAppError::InvalidInput(format!(
+                "failure reading od matrix from {}: {}",
+                path.as_ref().to_string_lossy(),
+                e
+            ))
+        })?;
+        matrix.validate()?;
+        Ok(matrix)
+    }
+
+    /// the cost of the route from an origin index to a destination index,
+    /// or None when it is unreachable or outside of the matrix.
+    pub fn get(&self, origin: usize, destination: usize) -> Option<Cost> {
+        self.costs
+            .get(origin)
+            .and_then(|row| row.get(destination))
+            .cloned()
+            .flatten()
+    }
+
+    fn validate(&self) -> Result<(), CompassAppError> {
+        if self.costs.len() != self.origins.len() {
+            return Err(CompassAppError::InvalidInput(format!(
+                "od matrix has {} origins but {} rows of costs",
+                self.origins.len(),
+                self.costs.len()
+            )));
+        }
+        for (row, origin) in self.costs.iter().zip(self.origins.iter()) {
+            if row.len() != self.destinations.len() {
+                return Err(CompassAppError::InvalidInput(format!(
+                    "od matrix has {} destinations but {} costs for origin {}",
+                    self.destinations.len(),
+                    row.len(),
+                    origin
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_od_matrix_bincode_round_trip() {
+        let matrix = OdMatrix::new(
+            vec![VertexId(0), VertexId(4)],
+            vec![VertexId(1), VertexId(2), VertexId(3)],
+            vec![
+                vec![Some(Cost::from(1.5)), None, Some(Cost::ZERO)],
+                vec![None, Some(Cost::from(42.0)), None],
+            ],
+        )
+        .unwrap();
+        let path = std::env::temp_dir().join("test_od_matrix_bincode_round_trip.bin");
+        matrix.write_bincode(&path).unwrap();
+        let result = OdMatrix::read_bincode(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(result, matrix);
+        assert_eq!(result.get(0, 0), Some(Cost::from(1.5)));
+        assert_eq!(result.get(0, 1), None);
+        assert_eq!(result.get(1, 1), Some(Cost::from(42.0)));
+        assert_eq!(result.get(1, 2), None);
+    }
+
+    #[test]
+    fn test_od_matrix_dimension_mismatch() {
+        let result = OdMatrix::new(
+            vec![VertexId(0)],
+            vec![VertexId(1), VertexId(2)],
+            vec![vec![Some(Cost::ONE)]],
+        );
+        assert!(result.is_err());
+    }
+}
@@ -1,4 +1,5 @@
 pub mod search_app;
 pub mod search_app_graph_ops;
+pub mod search_app_health;
 pub mod search_app_ops;
 pub mod search_app_result;
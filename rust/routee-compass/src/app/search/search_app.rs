@@ -1,29 +1,50 @@
-use super::{search_app_ops, search_app_result::SearchAppResult};
+use super::{
+    search_app_health::{GraphHealth, HealthReport},
+    search_app_ops,
+    search_app_result::SearchAppResult,
+};
 use crate::{
     app::compass::{
         compass_app_error::CompassAppError,
-        config::cost_model::cost_model_service::CostModelService,
+        config::{
+            compass_configuration_error::CompassConfigurationError,
+            cost_model::cost_model_service::CostModelService,
+        },
         search_orientation::SearchOrientation,
     },
-    plugin::input::input_json_extensions::InputJsonExtensions,
+    plugin::input::{
+        default::vertex_rtree::plugin::VertexRTree, input_field::InputField,
+        input_json_extensions::InputJsonExtensions,
+    },
 };
 use chrono::Local;
 use routee_compass_core::{
     algorithm::search::{
-        direction::Direction, search_algorithm::SearchAlgorithm,
+        backtrack, direction::Direction, edge_cost_cache::EdgeCostCache,
+        edge_traversal::EdgeTraversal, isochrone, search_algorithm::SearchAlgorithm,
         search_algorithm_result::SearchAlgorithmResult, search_error::SearchError,
-        search_instance::SearchInstance,
+        search_instance::SearchInstance, search_metrics::SearchMetrics,
     },
     model::{
         access::access_model_service::AccessModelService,
-        frontier::frontier_model_service::FrontierModelService, road_network::graph::Graph,
-        state::state_model::StateModel, termination::termination_model::TerminationModel,
-        traversal::traversal_model_service::TraversalModelService,
+        frontier::frontier_model_service::FrontierModelService,
+        road_network::{edge_id::EdgeId, graph::Graph, vertex_id::VertexId},
+        state::state_model::StateModel,
+        termination::termination_model::TerminationModel,
+        traversal::{
+            state::traversal_state::TraversalState, traversal_model_service::TraversalModelService,
+        },
     },
+    util::cache_policy::query_cache_policy::{QueryCachePolicy, QueryCachePolicyConfig},
 };
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
 use std::time;
 
+/// the source and target vertices of a route which is yet to be reconstructed
+/// from a search tree
+pub type RouteEnds = (VertexId, VertexId);
+
 /// a configured and loaded application to execute searches.
 pub struct SearchApp {
     pub search_algorithm: SearchAlgorithm,
@@ -34,9 +55,27 @@ pub struct SearchApp {
     pub cost_model_service: Arc<CostModelService>,
     pub frontier_model_service: Arc<dyn FrontierModelService>,
     pub termination_model: Arc<TerminationModel>,
+    pub edge_cost_cache: Option<Arc<EdgeCostCache>>,
+    /// results of recent queries and the search assets that produced them, returned
+    /// for repeated queries instead of searching
+    pub query_cache: Option<QueryCachePolicy<(SearchAppResult, SearchInstance)>>,
+    /// whether the results in the query cache keep their search trees
+    pub query_cache_keeps_trees: bool,
+    /// whether routes are reconstructed for `summary_only` queries, as their edges are read
+    pub summary_routes_required: bool,
+    /// the state feature that bounds searches without a destination by the reachable
+    /// limits of the query
+    pub reachable_feature: Option<String>,
+    /// spatial index of the graph vertices, built on first use
+    pub vertex_rtree: OnceLock<VertexRTree>,
 }
 
 impl SearchApp {
+    /// decimal places of the query coordinates when matching cached queries, by default
+    pub const DEFAULT_QUERY_CACHE_PRECISION: i32 = 5;
+    /// key of the search orientation in the queries stored in the query cache
+    const QUERY_CACHE_ORIENTATION_KEY: &'static str = "search_orientation";
+
     /// builds a new SearchApp from the required components.
     /// handles all of the specialized boxing that allows for simple parallelization.
     #[allow(clippy::too_many_arguments)]
@@ -59,9 +98,128 @@ impl SearchApp {
             cost_model_service: Arc::new(cost_model_service),
             frontier_model_service,
             termination_model: Arc::new(termination_model),
+            edge_cost_cache: None,
+            query_cache: None,
+            query_cache_keeps_trees: false,
+            summary_routes_required: false,
+            reachable_feature: None,
+            vertex_rtree: OnceLock::new(),
         }
     }
 
+    /// precomputes the traversal cost of every edge once so that searches read
+    /// edge costs from a lookup table instead of computing them. the cache is
+    /// built from the configured models and is only used by queries that do not
+    /// override the cost model and whose traversal model is static. if the traversal
+    /// model is not static, no cache is built and a warning is logged.
+    pub fn with_edge_cost_cache(mut self) -> Result<Self, CompassAppError> {
+        let si = self.build_search_instance(&serde_json::json!({}))?;
+        if !si.traversal_model.is_static() {
+            log::warn!("traversal model is not static, edge costs will not be cached");
+            return Ok(self);
+        }
+        let cache = EdgeCostCache::build(&si)?;
+        log::info!("cached traversal costs for {} edges", cache.len());
+        self.edge_cost_cache = Some(Arc::new(cache));
+        Ok(self)
+    }
+
+    /// keeps the search results of up to `cache_size` recent queries, which are
+    /// returned for repeated queries without running a search. queries are
+    /// matched by their normalized form and search orientation, where object
+    /// keys are sorted, the `query_index` and `request_id` identifiers are
+    /// ignored and the origin and destination coordinates are rounded to
+    /// `coordinate_precision` decimal places. since the graph and models do not
+    /// change while the app runs, cached results never become invalid.
+    ///
+    /// search trees can be far larger than routes, so they are only kept when
+    /// `keep_trees` is set, which should be the case when an output plugin reads
+    /// them. otherwise only the routes and metrics of a result are kept and
+    /// queries without a destination are not cached.
+    ///
+    /// # Arguments
+    ///
+    /// * `cache_size` - number of query results to keep
+    /// * `coordinate_precision` - decimal places of the coordinates when matching queries
+    /// * `keep_trees` - whether cached results keep their search trees
+    ///
+    /// # Returns
+    ///
+    /// the app with a query cache, or an error if the cache size is zero or the
+    /// precision is outside of [-10, 10]
+    pub fn with_query_cache(
+        mut self,
+        cache_size: usize,
+        coordinate_precision: i32,
+        keep_trees: bool,
+    ) -> Result<Self, CompassAppError> {
+        let config = QueryCachePolicyConfig {
+            cache_size,
+            coordinate_precision,
+        };
+        let coordinate_keys = [
+            InputField::OriginX,
+            InputField::OriginY,
+            InputField::DestinationX,
+            InputField::DestinationY,
+        ]
+        .map(|field| field.to_str());
+        let ignored_keys = [InputField::QueryIndex, InputField::RequestId].map(|f| f.to_str());
+        let cache = QueryCachePolicy::from_config(config, &coordinate_keys, &ignored_keys)
+            .map_err(CompassConfigurationError::CacheError)?;
+        self.query_cache = Some(cache);
+        self.query_cache_keeps_trees = keep_trees;
+        Ok(self)
+    }
+
+    /// sets whether routes are reconstructed from the search tree for queries
+    /// with `summary_only` set, which should be the case when an output plugin
+    /// reads the edges of routes. otherwise only the final state of each route is
+    /// read from the search tree.
+    pub fn with_summary_routes(mut self, required: bool) -> Self {
+        self.summary_routes_required = required;
+        self
+    }
+
+    /// sets the state feature that the `reachable_limit` and `reachable_limits` of a
+    /// query apply to. a search without a destination then stops expanding any branch
+    /// once this feature exceeds the largest limit, instead of searching the whole
+    /// graph, which should be the case when an output plugin reads the reachable edges.
+    pub fn with_reachable_feature(mut self, feature: Option<String>) -> Self {
+        self.reachable_feature = feature;
+        self
+    }
+
+    /// summarizes the loaded graph, search algorithm and state model. the state
+    /// model is that of a query without overrides. this is read-only and does not
+    /// run a search.
+    ///
+    /// # Returns
+    ///
+    /// the health report, without a plugin pipeline, or an error if the state
+    /// model could not be described
+    pub fn health(&self) -> Result<HealthReport, CompassAppError> {
+        let graph = GraphHealth {
+            vertex_count: self.directed_graph.n_vertices(),
+            edge_count: self.directed_graph.n_edges(),
+            bounds: self
+                .directed_graph
+                .bounds()
+                .map(|(min, max)| [min.x, min.y, max.x, max.y]),
+        };
+        let search_algorithm = serde_json::to_value(&self.search_algorithm)?;
+        // the state model is completed by the models of a search instance
+        let si = self.build_search_instance(&serde_json::json!({}))?;
+        let state_features = si.state_model.schema()?;
+        Ok(HealthReport {
+            graph,
+            search_algorithm,
+            state_features,
+            edge_cost_cache: self.edge_cost_cache.is_some(),
+            plugins: None,
+        })
+    }
+
     /// main interface for running search. takes a user query and some configured
     /// search orientation. builds the instance of the search assets and then executes
     /// a search. if a destination is set on the query, then the route is computed.
@@ -76,13 +234,66 @@ impl SearchApp {
     /// # Results
     ///
     /// The complete set of trees, routes, and search assets for this run.
+    /// When a query cache is set and the query was run before, the cached result
+    /// is returned with the search assets that produced it, without searching.
+    /// Its `search_executed_time`, runtimes and search metrics are those of the
+    /// search that produced it. Cached results have no search trees unless the
+    /// cache keeps them, see [`SearchApp::with_query_cache`].
     pub fn run(
         &self,
         query: &serde_json::Value,
         search_orientation: &SearchOrientation,
+    ) -> Result<(SearchAppResult, SearchInstance), CompassAppError> {
+        let query_cache = match &self.query_cache {
+            Some(query_cache) if self.query_cache_keeps_trees || Self::has_destination(query) => {
+                query_cache
+            }
+            _ => return self.run_search(query, search_orientation),
+        };
+        // the same query may have a different result in another orientation
+        let mut cache_query = query.clone();
+        cache_query[Self::QUERY_CACHE_ORIENTATION_KEY] = serde_json::json!(search_orientation);
+        let cached = query_cache
+            .get(&cache_query)
+            .map_err(|e| CompassAppError::InternalError(e.to_string()))?;
+        if let Some(cached) = cached {
+            // the states of the result are read with the state model they were built with
+            log::debug!("query result found in the query cache");
+            return Ok(cached);
+        }
+        let (result, si) = self.run_search(query, search_orientation)?;
+        let (result, cached_result) = if self.query_cache_keeps_trees {
+            // a copy has the same allocated size as the copies returned on a
+            // cache hit, which the summary plugin reports
+            let result = result.clone();
+            (result.clone(), result)
+        } else {
+            let cached_result = result.without_trees();
+            (result, cached_result)
+        };
+        query_cache
+            .update(&cache_query, (cached_result, si.clone()))
+            .map_err(|e| CompassAppError::InternalError(e.to_string()))?;
+        Ok((result, si))
+    }
+
+    /// true if the query has a destination, in which case its result has routes
+    fn has_destination(query: &serde_json::Value) -> bool {
+        [InputField::DestinationVertex, InputField::DestinationEdge]
+            .iter()
+            .any(|field| query.get(field.to_str()).is_some())
+    }
+
+    /// runs the search for a query, see [`SearchApp::run`].
+    fn run_search(
+        &self,
+        query: &serde_json::Value,
+        search_orientation: &SearchOrientation,
     ) -> Result<(SearchAppResult, SearchInstance), CompassAppError> {
         let search_start_time = Local::now();
-        let (results, si) = match search_orientation {
+        // queries that provide edge ids directly bypass the vertex orientation
+        let (mut results, si, route_ends) = match search_orientation {
+            SearchOrientation::Vertex if query.has_origin_edge() => self.run_edge_oriented(query),
             SearchOrientation::Vertex => self.run_vertex_oriented(query),
             SearchOrientation::Edge => self.run_edge_oriented(query),
         }?;
@@ -97,21 +308,139 @@ impl SearchApp {
             search_runtime.as_millis()
         );
 
+        let route_start_time = time::Instant::now();
+        let route_final_states = match route_ends {
+            None => None,
+            Some((source, target)) => {
+                let _span = tracing::info_span!("route_reconstruction").entered();
+                self.reconstruct_route(query, source, target, &mut results, &si)?
+            }
+        };
+        let route_runtime = route_start_time.elapsed();
+
         let result = SearchAppResult {
             routes: results.routes,
+            route_final_states,
             trees: results.trees,
             search_executed_time: search_start_time.to_rfc3339(),
             search_runtime,
+            route_runtime,
             iterations: results.iterations,
+            nodes_expanded: results.metrics.nodes_expanded,
+            edges_relaxed: results.metrics.edges_relaxed,
+            max_frontier_size: results.metrics.max_frontier_size,
         };
 
         Ok((result, si))
     }
 
+    /// reconstructs the route from source to target on the search tree of a search
+    /// which did not reconstruct it. when only the route summary is requested and
+    /// neither per-edge records nor an output plugin need the route edges, the
+    /// route is not backtracked, and only its final state is read from the tree.
+    ///
+    /// # Returns
+    ///
+    /// the final state of the route if only that was found, otherwise None, after
+    /// adding the route to the search results
+    fn reconstruct_route(
+        &self,
+        query: &serde_json::Value,
+        source: VertexId,
+        target: VertexId,
+        results: &mut SearchAlgorithmResult,
+        si: &SearchInstance,
+    ) -> Result<Option<Vec<TraversalState>>, CompassAppError> {
+        let tree = match results.trees.first() {
+            Some(tree) => tree,
+            None => return Ok(None),
+        };
+        let summary_only = query.get_summary_only()?
+            && !query.get_edge_breakdown()?
+            && !self.summary_routes_required;
+        if summary_only {
+            let final_state = match backtrack::vertex_oriented_route_end(source, target, tree)? {
+                Some(edge_traversal) => edge_traversal.result_state.clone(),
+                None => si.state_model.initial_state()?,
+            };
+            Ok(Some(vec![final_state]))
+        } else {
+            let route = backtrack::vertex_oriented_route(source, target, tree)?;
+            results.routes = vec![route];
+            Ok(None)
+        }
+    }
+
+    /// computes the cost of a given path under the models built for this query,
+    /// without running a search. each edge is traversed in order, accumulating
+    /// state and costs as a search would, so the result can be compared with
+    /// the route found by a search for the same query.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - a JSON search query, used to build the search assets
+    /// * `origin` - the vertex where the path begins
+    /// * `edges` - the edges of the path, in order
+    ///
+    /// # Results
+    ///
+    /// A result with the evaluated path as its only route, or an error if the
+    /// path does not begin at the origin or consecutive edges are not connected.
+    pub fn evaluate_path(
+        &self,
+        query: &serde_json::Value,
+        origin: VertexId,
+        edges: &[EdgeId],
+    ) -> Result<(SearchAppResult, SearchInstance), CompassAppError> {
+        let search_start_time = Local::now();
+        let si = self.build_search_instance(query)?;
+
+        let mut route: Vec<EdgeTraversal> = Vec::with_capacity(edges.len());
+        let mut state = si.state_model.initial_state()?;
+        let mut prev: Option<(EdgeId, VertexId)> = None;
+        for edge_id in edges.iter() {
+            let edge = self.directed_graph.get_edge(*edge_id)?;
+            match prev {
+                None if edge.src_vertex_id != origin => {
+                    return Err(SearchError::PathDoesNotStartAtOrigin(origin, *edge_id).into())
+                }
+                Some((prev_edge_id, prev_dst)) if edge.src_vertex_id != prev_dst => {
+                    return Err(SearchError::DisconnectedPath(prev_edge_id, *edge_id).into())
+                }
+                _ => {}
+            }
+            let prev_edge_id = prev.map(|(e, _)| e);
+            let et = EdgeTraversal::forward_traversal(*edge_id, prev_edge_id, &state, &si)?;
+            state.clone_from(&et.result_state);
+            route.push(et);
+            prev = Some((*edge_id, edge.dst_vertex_id));
+        }
+
+        let search_runtime = (Local::now() - search_start_time)
+            .to_std()
+            .unwrap_or(time::Duration::ZERO);
+        let result = SearchAppResult {
+            routes: vec![route],
+            route_final_states: None,
+            trees: vec![HashMap::new()],
+            search_executed_time: search_start_time.to_rfc3339(),
+            search_runtime,
+            route_runtime: time::Duration::ZERO,
+            iterations: 0,
+            nodes_expanded: 0,
+            edges_relaxed: 0,
+            max_frontier_size: 0,
+        };
+        Ok((result, si))
+    }
+
+    /// runs a vertex-oriented search for a query. when the search algorithm only
+    /// builds a search tree, the route is not reconstructed, and the source and
+    /// target vertices of the route are returned along with the result.
     pub fn run_vertex_oriented(
         &self,
         query: &serde_json::Value,
-    ) -> Result<(SearchAlgorithmResult, SearchInstance), CompassAppError> {
+    ) -> Result<(SearchAlgorithmResult, SearchInstance, Option<RouteEnds>), CompassAppError> {
         let o = query
             .get_origin_vertex()
             .map_err(CompassAppError::PluginError)?;
@@ -120,27 +449,76 @@ impl SearchApp {
             .map_err(CompassAppError::PluginError)?;
 
         let search_instance = self.build_search_instance(query)?;
-        self.search_algorithm
-            .run_vertex_oriented(o, d, &Direction::Forward, &search_instance)
-            .map(|search_result| (search_result, search_instance))
-            .map_err(CompassAppError::SearchError)
+
+        // origin and destination snapped to the same vertex: the route is empty
+        // and the search is skipped
+        if d == Some(o) {
+            let result = SearchAlgorithmResult {
+                trees: vec![HashMap::new()],
+                routes: vec![vec![]],
+                iterations: 0,
+                metrics: SearchMetrics::default(),
+            };
+            return Ok((result, search_instance, None));
+        }
+
+        let tree_result = self.search_algorithm.run_vertex_oriented_tree(
+            o,
+            d,
+            &Direction::Forward,
+            &search_instance,
+        )?;
+        if let Some(search_result) = tree_result {
+            let route_ends = d.map(|d| (o, d));
+            return Ok((search_result, search_instance, route_ends));
+        }
+        let search_result = self.search_algorithm.run_vertex_oriented(
+            o,
+            d,
+            &Direction::Forward,
+            &search_instance,
+        )?;
+        Ok((search_result, search_instance, None))
     }
 
+    /// runs an edge-oriented search for a query, see [`SearchApp::run_vertex_oriented`].
     pub fn run_edge_oriented(
         &self,
         query: &serde_json::Value,
-    ) -> Result<(SearchAlgorithmResult, SearchInstance), CompassAppError> {
+    ) -> Result<(SearchAlgorithmResult, SearchInstance, Option<RouteEnds>), CompassAppError> {
         let o = query
             .get_origin_edge()
             .map_err(CompassAppError::PluginError)?;
         let d_opt = query
             .get_destination_edge()
             .map_err(CompassAppError::PluginError)?;
+        for edge_id in std::iter::once(o).chain(d_opt) {
+            self.directed_graph.get_edge(edge_id)?;
+        }
         let search_instance = self.build_search_instance(query)?;
-        self.search_algorithm
-            .run_edge_oriented(o, d_opt, &Direction::Forward, &search_instance)
-            .map(|search_result| (search_result, search_instance))
-            .map_err(CompassAppError::SearchError)
+        let tree_result = self.search_algorithm.run_edge_oriented_tree(
+            o,
+            d_opt,
+            &Direction::Forward,
+            &search_instance,
+        )?;
+        if let Some(search_result) = tree_result {
+            let route_ends = match d_opt {
+                Some(d) if d != o => Some((
+                    self.directed_graph.src_vertex_id(o)?,
+                    self.directed_graph.dst_vertex_id(d)?,
+                )),
+                _ => None,
+            };
+            return Ok((search_result, search_instance, route_ends));
+        }
+        let search_result = self.search_algorithm.run_edge_oriented(
+            o,
+            d_opt,
+            &Direction::Forward,
+            &search_instance,
+        )?;
+        Ok((search_result, search_instance, None))
     }
 
     /// builds the assets that will run the search for this query instance.
@@ -164,6 +542,16 @@ impl SearchApp {
         let state_model_instance = self.state_model.extend(state_features)?;
         let state_model = Arc::new(state_model_instance);
 
+        let edge_cost_cache = match &self.edge_cost_cache {
+            Some(cache)
+                if !CostModelService::query_overrides_costs(query)
+                    && traversal_model.is_static() =>
+            {
+                Some(cache.clone())
+            }
+            _ => None,
+        };
+
         let cost_model = self
             .cost_model_service
             .build(query, state_model.clone())
@@ -180,8 +568,48 @@ impl SearchApp {
             cost_model,
             frontier_model,
             termination_model: self.termination_model.clone(),
+            edge_cost_cache,
         };
 
-        Ok(search_assets)
+        match self.reachable_limit(query)? {
+            Some((feature, limit)) => {
+                if !search_assets.state_model.contains_key(feature) {
+                    return Err(SearchError::BuildError(format!(
+                        "reachable feature '{}' is not in the state model, found [{}]",
+                        feature,
+                        search_assets.state_model.get_names()
+                    )));
+                }
+                Ok(isochrone::bounded_search_instance(
+                    &search_assets,
+                    feature,
+                    limit,
+                ))
+            }
+            None => Ok(search_assets),
+        }
+    }
+
+    /// the reachable feature and the largest reachable limit of a query without a
+    /// destination, which bound its search
+    fn reachable_limit<'a>(
+        &'a self,
+        query: &serde_json::Value,
+    ) -> Result<Option<(&'a String, f64)>, SearchError> {
+        let feature = match &self.reachable_feature {
+            Some(feature) if !Self::has_destination(query) => feature,
+            _ => return Ok(None),
+        };
+        let limit = query
+            .get_reachable_limit()
+            .map_err(|e| SearchError::BuildError(e.to_string()))?;
+        let limits = query
+            .get_reachable_limits()
+            .map_err(|e| SearchError::BuildError(e.to_string()))?;
+        let max_limit = limit
+            .into_iter()
+            .chain(limits.unwrap_or_default())
+            .reduce(f64::max);
+        Ok(max_limit.map(|limit| (feature, limit)))
     }
 }
@@ -1,5 +1,7 @@
 use super::search_app::SearchApp;
 use crate::app::compass::compass_app_error::CompassAppError;
+use crate::plugin::input::default::vertex_rtree::plugin::VertexRTree;
+use geo::Coord;
 use routee_compass_core::{
     algorithm::search::direction::Direction,
     model::road_network::{edge_id::EdgeId, vertex_id::VertexId},
@@ -19,6 +21,12 @@ pub trait SearchAppGraphOps {
         vertex_id: VertexId,
         direction: Direction,
     ) -> Result<Vec<EdgeId>, CompassAppError>;
+    fn get_nearest_vertices(
+        &self,
+        coord: Coord<f32>,
+        k: usize,
+        distance_unit: Option<DistanceUnit>,
+    ) -> Result<Vec<(VertexId, Distance)>, CompassAppError>;
 }
 
 impl SearchAppGraphOps for SearchApp {
@@ -66,4 +74,31 @@ impl SearchAppGraphOps for SearchApp {
             .map_err(CompassAppError::GraphError)?;
         Ok(incident_edges)
     }
+
+    /// finds the `k` nearest graph vertices to a coordinate by haversine distance
+    /// without running a search. the spatial index is built on the first call.
+    fn get_nearest_vertices(
+        &self,
+        coord: Coord<f32>,
+        k: usize,
+        distance_unit: Option<DistanceUnit>,
+    ) -> Result<Vec<(VertexId, Distance)>, CompassAppError> {
+        let rtree = self
+            .vertex_rtree
+            .get_or_init(|| VertexRTree::from_directed_graph(&self.directed_graph));
+        let nearest = rtree
+            .nearest_vertices_by_distance(coord, k)
+            .map_err(CompassAppError::InvalidInput)?;
+        let result = nearest
+            .into_iter()
+            .map(|(vertex, distance)| {
+                let distance = match distance_unit {
+                    Some(du) => DistanceUnit::Meters.convert(&distance, &du),
+                    None => distance,
+                };
+                (vertex.vertex_id, distance)
+            })
+            .collect();
+        Ok(result)
+    }
 }
@@ -0,0 +1,39 @@
+use crate::app::compass::search_orientation::SearchOrientation;
+use routee_compass_core::model::state::feature_schema::FeatureSchema;
+use serde::{Deserialize, Serialize};
+
+/// a summary of the loaded components of an application, for introspection of
+/// a running service. assembled from the loaded components without running a
+/// search, so it is cheap to produce.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HealthReport {
+    /// the loaded road network
+    pub graph: GraphHealth,
+    /// the configured search algorithm
+    pub search_algorithm: serde_json::Value,
+    /// the features of the state model along with their units, in state vector order
+    pub state_features: Vec<FeatureSchema>,
+    /// whether edge traversal costs were precomputed
+    pub edge_cost_cache: bool,
+    /// the plugin pipeline, when reported by a CompassApp
+    pub plugins: Option<PluginHealth>,
+}
+
+/// size and extent of the loaded road network
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct GraphHealth {
+    pub vertex_count: usize,
+    pub edge_count: usize,
+    /// bounding box of the vertices as [min_x, min_y, max_x, max_y], or None for an
+    /// empty graph
+    pub bounds: Option<[f32; 4]>,
+}
+
+/// configuration of the plugin pipeline around the search
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PluginHealth {
+    pub input_plugin_count: usize,
+    pub output_plugin_count: usize,
+    pub parallelism: usize,
+    pub search_orientation: SearchOrientation,
+}
@@ -55,3 +55,47 @@ pub fn collect_features(
     added_features.extend(user_features);
     Ok(added_features)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use routee_compass_core::model::{
+        access::default::no_access_model::NoAccessModel,
+        traversal::default::distance_traversal_model::DistanceTraversalModel, unit::DistanceUnit,
+    };
+
+    #[test]
+    fn test_collect_features_validates_query_features() {
+        let traversal_model = Arc::new(DistanceTraversalModel::new(DistanceUnit::Meters));
+        let access_model = Arc::new(NoAccessModel {});
+
+        // the features written by the traversal model are collected without a query
+        let features = collect_features(
+            &serde_json::json!({}),
+            traversal_model.clone(),
+            access_model.clone(),
+        )
+        .unwrap();
+        let names = features.iter().map(|(n, _)| n.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, vec!["distance"]);
+
+        // the query may override the initial conditions of a feature the model writes
+        let query = serde_json::json!({
+            "state_features": {
+                "distance": { "distance_unit": "kilometers", "initial": 1.0 }
+            }
+        });
+        assert!(collect_features(&query, traversal_model.clone(), access_model.clone()).is_ok());
+
+        // but declaring a feature that no model writes is rejected up front
+        let query = serde_json::json!({
+            "state_features": {
+                "energy": { "energy_unit": "kilowatt_hours", "initial": 0.0 }
+            }
+        });
+        match collect_features(&query, traversal_model, access_model) {
+            Err(StateError::UnknownStateVariableName(name, _)) => assert_eq!(name, "energy"),
+            other => panic!("expected unknown state variable error, found {:?}", other),
+        }
+    }
+}
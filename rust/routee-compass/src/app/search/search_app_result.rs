@@ -2,16 +2,47 @@ use allocative::Allocative;
 
 use routee_compass_core::{
     algorithm::search::{edge_traversal::EdgeTraversal, search_tree_branch::SearchTreeBranch},
-    model::road_network::vertex_id::VertexId,
+    model::{road_network::vertex_id::VertexId, traversal::state::traversal_state::TraversalState},
 };
 
 use std::{collections::HashMap, time::Duration};
 
-#[derive(Allocative)]
+#[derive(Allocative, Clone)]
 pub struct SearchAppResult {
     pub routes: Vec<Vec<EdgeTraversal>>,
+    /// final state of each route, set instead of the routes when only route
+    /// summaries were requested and the routes were not reconstructed
+    pub route_final_states: Option<Vec<TraversalState>>,
     pub trees: Vec<HashMap<VertexId, SearchTreeBranch>>,
     pub search_executed_time: String,
     pub search_runtime: Duration,
+    /// time spent reconstructing routes from the search tree, which is zero
+    /// for search algorithms that reconstruct their routes during the search
+    pub route_runtime: Duration,
     pub iterations: u64,
+    /// number of vertices expanded by the search
+    pub nodes_expanded: usize,
+    /// number of edges traversed from expanded vertices
+    pub edges_relaxed: usize,
+    /// largest number of vertices in the search frontier at once
+    pub max_frontier_size: usize,
+}
+
+impl SearchAppResult {
+    /// a copy of this result without its search trees, which can be far larger
+    /// than its routes.
+    pub fn without_trees(&self) -> SearchAppResult {
+        SearchAppResult {
+            routes: self.routes.clone(),
+            route_final_states: self.route_final_states.clone(),
+            trees: vec![],
+            search_executed_time: self.search_executed_time.clone(),
+            search_runtime: self.search_runtime,
+            route_runtime: self.route_runtime,
+            iterations: self.iterations,
+            nodes_expanded: self.nodes_expanded,
+            edges_relaxed: self.edges_relaxed,
+            max_frontier_size: self.max_frontier_size,
+        }
+    }
 }
@@ -21,7 +21,7 @@ use routee_compass_core::{
     model::unit::{as_f64::AsF64, Distance, DistanceUnit, BASE_DISTANCE_UNIT},
     util::{
         fs::{read_decoders, read_utils},
-        geo::geo_io_utils::read_linestring_text_file,
+        geo::{geo_io_utils::read_linestring_text_file, haversine},
     },
 };
 use rstar::RTree;
@@ -49,7 +49,11 @@ pub struct EdgeRtreeInputPlugin {
 impl InputPlugin for EdgeRtreeInputPlugin {
     /// finds the nearest edge ids to the user-provided origin and destination coordinates.
     /// optionally restricts the search to a subset of road classes tagged by the user.
+    /// if the query already provides an origin edge, snapping is skipped.
     fn process(&self, query: &mut serde_json::Value) -> Result<(), PluginError> {
+        if query.has_origin_edge() {
+            return Ok(());
+        }
         let road_classes = self.road_class_parser.read_query(query).map_err(|e| {
             PluginError::InputError(format!(
                 "Unable to apply EdgeRtree Input Plugin due to:\n\n{}",
@@ -70,6 +74,7 @@ impl InputPlugin for EdgeRtreeInputPlugin {
             &self.vehicle_restrictions,
             &vehicle_parameters,
         )?
+        .map(|(edge_id, _)| edge_id)
         .ok_or_else(|| matching_error(&src_coord, self.tolerance))?;
         let destination_edge_id_option = match dst_coord_option {
             None => Ok(None),
@@ -82,7 +87,7 @@ impl InputPlugin for EdgeRtreeInputPlugin {
                 &self.vehicle_restrictions,
                 &vehicle_parameters,
             )?
-            .map(Some)
+            .map(|(edge_id, _)| Some(edge_id))
             .ok_or_else(|| matching_error(&dst_coord, self.tolerance)),
         }?;
 
@@ -99,6 +104,22 @@ impl InputPlugin for EdgeRtreeInputPlugin {
 }
 
 impl EdgeRtreeInputPlugin {
+    /// finds the nearest edge to a coordinate, along with the coordinate projected
+    /// onto the geometry of that edge, without any road class or vehicle restrictions.
+    /// ties between equally near edges go to the lowest edge id.
+    pub fn nearest_edge(&self, coord: Coord<f32>) -> Result<(EdgeId, Coord<f32>), PluginError> {
+        search(
+            coord,
+            &self.rtree,
+            self.tolerance,
+            &None,
+            &None,
+            &None,
+            &None,
+        )?
+        .ok_or_else(|| matching_error(&coord, self.tolerance))
+    }
+
     pub fn new(
         road_class_file: Option<String>,
         vehicle_restriction_file: Option<String>,
@@ -164,14 +185,17 @@ impl EdgeRtreeInputPlugin {
     }
 }
 
-/// finds the nearest edge to some coordinate, optionally within some distance tolerance
+/// finds the nearest edge to some coordinate, optionally within some distance tolerance.
+/// the coordinate is projected onto the geometry of each candidate edge, and when
+/// several valid edges are equally near, the edge with the lowest id is chosen so that
+/// matching is deterministic.
 ///
 /// # Arguments
 ///
 /// * `coord` - coordinate from which to find a nearest edge
 /// * `rtree` - search tree containing all road network edges
-/// * `tolerance` - distance tolerance argument. if provided, result edge must be within this
-///                 distance/distance unit of the coord provided.
+/// * `tolerance` - distance tolerance argument. if provided, the projected point must be
+///   within this distance/distance unit of the coord provided.
 /// * `road_class_lookup` - optional lookup table for road classes
 /// * `road_classes` - optional set of road classes to restrict search to
 /// * `vehicle_restrictions` - optional lookup table for truck restrictions
@@ -179,7 +203,8 @@ impl EdgeRtreeInputPlugin {
 ///
 /// # Result
 ///
-/// the EdgeId of the nearest edge that meets the tolerance requirement, if provided
+/// the EdgeId of the nearest edge that meets the tolerance requirement, if provided,
+/// along with the coordinate projected onto that edge
 fn search(
     coord: Coord<f32>,
     rtree: &RTree<EdgeRtreeRecord>,
@@ -188,11 +213,25 @@ fn search(
     road_classes: &Option<HashSet<u8>>,
     vehicle_restrictions: &Option<HashMap<EdgeId, Vec<VehicleRestriction>>>,
     vehicle_parameters: &Option<VehicleParameters>,
-) -> Result<Option<EdgeId>, PluginError> {
+) -> Result<Option<(EdgeId, Coord<f32>)>, PluginError> {
     let point = geo::Point(coord);
-    for (record, distance_meters) in rtree.nearest_neighbor_iter_with_distance_2(&point) {
+    let mut best: Option<(f32, EdgeId, Coord<f32>)> = None;
+    for (record, distance_2) in rtree.nearest_neighbor_iter_with_distance_2(&point) {
+        // records arrive nearest first, so any record farther than the best is not a tie
+        if let Some((best_distance_2, _, _)) = best {
+            if distance_2 > best_distance_2 {
+                break;
+            }
+        }
+        let projected = match record.closest_point(&point) {
+            Some(p) => p.0,
+            None => continue,
+        };
+        let distance_meters = haversine::coord_distance_meters(&coord, &projected)
+            .map_err(PluginError::PluginFailed)?
+            .as_f64() as f32;
         if !within_tolerance(tolerance, &distance_meters) {
-            return Ok(None);
+            break;
         }
         let valid_class = match (road_classes, road_class_lookup) {
             (Some(valid_classes), Some(lookup)) => {
@@ -219,11 +258,14 @@ fn search(
             }
             _ => true,
         };
-        if valid_class && valid_truck {
-            return Ok(Some(record.edge_id));
+        let lower_id = best
+            .map(|(_, edge_id, _)| record.edge_id < edge_id)
+            .unwrap_or(true);
+        if valid_class && valid_truck && lower_id {
+            best = Some((distance_2, record.edge_id, projected));
         }
     }
-    Ok(None)
+    Ok(best.map(|(_, edge_id, projected)| (edge_id, projected)))
 }
 
 /// helper to build a matching error response
@@ -248,3 +290,77 @@ fn within_tolerance(tolerance: Option<(Distance, DistanceUnit)>, distance_meters
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geo::{coord, line_string};
+
+    fn build_plugin() -> EdgeRtreeInputPlugin {
+        let records = vec![
+            // a single-point geometry
+            EdgeRtreeRecord::new(EdgeId(0), line_string![(x: 5.0, y: 5.0)]),
+            // two edges equally far from (1, 0)
+            EdgeRtreeRecord::new(EdgeId(2), line_string![(x: 0.0, y: 1.0), (x: 2.0, y: 1.0)]),
+            EdgeRtreeRecord::new(
+                EdgeId(1),
+                line_string![(x: 0.0, y: -1.0), (x: 2.0, y: -1.0)],
+            ),
+            // a long edge whose midpoint is far from points near its start
+            EdgeRtreeRecord::new(
+                EdgeId(3),
+                line_string![(x: 10.0, y: 0.0), (x: 20.0, y: 0.0)],
+            ),
+            EdgeRtreeRecord::new(
+                EdgeId(4),
+                line_string![(x: 12.0, y: 0.5), (x: 12.2, y: 0.5)],
+            ),
+        ];
+        EdgeRtreeInputPlugin {
+            rtree: RTree::bulk_load(records),
+            tolerance: None,
+            road_class_lookup: None,
+            road_class_parser: RoadClassParser::default(),
+            vehicle_restrictions: None,
+        }
+    }
+
+    #[test]
+    fn test_nearest_edge_projection() {
+        let plugin = build_plugin();
+        let (edge_id, projected) = plugin.nearest_edge(coord! {x: 0.5, y: 1.2}).unwrap();
+        assert_eq!(edge_id, EdgeId(2));
+        assert_eq!(projected, coord! {x: 0.5, y: 1.0});
+
+        // the point is nearest to the line of edge 3, not to its midpoint
+        let (edge_id, projected) = plugin.nearest_edge(coord! {x: 11.0, y: 0.1}).unwrap();
+        assert_eq!(edge_id, EdgeId(3));
+        assert_eq!(projected, coord! {x: 11.0, y: 0.0});
+
+        // a single-point geometry projects onto its point
+        let (edge_id, projected) = plugin.nearest_edge(coord! {x: 5.1, y: 5.0}).unwrap();
+        assert_eq!(edge_id, EdgeId(0));
+        assert_eq!(projected, coord! {x: 5.0, y: 5.0});
+    }
+
+    #[test]
+    fn test_nearest_edge_tie_picks_lower_id() {
+        let plugin = build_plugin();
+        let (edge_id, projected) = plugin.nearest_edge(coord! {x: 1.0, y: 0.0}).unwrap();
+        assert_eq!(edge_id, EdgeId(1));
+        assert_eq!(projected, coord! {x: 1.0, y: -1.0});
+    }
+
+    #[test]
+    fn test_nearest_edge_tolerance() {
+        let plugin = EdgeRtreeInputPlugin {
+            tolerance: Some((Distance::new(1.0), DistanceUnit::Kilometers)),
+            ..build_plugin()
+        };
+        // about 11 kilometers from edge 3 and well beyond the tolerance
+        let result = plugin.nearest_edge(coord! {x: 11.0, y: 0.1});
+        assert!(matches!(result, Err(PluginError::PluginFailed(_))));
+        let (edge_id, _) = plugin.nearest_edge(coord! {x: 11.0, y: 0.001}).unwrap();
+        assert_eq!(edge_id, EdgeId(3));
+    }
+}
@@ -1,4 +1,4 @@
-use geo::{Centroid, LineString, Point};
+use geo::{Closest, ClosestPoint, LineString, Point};
 use routee_compass_core::model::road_network::edge_id::EdgeId;
 use rstar::{PointDistance, RTreeObject, AABB};
 
@@ -11,6 +11,19 @@ impl EdgeRtreeRecord {
     pub fn new(edge_id: EdgeId, geometry: LineString<f32>) -> EdgeRtreeRecord {
         EdgeRtreeRecord { edge_id, geometry }
     }
+
+    /// projects a point onto the geometry of this edge. a geometry with a single
+    /// point, or with repeated points only, projects onto its first point.
+    ///
+    /// # Returns
+    ///
+    /// the closest point along the geometry, or None if the geometry is empty
+    pub fn closest_point(&self, point: &Point<f32>) -> Option<Point<f32>> {
+        match self.geometry.closest_point(point) {
+            Closest::Intersection(p) | Closest::SinglePoint(p) => Some(p),
+            Closest::Indeterminate => self.geometry.points().next(),
+        }
+    }
 }
 
 impl RTreeObject for EdgeRtreeRecord {
@@ -21,8 +34,8 @@ impl RTreeObject for EdgeRtreeRecord {
 }
 
 impl PointDistance for EdgeRtreeRecord {
-    /// compares query nearness via the "centroid" of this LineString,
-    /// the midpoint of the bounding box of the line.
+    /// compares query nearness via the point of this LineString that is closest
+    /// to the query point.
     ///
     /// # Arguments
     ///
@@ -30,21 +43,19 @@ impl PointDistance for EdgeRtreeRecord {
     ///
     /// # Returns
     ///
-    /// * distance in meters (assumes points are in WGS84)
+    /// * squared euclidean distance in degrees (assumes points are in WGS84), which
+    ///   is infinite for an empty geometry
     fn distance_2(&self, point: &Point<f32>) -> f32 {
-        let this_point = self
-            .geometry
-            .centroid()
-            .unwrap_or_else(|| panic!("empty linestring in geometry file"));
         // as noted in the comments for PointDistance, this should return the squared distance.
         // haversine *should* work but squared haversine in meters is giving weird results for
         // the vertex rtree plugin, so, i'm reverting this to euclidean for now. -rjf 2023-12-01
-        // let distance = haversine::coord_distance_meters(this_point.0, point.0)
-        //     .unwrap_or(Distance::new(f64::MAX))
-        //     .as_f64();
-        // distance * distance
-        let dx = this_point.x() - point.x();
-        let dy = this_point.y() - point.y();
-        dx * dx + dy * dy
+        match self.closest_point(point) {
+            None => f32::INFINITY,
+            Some(this_point) => {
+                let dx = this_point.x() - point.x();
+                let dy = this_point.y() - point.y();
+                dx * dx + dy * dy
+            }
+        }
     }
 }
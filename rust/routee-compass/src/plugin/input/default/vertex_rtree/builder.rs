@@ -10,10 +10,17 @@ use crate::{
     plugin::input::input_plugin::InputPlugin,
 };
 
-use super::plugin::RTreePlugin;
+use super::{
+    od_separation_policy::OdSeparationPolicy, plugin::RTreePlugin, snap_ranking::SnapRanking,
+};
 
 pub struct VertexRTreeBuilder {}
 
+impl VertexRTreeBuilder {
+    /// default decimal places of snap cache coordinates, about 1 meter in degrees
+    const DEFAULT_SNAP_CACHE_PRECISION: i32 = 5;
+}
+
 impl InputPluginBuilder for VertexRTreeBuilder {
     fn build(
         &self,
@@ -25,8 +32,45 @@ impl InputPluginBuilder for VertexRTreeBuilder {
             parameters.get_config_serde_optional::<Distance>(&"distance_tolerance", &parent_key)?;
         let distance_unit =
             parameters.get_config_serde_optional::<DistanceUnit>(&"distance_unit", &parent_key)?;
-        let rtree = RTreePlugin::new(&vertex_path, tolerance_distance, distance_unit)
-            .map_err(CompassConfigurationError::PluginError)?;
+        let bounds_margin =
+            parameters.get_config_serde_optional::<Distance>(&"bounds_margin", &parent_key)?;
+        let initial_snap_radius = parameters
+            .get_config_serde_optional::<Distance>(&"initial_snap_radius", &parent_key)?;
+        let max_snap_radius =
+            parameters.get_config_serde_optional::<Distance>(&"max_snap_radius", &parent_key)?;
+        let min_od_separation =
+            parameters.get_config_serde_optional::<Distance>(&"min_od_separation", &parent_key)?;
+        let od_separation_policy = parameters
+            .get_config_serde_optional::<OdSeparationPolicy>(&"od_separation_policy", &parent_key)?
+            .unwrap_or_default();
+        let snap_cache_size =
+            parameters.get_config_serde_optional::<usize>(&"snap_cache_size", &parent_key)?;
+        let snap_cache_precision = parameters
+            .get_config_serde_optional::<i32>(&"snap_cache_precision", &parent_key)?
+            .unwrap_or(Self::DEFAULT_SNAP_CACHE_PRECISION);
+        let snap_ranking = parameters
+            .get_config_serde_optional::<SnapRanking>(&"snap_ranking", &parent_key)?
+            .unwrap_or_default();
+        let edge_path = match snap_ranking {
+            SnapRanking::Geometric => None,
+            SnapRanking::Network => {
+                Some(parameters.get_config_path(&"edges_input_file", &parent_key)?)
+            }
+        };
+        let rtree = RTreePlugin::new(
+            &vertex_path,
+            tolerance_distance,
+            distance_unit,
+            bounds_margin,
+        )
+        .and_then(|p| p.with_snap_radius(initial_snap_radius, max_snap_radius))
+        .and_then(|p| p.with_min_od_separation(min_od_separation, od_separation_policy))
+        .and_then(|p| p.with_snap_ranking(snap_ranking, edge_path.as_deref()))
+        .and_then(|p| match snap_cache_size {
+            None => Ok(p),
+            Some(size) => p.with_snap_cache(size, snap_cache_precision),
+        })
+        .map_err(CompassConfigurationError::PluginError)?;
         let m: Arc<dyn InputPlugin> = Arc::new(rtree);
         Ok(m)
     }
@@ -1,2 +1,4 @@
 pub mod builder;
+pub mod od_separation_policy;
 pub mod plugin;
+pub mod snap_ranking;
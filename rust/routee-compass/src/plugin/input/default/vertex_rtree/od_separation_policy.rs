@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+/// declares how the vertex RTree plugin handles a query whose origin and
+/// destination coordinates are closer together than the minimum separation.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OdSeparationPolicy {
+    /// fail the query with an error
+    #[default]
+    Reject,
+    /// snap both coordinates to the origin vertex, which produces an empty route
+    EmptyRoute,
+}
@@ -1,15 +1,40 @@
 use std::path::Path;
 
+use super::od_separation_policy::OdSeparationPolicy;
+use super::snap_ranking::SnapRanking;
+use crate::plugin::input::input_field::InputField;
 use crate::plugin::input::input_json_extensions::InputJsonExtensions;
 use crate::plugin::input::input_plugin::InputPlugin;
 use crate::plugin::plugin_error::PluginError;
 use geo::{coord, Coord};
 use routee_compass_core::{
-    model::unit::{Distance, DistanceUnit, BASE_DISTANCE_UNIT},
-    model::{property::vertex::Vertex, road_network::graph::Graph},
-    util::{fs::read_utils, geo::haversine},
+    model::unit::{as_f64::AsF64, Distance, DistanceUnit, BASE_DISTANCE_UNIT},
+    model::{
+        property::{edge::Edge, vertex::Vertex},
+        road_network::{graph::Graph, vertex_id::VertexId},
+    },
+    util::{
+        cache_policy::float_cache_policy::{FloatCachePolicy, FloatCachePolicyConfig},
+        fs::read_utils,
+        geo::{coord::bounding_box, haversine},
+    },
 };
-use rstar::{PointDistance, RTree, RTreeObject, AABB};
+use rstar::{
+    primitives::{GeomWithData, Line},
+    PointDistance, RTree, RTreeObject, AABB,
+};
+
+/// bounding box (min, max) of the graph vertices along with the margin distance
+/// a query coordinate may lie outside of it.
+type VertexBounds = ((Coord<f32>, Coord<f32>), (Distance, DistanceUnit));
+
+/// the straight-line segment between the endpoints of an edge, with the ids of
+/// its source and destination vertices.
+type EdgeSegment = GeomWithData<Line<Coord<f32>>, (VertexId, VertexId)>;
+
+/// approximate length of one degree of latitude, used to size the RTree search
+/// window for a snap radius before checking candidates by haversine distance.
+const METERS_PER_DEGREE: f64 = 111_320.0;
 
 pub struct RTreeVertex {
     vertex: Vertex,
@@ -43,6 +68,11 @@ impl VertexRTree {
         Self::new(vertices)
     }
 
+    /// all vertices in the RTree, in no particular order
+    pub fn vertices(&self) -> impl Iterator<Item = &Vertex> {
+        self.rtree.iter().map(|rtv| &rtv.vertex)
+    }
+
     pub fn nearest_vertex(&self, point: Coord<f32>) -> Option<&Vertex> {
         match self.rtree.nearest_neighbor(&point) {
             Some(rtree_vertex) => Some(&rtree_vertex.vertex),
@@ -57,6 +87,110 @@ impl VertexRTree {
             .map(|rtv| &rtv.vertex)
             .collect()
     }
+
+    /// finds the `k` nearest vertices to a point by haversine distance. the RTree
+    /// orders vertices by planar distance in degrees, which differs from haversine
+    /// order away from the equator, so vertices are read from the RTree until the
+    /// shortest haversine distance the next vertex could have exceeds that of the
+    /// `k`th nearest vertex found so far.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - the coordinate to search from
+    /// * `k` - number of vertices to return
+    ///
+    /// # Returns
+    ///
+    /// up to `k` vertices along with their distance in meters, nearest first, or an
+    /// error if a distance could not be computed
+    pub fn nearest_vertices_by_distance(
+        &self,
+        point: Coord<f32>,
+        k: usize,
+    ) -> Result<Vec<(&Vertex, Distance)>, String> {
+        let mut nearest: Vec<(&Vertex, Distance)> = Vec::with_capacity(k + 1);
+        if k == 0 {
+            return Ok(nearest);
+        }
+        for rtv in self.rtree.nearest_neighbor_iter(&point) {
+            if let Some((_, kth_distance)) = nearest.get(k - 1) {
+                // a degree of longitude is shortest at the latitude furthest from
+                // the equator that this vertex could lie on
+                let degrees = (rtv.distance_2(&point) as f64).sqrt();
+                let max_lat = (point.y.abs() as f64 + degrees).min(89.0);
+                let min_meters = degrees * METERS_PER_DEGREE * max_lat.to_radians().cos();
+                if min_meters > kth_distance.as_f64() {
+                    break;
+                }
+            }
+            let distance = haversine::coord_distance_meters(&point, &rtv.vertex.coordinate)?;
+            let index = nearest.partition_point(|(_, d)| *d <= distance);
+            if index < k {
+                nearest.insert(index, (&rtv.vertex, distance));
+                nearest.truncate(k);
+            }
+        }
+        Ok(nearest)
+    }
+
+    /// finds the nearest vertex to a point within a search radius that starts at
+    /// `initial_radius` and doubles, up to `max_radius`, until a vertex is found.
+    /// this keeps the scan small where vertices are dense while still reaching
+    /// vertices in sparse regions.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - the coordinate to snap
+    /// * `initial_radius` - first search radius
+    /// * `max_radius` - largest search radius
+    /// * `distance_unit` - unit of both radii
+    ///
+    /// # Returns
+    ///
+    /// the nearest vertex within the largest radius searched, None if no vertex lies
+    /// within `max_radius`, or an error if a distance could not be computed
+    pub fn nearest_vertex_within_radius(
+        &self,
+        point: Coord<f32>,
+        initial_radius: Distance,
+        max_radius: Distance,
+        distance_unit: &DistanceUnit,
+    ) -> Result<Option<&Vertex>, String> {
+        let mut radius = initial_radius;
+        loop {
+            let radius_meters = distance_unit.convert(&radius, &DistanceUnit::Meters);
+            if let Some(vertex) = self.nearest_vertex_within_meters(point, radius_meters)? {
+                return Ok(Some(vertex));
+            }
+            if radius >= max_radius {
+                return Ok(None);
+            }
+            radius = Distance::new(radius.as_f64() * 2.0).min(max_radius);
+        }
+    }
+
+    fn nearest_vertex_within_meters(
+        &self,
+        point: Coord<f32>,
+        radius_meters: Distance,
+    ) -> Result<Option<&Vertex>, String> {
+        // a degree of longitude shrinks toward the poles, so size the window using
+        // the latitude furthest from the equator that the radius could reach
+        let lat_degrees = radius_meters.as_f64() / METERS_PER_DEGREE;
+        let max_lat = (point.y.abs() as f64 + lat_degrees).min(89.0);
+        let lon_degrees = lat_degrees / max_lat.to_radians().cos();
+        let window = lat_degrees.max(lon_degrees) as f32;
+
+        let mut nearest: Option<(&Vertex, Distance)> = None;
+        for rtv in self.rtree.locate_within_distance(point, window * window) {
+            let distance = haversine::coord_distance_meters(&point, &rtv.vertex.coordinate)?;
+            let is_nearer = nearest.is_none_or(|(_, d)| distance < d);
+            if distance <= radius_meters && is_nearer {
+                nearest = Some((&rtv.vertex, distance));
+            }
+        }
+        Ok(nearest.map(|(vertex, _)| vertex))
+    }
 }
 
 impl RTreeObject for RTreeVertex {
@@ -90,6 +224,109 @@ impl PointDistance for RTreeVertex {
 pub struct RTreePlugin {
     vertex_rtree: VertexRTree,
     tolerance: Option<(Distance, DistanceUnit)>,
+    bounds: Option<VertexBounds>,
+    distance_unit: DistanceUnit,
+    initial_snap_radius: Option<Distance>,
+    max_snap_radius: Option<Distance>,
+    min_od_separation: Option<(Distance, OdSeparationPolicy)>,
+    snap_cache: Option<SnapCache>,
+    edge_index: Option<EdgeSnapIndex>,
+}
+
+/// a cache of recently snapped coordinates, keyed by the quantized coordinate and
+/// the snap radius, holding the id of the vertex each was snapped to. since the
+/// graph is static, entries never become invalid.
+struct SnapCache {
+    cache: FloatCachePolicy,
+    /// the vertices of the RTree, sorted by vertex id
+    vertices: Box<[Vertex]>,
+}
+
+impl SnapCache {
+    /// precision of the snap radius in the cache key, in decimal places
+    const RADIUS_PRECISION: i32 = 6;
+
+    fn key(coord: &Coord<f32>, snap_radius: &Option<(Distance, Distance)>) -> Vec<f64> {
+        let mut key = vec![coord.x as f64, coord.y as f64];
+        if let Some((initial, max)) = snap_radius {
+            key.extend([initial.as_f64(), max.as_f64()]);
+        }
+        key
+    }
+
+    fn get(&self, key: &[f64]) -> Result<Option<&Vertex>, PluginError> {
+        let vertex_id = match self.cache.get(key) {
+            Ok(Some(vertex_id)) => vertex_id as usize,
+            Ok(None) => return Ok(None),
+            Err(e) => return Err(PluginError::InternalError(e.to_string())),
+        };
+        let vertex = self
+            .vertices
+            .binary_search_by_key(&vertex_id, |v| v.vertex_id.0)
+            .ok()
+            .map(|index| &self.vertices[index]);
+        Ok(vertex)
+    }
+
+    fn update(&self, key: &[f64], vertex: &Vertex) -> Result<(), PluginError> {
+        self.cache
+            .update(key, vertex.vertex_id.0 as f64)
+            .map_err(|e| PluginError::InternalError(e.to_string()))
+    }
+}
+
+/// an RTree of the straight-line segments between the endpoints of each edge,
+/// used to rank snap candidates by network distance.
+struct EdgeSnapIndex {
+    rtree: RTree<EdgeSegment>,
+    /// the vertices of the RTree, sorted by vertex id
+    vertices: Box<[Vertex]>,
+}
+
+impl EdgeSnapIndex {
+    fn get_vertex(&self, vertex_id: &VertexId) -> Result<&Vertex, PluginError> {
+        self.vertices
+            .binary_search_by_key(&vertex_id.0, |v| v.vertex_id.0)
+            .map(|index| &self.vertices[index])
+            .map_err(|_| {
+                PluginError::PluginFailed(format!("edge references missing vertex {}", vertex_id))
+            })
+    }
+
+    /// finds the edge nearest to a coordinate and the vertex at the end of that edge
+    /// with the shortest network distance from the coordinate.
+    ///
+    /// # Arguments
+    ///
+    /// * `coord` - the coordinate to snap
+    ///
+    /// # Returns
+    ///
+    /// the vertex along with the distance in meters from the coordinate to the edge,
+    /// None if there are no edges, or an error if a distance could not be computed
+    fn nearest_vertex_by_network_distance(
+        &self,
+        coord: Coord<f32>,
+    ) -> Result<Option<(&Vertex, Distance)>, PluginError> {
+        let segment = match self.rtree.nearest_neighbor(&coord) {
+            None => return Ok(None),
+            Some(segment) => segment,
+        };
+        let projected = segment.geom().nearest_point(&coord);
+        let access_meters = haversine::coord_distance_meters(&coord, &projected)
+            .map_err(PluginError::PluginFailed)?;
+        let (src_id, dst_id) = segment.data;
+        let src = self.get_vertex(&src_id)?;
+        let dst = self.get_vertex(&dst_id)?;
+        // the distance to the edge is shared by both endpoints, so they are ranked by
+        // the distance along the edge from the projected point
+        let src_meters = haversine::coord_distance_meters(&projected, &src.coordinate)
+            .map_err(PluginError::PluginFailed)?;
+        let dst_meters = haversine::coord_distance_meters(&projected, &dst.coordinate)
+            .map_err(PluginError::PluginFailed)?;
+        let vertex = if dst_meters < src_meters { dst } else { src };
+        Ok(Some((vertex, access_meters)))
+    }
 }
 
 impl RTreePlugin {
@@ -99,7 +336,9 @@ impl RTreePlugin {
     ///
     /// * `vertex_file` - file containing vertices
     /// * `tolerance_distance` - optional max distance to nearest vertex (assumed infinity if not included)
-    /// * `distance_unit` - distance unit for tolerance, assumed BASE_DISTANCE_UNIT if not provided
+    /// * `distance_unit` - distance unit for tolerance and bounds margin, assumed BASE_DISTANCE_UNIT if not provided
+    /// * `bounds_margin` - optional max distance a coordinate may lie outside of the vertex bounding box
+    ///   (no bounds check if not included)
     ///
     /// # Returns
     ///
@@ -108,21 +347,273 @@ impl RTreePlugin {
         vertex_file: &Path,
         tolerance_distance: Option<Distance>,
         distance_unit: Option<DistanceUnit>,
+        bounds_margin: Option<Distance>,
     ) -> Result<Self, PluginError> {
         let vertices: Box<[Vertex]> =
             read_utils::from_csv(&vertex_file, true, None).map_err(PluginError::CsvReadError)?;
-        let vertex_rtree = VertexRTree::new(vertices.to_vec());
-        let tolerance = match (tolerance_distance, distance_unit) {
-            (None, None) => None,
-            (None, Some(_)) => None,
-            (Some(t), None) => Some((t, BASE_DISTANCE_UNIT)),
-            (Some(t), Some(u)) => Some((t, u)),
+        let unit = distance_unit.unwrap_or(BASE_DISTANCE_UNIT);
+        let tolerance = tolerance_distance.map(|t| (t, unit));
+        let bounds = match bounds_margin {
+            None => None,
+            Some(margin) => {
+                let bbox =
+                    bounding_box(vertices.iter().map(|v| v.coordinate.0)).ok_or_else(|| {
+                        PluginError::PluginFailed(String::from(
+                            "cannot compute bounds of empty vertex file",
+                        ))
+                    })?;
+                Some((bbox, (margin, unit)))
+            }
         };
+        let vertex_rtree = VertexRTree::new(vertices.to_vec());
         Ok(RTreePlugin {
             vertex_rtree,
             tolerance,
+            bounds,
+            distance_unit: unit,
+            initial_snap_radius: None,
+            max_snap_radius: None,
+            min_od_separation: None,
+            snap_cache: None,
+            edge_index: None,
         })
     }
+
+    /// sets how candidate vertices are ranked when snapping a coordinate. network
+    /// ranking projects the coordinate onto the nearest edge, by the straight line
+    /// between its endpoints, and snaps to the endpoint with the shortest distance
+    /// along the edge. this avoids snapping across a barrier, such as a river, to a
+    /// vertex that is geometrically close but far away in the network.
+    ///
+    /// # Arguments
+    ///
+    /// * `snap_ranking` - how to rank candidate vertices
+    /// * `edge_file` - file containing the graph edges, required for network ranking
+    ///
+    /// # Returns
+    ///
+    /// the plugin with the ranking set, or an error if network ranking is requested
+    /// without an edge file or the edges could not be read
+    pub fn with_snap_ranking(
+        mut self,
+        snap_ranking: SnapRanking,
+        edge_file: Option<&Path>,
+    ) -> Result<Self, PluginError> {
+        self.edge_index = match (snap_ranking, edge_file) {
+            (SnapRanking::Geometric, _) => None,
+            (SnapRanking::Network, None) => {
+                return Err(PluginError::PluginFailed(String::from(
+                    "network snap ranking requires an edges input file",
+                )))
+            }
+            (SnapRanking::Network, Some(edge_file)) => {
+                let edges: Box<[Edge]> = read_utils::from_csv(&edge_file, true, None)
+                    .map_err(PluginError::CsvReadError)?;
+                let mut vertices = self.vertex_rtree.vertices().cloned().collect::<Vec<_>>();
+                vertices.sort_by_key(|v| v.vertex_id.0);
+                let mut index = EdgeSnapIndex {
+                    rtree: RTree::new(),
+                    vertices: vertices.into_boxed_slice(),
+                };
+                let segments = edges
+                    .iter()
+                    .map(|edge| {
+                        let src = index.get_vertex(&edge.src_vertex_id)?;
+                        let dst = index.get_vertex(&edge.dst_vertex_id)?;
+                        let line = Line::new(src.coordinate.0, dst.coordinate.0);
+                        Ok(GeomWithData::new(
+                            line,
+                            (edge.src_vertex_id, edge.dst_vertex_id),
+                        ))
+                    })
+                    .collect::<Result<Vec<_>, PluginError>>()?;
+                index.rtree = RTree::bulk_load(segments);
+                Some(index)
+            }
+        };
+        Ok(self)
+    }
+
+    /// sets up a cache of recently snapped coordinates, so that repeated queries at
+    /// nearby coordinates skip the RTree lookup. coordinates are rounded to
+    /// `precision` decimal places to form the cache key, so all coordinates that
+    /// round to the same key share the vertex snapped for the first of them.
+    ///
+    /// # Arguments
+    ///
+    /// * `cache_size` - max number of coordinates held, evicting the least recently used
+    /// * `precision` - decimal places of the coordinates in the cache key
+    ///
+    /// # Returns
+    ///
+    /// the plugin with the cache set, or an error if the cache size is zero or the
+    /// precision is out of range
+    pub fn with_snap_cache(
+        mut self,
+        cache_size: usize,
+        precision: i32,
+    ) -> Result<Self, PluginError> {
+        let config = FloatCachePolicyConfig {
+            cache_size,
+            key_precisions: vec![
+                precision,
+                precision,
+                SnapCache::RADIUS_PRECISION,
+                SnapCache::RADIUS_PRECISION,
+            ],
+        };
+        let cache = FloatCachePolicy::from_config(config)
+            .map_err(|e| PluginError::PluginFailed(format!("invalid snap cache: {}", e)))?;
+        let mut vertices = self.vertex_rtree.vertices().cloned().collect::<Vec<_>>();
+        vertices.sort_by_key(|v| v.vertex_id.0);
+        self.snap_cache = Some(SnapCache {
+            cache,
+            vertices: vertices.into_boxed_slice(),
+        });
+        Ok(self)
+    }
+
+    /// sets the default snap radius, in the distance unit of the plugin. when a max
+    /// radius is set, coordinates are snapped by searching within a radius that starts
+    /// at the initial radius and doubles until a vertex is found or the max radius is
+    /// exceeded. queries may override either value. if only a max radius is set, it is
+    /// also used as the initial radius.
+    ///
+    /// # Arguments
+    ///
+    /// * `initial_snap_radius` - optional first search radius
+    /// * `max_snap_radius` - optional largest search radius
+    ///
+    /// # Returns
+    ///
+    /// the plugin with the snap radius set, or an error if the radii are invalid
+    pub fn with_snap_radius(
+        mut self,
+        initial_snap_radius: Option<Distance>,
+        max_snap_radius: Option<Distance>,
+    ) -> Result<Self, PluginError> {
+        resolve_snap_radius(initial_snap_radius, max_snap_radius)?;
+        self.initial_snap_radius = initial_snap_radius;
+        self.max_snap_radius = max_snap_radius;
+        Ok(self)
+    }
+
+    /// sets the minimum distance between the origin and destination coordinates of a
+    /// query, in the distance unit of the plugin. queries with closer coordinates are
+    /// handled by the separation policy instead of being snapped separately, which
+    /// avoids searching between nearly identical points.
+    ///
+    /// # Arguments
+    ///
+    /// * `min_od_separation` - optional minimum origin-destination distance
+    /// * `policy` - how to handle queries below the minimum separation
+    ///
+    /// # Returns
+    ///
+    /// the plugin with the minimum separation set, or an error if it is negative
+    pub fn with_min_od_separation(
+        mut self,
+        min_od_separation: Option<Distance>,
+        policy: OdSeparationPolicy,
+    ) -> Result<Self, PluginError> {
+        if let Some(min) = min_od_separation {
+            if min < Distance::ZERO {
+                return Err(PluginError::PluginFailed(format!(
+                    "min_od_separation must be non-negative, found {}",
+                    min
+                )));
+            }
+        }
+        self.min_od_separation = min_od_separation.map(|min| (min, policy));
+        Ok(self)
+    }
+
+    /// applies the separation policy when the origin and destination coordinates are
+    /// closer than the minimum separation.
+    ///
+    /// # Returns
+    ///
+    /// true if the destination should be snapped to the origin vertex, or an error
+    /// if the query is rejected
+    fn check_od_separation(&self, src: &Coord<f32>, dst: &Coord<f32>) -> Result<bool, PluginError> {
+        match &self.min_od_separation {
+            None => Ok(false),
+            Some((min, policy)) => {
+                let distance_meters = haversine::coord_distance_meters(src, dst)
+                    .map_err(PluginError::PluginFailed)?;
+                let distance = DistanceUnit::Meters.convert(&distance_meters, &self.distance_unit);
+                if &distance < min {
+                    match policy {
+                        OdSeparationPolicy::Reject => Err(PluginError::InputError(format!(
+                            "origin {:?} and destination {:?} are {} {} apart, below the minimum separation of {} {}",
+                            src, dst, distance, self.distance_unit, min, self.distance_unit
+                        ))),
+                        OdSeparationPolicy::EmptyRoute => Ok(true),
+                    }
+                } else {
+                    Ok(false)
+                }
+            }
+        }
+    }
+
+    /// finds the vertex to snap a coordinate to, either the nearest vertex overall or,
+    /// when a snap radius applies, the nearest vertex found by an expanding radius search.
+    /// when a snap cache is set, it is consulted first and updated with the result.
+    fn snap(
+        &self,
+        coord: Coord<f32>,
+        snap_radius: &Option<(Distance, Distance)>,
+    ) -> Result<Option<&Vertex>, PluginError> {
+        let snap_cache = match &self.snap_cache {
+            None => return self.snap_rtree(coord, snap_radius),
+            Some(snap_cache) => snap_cache,
+        };
+        let key = SnapCache::key(&coord, snap_radius);
+        if let Some(vertex) = snap_cache.get(&key)? {
+            return Ok(Some(vertex));
+        }
+        let vertex = self.snap_rtree(coord, snap_radius)?;
+        if let Some(v) = vertex {
+            snap_cache.update(&key, v)?;
+        }
+        Ok(vertex)
+    }
+
+    /// finds the vertex to snap a coordinate to using the RTree. with network ranking,
+    /// a snap radius limits the distance from the coordinate to the nearest edge.
+    fn snap_rtree(
+        &self,
+        coord: Coord<f32>,
+        snap_radius: &Option<(Distance, Distance)>,
+    ) -> Result<Option<&Vertex>, PluginError> {
+        if let Some(edge_index) = &self.edge_index {
+            let nearest = edge_index.nearest_vertex_by_network_distance(coord)?;
+            return match (nearest, snap_radius) {
+                (None, _) => Ok(None),
+                (Some((vertex, _)), None) => Ok(Some(vertex)),
+                (Some((vertex, access_meters)), Some((_, max))) => {
+                    let max_meters = self.distance_unit.convert(max, &DistanceUnit::Meters);
+                    Ok(Some(vertex).filter(|_| access_meters <= max_meters))
+                }
+            };
+        }
+        match snap_radius {
+            None => Ok(self.vertex_rtree.nearest_vertex(coord)),
+            Some((initial, max)) => self
+                .vertex_rtree
+                .nearest_vertex_within_radius(coord, *initial, *max, &self.distance_unit)
+                .map_err(PluginError::PluginFailed),
+        }
+    }
+
+    /// describes the snap radius for error messages
+    fn snap_radius_description(&self, snap_radius: &Option<(Distance, Distance)>) -> String {
+        match snap_radius {
+            None => String::from(""),
+            Some((_, max)) => format!(" within {} {}", max, self.distance_unit),
+        }
+    }
 }
 
 impl InputPlugin for RTreePlugin {
@@ -130,20 +621,43 @@ impl InputPlugin for RTreePlugin {
     ///
     /// # Arguments
     ///
-    /// * `query` - search query assumed to have at least an origin coordinate entry
+    /// * `query` - search query assumed to have at least an origin coordinate entry,
+    ///   unless an origin edge is provided, in which case snapping is skipped
     ///
     /// # Returns
     ///
     /// * either vertex ids for the nearest coordinates to the the origin (and optionally destination),
     ///   or, an error if not found or not within tolerance
     fn process(&self, query: &mut serde_json::Value) -> Result<(), PluginError> {
+        if query.has_origin_edge() {
+            return Ok(());
+        }
         let src_coord = query.get_origin_coordinate()?;
         let dst_coord_option = query.get_destination_coordinate()?;
+        validate_bounds(&src_coord, &self.bounds)?;
+        if let Some(dst_coord) = &dst_coord_option {
+            validate_bounds(dst_coord, &self.bounds)?;
+        }
+        let snap_destination_to_origin = match &dst_coord_option {
+            Some(dst_coord) => self.check_od_separation(&src_coord, dst_coord)?,
+            None => false,
+        };
+        let snap_radius = resolve_snap_radius(
+            query
+                .get_initial_snap_radius()?
+                .map(Distance::new)
+                .or(self.initial_snap_radius),
+            query
+                .get_max_snap_radius()?
+                .map(Distance::new)
+                .or(self.max_snap_radius),
+        )?;
 
-        let src_vertex = self.vertex_rtree.nearest_vertex(src_coord).ok_or_else(|| {
+        let src_vertex = self.snap(src_coord, &snap_radius)?.ok_or_else(|| {
             PluginError::PluginFailed(format!(
-                "nearest vertex not found for origin coordinate {:?}",
-                src_coord
+                "nearest vertex not found for origin coordinate {:?}{}",
+                src_coord,
+                self.snap_radius_description(&snap_radius)
             ))
         })?;
 
@@ -152,11 +666,17 @@ impl InputPlugin for RTreePlugin {
 
         match dst_coord_option {
             None => {}
+            Some(_) if snap_destination_to_origin => {
+                // the points are effectively identical, so the destination shares the
+                // origin vertex and the search returns an empty route
+                query.add_destination_vertex(src_vertex.vertex_id)?;
+            }
             Some(dst_coord) => {
-                let dst_vertex = self.vertex_rtree.nearest_vertex(dst_coord).ok_or_else(|| {
+                let dst_vertex = self.snap(dst_coord, &snap_radius)?.ok_or_else(|| {
                     PluginError::PluginFailed(format!(
-                        "nearest vertex not found for destination coordinate {:?}",
-                        dst_coord
+                        "nearest vertex not found for destination coordinate {:?}{}",
+                        dst_coord,
+                        self.snap_radius_description(&snap_radius)
                     ))
                 })?;
                 validate_tolerance(&dst_coord, &dst_vertex.coordinate, &self.tolerance)?;
@@ -168,6 +688,46 @@ impl InputPlugin for RTreePlugin {
     }
 }
 
+/// combines an initial and max snap radius into the radii of an expanding search.
+/// if only a max radius is provided, it is also used as the initial radius.
+///
+/// # Arguments
+///
+/// * `initial` - optional first search radius
+/// * `max` - optional largest search radius
+///
+/// # Returns
+///
+/// * the (initial, max) radii, None if no max radius is provided, or an error if an
+///   initial radius is provided without a max radius or the radii are out of order
+fn resolve_snap_radius(
+    initial: Option<Distance>,
+    max: Option<Distance>,
+) -> Result<Option<(Distance, Distance)>, PluginError> {
+    match (initial, max) {
+        (None, None) => Ok(None),
+        (Some(_), None) => Err(PluginError::InputError(format!(
+            "{} requires {} to be set",
+            InputField::InitialSnapRadius,
+            InputField::MaxSnapRadius
+        ))),
+        (initial, Some(max)) => {
+            let initial = initial.unwrap_or(max);
+            if initial <= Distance::ZERO || initial > max {
+                Err(PluginError::InputError(format!(
+                    "{} must be positive and no greater than {}, found {} and {}",
+                    InputField::InitialSnapRadius,
+                    InputField::MaxSnapRadius,
+                    initial,
+                    max
+                )))
+            } else {
+                Ok(Some((initial, max)))
+            }
+        }
+    }
+}
+
 /// confirms that two coordinates are within some stated distance tolerance.
 /// if no tolerance is provided, the dst coordinate is assumed to be a valid distance.
 ///
@@ -213,6 +773,39 @@ fn validate_tolerance(
     }
 }
 
+/// confirms that a coordinate lies within the bounding box of the graph vertices,
+/// or within some margin distance of it. if no bounds are provided, the coordinate
+/// is assumed to be in bounds.
+///
+/// # Arguments
+///
+/// * `coord` - query coordinate
+/// * `bounds` - bounding box (min, max) of the graph along with the margin distance
+///
+/// # Returns
+///
+/// * nothing, or an error if the coordinate is out of bounds
+fn validate_bounds(coord: &Coord<f32>, bounds: &Option<VertexBounds>) -> Result<(), PluginError> {
+    match bounds {
+        Some(((min, max), (margin, margin_unit))) => {
+            // the nearest point on (or within) the bounding box
+            let nearest = coord! {
+                x: coord.x.clamp(min.x, max.x),
+                y: coord.y.clamp(min.y, max.y),
+            };
+            let distance_meters = haversine::coord_distance_meters(coord, &nearest)
+                .map_err(PluginError::PluginFailed)?;
+            let distance = DistanceUnit::Meters.convert(&distance_meters, margin_unit);
+            if &distance > margin {
+                Err(PluginError::CoordinateOutOfBounds { coord: *coord })
+            } else {
+                Ok(())
+            }
+        }
+        None => Ok(()),
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::{
@@ -221,7 +814,6 @@ mod test {
     };
 
     use super::*;
-    use crate::plugin::input::input_field::InputField;
     use serde_json::json;
 
     #[test]
@@ -244,7 +836,7 @@ mod test {
             .join("test")
             .join("rtree_query.json");
         let query_str = fs::read_to_string(query_filepath).unwrap();
-        let rtree_plugin = RTreePlugin::new(&vertices_filepath, None, None).unwrap();
+        let rtree_plugin = RTreePlugin::new(&vertices_filepath, None, None, None).unwrap();
         let mut query: serde_json::Value = serde_json::from_str(&query_str).unwrap();
         rtree_plugin.process(&mut query).unwrap();
 
@@ -267,4 +859,291 @@ mod test {
             other => panic!("expected object result, found {}", other),
         }
     }
+
+    #[test]
+    fn test_rtree_plugin_snap_cache() {
+        let vertices_filepath = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join("plugin")
+            .join("input")
+            .join("default")
+            .join("vertex_rtree")
+            .join("test")
+            .join("rtree_vertices.csv");
+        let rtree_plugin = RTreePlugin::new(&vertices_filepath, None, None, None)
+            .unwrap()
+            .with_snap_cache(10, 3)
+            .unwrap();
+        let snap_origin = |x: f64, y: f64| {
+            let mut query = json!({
+                InputField::OriginX.to_str(): x,
+                InputField::OriginY.to_str(): y,
+            });
+            rtree_plugin.process(&mut query).unwrap();
+            query[InputField::OriginVertex.to_str()].as_u64().unwrap()
+        };
+
+        // the first lookup goes to the RTree and is cached
+        assert_eq!(snap_origin(0.1, 0.1), 0);
+        let snap_cache = rtree_plugin.snap_cache.as_ref().unwrap();
+        let key = SnapCache::key(&coord! {x: 0.1, y: 0.1}, &None);
+        let cached = snap_cache.get(&key).unwrap().unwrap();
+        assert_eq!(cached.vertex_id.0, 0);
+
+        // a repeated coordinate is answered by the cache, as shown by planting a
+        // different vertex in the cache entry, which is returned instead of the
+        // nearest vertex. coordinates that round to the same key share the entry.
+        snap_cache.update(&key, &Vertex::new(1, 1.0, 1.0)).unwrap();
+        assert_eq!(snap_origin(0.1, 0.1), 1);
+        assert_eq!(snap_origin(0.1004, 0.1), 1);
+        assert_eq!(snap_origin(0.2, 0.1), 0);
+
+        // an empty cache is rejected
+        let result = RTreePlugin::new(&vertices_filepath, None, None, None)
+            .unwrap()
+            .with_snap_cache(0, 3);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_nearest_vertices_by_haversine_distance() {
+        // at 60 degrees north a degree of longitude is half as long as a degree of
+        // latitude, so vertex 1 is nearer than vertex 0 despite being further in degrees
+        let vertices = vec![
+            Vertex::new(0, 0.0, 60.9),
+            Vertex::new(1, 1.5, 60.0),
+            Vertex::new(2, 3.0, 60.0),
+            Vertex::new(3, 0.0, 62.0),
+            Vertex::new(4, -1.2, 60.5),
+        ];
+        let point = coord! {x: 0.0, y: 60.0};
+        let mut expected = vertices
+            .iter()
+            .map(|v| {
+                let d = haversine::coord_distance_meters(&point, &v.coordinate).unwrap();
+                (v.vertex_id, d)
+            })
+            .collect::<Vec<_>>();
+        expected.sort_by_key(|(_, d)| *d);
+
+        let rtree = VertexRTree::new(vertices);
+        for k in 0..=6 {
+            let nearest = rtree
+                .nearest_vertices_by_distance(point, k)
+                .unwrap()
+                .into_iter()
+                .map(|(v, d)| (v.vertex_id, d))
+                .collect::<Vec<_>>();
+            let expected_k = expected.iter().take(k).cloned().collect::<Vec<_>>();
+            assert_eq!(nearest, expected_k, "k={}", k);
+        }
+        assert_eq!(rtree.nearest_vertices(point, 1)[0].vertex_id.0, 0);
+    }
+
+    #[test]
+    fn test_rtree_plugin_out_of_bounds() {
+        let vertices_filepath = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join("plugin")
+            .join("input")
+            .join("default")
+            .join("vertex_rtree")
+            .join("test")
+            .join("rtree_vertices.csv");
+        let rtree_plugin = RTreePlugin::new(
+            &vertices_filepath,
+            None,
+            Some(DistanceUnit::Kilometers),
+            Some(Distance::new(50.0)),
+        )
+        .unwrap();
+
+        // within the margin of the vertex bounding box [(0, 0), (2, 2)]
+        let mut query = json!({
+            InputField::OriginX.to_str(): 2.1,
+            InputField::OriginY.to_str(): 2.1,
+        });
+        rtree_plugin.process(&mut query).unwrap();
+
+        // far outside of the vertex bounding box
+        let mut query = json!({
+            InputField::OriginX.to_str(): 0.1,
+            InputField::OriginY.to_str(): 0.1,
+            InputField::DestinationX.to_str(): 40.0,
+            InputField::DestinationY.to_str(): 40.0,
+        });
+        let result = rtree_plugin.process(&mut query);
+        assert!(matches!(
+            result,
+            Err(PluginError::CoordinateOutOfBounds { coord }) if coord.x == 40.0 && coord.y == 40.0
+        ));
+    }
+
+    #[test]
+    fn test_rtree_plugin_min_od_separation() {
+        let vertices_filepath = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join("plugin")
+            .join("input")
+            .join("default")
+            .join("vertex_rtree")
+            .join("test")
+            .join("rtree_vertices.csv");
+        let build = |policy: OdSeparationPolicy| {
+            RTreePlugin::new(&vertices_filepath, None, Some(DistanceUnit::Meters), None)
+                .unwrap()
+                .with_min_od_separation(Some(Distance::new(1.0)), policy)
+                .unwrap()
+        };
+        // a few centimeters apart, on either side of the midpoint between vertices 0 and 1
+        let near_coincident = json!({
+            InputField::OriginX.to_str(): 0.4999998,
+            InputField::OriginY.to_str(): 0.4999998,
+            InputField::DestinationX.to_str(): 0.5000002,
+            InputField::DestinationY.to_str(): 0.5000002,
+        });
+
+        let mut query = near_coincident.clone();
+        let result = build(OdSeparationPolicy::Reject).process(&mut query);
+        assert!(matches!(result, Err(PluginError::InputError(_))));
+
+        let mut query = near_coincident.clone();
+        build(OdSeparationPolicy::EmptyRoute)
+            .process(&mut query)
+            .unwrap();
+        assert_eq!(query[InputField::OriginVertex.to_str()], json!(0));
+        assert_eq!(query[InputField::DestinationVertex.to_str()], json!(0));
+
+        // without a minimum separation, the points snap to different vertices
+        let mut query = near_coincident.clone();
+        RTreePlugin::new(&vertices_filepath, None, None, None)
+            .unwrap()
+            .process(&mut query)
+            .unwrap();
+        assert_ne!(
+            query[InputField::OriginVertex.to_str()],
+            query[InputField::DestinationVertex.to_str()]
+        );
+
+        // points further apart than the minimum separation are snapped as usual
+        let mut query = json!({
+            InputField::OriginX.to_str(): 0.1,
+            InputField::OriginY.to_str(): 0.1,
+            InputField::DestinationX.to_str(): 1.9,
+            InputField::DestinationY.to_str(): 2.1,
+        });
+        build(OdSeparationPolicy::Reject)
+            .process(&mut query)
+            .unwrap();
+        assert_eq!(query[InputField::DestinationVertex.to_str()], json!(2));
+    }
+
+    #[test]
+    fn test_rtree_plugin_expanding_snap_radius() {
+        // a dense cluster of vertices downtown along with a lone rural vertex ~43km east
+        let vertices_filepath = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join("plugin")
+            .join("input")
+            .join("default")
+            .join("vertex_rtree")
+            .join("test")
+            .join("rtree_sparse_vertices.csv");
+        let rtree_plugin = RTreePlugin::new(
+            &vertices_filepath,
+            None,
+            Some(DistanceUnit::Kilometers),
+            None,
+        )
+        .unwrap()
+        .with_snap_radius(Some(Distance::new(0.5)), Some(Distance::new(4.0)))
+        .unwrap();
+
+        // downtown coordinates snap within the initial radius
+        let mut query = json!({
+            InputField::OriginX.to_str(): -105.0009,
+            InputField::OriginY.to_str(): 39.7004,
+        });
+        rtree_plugin.process(&mut query).unwrap();
+        assert_eq!(query[InputField::OriginVertex.to_str()], json!(1));
+
+        // a rural coordinate ~5km from the nearest vertex is not found within the 4km default
+        let mut query = json!({
+            InputField::OriginX.to_str(): -104.4400,
+            InputField::OriginY.to_str(): 39.7000,
+        });
+        assert!(rtree_plugin.process(&mut query).is_err());
+
+        // but is found as the radius grows 0.5 -> 1 -> 2 -> 4 -> 8 km under a query override
+        let mut query = json!({
+            InputField::OriginX.to_str(): -104.4400,
+            InputField::OriginY.to_str(): 39.7000,
+            InputField::MaxSnapRadius.to_str(): 10.0,
+        });
+        rtree_plugin.process(&mut query).unwrap();
+        assert_eq!(query[InputField::OriginVertex.to_str()], json!(3));
+
+        // an initial radius beyond the max radius is rejected
+        let mut query = json!({
+            InputField::OriginX.to_str(): -105.0,
+            InputField::OriginY.to_str(): 39.7,
+            InputField::InitialSnapRadius.to_str(): 5.0,
+        });
+        assert!(matches!(
+            rtree_plugin.process(&mut query),
+            Err(PluginError::InputError(_))
+        ));
+    }
+
+    #[test]
+    fn test_rtree_plugin_network_snap_ranking() {
+        // an edge between (0) and (1) along a road, and an edge between (2) and (3)
+        // across a barrier to the north of it
+        let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join("plugin")
+            .join("input")
+            .join("default")
+            .join("vertex_rtree")
+            .join("test");
+        let vertices_filepath = test_dir.join("rtree_barrier_vertices.csv");
+        let edges_filepath = test_dir.join("rtree_barrier_edges.csv");
+        let build = |snap_ranking: SnapRanking| {
+            RTreePlugin::new(&vertices_filepath, None, None, None)
+                .unwrap()
+                .with_snap_ranking(snap_ranking, Some(&edges_filepath))
+                .unwrap()
+        };
+        // ~56m from (2) across the barrier, ~76m from (0) and ~33m from the road
+        let query = json!({
+            InputField::OriginX.to_str(): -105.0008,
+            InputField::OriginY.to_str(): 39.7003,
+        });
+
+        let mut geometric_query = query.clone();
+        build(SnapRanking::Geometric)
+            .process(&mut geometric_query)
+            .unwrap();
+        assert_eq!(geometric_query[InputField::OriginVertex.to_str()], json!(2));
+
+        // the coordinate projects onto the road ~68m from (0) and ~103m from (1)
+        let mut network_query = query.clone();
+        build(SnapRanking::Network)
+            .process(&mut network_query)
+            .unwrap();
+        assert_eq!(network_query[InputField::OriginVertex.to_str()], json!(0));
+
+        // a snap radius limits the distance to the road
+        let mut network_query = query.clone();
+        network_query[InputField::MaxSnapRadius.to_str()] = json!(0.02);
+        assert!(build(SnapRanking::Network)
+            .process(&mut network_query)
+            .is_err());
+
+        // network ranking requires the edges
+        let result = RTreePlugin::new(&vertices_filepath, None, None, None)
+            .unwrap()
+            .with_snap_ranking(SnapRanking::Network, None);
+        assert!(result.is_err());
+    }
 }
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+/// declares how the vertex RTree plugin ranks the candidate vertices for a
+/// query coordinate.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SnapRanking {
+    /// snap to the vertex nearest to the coordinate by straight-line distance
+    #[default]
+    Geometric,
+    /// project the coordinate onto the nearest edge and snap to the endpoint of
+    /// that edge with the shortest network distance, which is the distance to the
+    /// edge plus the distance along the edge to the endpoint
+    Network,
+}
@@ -11,6 +11,15 @@ pub enum InputField {
     DestinationEdge,
     GridSearch,
     QueryWeightEstimate,
+    SummaryOnly,
+    EdgeBreakdown,
+    OriginElevation,
+    ReachableLimit,
+    ReachableLimits,
+    InitialSnapRadius,
+    MaxSnapRadius,
+    QueryIndex,
+    RequestId,
 }
 
 impl InputField {
@@ -27,6 +36,15 @@ impl InputField {
             I::DestinationEdge => "destination_edge",
             I::GridSearch => "grid_search",
             I::QueryWeightEstimate => "query_weight_estimate",
+            I::SummaryOnly => "summary_only",
+            I::EdgeBreakdown => "edge_breakdown",
+            I::OriginElevation => "origin_elevation",
+            I::ReachableLimit => "reachable_limit",
+            I::ReachableLimits => "reachable_limits",
+            I::InitialSnapRadius => "initial_snap_radius",
+            I::MaxSnapRadius => "max_snap_radius",
+            I::QueryIndex => "query_index",
+            I::RequestId => "request_id",
         }
     }
 }
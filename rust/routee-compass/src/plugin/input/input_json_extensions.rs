@@ -14,10 +14,18 @@ pub trait InputJsonExtensions {
     fn get_origin_vertex(&self) -> Result<VertexId, PluginError>;
     fn get_destination_vertex(&self) -> Result<Option<VertexId>, PluginError>;
     fn get_origin_edge(&self) -> Result<EdgeId, PluginError>;
+    fn has_origin_edge(&self) -> bool;
     fn get_destination_edge(&self) -> Result<Option<EdgeId>, PluginError>;
     fn get_grid_search(&self) -> Option<&serde_json::Value>;
     fn add_query_weight_estimate(&mut self, weight: f64) -> Result<(), PluginError>;
     fn get_query_weight_estimate(&self) -> Result<Option<f64>, PluginError>;
+    fn get_summary_only(&self) -> Result<bool, PluginError>;
+    fn get_edge_breakdown(&self) -> Result<bool, PluginError>;
+    fn get_origin_elevation(&self) -> Result<Option<f64>, PluginError>;
+    fn get_reachable_limit(&self) -> Result<Option<f64>, PluginError>;
+    fn get_reachable_limits(&self) -> Result<Option<Vec<f64>>, PluginError>;
+    fn get_initial_snap_radius(&self) -> Result<Option<f64>, PluginError>;
+    fn get_max_snap_radius(&self) -> Result<Option<f64>, PluginError>;
 }
 
 impl InputJsonExtensions for serde_json::Value {
@@ -128,6 +136,10 @@ impl InputJsonExtensions for serde_json::Value {
             })
     }
 
+    fn has_origin_edge(&self) -> bool {
+        self.get(InputField::OriginEdge.to_string()).is_some()
+    }
+
     fn get_destination_edge(&self) -> Result<Option<EdgeId>, PluginError> {
         match self.get(InputField::DestinationEdge.to_string()) {
             None => Ok(None),
@@ -193,6 +205,75 @@ impl InputJsonExtensions for serde_json::Value {
             }),
         }
     }
+
+    fn get_summary_only(&self) -> Result<bool, PluginError> {
+        match self.get(InputField::SummaryOnly.to_string()) {
+            None => Ok(false),
+            Some(v) => v.as_bool().ok_or_else(|| {
+                PluginError::ParseError(InputField::SummaryOnly.to_string(), String::from("bool"))
+            }),
+        }
+    }
+
+    fn get_edge_breakdown(&self) -> Result<bool, PluginError> {
+        match self.get(InputField::EdgeBreakdown.to_string()) {
+            None => Ok(false),
+            Some(v) => v.as_bool().ok_or_else(|| {
+                PluginError::ParseError(InputField::EdgeBreakdown.to_string(), String::from("bool"))
+            }),
+        }
+    }
+
+    fn get_origin_elevation(&self) -> Result<Option<f64>, PluginError> {
+        match self.get(InputField::OriginElevation.to_string()) {
+            None => Ok(None),
+            Some(v) => v.as_f64().map(Some).ok_or_else(|| {
+                PluginError::ParseError(
+                    InputField::OriginElevation.to_string(),
+                    String::from("f64"),
+                )
+            }),
+        }
+    }
+
+    fn get_reachable_limit(&self) -> Result<Option<f64>, PluginError> {
+        match self.get(InputField::ReachableLimit.to_string()) {
+            None => Ok(None),
+            Some(v) => v.as_f64().map(Some).ok_or_else(|| {
+                PluginError::ParseError(InputField::ReachableLimit.to_string(), String::from("f64"))
+            }),
+        }
+    }
+    fn get_reachable_limits(&self) -> Result<Option<Vec<f64>>, PluginError> {
+        match self.get(InputField::ReachableLimits.to_string()) {
+            None => Ok(None),
+            Some(v) => serde_json::from_value(v.clone()).map(Some).map_err(|_| {
+                PluginError::ParseError(
+                    InputField::ReachableLimits.to_string(),
+                    String::from("array of f64"),
+                )
+            }),
+        }
+    }
+    fn get_initial_snap_radius(&self) -> Result<Option<f64>, PluginError> {
+        match self.get(InputField::InitialSnapRadius.to_string()) {
+            None => Ok(None),
+            Some(v) => v.as_f64().map(Some).ok_or_else(|| {
+                PluginError::ParseError(
+                    InputField::InitialSnapRadius.to_string(),
+                    String::from("f64"),
+                )
+            }),
+        }
+    }
+    fn get_max_snap_radius(&self) -> Result<Option<f64>, PluginError> {
+        match self.get(InputField::MaxSnapRadius.to_string()) {
+            None => Ok(None),
+            Some(v) => v.as_f64().map(Some).ok_or_else(|| {
+                PluginError::ParseError(InputField::MaxSnapRadius.to_string(), String::from("f64"))
+            }),
+        }
+    }
 }
 
 // pub type DecodeOp<T> = Box<dyn Fn(&serde_json::Value) -> Option<T>>;
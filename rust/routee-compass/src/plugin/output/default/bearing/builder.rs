@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use super::plugin::BearingOutputPlugin;
+use crate::{
+    app::compass::config::{
+        builders::OutputPluginBuilder, compass_configuration_error::CompassConfigurationError,
+        config_json_extension::ConfigJsonExtensions,
+    },
+    plugin::output::output_plugin::OutputPlugin,
+};
+
+/// Builds a plugin that outputs the heading of each edge along the route.
+///
+/// # Configuration
+///
+/// This plugin expects the following keys:
+/// * `geometry_input_file` - the filename providing edge geometries
+///
+/// # Example Configuration
+///
+/// ```toml
+/// [[plugin.output_plugins]]
+/// type = "bearing"
+/// geometry_input_file = "edges-geometries-enumerated.txt.gz"
+/// ```
+///
+pub struct BearingOutputPluginBuilder {}
+
+impl OutputPluginBuilder for BearingOutputPluginBuilder {
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+    ) -> Result<Arc<dyn OutputPlugin>, CompassConfigurationError> {
+        let parent_key = String::from("bearing");
+        let geometry_filename = parameters.get_config_path(&"geometry_input_file", &parent_key)?;
+        let plugin = BearingOutputPlugin::from_file(&geometry_filename)
+            .map_err(CompassConfigurationError::PluginError)?;
+        Ok(Arc::new(plugin))
+    }
+}
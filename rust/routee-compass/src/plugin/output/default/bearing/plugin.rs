@@ -0,0 +1,151 @@
+use crate::app::compass::compass_app_error::CompassAppError;
+use crate::app::search::search_app_result::SearchAppResult;
+use crate::plugin::output::output_plugin::OutputPlugin;
+use crate::plugin::plugin_error::PluginError;
+use geo::LineString;
+use routee_compass_core::algorithm::search::edge_traversal::EdgeTraversal;
+use routee_compass_core::algorithm::search::search_instance::SearchInstance;
+use routee_compass_core::model::road_network::edge_id::EdgeId;
+use routee_compass_core::util::fs::read_utils::read_raw_file;
+use routee_compass_core::util::geo::{bearing, geo_io_utils};
+use serde_json::json;
+use std::path::Path;
+
+/// output plugin that reports the compass heading at the start of each edge
+/// along a route, computed from the first two points of the edge geometry, along
+/// with the arrival heading at the end of the route. headings are in degrees
+/// clockwise from north in the range [0, 360).
+pub struct BearingOutputPlugin {
+    geoms: Box<[LineString<f32>]>,
+}
+
+impl BearingOutputPlugin {
+    pub fn from_file<P: AsRef<Path>>(filename: &P) -> Result<BearingOutputPlugin, PluginError> {
+        let geoms = read_raw_file(filename, geo_io_utils::parse_linestring, None).map_err(|e| {
+            PluginError::FileReadError(filename.as_ref().to_path_buf(), e.to_string())
+        })?;
+        Ok(BearingOutputPlugin { geoms })
+    }
+
+    /// gets the geometry of an edge. when the geometry has fewer than two points,
+    /// the straight line between the edge's vertices is used instead.
+    fn edge_linestring(
+        &self,
+        et: &EdgeTraversal,
+        si: &SearchInstance,
+    ) -> Result<LineString<f32>, PluginError> {
+        match self.geoms.get(et.edge_id.as_usize()) {
+            Some(geom) if geom.0.len() >= 2 => Ok(geom.clone()),
+            _ => {
+                let edge = si
+                    .directed_graph
+                    .get_edge(et.edge_id)
+                    .map_err(|e| PluginError::PluginFailed(e.to_string()))?;
+                let src = si
+                    .directed_graph
+                    .get_vertex(edge.src_vertex_id)
+                    .map_err(|e| PluginError::PluginFailed(e.to_string()))?;
+                let dst = si
+                    .directed_graph
+                    .get_vertex(edge.dst_vertex_id)
+                    .map_err(|e| PluginError::PluginFailed(e.to_string()))?;
+                Ok(LineString::new(vec![src.coordinate.0, dst.coordinate.0]))
+            }
+        }
+    }
+}
+
+impl OutputPlugin for BearingOutputPlugin {
+    fn process(
+        &self,
+        output: &mut serde_json::Value,
+        search_result: &Result<(SearchAppResult, SearchInstance), CompassAppError>,
+    ) -> Result<(), PluginError> {
+        match search_result {
+            Err(_) => Ok(()),
+            Ok((result, si)) => {
+                let routes = result
+                    .routes
+                    .iter()
+                    .map(|route| {
+                        let edges = route
+                            .iter()
+                            .map(|et| Ok((et.edge_id, self.edge_linestring(et, si)?)))
+                            .collect::<Result<Vec<_>, PluginError>>()?;
+                        Ok(route_bearings(&edges))
+                    })
+                    .collect::<Result<Vec<_>, PluginError>>()?;
+
+                // match the traversal plugin: null for no route, a single result
+                // for one route, or an array of results for many routes
+                output["route_bearings"] = match routes.as_slice() {
+                    [] => serde_json::Value::Null,
+                    [route] => route.to_owned(),
+                    _ => json![routes],
+                };
+                Ok(())
+            }
+        }
+    }
+
+    fn uses_route_edges(&self) -> bool {
+        true
+    }
+}
+
+/// computes the heading at the start of each edge of a route along with the
+/// heading at the end of the final edge.
+///
+/// # Arguments
+///
+/// * `edges` - the id and geometry of each edge along the route, in order
+///
+/// # Returns
+///
+/// a JSON object with an `edges` list of `{ edge_id, bearing }` entries and
+/// the `arrival_heading`, which is null for an empty route
+pub fn route_bearings(edges: &[(EdgeId, LineString<f32>)]) -> serde_json::Value {
+    let edge_bearings = edges
+        .iter()
+        .map(|(edge_id, geom)| {
+            json!({
+                "edge_id": edge_id,
+                "bearing": bearing::start_bearing(geom),
+            })
+        })
+        .collect::<Vec<_>>();
+    let arrival_heading = edges
+        .last()
+        .and_then(|(_, geom)| bearing::end_bearing(geom));
+    json!({
+        "edges": edge_bearings,
+        "arrival_heading": arrival_heading,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_route_bearings_north_then_east() {
+        let north = LineString::from(vec![(0.0, 0.0), (0.0, 0.5), (0.0, 1.0)]);
+        let east = LineString::from(vec![(0.0, 1.0), (0.5, 1.0), (1.0, 1.0)]);
+        let result = route_bearings(&[(EdgeId(0), north), (EdgeId(1), east)]);
+
+        let bearings = result["edges"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| e["bearing"].as_f64().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(result["edges"][1]["edge_id"], json!(1));
+        assert!((bearings[0] - 0.0).abs() < 0.05, "{}", bearings[0]);
+        assert!((bearings[1] - 90.0).abs() < 0.05, "{}", bearings[1]);
+        let arrival = result["arrival_heading"].as_f64().unwrap();
+        assert!((arrival - 90.0).abs() < 0.05, "{}", arrival);
+
+        let empty = route_bearings(&[]);
+        assert_eq!(empty["arrival_heading"], serde_json::Value::Null);
+    }
+}
@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use super::plugin::ElevationOutputPlugin;
+use crate::{
+    app::compass::config::{
+        builders::OutputPluginBuilder, compass_configuration_error::CompassConfigurationError,
+        config_json_extension::ConfigJsonExtensions,
+    },
+    plugin::output::output_plugin::OutputPlugin,
+};
+use routee_compass_core::model::unit::{DistanceUnit, GradeUnit};
+
+/// Builds a plugin that outputs the elevation profile of each route.
+///
+/// # Configuration
+///
+/// This plugin expects the following keys:
+/// * `grade_input_file` - the filename providing a grade for each edge
+/// * `grade_unit` - the unit of the values in the grade file
+/// * `distance_unit` (optional) - the unit of the output distances and elevations, defaults to meters
+///
+/// # Example Configuration
+///
+/// ```toml
+/// [[plugin.output_plugins]]
+/// type = "elevation"
+/// grade_input_file = "edges-grade-enumerated.txt.gz"
+/// grade_unit = "decimal"
+/// distance_unit = "meters"
+/// ```
+///
+pub struct ElevationOutputPluginBuilder {}
+
+impl OutputPluginBuilder for ElevationOutputPluginBuilder {
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+    ) -> Result<Arc<dyn OutputPlugin>, CompassConfigurationError> {
+        let parent_key = String::from("elevation");
+        let grade_filename = parameters.get_config_path(&"grade_input_file", &parent_key)?;
+        let grade_unit = parameters.get_config_serde::<GradeUnit>(&"grade_unit", &parent_key)?;
+        let distance_unit =
+            parameters.get_config_serde_optional::<DistanceUnit>(&"distance_unit", &parent_key)?;
+
+        let plugin = ElevationOutputPlugin::from_file(&grade_filename, grade_unit, distance_unit)
+            .map_err(CompassConfigurationError::PluginError)?;
+        Ok(Arc::new(plugin))
+    }
+}
@@ -0,0 +1,167 @@
+use crate::app::compass::compass_app_error::CompassAppError;
+use crate::app::search::search_app_result::SearchAppResult;
+use crate::plugin::input::input_json_extensions::InputJsonExtensions;
+use crate::plugin::output::output_plugin::OutputPlugin;
+use crate::plugin::plugin_error::PluginError;
+use routee_compass_core::algorithm::search::edge_traversal::EdgeTraversal;
+use routee_compass_core::algorithm::search::search_instance::SearchInstance;
+use routee_compass_core::model::unit::{
+    as_f64::AsF64, Distance, DistanceUnit, Grade, GradeUnit, BASE_DISTANCE_UNIT,
+};
+use routee_compass_core::util::fs::{read_decoders, read_utils};
+use serde_json::json;
+use std::path::Path;
+
+/// output plugin that computes an elevation profile along each route from
+/// the grade and distance of each edge, starting from an elevation provided
+/// on the query as `origin_elevation` (or zero if not provided). elevations
+/// and distances are reported in the same distance unit.
+pub struct ElevationOutputPlugin {
+    grades: Box<[Grade]>,
+    grade_unit: GradeUnit,
+    distance_unit: DistanceUnit,
+}
+
+impl ElevationOutputPlugin {
+    pub fn from_file<P: AsRef<Path>>(
+        filename: &P,
+        grade_unit: GradeUnit,
+        distance_unit: Option<DistanceUnit>,
+    ) -> Result<ElevationOutputPlugin, PluginError> {
+        let grades =
+            read_utils::read_raw_file(filename, read_decoders::default, None).map_err(|e| {
+                PluginError::FileReadError(filename.as_ref().to_path_buf(), e.to_string())
+            })?;
+        Ok(ElevationOutputPlugin {
+            grades,
+            grade_unit,
+            distance_unit: distance_unit.unwrap_or(BASE_DISTANCE_UNIT),
+        })
+    }
+
+    /// collects the distance and grade (as a decimal) of each edge along a route.
+    fn route_segments(
+        &self,
+        route: &[EdgeTraversal],
+        si: &SearchInstance,
+    ) -> Result<Vec<(Distance, Grade)>, PluginError> {
+        route
+            .iter()
+            .map(|et| {
+                let edge = si
+                    .directed_graph
+                    .get_edge(et.edge_id)
+                    .map_err(|e| PluginError::PluginFailed(e.to_string()))?;
+                let distance = BASE_DISTANCE_UNIT.convert(&edge.distance, &self.distance_unit);
+                let grade = self.grades.get(et.edge_id.as_usize()).ok_or_else(|| {
+                    PluginError::PluginFailed(format!(
+                        "no grade found for edge {} in elevation plugin",
+                        et.edge_id
+                    ))
+                })?;
+                let grade_decimal = self.grade_unit.convert(grade, &GradeUnit::Decimal);
+                Ok((distance, grade_decimal))
+            })
+            .collect()
+    }
+}
+
+impl OutputPlugin for ElevationOutputPlugin {
+    fn process(
+        &self,
+        output: &mut serde_json::Value,
+        search_result: &Result<(SearchAppResult, SearchInstance), CompassAppError>,
+    ) -> Result<(), PluginError> {
+        match search_result {
+            Err(_) => Ok(()),
+            Ok((result, si)) => {
+                let origin_elevation = output
+                    .get("request")
+                    .map(|req| req.get_origin_elevation())
+                    .transpose()?
+                    .flatten()
+                    .unwrap_or_default();
+                let profiles = result
+                    .routes
+                    .iter()
+                    .map(|route| {
+                        let segments = self.route_segments(route, si)?;
+                        let profile = elevation_profile(Distance::new(origin_elevation), &segments);
+                        let points = profile
+                            .iter()
+                            .map(|(d, e)| json![[d.as_f64(), e.as_f64()]])
+                            .collect::<Vec<_>>();
+                        Ok(json![points])
+                    })
+                    .collect::<Result<Vec<_>, PluginError>>()?;
+
+                // match the traversal plugin: null for no route, a single profile
+                // for one route, or an array of profiles for many routes
+                output["elevation_profile"] = match profiles.as_slice() {
+                    [] => serde_json::Value::Null,
+                    [profile] => profile.to_owned(),
+                    _ => json![profiles],
+                };
+                Ok(())
+            }
+        }
+    }
+
+    fn uses_route_edges(&self) -> bool {
+        true
+    }
+}
+
+/// computes the (cumulative distance, elevation) at the start of a route and at
+/// the end of each edge. the rise over an edge is its grade multiplied by its
+/// distance, so a positive grade (uphill) increases elevation and a negative
+/// grade (downhill) decreases it.
+///
+/// # Arguments
+///
+/// * `origin_elevation` - elevation at the start of the route
+/// * `segments`         - distance and decimal grade of each edge along the route
+///
+/// # Returns
+///
+/// one point per edge plus the origin, in the distance unit of the inputs
+pub fn elevation_profile(
+    origin_elevation: Distance,
+    segments: &[(Distance, Grade)],
+) -> Vec<(Distance, Distance)> {
+    let mut points = Vec::with_capacity(segments.len() + 1);
+    let mut distance = Distance::ZERO;
+    let mut elevation = origin_elevation;
+    points.push((distance, elevation));
+    for (edge_distance, grade) in segments.iter() {
+        distance = distance + *edge_distance;
+        elevation = elevation + Distance::new(edge_distance.as_f64() * grade.as_f64());
+        points.push((distance, elevation));
+    }
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_elevation_profile_up_then_down() {
+        // 100 units at +5% grade, then 50 units at -10% grade
+        let segments = vec![
+            (Distance::new(100.0), Grade::new(0.05)),
+            (Distance::new(50.0), Grade::new(-0.10)),
+        ];
+        let profile = elevation_profile(Distance::new(1000.0), &segments);
+        let result = profile
+            .iter()
+            .map(|(d, e)| (d.as_f64(), e.as_f64()))
+            .collect::<Vec<_>>();
+        let expected = [(0.0, 1000.0), (100.0, 1005.0), (150.0, 1000.0)];
+        assert_eq!(result.len(), expected.len());
+        for ((d, e), (exp_d, exp_e)) in result.iter().zip(expected.iter()) {
+            assert!((d - exp_d).abs() < 1e-9, "{} != {}", d, exp_d);
+            assert!((e - exp_e).abs() < 1e-9, "{} != {}", e, exp_e);
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use routee_compass_core::model::unit::{
+    as_f64::AsF64, Distance, DistanceUnit, Energy, EnergyUnit, Speed, SpeedUnit, Time, TimeUnit,
+};
+use serde::{Deserialize, Serialize};
+
+/// formatting applied to a single output field, which may be written by any
+/// number of output plugins. each path is a '.'-delimited sequence of object
+/// keys or array indices into the output JSON, where '*' matches every key or
+/// index at that level. a path that does not exist in an output is skipped.
+///
+/// # Example
+///
+/// ### Deserialization
+///
+/// convert route distances from kilometers to miles with 2 decimal places:
+///
+/// ```toml
+/// [[plugin.output_formatting.fields]]
+/// paths = ["route.traversal_summary.distance", "elevation_profile.*.0"]
+/// precision = 2
+/// unit = { type = "distance", from = "kilometers", to = "miles" }
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct FieldFormat {
+    pub paths: Vec<String>,
+    #[serde(default)]
+    pub precision: Option<u32>,
+    #[serde(default)]
+    pub unit: Option<UnitConversion>,
+}
+
+/// a unit conversion applied to the numeric values of a field.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum UnitConversion {
+    Distance {
+        from: DistanceUnit,
+        to: DistanceUnit,
+    },
+    Time {
+        from: TimeUnit,
+        to: TimeUnit,
+    },
+    Energy {
+        from: EnergyUnit,
+        to: EnergyUnit,
+    },
+    Speed {
+        from: SpeedUnit,
+        to: SpeedUnit,
+    },
+}
+
+impl UnitConversion {
+    pub fn convert(&self, value: f64) -> f64 {
+        match self {
+            UnitConversion::Distance { from, to } => {
+                from.convert(&Distance::new(value), to).as_f64()
+            }
+            UnitConversion::Time { from, to } => from.convert(&Time::new(value), to).as_f64(),
+            UnitConversion::Energy { from, to } => from.convert(&Energy::new(value), to).as_f64(),
+            UnitConversion::Speed { from, to } => from.convert(&Speed::new(value), to).as_f64(),
+        }
+    }
+}
+
+impl FieldFormat {
+    /// converts and then rounds a value according to this field format
+    pub fn format(&self, value: f64) -> f64 {
+        let converted = match &self.unit {
+            Some(conversion) => conversion.convert(value),
+            None => value,
+        };
+        match self.precision {
+            Some(digits) => {
+                let scale = 10_f64.powi(digits as i32);
+                (converted * scale).round() / scale
+            }
+            None => converted,
+        }
+    }
+
+    /// applies this field format to every value found at any of its paths.
+    pub fn apply(&self, output: &mut serde_json::Value) {
+        for path in self.paths.iter() {
+            let segments = path.split('.').collect::<Vec<_>>();
+            apply_at_path(output, &segments, &|v| self.format(v));
+        }
+    }
+}
+
+/// walks the path segments into the JSON value, formatting the numeric values found
+/// at the end of the path. arrays found at the end of the path are formatted element-wise.
+fn apply_at_path(value: &mut serde_json::Value, segments: &[&str], f: &dyn Fn(f64) -> f64) {
+    match segments.split_first() {
+        None => match value {
+            serde_json::Value::Number(n) => {
+                if let Some(formatted) = n.as_f64().map(f).and_then(serde_json::Number::from_f64) {
+                    *n = formatted;
+                }
+            }
+            serde_json::Value::Array(values) => {
+                for v in values.iter_mut() {
+                    apply_at_path(v, segments, f);
+                }
+            }
+            _ => {}
+        },
+        Some((&"*", rest)) => match value {
+            serde_json::Value::Object(map) => {
+                for v in map.values_mut() {
+                    apply_at_path(v, rest, f);
+                }
+            }
+            serde_json::Value::Array(values) => {
+                for v in values.iter_mut() {
+                    apply_at_path(v, rest, f);
+                }
+            }
+            _ => {}
+        },
+        Some((key, rest)) => {
+            let child = match value {
+                serde_json::Value::Object(map) => map.get_mut(*key),
+                serde_json::Value::Array(values) => {
+                    key.parse::<usize>().ok().and_then(|i| values.get_mut(i))
+                }
+                _ => None,
+            };
+            if let Some(child) = child {
+                apply_at_path(child, rest, f);
+            }
+        }
+    }
+}
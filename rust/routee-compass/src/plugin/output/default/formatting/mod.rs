@@ -0,0 +1,2 @@
+pub mod field_format;
+pub mod plugin;
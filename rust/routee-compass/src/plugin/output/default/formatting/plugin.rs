@@ -0,0 +1,84 @@
+use super::field_format::FieldFormat;
+use crate::app::{
+    compass::compass_app_error::CompassAppError, search::search_app_result::SearchAppResult,
+};
+use crate::plugin::output::output_plugin::OutputPlugin;
+use crate::plugin::plugin_error::PluginError;
+use routee_compass_core::algorithm::search::search_instance::SearchInstance;
+use serde::{Deserialize, Serialize};
+
+/// shared post-processing stage that applies precision and unit conversions
+/// to output fields after all other output plugins have run, so that a field
+/// is formatted consistently regardless of which plugin wrote it.
+///
+/// this plugin is not listed with the other output plugins. instead, it is built
+/// from the `[plugin.output_formatting]` section and always runs last.
+///
+/// # Example
+///
+/// ### Deserialization
+///
+/// ```toml
+/// [plugin.output_formatting]
+/// fields = [
+///   { paths = ["route.traversal_summary.time"], precision = 1, unit = { type = "time", from = "seconds", to = "minutes" } },
+///   { paths = ["route.cost.total_cost"], precision = 3 }
+/// ]
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct OutputFormattingPlugin {
+    pub fields: Vec<FieldFormat>,
+}
+
+impl OutputPlugin for OutputFormattingPlugin {
+    fn process(
+        &self,
+        output: &mut serde_json::Value,
+        _search_result: &Result<(SearchAppResult, SearchInstance), CompassAppError>,
+    ) -> Result<(), PluginError> {
+        for field in self.fields.iter() {
+            field.apply(output);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_same_field_formatted_consistently_across_plugins() {
+        let plugin: OutputFormattingPlugin = serde_json::from_value(json!({
+            "fields": [{
+                "paths": ["route.traversal_summary.distance", "elevation_profile.*.0"],
+                "precision": 3,
+                "unit": { "type": "distance", "from": "kilometers", "to": "miles" }
+            }]
+        }))
+        .unwrap();
+
+        // fragments written by the traversal and elevation plugins, which both
+        // report the route distance of 12.3456789 kilometers
+        let mut output = json!({
+            "route": {
+                "traversal_summary": { "distance": 12.3456789, "time": 600.0 },
+                "cost": { "distance": 12.3456789 }
+            },
+            "elevation_profile": [[0.0, 100.0], [12.3456789, 104.5]]
+        });
+        let result = Err(CompassAppError::InternalError(String::from("unused")));
+        plugin.process(&mut output, &result).unwrap();
+
+        let summary_distance = &output["route"]["traversal_summary"]["distance"];
+        let profile_distance = &output["elevation_profile"][1][0];
+        assert_eq!(summary_distance, &json!(7.673));
+        assert_eq!(summary_distance, profile_distance);
+
+        // fields that are not listed are unchanged
+        assert_eq!(output["elevation_profile"][1][1], json!(104.5));
+        assert_eq!(output["route"]["cost"]["distance"], json!(12.3456789));
+        assert_eq!(output["route"]["traversal_summary"]["time"], json!(600.0));
+    }
+}
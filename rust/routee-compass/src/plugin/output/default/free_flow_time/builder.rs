@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use super::plugin::FreeFlowTimeOutputPlugin;
+use crate::{
+    app::compass::config::{
+        builders::OutputPluginBuilder, compass_configuration_error::CompassConfigurationError,
+        config_json_extension::ConfigJsonExtensions,
+    },
+    plugin::output::output_plugin::OutputPlugin,
+};
+use routee_compass_core::model::unit::{SpeedUnit, TimeUnit};
+
+/// Builds a plugin that outputs the free-flow travel time of each route along
+/// with its modeled travel time.
+///
+/// # Configuration
+///
+/// This plugin expects the following keys:
+/// * `speed_input_file` - the filename providing a free-flow speed for each edge
+/// * `speed_unit` (optional) - the unit of the values in the speed file, required if the file has no unit header
+/// * `time_unit` (optional) - the unit of the output times, defaults to seconds
+/// * `time_feature` (optional) - the state feature with the modeled time, defaults to "time"
+///
+/// # Example Configuration
+///
+/// ```toml
+/// [[plugin.output_plugins]]
+/// type = "free_flow_time"
+/// speed_input_file = "edges-free-flow-speeds.txt.gz"
+/// speed_unit = "kilometers_per_hour"
+/// time_unit = "minutes"
+/// ```
+///
+pub struct FreeFlowTimeOutputPluginBuilder {}
+
+impl OutputPluginBuilder for FreeFlowTimeOutputPluginBuilder {
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+    ) -> Result<Arc<dyn OutputPlugin>, CompassConfigurationError> {
+        let parent_key = String::from("free_flow_time");
+        let speed_filename = parameters.get_config_path(&"speed_input_file", &parent_key)?;
+        let speed_unit =
+            parameters.get_config_serde_optional::<SpeedUnit>(&"speed_unit", &parent_key)?;
+        let time_unit =
+            parameters.get_config_serde_optional::<TimeUnit>(&"time_unit", &parent_key)?;
+        let time_feature =
+            parameters.get_config_serde_optional::<String>(&"time_feature", &parent_key)?;
+
+        let plugin = FreeFlowTimeOutputPlugin::from_file(
+            &speed_filename,
+            speed_unit,
+            time_unit,
+            time_feature,
+        )
+        .map_err(CompassConfigurationError::PluginError)?;
+        Ok(Arc::new(plugin))
+    }
+}
@@ -0,0 +1,260 @@
+use crate::app::compass::compass_app_error::CompassAppError;
+use crate::app::search::search_app_result::SearchAppResult;
+use crate::plugin::output::output_plugin::OutputPlugin;
+use crate::plugin::plugin_error::PluginError;
+use routee_compass_core::algorithm::search::edge_traversal::EdgeTraversal;
+use routee_compass_core::algorithm::search::search_instance::SearchInstance;
+use routee_compass_core::model::traversal::default::speed_traversal_engine::SpeedTraversalEngine;
+use routee_compass_core::model::traversal::default::speed_traversal_model::get_speed;
+use routee_compass_core::model::unit::{
+    as_f64::AsF64, SpeedUnit, Time, TimeUnit, BASE_DISTANCE_UNIT,
+};
+use serde_json::json;
+use std::path::Path;
+
+/// output plugin that reports the free-flow travel time of each route alongside
+/// the travel time found by the search, so that congestion delay can be measured.
+/// the free-flow time is accumulated along the chosen route from a separate table
+/// of free-flow speeds, while the modeled time is read from the final search state.
+pub struct FreeFlowTimeOutputPlugin {
+    engine: SpeedTraversalEngine,
+    time_feature: String,
+}
+
+impl FreeFlowTimeOutputPlugin {
+    const DEFAULT_TIME_FEATURE: &'static str = "time";
+
+    pub fn from_file<P: AsRef<Path>>(
+        filename: &P,
+        speed_unit: Option<SpeedUnit>,
+        time_unit: Option<TimeUnit>,
+        time_feature: Option<String>,
+    ) -> Result<FreeFlowTimeOutputPlugin, PluginError> {
+        let engine =
+            SpeedTraversalEngine::new(filename, speed_unit, None, time_unit).map_err(|e| {
+                PluginError::FileReadError(filename.as_ref().to_path_buf(), e.to_string())
+            })?;
+        Ok(FreeFlowTimeOutputPlugin {
+            engine,
+            time_feature: time_feature.unwrap_or(String::from(Self::DEFAULT_TIME_FEATURE)),
+        })
+    }
+
+    /// accumulates the time to traverse each edge of a route at its free-flow speed.
+    fn free_flow_time(
+        &self,
+        route: &[EdgeTraversal],
+        si: &SearchInstance,
+    ) -> Result<Time, PluginError> {
+        route.iter().try_fold(Time::ZERO, |acc, et| {
+            let edge = si
+                .directed_graph
+                .get_edge(et.edge_id)
+                .map_err(|e| PluginError::PluginFailed(e.to_string()))?;
+            let distance = BASE_DISTANCE_UNIT.convert(&edge.distance, &self.engine.distance_unit);
+            let speed = get_speed(&self.engine.speed_table, et.edge_id)
+                .map_err(|e| PluginError::PluginFailed(e.to_string()))?;
+            let edge_time = Time::create(
+                &speed,
+                &self.engine.speed_unit,
+                &distance,
+                &self.engine.distance_unit,
+                &self.engine.time_unit,
+            )
+            .map_err(|e| PluginError::PluginFailed(e.to_string()))?;
+            Ok(acc + edge_time)
+        })
+    }
+
+    /// the modeled and free-flow times of a route, along with their ratio.
+    fn route_summary(
+        &self,
+        route: &[EdgeTraversal],
+        si: &SearchInstance,
+    ) -> Result<serde_json::Value, PluginError> {
+        let time = match route.last() {
+            None => Time::ZERO,
+            Some(et) => si
+                .state_model
+                .get_time(&et.result_state, &self.time_feature, &self.engine.time_unit)
+                .map_err(|e| PluginError::PluginFailed(e.to_string()))?,
+        };
+        let free_flow_time = self.free_flow_time(route, si)?;
+        Ok(free_flow_summary(
+            time,
+            free_flow_time,
+            &self.engine.time_unit,
+        ))
+    }
+}
+
+impl OutputPlugin for FreeFlowTimeOutputPlugin {
+    fn process(
+        &self,
+        output: &mut serde_json::Value,
+        search_result: &Result<(SearchAppResult, SearchInstance), CompassAppError>,
+    ) -> Result<(), PluginError> {
+        match search_result {
+            Err(_) => Ok(()),
+            Ok((result, si)) => {
+                let summaries = result
+                    .routes
+                    .iter()
+                    .map(|route| self.route_summary(route, si))
+                    .collect::<Result<Vec<_>, PluginError>>()?;
+
+                // match the traversal plugin: null for no route, a single summary
+                // for one route, or an array of summaries for many routes
+                output["free_flow_summary"] = match summaries.as_slice() {
+                    [] => serde_json::Value::Null,
+                    [summary] => summary.to_owned(),
+                    _ => json![summaries],
+                };
+                Ok(())
+            }
+        }
+    }
+
+    fn uses_route_edges(&self) -> bool {
+        true
+    }
+}
+
+/// summarizes the modeled and free-flow times of a route. the delay ratio is the
+/// modeled time over the free-flow time, so a congested route has a ratio above 1.
+///
+/// # Arguments
+///
+/// * `time`           - the travel time found by the search
+/// * `free_flow_time` - the travel time at free-flow speeds along the same route
+/// * `time_unit`      - unit of both times
+///
+/// # Returns
+///
+/// a JSON object with the `time`, `free_flow_time`, `delay_ratio` and `time_unit`.
+/// the delay ratio is null when the free-flow time is zero.
+pub fn free_flow_summary(
+    time: Time,
+    free_flow_time: Time,
+    time_unit: &TimeUnit,
+) -> serde_json::Value {
+    let delay_ratio = if free_flow_time > Time::ZERO {
+        Some(time.as_f64() / free_flow_time.as_f64())
+    } else {
+        None
+    };
+    json!({
+        "time": time.as_f64(),
+        "free_flow_time": free_flow_time.as_f64(),
+        "delay_ratio": delay_ratio,
+        "time_unit": time_unit,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use routee_compass_core::algorithm::search::a_star::a_star_algorithm::run_a_star;
+    use routee_compass_core::algorithm::search::backtrack::vertex_oriented_route;
+    use routee_compass_core::algorithm::search::direction::Direction;
+    use routee_compass_core::model::access::default::no_access_model::NoAccessModel;
+    use routee_compass_core::model::cost::cost_aggregation::CostAggregation;
+    use routee_compass_core::model::cost::cost_model::CostModel;
+    use routee_compass_core::model::cost::vehicle::vehicle_cost_rate::VehicleCostRate;
+    use routee_compass_core::model::frontier::default::no_restriction::NoRestriction;
+    use routee_compass_core::model::property::{edge::Edge, vertex::Vertex};
+    use routee_compass_core::model::road_network::{graph::Graph, vertex_id::VertexId};
+    use routee_compass_core::model::state::state_model::StateModel;
+    use routee_compass_core::model::termination::termination_model::TerminationModel;
+    use routee_compass_core::model::traversal::default::speed_traversal_model::SpeedTraversalModel;
+    use routee_compass_core::model::traversal::traversal_model::TraversalModel;
+    use routee_compass_core::model::unit::{DistanceUnit, Speed};
+    use routee_compass_core::util::compact_ordered_hash_map::CompactOrderedHashMap;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    fn speed_engine(speeds: Vec<f64>) -> SpeedTraversalEngine {
+        SpeedTraversalEngine {
+            speed_table: speeds.into_iter().map(Speed::new).collect(),
+            speed_unit: SpeedUnit::KilometersPerHour,
+            time_unit: TimeUnit::Minutes,
+            distance_unit: DistanceUnit::Kilometers,
+            max_speed: Speed::new(50.0),
+        }
+    }
+
+    /// (0) -[0]-> (1) -[1]-> (2), with edges of 1 kilometer each, searched using
+    /// the given (congested) speeds
+    fn build_search_instance(speeds: Vec<f64>) -> SearchInstance {
+        let vertices = (0..3).map(|i| Vertex::new(i, 0.0, 0.0)).collect::<Vec<_>>();
+        let edges = vec![Edge::new(0, 0, 1, 1000.0), Edge::new(1, 1, 2, 1000.0)];
+        let mut adj = vec![CompactOrderedHashMap::empty(); vertices.len()];
+        let mut rev = vec![CompactOrderedHashMap::empty(); vertices.len()];
+        for edge in &edges {
+            adj[edge.src_vertex_id.0].insert(edge.edge_id, edge.dst_vertex_id);
+            rev[edge.dst_vertex_id.0].insert(edge.edge_id, edge.src_vertex_id);
+        }
+        let graph = Graph {
+            adj: adj.into_boxed_slice(),
+            rev: rev.into_boxed_slice(),
+            edges: edges.into_boxed_slice(),
+            vertices: vertices.into_boxed_slice(),
+        };
+        let traversal_model = Arc::new(SpeedTraversalModel::new(Arc::new(speed_engine(speeds))));
+        let state_model = Arc::new(
+            StateModel::empty()
+                .extend(traversal_model.state_features())
+                .unwrap(),
+        );
+        let cost_model = CostModel::new(
+            Arc::new(HashMap::from([(String::from("time"), 1.0)])),
+            Arc::new(HashMap::from([(
+                String::from("time"),
+                VehicleCostRate::Raw,
+            )])),
+            Arc::new(HashMap::new()),
+            CostAggregation::Sum,
+            Arc::new(vec![]),
+            state_model.clone(),
+        )
+        .unwrap();
+        SearchInstance {
+            directed_graph: Arc::new(graph),
+            state_model,
+            traversal_model,
+            access_model: Arc::new(NoAccessModel {}),
+            cost_model,
+            frontier_model: Arc::new(NoRestriction {}),
+            termination_model: Arc::new(TerminationModel::IterationsLimit { limit: 20 }),
+            edge_cost_cache: None,
+        }
+    }
+
+    #[test]
+    fn test_congested_time_exceeds_free_flow_time() {
+        // the second edge is slowed from 50 to 25 kph
+        let si = build_search_instance(vec![50.0, 25.0]);
+        let (o, d) = (VertexId(0), VertexId(2));
+        let result = run_a_star(o, Some(d), &Direction::Forward, None, &si).unwrap();
+        let route = vertex_oriented_route(o, d, &result.tree).unwrap();
+
+        let plugin = FreeFlowTimeOutputPlugin {
+            engine: speed_engine(vec![50.0, 50.0]),
+            time_feature: String::from("time"),
+        };
+        let summary = plugin.route_summary(&route, &si).unwrap();
+
+        // 1.2 minutes per kilometer at 50 kph, and 2.4 minutes at 25 kph
+        let time = summary["time"].as_f64().unwrap();
+        let free_flow_time = summary["free_flow_time"].as_f64().unwrap();
+        let delay_ratio = summary["delay_ratio"].as_f64().unwrap();
+        assert!((time - 3.6).abs() < 1e-6, "{}", time);
+        assert!((free_flow_time - 2.4).abs() < 1e-6, "{}", free_flow_time);
+        assert!((delay_ratio - 1.5).abs() < 1e-6, "{}", delay_ratio);
+        assert_eq!(summary["time_unit"], json!("minutes"));
+
+        // an empty route has no delay ratio
+        let empty = plugin.route_summary(&[], &si).unwrap();
+        assert_eq!(empty["delay_ratio"], serde_json::Value::Null);
+    }
+}
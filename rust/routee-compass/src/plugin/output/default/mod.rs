@@ -1,3 +1,8 @@
+pub mod bearing;
+pub mod elevation;
+pub mod formatting;
+pub mod free_flow_time;
+pub mod reachable_edges;
 pub mod summary;
 pub mod traversal;
 pub mod uuid;
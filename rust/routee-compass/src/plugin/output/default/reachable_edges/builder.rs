@@ -0,0 +1,53 @@
+use std::sync::Arc;
+
+use super::plugin::ReachableEdgesOutputPlugin;
+use crate::{
+    app::compass::config::{
+        builders::OutputPluginBuilder, compass_configuration_error::CompassConfigurationError,
+        config_json_extension::ConfigJsonExtensions,
+    },
+    plugin::output::output_plugin::OutputPlugin,
+};
+use routee_compass_core::algorithm::search::isochrone::IsochroneHull;
+
+/// Builds a plugin that outputs the edges reachable within the limit of an isochrone query.
+///
+/// # Configuration
+///
+/// This plugin expects the following keys:
+/// * `feature` - the state feature that the query limit applies to
+/// * `include_partial` - optional, whether to report boundary edges (default false)
+/// * `hull` - optional, the polygon drawn around nested isochrones, either
+///   `{ type = "convex" }` or `{ type = "concave", concavity = 2.0 }`
+///
+/// # Example Configuration
+///
+/// ```toml
+/// [[plugin.output_plugins]]
+/// type = "reachable_edges"
+/// feature = "time"
+/// include_partial = true
+/// hull = { type = "concave", concavity = 2.0 }
+/// ```
+///
+pub struct ReachableEdgesOutputPluginBuilder {}
+
+impl OutputPluginBuilder for ReachableEdgesOutputPluginBuilder {
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+    ) -> Result<Arc<dyn OutputPlugin>, CompassConfigurationError> {
+        let parent_key = String::from("reachable_edges");
+        let feature = parameters.get_config_string(&"feature", &parent_key)?;
+        let include_partial = parameters
+            .get_config_serde_optional::<bool>(&"include_partial", &parent_key)?
+            .unwrap_or(false);
+        let hull = parameters.get_config_serde_optional::<IsochroneHull>(&"hull", &parent_key)?;
+        let plugin = ReachableEdgesOutputPlugin {
+            feature,
+            include_partial,
+            hull,
+        };
+        Ok(Arc::new(plugin))
+    }
+}
@@ -0,0 +1,108 @@
+use crate::app::compass::compass_app_error::CompassAppError;
+use crate::app::search::search_app_result::SearchAppResult;
+use crate::plugin::input::input_json_extensions::InputJsonExtensions;
+use crate::plugin::output::output_plugin::OutputPlugin;
+use crate::plugin::plugin_error::PluginError;
+use routee_compass_core::algorithm::search::isochrone::{
+    isochrone_hull, isochrones_from_tree, IsochroneHull,
+};
+use routee_compass_core::algorithm::search::reachable_edges::reachable_edges;
+use routee_compass_core::algorithm::search::search_instance::SearchInstance;
+use serde_json::json;
+
+/// output plugin that reports the edges reachable from the origin of an isochrone
+/// query within a limit on some state feature, such as time or distance. the
+/// limit is read from the `reachable_limit` key of the query, in the unit of the
+/// feature, and queries without a limit are not modified.
+///
+/// edges that can be traversed completely within the limit are reported as
+/// `full`. when `include_partial` is set, boundary edges that begin within the
+/// limit but end beyond it are also reported as `partial` with the fraction of
+/// the edge that is reachable.
+///
+/// nested isochrones are found from the same search tree with a list of limits in
+/// the `reachable_limits` key of the query, which adds an `isochrones` entry with
+/// the limit and reachable edges of each. when a `hull` is configured, each
+/// isochrone also has a GeoJSON polygon drawn around its reachable edges.
+///
+/// the search app stops expanding the search tree once the feature exceeds the
+/// largest limit of the query, see [`crate::app::search::search_app::SearchApp::with_reachable_feature`].
+pub struct ReachableEdgesOutputPlugin {
+    pub feature: String,
+    pub include_partial: bool,
+    pub hull: Option<IsochroneHull>,
+}
+
+impl OutputPlugin for ReachableEdgesOutputPlugin {
+    fn process(
+        &self,
+        output: &mut serde_json::Value,
+        search_result: &Result<(SearchAppResult, SearchInstance), CompassAppError>,
+    ) -> Result<(), PluginError> {
+        match search_result {
+            Err(_) => Ok(()),
+            Ok((result, si)) => {
+                let request = match output.get("request") {
+                    Some(request) => request,
+                    None => return Ok(()),
+                };
+                let limit = request.get_reachable_limit()?;
+                let limits = request.get_reachable_limits()?;
+                if limit.is_none() && limits.is_none() {
+                    return Ok(());
+                }
+                let origin = request.get_origin_vertex()?;
+                let tree = result.trees.first().ok_or_else(|| {
+                    PluginError::PluginFailed(String::from("reachable edges require a search tree"))
+                })?;
+                if let Some(limit) = limit {
+                    let edges = reachable_edges(
+                        origin,
+                        tree,
+                        &self.feature,
+                        limit,
+                        self.include_partial,
+                        si,
+                    )
+                    .map_err(|e| PluginError::PluginFailed(e.to_string()))?;
+                    output["reachable_edges"] = json![edges];
+                }
+                if let Some(limits) = limits {
+                    let isochrones = isochrones_from_tree(
+                        origin,
+                        tree,
+                        &self.feature,
+                        &limits,
+                        self.include_partial,
+                        si,
+                    )
+                    .map_err(|e| PluginError::PluginFailed(e.to_string()))?;
+                    let mut isochrones_json = vec![];
+                    for isochrone in isochrones.iter() {
+                        let mut isochrone_json = json![{
+                            "limit": isochrone.threshold,
+                            "reachable_edges": isochrone.edges,
+                        }];
+                        if let Some(hull) = &self.hull {
+                            let polygon =
+                                isochrone_hull(&isochrone.edges, origin, hull, &si.directed_graph)
+                                    .map_err(|e| PluginError::PluginFailed(e.to_string()))?;
+                            isochrone_json["polygon"] = json![geojson::Geometry::from(&polygon)];
+                        }
+                        isochrones_json.push(isochrone_json);
+                    }
+                    output["isochrones"] = json![isochrones_json];
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn uses_search_trees(&self) -> bool {
+        true
+    }
+
+    fn reachable_feature(&self) -> Option<&str> {
+        Some(&self.feature)
+    }
+}
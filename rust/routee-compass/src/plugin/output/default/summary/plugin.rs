@@ -34,4 +34,14 @@ impl OutputPlugin for SummaryOutputPlugin {
             }
         }
     }
+
+    /// the tree size and the result size both include the search trees
+    fn uses_search_trees(&self) -> bool {
+        true
+    }
+
+    /// the route edge count is read from the routes
+    fn uses_route_edges(&self) -> bool {
+        true
+    }
 }
@@ -17,6 +17,8 @@ use crate::{
 /// * `geometry_file` - the filename providing edge geometries
 /// * `route` (optional) - traversal output format for the route result
 /// * `tree` (optional) - traversal output format for the search tree result
+/// * `simplify_tolerance` (optional) - Douglas-Peucker tolerance, in degrees, used to
+///   simplify the `wkt` route geometry. no simplification if not provided.
 ///
 /// See [TraversalOutputFormat] for information on the output formats supported.
 ///
@@ -49,7 +51,11 @@ impl OutputPluginBuilder for TraversalPluginBuilder {
         let tree: Option<TraversalOutputFormat> =
             parameters.get_config_serde_optional(&"tree", &parent_key)?;
 
-        let geom_plugin = TraversalPlugin::from_file(&geometry_filename, route, tree)?;
+        let simplify_tolerance =
+            parameters.get_config_serde_optional::<f32>(&"simplify_tolerance", &parent_key)?;
+
+        let geom_plugin =
+            TraversalPlugin::from_file(&geometry_filename, route, tree, simplify_tolerance)?;
         Ok(Arc::new(geom_plugin))
     }
 }
@@ -2,6 +2,7 @@ use super::json_extensions::TraversalJsonField;
 use super::traversal_output_format::TraversalOutputFormat;
 use crate::app::compass::compass_app_error::CompassAppError;
 use crate::app::search::search_app_result::SearchAppResult;
+use crate::plugin::input::input_json_extensions::InputJsonExtensions;
 use crate::plugin::output::output_plugin::OutputPlugin;
 use crate::plugin::plugin_error::PluginError;
 use geo::LineString;
@@ -9,6 +10,8 @@ use kdam::Bar;
 use kdam::BarExt;
 use routee_compass_core::algorithm::search::edge_traversal::EdgeTraversal;
 use routee_compass_core::algorithm::search::search_instance::SearchInstance;
+use routee_compass_core::model::traversal::state::state_variable::StateVar;
+use routee_compass_core::util::duration_extension::DurationExtension;
 use routee_compass_core::util::fs::fs_utils;
 use routee_compass_core::util::fs::read_utils::read_raw_file;
 use routee_compass_core::util::geo::geo_io_utils;
@@ -19,6 +22,7 @@ pub struct TraversalPlugin {
     geoms: Box<[LineString<f32>]>,
     route: Option<TraversalOutputFormat>,
     tree: Option<TraversalOutputFormat>,
+    simplify_tolerance: Option<f32>,
     route_key: String,
     tree_key: String,
 }
@@ -28,7 +32,22 @@ impl TraversalPlugin {
         filename: &P,
         route: Option<TraversalOutputFormat>,
         tree: Option<TraversalOutputFormat>,
+        simplify_tolerance: Option<f32>,
     ) -> Result<TraversalPlugin, PluginError> {
+        if let Some(tolerance) = simplify_tolerance {
+            if tolerance.is_nan() || tolerance < 0.0 {
+                return Err(PluginError::InputError(format!(
+                    "simplify_tolerance must be non-negative, found {}",
+                    tolerance
+                )));
+            }
+        }
+        if let Some(format) = tree.as_ref().filter(|f| f.route_only()) {
+            return Err(PluginError::InputError(format!(
+                "{} output is only supported for routes",
+                format.name()
+            )));
+        }
         let count = fs_utils::line_count(filename, fs_utils::is_gzip(filename)).map_err(|e| {
             PluginError::FileReadError(filename.as_ref().to_path_buf(), e.to_string())
         })?;
@@ -55,6 +74,7 @@ impl TraversalPlugin {
             geoms,
             route,
             tree,
+            simplify_tolerance,
             route_key,
             tree_key,
         })
@@ -70,17 +90,47 @@ impl OutputPlugin for TraversalPlugin {
         match search_result {
             Err(_) => Ok(()),
             Ok((result, si)) => {
-                match self.route {
+                match &self.route {
                     None => {}
                     Some(route_args) => {
-                        let routes_serialized = result
-                            .routes
-                            .iter()
-                            .map(|route| {
-                                construct_route_output(route, si, &route_args, &self.geoms)
-                            })
-                            .collect::<Result<Vec<_>, _>>()
-                            .map_err(PluginError::PluginFailed)?;
+                        // when only the summary is requested, the route path and
+                        // geometry are not assembled
+                        let summary_only = output
+                            .get("request")
+                            .map(|req| req.get_summary_only())
+                            .transpose()?
+                            .unwrap_or(false);
+                        // per-edge records are only written when requested
+                        let edge_breakdown = output
+                            .get("request")
+                            .map(|req| req.get_edge_breakdown())
+                            .transpose()?
+                            .unwrap_or(false);
+                        let route_start_time = std::time::Instant::now();
+                        let routes_serialized = match &result.route_final_states {
+                            // the routes were not reconstructed, only their summaries
+                            Some(final_states) => final_states
+                                .iter()
+                                .map(|final_state| construct_summary_output(final_state, si))
+                                .collect::<Result<Vec<_>, _>>(),
+                            None => result
+                                .routes
+                                .iter()
+                                .map(|route| {
+                                    construct_route_output(
+                                        route,
+                                        si,
+                                        route_args,
+                                        &self.geoms,
+                                        self.simplify_tolerance,
+                                        summary_only,
+                                        edge_breakdown,
+                                    )
+                                })
+                                .collect::<Result<Vec<_>, _>>(),
+                        }
+                        .map_err(PluginError::PluginFailed)?;
+                        let route_runtime = result.route_runtime + route_start_time.elapsed();
 
                         // vary the type of value stored at the route key. if there is
                         // no route, store 'null'. if one, store an output object. if
@@ -91,10 +141,11 @@ impl OutputPlugin for TraversalPlugin {
                             _ => json![routes_serialized],
                         };
                         output[&self.route_key] = routes_json;
+                        output["route_runtime"] = json![route_runtime.hhmmss()];
                     }
                 }
 
-                match self.tree {
+                match &self.tree {
                     None => {}
                     Some(tree_args) => {
                         let trees_serialized = result
@@ -115,26 +166,55 @@ impl OutputPlugin for TraversalPlugin {
             }
         }
     }
+
+    fn uses_search_trees(&self) -> bool {
+        self.tree.is_some()
+    }
 }
 
-/// creates the JSON output for a route.
+/// creates the JSON output for a route. when summary_only is set, the
+/// path is omitted and only the summary of the final route state is returned.
+/// an empty route (origin equals destination) is summarized by the initial state.
+/// when edge_breakdown is set, a record of each edge of the route is added.
 fn construct_route_output(
     route: &Vec<EdgeTraversal>,
     si: &SearchInstance,
     output_format: &TraversalOutputFormat,
     geoms: &[LineString<f32>],
+    simplify_tolerance: Option<f32>,
+    summary_only: bool,
+    edge_breakdown: bool,
 ) -> Result<serde_json::Value, String> {
-    let last_edge = route
-        .last()
-        .ok_or_else(|| String::from("cannot find result route state when route is empty"))?;
-    let path_json = output_format
-        .generate_route_output(route, geoms)
-        .map_err(|e| e.to_string())?;
-    let traversal_summary = si.state_model.serialize_state(&last_edge.result_state);
+    let final_state = match route.last() {
+        Some(last_edge) => last_edge.result_state.clone(),
+        None => si.state_model.initial_state().map_err(|e| e.to_string())?,
+    };
+    let mut result = construct_summary_output(&final_state, si)?;
+    if !summary_only {
+        let traversal_summary = result["traversal_summary"].clone();
+        let path_json = output_format
+            .generate_route_output(route, geoms, simplify_tolerance, &traversal_summary)
+            .map_err(|e| e.to_string())?;
+        result["path"] = path_json;
+    }
+    if edge_breakdown {
+        let records = EdgeTraversal::route_breakdown(route, si).map_err(|e| e.to_string())?;
+        result["edge_breakdown"] = serde_json::json![records];
+    }
+    Ok(result)
+}
+
+/// creates the JSON summary of a route from its final state, which is the
+/// route output without a path.
+fn construct_summary_output(
+    final_state: &[StateVar],
+    si: &SearchInstance,
+) -> Result<serde_json::Value, String> {
+    let traversal_summary = si.state_model.serialize_state(final_state);
     let state_model = si.state_model.serialize_state_model();
     let cost = si
         .cost_model
-        .serialize_cost(&last_edge.result_state)
+        .serialize_cost(final_state)
         .map_err(|e| e.to_string())?;
     let cost_model = si
         .cost_model
@@ -145,7 +225,6 @@ fn construct_route_output(
         "state_model": state_model,
         "cost_model": cost_model,
         "cost": cost,
-        "path": path_json
     }];
     Ok(result)
 }
@@ -1,5 +1,5 @@
 use crate::plugin::plugin_error::PluginError;
-use geo::{LineString, MultiLineString, Point};
+use geo::{LineString, MultiLineString, Point, Simplify};
 use geo_types::MultiPoint;
 use geojson::feature::Id;
 use geojson::{Feature, FeatureCollection};
@@ -125,6 +125,125 @@ pub fn create_route_linestring(
     Ok(geometry)
 }
 
+/// simplifies a route linestring with the Douglas-Peucker algorithm, dropping
+/// intermediate points that lie within the tolerance of the simplified line.
+/// the tolerance is in the units of the coordinates, which are degrees for WGS84.
+/// if no tolerance is provided, the linestring is returned unchanged.
+pub fn simplify_route_linestring(
+    linestring: LineString<f32>,
+    simplify_tolerance: Option<f32>,
+) -> LineString<f32> {
+    match simplify_tolerance {
+        Some(tolerance) => linestring.simplify(&tolerance),
+        None => linestring,
+    }
+}
+
+/// collects the points of the edge geometries of a route in traversal order,
+/// writing the point shared by consecutive edges once.
+pub fn create_route_points(
+    route: &[EdgeTraversal],
+    geoms: &[LineString<f32>],
+) -> Result<Vec<Point<f32>>, PluginError> {
+    let mut points: Vec<Point<f32>> = vec![];
+    for traversal in route.iter() {
+        let geom = geoms
+            .get(traversal.edge_id.0)
+            .ok_or_else(|| PluginError::EdgeGeometryMissing(traversal.edge_id))?;
+        for point in geom.points() {
+            if points.last() != Some(&point) {
+                points.push(point);
+            }
+        }
+    }
+    Ok(points)
+}
+
+/// creates a single GeoJSON Feature for a route with the provided properties.
+/// the geometry is a LineString of the route points, or when per_edge is set, a
+/// MultiLineString of the edge geometries, with each edge traversal listed in
+/// an "edges" property.
+pub fn create_route_geojson_feature(
+    route: &[EdgeTraversal],
+    geoms: &[LineString<f32>],
+    mut properties: serde_json::Map<String, serde_json::Value>,
+    per_edge: bool,
+) -> Result<serde_json::Value, PluginError> {
+    let geometry = if per_edge {
+        let edge_linestrings = route
+            .iter()
+            .map(|t| create_edge_geometry(t, geoms))
+            .collect::<Result<Vec<_>, PluginError>>()?;
+        properties.insert(String::from("edges"), serde_json::to_value(route)?);
+        geojson::Geometry::from(&MultiLineString::new(edge_linestrings))
+    } else {
+        let linestring = LineString::from_iter(create_route_points(route, geoms)?);
+        geojson::Geometry::from(&linestring)
+    };
+    let feature = Feature {
+        bbox: None,
+        geometry: Some(geometry),
+        id: None,
+        properties: Some(properties),
+        foreign_members: None,
+    };
+    let result = serde_json::to_value(feature)?;
+    Ok(result)
+}
+
+/// selects features of a serialized traversal summary to use as GeoJSON properties.
+/// all features are selected if no names are provided.
+pub fn select_summary_properties(
+    traversal_summary: &serde_json::Value,
+    names: Option<&[String]>,
+) -> Result<serde_json::Map<String, serde_json::Value>, PluginError> {
+    let summary = traversal_summary.as_object().cloned().ok_or_else(|| {
+        PluginError::InternalError(format!(
+            "traversal summary was not a JSON object: {}",
+            traversal_summary
+        ))
+    })?;
+    match names {
+        None => Ok(summary),
+        Some(names) => names
+            .iter()
+            .map(|name| match summary.get(name) {
+                Some(value) => Ok((name.clone(), value.clone())),
+                None => Err(PluginError::InputError(format!(
+                    "traversal summary has no feature '{}' to include in the geojson properties",
+                    name
+                ))),
+            })
+            .collect(),
+    }
+}
+
+/// writes the route geometry as a GPX 1.1 document with a single track segment.
+/// track points follow the edge geometries in traversal order, and the point
+/// shared by consecutive edges is written once. coordinates are x/y, so x is
+/// written as the longitude and y as the latitude.
+pub fn create_route_gpx(
+    route: &[EdgeTraversal],
+    geoms: &[LineString<f32>],
+) -> Result<String, PluginError> {
+    let points = create_route_points(route, geoms)?;
+
+    let mut gpx = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <gpx version=\"1.1\" creator=\"routee-compass\" xmlns=\"http://www.topografix.com/GPX/1/1\">\n\
+         <trk>\n<trkseg>\n",
+    );
+    for point in points.iter() {
+        gpx.push_str(&format!(
+            "<trkpt lat=\"{}\" lon=\"{}\"/>\n",
+            point.y(),
+            point.x()
+        ));
+    }
+    gpx.push_str("</trkseg>\n</trk>\n</gpx>\n");
+    Ok(gpx)
+}
+
 pub fn create_tree_multilinestring(
     tree: &HashMap<VertexId, SearchTreeBranch>,
     geoms: &[LineString<f32>],
@@ -181,3 +300,140 @@ pub fn create_tree_multipoint(
     let geometry = MultiPoint::new(tree_destinations);
     Ok(geometry)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geo::{EuclideanDistance, Line};
+
+    #[test]
+    fn test_simplify_route_linestring() {
+        // a line heading east with small wiggles, along with one large detour
+        let original = LineString::from(vec![
+            (0.0, 0.0),
+            (1.0, 0.01),
+            (2.0, -0.01),
+            (3.0, 0.02),
+            (4.0, 0.0),
+            (5.0, 1.0),
+            (6.0, 0.0),
+        ]);
+        let tolerance = 0.05;
+        let simplified = simplify_route_linestring(original.clone(), Some(tolerance));
+        assert!(simplified.0.len() < original.0.len());
+        assert_eq!(simplified.0.first(), original.0.first());
+        assert_eq!(simplified.0.last(), original.0.last());
+        for point in original.points() {
+            let distance = simplified
+                .lines()
+                .map(|line: Line<f32>| point.euclidean_distance(&line))
+                .fold(f32::INFINITY, f32::min);
+            assert!(distance <= tolerance, "{:?} is {} away", point, distance);
+        }
+
+        let unchanged = simplify_route_linestring(original.clone(), None);
+        assert_eq!(unchanged, original);
+    }
+
+    /// reads the (lat, lon) attributes of each track point of a GPX document
+    fn read_gpx_track_points(gpx: &str) -> Vec<(f32, f32)> {
+        let attribute = |element: &str, name: &str| -> f32 {
+            let start = element.find(&format!("{}=\"", name)).unwrap() + name.len() + 2;
+            let end = start + element[start..].find('"').unwrap();
+            element[start..end].parse().unwrap()
+        };
+        gpx.split("<trkpt ")
+            .skip(1)
+            .map(|element| (attribute(element, "lat"), attribute(element, "lon")))
+            .collect()
+    }
+
+    #[test]
+    fn test_create_route_gpx() {
+        use routee_compass_core::model::{
+            road_network::edge_id::EdgeId, traversal::state::state_variable::StateVar, unit::Cost,
+        };
+        let traversal = |edge_id: usize| EdgeTraversal {
+            edge_id: EdgeId(edge_id),
+            access_cost: Cost::ZERO,
+            traversal_cost: Cost::ONE,
+            result_state: vec![StateVar::ZERO],
+        };
+        // geometries are (lon, lat); edges 0 and 1 share the point (-105.1, 39.8)
+        let geoms = vec![
+            LineString::from(vec![(-105.0, 39.7), (-105.05, 39.75), (-105.1, 39.8)]),
+            LineString::from(vec![(-105.1, 39.8), (-105.2, 39.9)]),
+        ];
+        let route = vec![traversal(0), traversal(1)];
+        let gpx = create_route_gpx(&route, &geoms).unwrap();
+
+        assert!(gpx.contains("<gpx version=\"1.1\""));
+        assert_eq!(gpx.matches("<trk>").count(), 1);
+        assert_eq!(gpx.matches("<trkseg>").count(), 1);
+        let points = read_gpx_track_points(&gpx);
+        assert_eq!(
+            points,
+            vec![
+                (39.7, -105.0),
+                (39.75, -105.05),
+                (39.8, -105.1),
+                (39.9, -105.2)
+            ]
+        );
+
+        let missing = create_route_gpx(&[traversal(2)], &geoms);
+        assert!(matches!(
+            missing,
+            Err(PluginError::EdgeGeometryMissing(EdgeId(2)))
+        ));
+    }
+
+    #[test]
+    fn test_create_route_geojson_feature() {
+        use routee_compass_core::model::{
+            road_network::edge_id::EdgeId, traversal::state::state_variable::StateVar, unit::Cost,
+        };
+        let traversal = |edge_id: usize| EdgeTraversal {
+            edge_id: EdgeId(edge_id),
+            access_cost: Cost::ZERO,
+            traversal_cost: Cost::ONE,
+            result_state: vec![StateVar::ZERO],
+        };
+        let geoms = vec![
+            LineString::from(vec![(-105.0, 39.7), (-105.1, 39.8)]),
+            LineString::from(vec![(-105.1, 39.8), (-105.2, 39.9)]),
+        ];
+        let route = vec![traversal(0), traversal(1)];
+        let summary = serde_json::json!({ "distance": 10.0, "time": 2.0, "energy": 0.5 });
+
+        let properties =
+            select_summary_properties(&summary, Some(&["distance".into(), "time".into()])).unwrap();
+        let feature = create_route_geojson_feature(&route, &geoms, properties, false).unwrap();
+        assert_eq!(feature["type"], "Feature");
+        assert_eq!(feature["geometry"]["type"], "LineString");
+        let coordinates = feature["geometry"]["coordinates"].as_array().unwrap();
+        assert_eq!(coordinates.len(), 3);
+        assert_eq!(
+            feature["properties"],
+            serde_json::json!({ "distance": 10.0, "time": 2.0 })
+        );
+
+        let properties = select_summary_properties(&summary, None).unwrap();
+        let feature = create_route_geojson_feature(&route, &geoms, properties, true).unwrap();
+        assert_eq!(feature["geometry"]["type"], "MultiLineString");
+        assert_eq!(
+            feature["geometry"]["coordinates"].as_array().unwrap().len(),
+            2
+        );
+        assert_eq!(feature["properties"]["energy"], 0.5);
+        assert_eq!(feature["properties"]["edges"].as_array().unwrap().len(), 2);
+
+        assert!(select_summary_properties(&summary, Some(&["speed".into()])).is_err());
+        let missing =
+            create_route_geojson_feature(&[traversal(2)], &geoms, serde_json::Map::new(), false);
+        assert!(matches!(
+            missing,
+            Err(PluginError::EdgeGeometryMissing(EdgeId(2)))
+        ));
+    }
+}
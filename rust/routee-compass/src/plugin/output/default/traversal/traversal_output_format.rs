@@ -10,7 +10,7 @@ use routee_compass_core::{
 use serde::{Deserialize, Serialize};
 use wkt::ToWkt;
 
-#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "snake_case")]
 pub enum TraversalOutputFormat {
     // concatenates all LINESTRINGS and returns the geometry as a WKT
@@ -20,18 +20,38 @@ pub enum TraversalOutputFormat {
     // returns the geometries and properties as GeoJSON
     GeoJson,
     EdgeId,
+    // returns the route geometry as a GPX 1.1 track. not supported for trees
+    Gpx,
+    // returns the route as a single GeoJSON Feature with a LineString geometry and the
+    // traversal summary as properties. not supported for trees
+    GeoJsonFeature {
+        // traversal summary features to include as properties, all features if not provided
+        #[serde(default)]
+        properties: Option<Vec<String>>,
+        // use a MultiLineString with a LineString for each edge, and add each
+        // edge traversal to the properties
+        #[serde(default)]
+        per_edge: bool,
+    },
 }
 
 impl TraversalOutputFormat {
-    /// generates output for a route based on the configured TraversalOutputFormat
+    /// generates output for a route based on the configured TraversalOutputFormat.
+    /// the optional simplify tolerance applies to the concatenated WKT route geometry,
+    /// and the traversal summary of the route is used by the GeoJSON Feature output.
     pub fn generate_route_output(
         &self,
         route: &Vec<EdgeTraversal>,
         geoms: &[LineString<f32>],
+        simplify_tolerance: Option<f32>,
+        traversal_summary: &serde_json::Value,
     ) -> Result<serde_json::Value, PluginError> {
         match self {
             TraversalOutputFormat::Wkt => {
-                let route_geometry = ops::create_route_linestring(route, geoms)?;
+                let route_geometry = ops::simplify_route_linestring(
+                    ops::create_route_linestring(route, geoms)?,
+                    simplify_tolerance,
+                );
                 let route_wkt = route_geometry.wkt_string();
                 Ok(serde_json::Value::String(route_wkt))
             }
@@ -48,9 +68,42 @@ impl TraversalOutputFormat {
                 let json = serde_json::json![route_ids];
                 Ok(json)
             }
+            TraversalOutputFormat::Gpx => {
+                let route_gpx = ops::create_route_gpx(route, geoms)?;
+                Ok(serde_json::Value::String(route_gpx))
+            }
+            TraversalOutputFormat::GeoJsonFeature {
+                properties,
+                per_edge,
+            } => {
+                let summary =
+                    ops::select_summary_properties(traversal_summary, properties.as_deref())?;
+                let result = ops::create_route_geojson_feature(route, geoms, summary, *per_edge)?;
+                Ok(result)
+            }
         }
     }
 
+    /// the name of this format in the plugin configuration
+    pub fn name(&self) -> &'static str {
+        match self {
+            TraversalOutputFormat::Wkt => "wkt",
+            TraversalOutputFormat::Json => "json",
+            TraversalOutputFormat::GeoJson => "geo_json",
+            TraversalOutputFormat::EdgeId => "edge_id",
+            TraversalOutputFormat::Gpx => "gpx",
+            TraversalOutputFormat::GeoJsonFeature { .. } => "geo_json_feature",
+        }
+    }
+
+    /// true if this format can only be generated for routes, not search trees
+    pub fn route_only(&self) -> bool {
+        matches!(
+            self,
+            TraversalOutputFormat::Gpx | TraversalOutputFormat::GeoJsonFeature { .. }
+        )
+    }
+
     /// generates output for a tree based on the configured TraversalOutputFormat
     pub fn generate_tree_output(
         &self,
@@ -79,6 +132,12 @@ impl TraversalOutputFormat {
                 let json = serde_json::json![tree_ids];
                 Ok(json)
             }
+            TraversalOutputFormat::Gpx | TraversalOutputFormat::GeoJsonFeature { .. } => {
+                Err(PluginError::InputError(format!(
+                    "{} output is only supported for routes",
+                    self.name()
+                )))
+            }
         }
     }
 }
@@ -121,10 +180,15 @@ mod test {
         ];
         let result = SearchAppResult {
             routes: vec![route],
+            route_final_states: None,
             trees: vec![],
             search_executed_time: Local::now().to_rfc3339(),
             search_runtime: Duration::ZERO,
+            route_runtime: Duration::ZERO,
             iterations: 0,
+            nodes_expanded: 0,
+            edges_relaxed: 0,
+            max_frontier_size: 0,
         };
 
         let geoms = vec![
@@ -141,26 +205,55 @@ mod test {
         println!(
             "{:?}",
             TraversalOutputFormat::Wkt
-                .generate_route_output(&result.routes[0], &geoms)
+                .generate_route_output(&result.routes[0], &geoms, None, &serde_json::json!({}))
                 .map(|r| serde_json::to_string_pretty(&r))
         );
         println!(
             "{:?}",
             TraversalOutputFormat::Json
-                .generate_route_output(&result.routes[0], &geoms)
+                .generate_route_output(&result.routes[0], &geoms, None, &serde_json::json!({}))
                 .map(|r| serde_json::to_string_pretty(&r))
         );
         println!(
             "{:?}",
             TraversalOutputFormat::GeoJson
-                .generate_route_output(&result.routes[0], &geoms)
+                .generate_route_output(&result.routes[0], &geoms, None, &serde_json::json!({}))
                 .map(|r| serde_json::to_string_pretty(&r))
         );
         println!(
             "{:?}",
             TraversalOutputFormat::EdgeId
-                .generate_route_output(&result.routes[0], &geoms)
+                .generate_route_output(&result.routes[0], &geoms, None, &serde_json::json!({}))
                 .map(|r| serde_json::to_string_pretty(&r))
         );
     }
+
+    #[test]
+    fn test_deserialize_geo_json_feature() {
+        let format: TraversalOutputFormat = serde_json::from_value(serde_json::json!({
+            "geo_json_feature": { "properties": ["distance", "time"] }
+        }))
+        .unwrap();
+        match format {
+            TraversalOutputFormat::GeoJsonFeature {
+                properties,
+                per_edge,
+            } => {
+                assert_eq!(
+                    properties,
+                    Some(vec![String::from("distance"), String::from("time")])
+                );
+                assert!(!per_edge);
+            }
+            other => panic!("expected geo_json_feature, found {:?}", other),
+        }
+        assert!(format_is_route_only("gpx"));
+        assert!(!format_is_route_only("geo_json"));
+    }
+
+    fn format_is_route_only(name: &str) -> bool {
+        serde_json::from_value::<TraversalOutputFormat>(serde_json::json!(name))
+            .unwrap()
+            .route_only()
+    }
 }
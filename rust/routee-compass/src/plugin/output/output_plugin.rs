@@ -41,4 +41,24 @@ pub trait OutputPlugin: Send + Sync {
         output: &mut serde_json::Value,
         result: &Result<(SearchAppResult, SearchInstance), CompassAppError>,
     ) -> Result<(), PluginError>;
+
+    /// true if this plugin reads the search trees of a result. results kept in the
+    /// query cache keep their search trees only when a configured plugin reads them.
+    fn uses_search_trees(&self) -> bool {
+        false
+    }
+
+    /// true if this plugin reads the edges of the routes of a result. routes are
+    /// only left unreconstructed for `summary_only` queries when no configured
+    /// plugin reads their edges.
+    fn uses_route_edges(&self) -> bool {
+        false
+    }
+
+    /// the state feature that the reachable limits of a query apply to, if this
+    /// plugin reads them. searches without a destination stop expanding once this
+    /// feature exceeds the largest limit of the query.
+    fn reachable_feature(&self) -> Option<&str> {
+        None
+    }
 }
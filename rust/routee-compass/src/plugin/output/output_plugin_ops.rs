@@ -6,7 +6,8 @@ use routee_compass_core::algorithm::search::search_instance::SearchInstance;
 use serde_json::{json, Value};
 
 /// creates the initial output with summary information from the search app,
-/// which happens regardless of the output plugin setup.
+/// which happens regardless of the output plugin setup. this includes the
+/// counters of the work done by the search.
 pub fn create_initial_output(
     req: &Value,
     res: &Result<(SearchAppResult, SearchInstance), CompassAppError>,
@@ -14,9 +15,12 @@ pub fn create_initial_output(
 ) -> Result<Value, Value> {
     match &res {
         Err(e) => Err(package_error(req, e)),
-        Ok((_, _)) => {
+        Ok((result, _)) => {
             let mut init_output = serde_json::json!({
                 "request": req,
+                "nodes_expanded": result.nodes_expanded,
+                "edges_relaxed": result.edges_relaxed,
+                "max_frontier_size": result.max_frontier_size,
             });
 
             let output_plugin_executed_time = chrono::Local::now();
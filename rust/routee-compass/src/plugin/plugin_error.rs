@@ -1,3 +1,4 @@
+use geo::Coord;
 use std::path::PathBuf;
 
 use routee_compass_core::{
@@ -32,6 +33,8 @@ pub enum PluginError {
     SearchError(#[from] SearchError),
     #[error("expected query to be a json object '{{}}' but found {0}")]
     UnexpectedQueryStructure(String),
+    #[error("coordinate {coord:?} lies outside of the bounds of the road network")]
+    CoordinateOutOfBounds { coord: Coord<f32> },
     #[error("unexpected error {0}")]
     InternalError(String),
 }